@@ -569,6 +569,34 @@ pub struct MerchantConnectorId {
     pub merchant_connector_id: String,
 }
 
+/// Filters for the knowledge graph built from the merchant connector accounts of a merchant
+#[derive(Default, Debug, Clone, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MerchantConnectorGraphQuery {
+    /// Build the graph from the merchant connector accounts of this business profile only
+    pub profile_id: Option<String>,
+    /// Build the graph used to route transactions of this type, payments by default
+    #[schema(value_type = Option<TransactionType>, example = "payment")]
+    pub transaction_type: Option<api_enums::TransactionType>,
+    /// Prune the graph to the part relevant to this connector
+    #[schema(value_type = Option<RoutableConnectors>, example = "stripe")]
+    pub connector: Option<api_enums::RoutableConnectors>,
+    /// Prune the graph to the part relevant to this payment method type
+    #[schema(value_type = Option<PaymentMethodType>, example = "credit")]
+    pub payment_method_type: Option<api_enums::PaymentMethodType>,
+}
+
+/// Renderings of the knowledge graph built from the merchant connector accounts of a merchant
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MerchantConnectorGraphResponse {
+    /// The graph in the Graphviz DOT language
+    pub dot: String,
+    /// The graph as a Mermaid flowchart
+    pub mermaid: String,
+    /// Number of nodes in the rendered graph
+    pub node_count: usize,
+}
+
 /// Create a new Merchant Connector for the merchant account. The connector could be a payment processor / facilitator / acquirer or specialized services like Fraud / Accounting etc."
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(deny_unknown_fields)]
@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// The statistics of the in-memory caches of the application instance which served the request.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CacheStatisticsResponse {
+    /// The statistics of each in-memory cache.
+    pub caches: Vec<CacheStatistics>,
+}
+
+/// The statistics of an in-memory cache, the counters being counted since the application
+/// instance started.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CacheStatistics {
+    /// The kind of the keys of the cache, which identifies the cache in the other cache APIs.
+    #[schema(example = "accounts")]
+    pub cache: String,
+
+    /// The name of the cache.
+    #[schema(example = "ACCOUNTS_CACHE")]
+    pub name: String,
+
+    /// The approximate number of entries in the cache.
+    pub entry_count: u64,
+
+    /// The approximate total size of the entries in the cache.
+    pub weighted_size: u64,
+
+    /// The maximum size of the cache, if the cache is bounded.
+    pub max_capacity: Option<u64>,
+
+    /// The time an entry lives in the cache for after being inserted, in seconds.
+    pub time_to_live_in_seconds: Option<u64>,
+
+    /// The time an entry lives in the cache for after being last accessed, in seconds.
+    pub time_to_idle_in_seconds: Option<u64>,
+
+    /// The number of lookups which found an entry.
+    pub hits: u64,
+
+    /// The number of lookups which did not find an entry.
+    pub misses: u64,
+
+    /// The number of entries removed because they expired or because the cache was full.
+    pub evictions: u64,
+
+    /// The number of entries removed by the application.
+    pub invalidations: u64,
+}
+
+/// The constraints to apply when listing the keys of an in-memory cache.
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CacheKeysListConstraints {
+    /// Include only the keys starting with the specified prefix.
+    #[schema(example = "merchant_")]
+    pub prefix: Option<String>,
+
+    /// Include at most the specified number of keys.
+    pub limit: Option<u16>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CacheKeysListRequestInternal {
+    pub cache: String,
+    pub constraints: CacheKeysListConstraints,
+}
+
+/// The keys of an in-memory cache of the application instance which served the request, in
+/// lexicographic order.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CacheKeysListResponse {
+    /// The kind of the keys of the cache.
+    #[schema(example = "accounts")]
+    pub cache: String,
+
+    /// The number of keys in the list.
+    pub count: usize,
+
+    /// The keys of the cache.
+    pub keys: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CacheKeyInvalidateRequestInternal {
+    pub cache: String,
+    pub key: String,
+}
+
+/// The propagation of the invalidation of a key of an in-memory cache to the application
+/// instances.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CacheKeyInvalidateResponse {
+    /// The kind of the keys of the cache.
+    #[schema(example = "accounts")]
+    pub cache: String,
+
+    /// The invalidated key.
+    pub key: String,
+
+    /// The number of application instances the invalidation was delivered to.
+    pub receivers: usize,
+
+    /// The application instances which acknowledged the invalidation.
+    pub acknowledged_by: Vec<String>,
+
+    /// Whether every application instance the invalidation was delivered to acknowledged it.
+    pub propagated: bool,
+}
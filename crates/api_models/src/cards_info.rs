@@ -29,3 +29,48 @@ pub struct CardInfoResponse {
     #[schema(example = "INDIA")]
     pub card_issuing_country: Option<String>,
 }
+
+/// Card info of a BIN, or of a range of BINs, to be imported into the card info table
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize, ToSchema)]
+pub struct CardInfoRecord {
+    /// The BIN, or the first BIN of the range, of 6 or 8 digits
+    #[schema(example = "424242")]
+    pub card_iin: String,
+    /// The last BIN of the range, of as many digits as `card_iin`. The record applies to
+    /// `card_iin` alone if not provided.
+    #[schema(example = "424299")]
+    pub card_iin_range_end: Option<String>,
+    #[schema(example = "STRIPE PAYMENTS UK LIMITED")]
+    pub card_issuer: Option<String>,
+    #[schema(value_type = Option<CardNetwork>, example = "Visa")]
+    pub card_network: Option<common_enums::CardNetwork>,
+    #[schema(example = "CREDIT")]
+    pub card_type: Option<String>,
+    #[schema(example = "CLASSIC")]
+    pub card_subtype: Option<String>,
+    #[schema(example = "UNITEDKINGDOM")]
+    pub card_issuing_country: Option<String>,
+    pub bank_code_id: Option<String>,
+    pub bank_code: Option<String>,
+    pub country_code: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, ToSchema)]
+pub struct CardsInfoImportRequest {
+    /// The provider of the card info, recorded as the last provider to have updated the imported
+    /// records
+    #[schema(example = "binlist")]
+    pub provider: Option<String>,
+    /// The card info to be inserted, or to replace the card info of the same BINs
+    pub records: Vec<CardInfoRecord>,
+}
+
+#[derive(Debug, serde::Serialize, ToSchema)]
+pub struct CardsInfoImportResponse {
+    /// The number of records inserted
+    #[schema(example = 10)]
+    pub inserted: usize,
+    /// The number of records that replaced existing card info
+    #[schema(example = 2)]
+    pub updated: usize,
+}
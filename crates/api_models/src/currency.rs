@@ -7,6 +7,9 @@ pub struct CurrencyConversionParams {
     pub amount: MinorUnit,
     pub to_currency: String,
     pub from_currency: String,
+    /// Convert at the exchange rates that applied at this time instead of the current ones
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub as_of: Option<time::PrimitiveDateTime>,
 }
 
 /// Response to be send for convert currency route
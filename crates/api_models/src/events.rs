@@ -27,12 +27,14 @@ use crate::{
         outgoing_webhook_event::OutgoingWebhookLogsRequest, sdk_events::*, search::*, *,
     },
     api_keys::*,
+    cache::*,
     cards_info::*,
     disputes::*,
     files::*,
     mandates::*,
     payment_methods::*,
     payments::*,
+    process_tracker::*,
     verifications::*,
 };
 
@@ -65,6 +67,8 @@ impl_misc_api_event_type!(
     CreateFileResponse,
     MerchantConnectorResponse,
     MerchantConnectorId,
+    MerchantConnectorGraphQuery,
+    MerchantConnectorGraphResponse,
     MandateResponse,
     MandateRevokedResponse,
     RetrievePaymentLinkRequest,
@@ -84,6 +88,8 @@ impl_misc_api_event_type!(
     MerchantAccountDeleteResponse,
     MerchantAccountUpdate,
     CardInfoResponse,
+    CardsInfoImportRequest,
+    CardsInfoImportResponse,
     CreateApiKeyResponse,
     CreateApiKeyRequest,
     MerchantConnectorDeleteResponse,
@@ -125,7 +131,23 @@ impl_misc_api_event_type!(
     GetSearchRequestWithIndex,
     GetDisputeFilterRequest,
     DisputeFiltersResponse,
-    GetDisputeMetricRequest
+    GetDisputeMetricRequest,
+    RecurringJobListRequestInternal,
+    RecurringJobCreateRequestInternal,
+    RecurringJobRequestInternal,
+    RecurringJobResponse,
+    RecurringJobListResponse,
+    ProcessTrackerTaskListConstraints,
+    ProcessTrackerTaskResponse,
+    ProcessTrackerTaskListResponse,
+    ProcessTrackerTaskRescheduleRequestInternal,
+    LimboTasksResetRequest,
+    LimboTasksResetResponse,
+    CacheStatisticsResponse,
+    CacheKeysListRequestInternal,
+    CacheKeysListResponse,
+    CacheKeyInvalidateRequestInternal,
+    CacheKeyInvalidateResponse
 );
 
 #[cfg(feature = "stripe")]
@@ -4,6 +4,7 @@ pub mod analytics;
 pub mod api_keys;
 pub mod apple_pay_certificates_migration;
 pub mod blocklist;
+pub mod cache;
 pub mod cards_info;
 pub mod conditional_configs;
 pub mod connector_onboarding;
@@ -27,6 +28,7 @@ pub mod payments;
 pub mod payouts;
 pub mod pm_auth;
 pub mod poll;
+pub mod process_tracker;
 #[cfg(feature = "recon")]
 pub mod recon;
 pub mod refunds;
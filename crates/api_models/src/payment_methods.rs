@@ -16,6 +16,7 @@ use crate::payouts;
 use crate::{
     admin, customers, enums as api_enums,
     payments::{self, BankCodeResponse},
+    routing,
 };
 
 #[derive(Debug, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
@@ -816,6 +817,11 @@ pub struct PaymentMethodListRequest {
     /// Indicates the limit of last used payment methods
     #[schema(example = 1)]
     pub limit: Option<i64>,
+
+    /// Whether to explain why connectors were excluded from the list, for debugging. Only honoured
+    /// for requests authenticated with an API key.
+    #[schema(example = true)]
+    pub explain_ineligibility: Option<bool>,
 }
 
 impl<'de> serde::Deserialize<'de> for PaymentMethodListRequest {
@@ -887,6 +893,13 @@ impl<'de> serde::Deserialize<'de> for PaymentMethodListRequest {
                         "limit" => {
                             set_or_reject_duplicate(&mut output.limit, "limit", map.next_value()?)?;
                         }
+                        "explain_ineligibility" => {
+                            set_or_reject_duplicate(
+                                &mut output.explain_ineligibility,
+                                "explain_ineligibility",
+                                map.next_value()?,
+                            )?;
+                        }
                         _ => {}
                     }
                 }
@@ -961,6 +974,11 @@ pub struct PaymentMethodListResponse {
 
     /// flag that indicates whether to collect billing details from wallets or from the customer
     pub collect_billing_details_from_wallets: Option<bool>,
+
+    /// Why connectors were excluded from the list, present only if `explain_ineligibility` was
+    /// requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ineligible_connectors: Option<Vec<routing::ConnectorIneligibility>>,
 }
 
 #[derive(Eq, PartialEq, Hash, Debug, serde::Deserialize, ToSchema)]
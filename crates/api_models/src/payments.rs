@@ -12,7 +12,7 @@ use common_utils::{
     hashing::HashedString,
     id_type,
     pii::{self, Email, EmailStrategy},
-    types::{keymanager::ToEncryptable, MinorUnit, StringMajorUnit},
+    types::{keymanager::ToEncryptable, DccOffer, MinorUnit, StringMajorUnit},
 };
 use euclid::dssa::graph::euclid_graph_prelude::FxHashMap;
 use masking::{ExposeInterface, PeekInterface, Secret, SwitchStrategy, WithType};
@@ -518,6 +518,11 @@ pub struct PaymentsRequest {
         example = "Custom_Order_id_123"
     )]
     pub merchant_order_reference_id: Option<String>,
+
+    /// Whether the customer accepted or declined to pay in the currency of their card, as offered in the `dcc_offer` of the payment. Passed when confirming the payment again after the offer.
+    #[schema(value_type = Option<DccDecision>, example = "accepted")]
+    #[remove_in(PaymentsUpdateRequest, PaymentsCreateRequest)]
+    pub dcc_decision: Option<api_enums::DccDecision>,
 }
 
 /// Checks if the inner values of two options are equal
@@ -3303,6 +3308,7 @@ pub enum NextActionType {
     TriggerApi,
     DisplayBankTransferInformation,
     DisplayWaitScreen,
+    DisplayDccOffer,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, ToSchema)]
@@ -3352,6 +3358,11 @@ pub enum NextActionData {
     InvokeSdkClient {
         next_action_data: SdkNextActionData,
     },
+    /// Contains the offer to pay in the currency of the card, the payment has to be confirmed again with the decision of the customer on it
+    DisplayDccOffer {
+        #[schema(value_type = DccOffer)]
+        dcc_offer: DccOffer,
+    },
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, ToSchema)]
@@ -3869,6 +3880,10 @@ pub struct PaymentsResponse {
         example = "Custom_Order_id_123"
     )]
     pub merchant_order_reference_id: Option<String>,
+
+    /// Offer to the customer to pay in the currency of their card, along with their decision on it
+    #[schema(value_type = Option<DccOffer>)]
+    pub dcc_offer: Option<DccOffer>,
 }
 
 /// Fee information to be charged on the payment being collected
@@ -0,0 +1,283 @@
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+use utoipa::ToSchema;
+
+/// The constraints to apply when listing recurring jobs.
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RecurringJobListConstraints {
+    /// Include at most the specified number of recurring jobs.
+    pub limit: Option<u16>,
+
+    /// Include recurring jobs after the specified offset.
+    pub offset: Option<u16>,
+}
+
+/// The request body to create a job the scheduler runs on the schedule of a cron expression.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RecurringJobCreateRequest {
+    /// The workflow the job runs.
+    #[schema(example = "API_KEY_EXPIRY_WORKFLOW")]
+    pub runner: String,
+
+    /// The name of the task the job runs.
+    pub name: String,
+
+    /// The data the workflow runs the job with.
+    #[schema(value_type = Object)]
+    pub tracking_data: serde_json::Value,
+
+    /// The cron expression the job runs on the schedule of.
+    #[schema(example = "0 2 * * *")]
+    pub cron_expression: String,
+
+    /// The IANA timezone the cron expression is evaluated in, `UTC` by default.
+    #[schema(example = "Asia/Kolkata")]
+    pub timezone: Option<String>,
+
+    /// What happens to the runs of the job that are missed: `skip`, the default, or `catch_up`.
+    #[schema(example = "skip")]
+    pub missed_run_policy: Option<String>,
+}
+
+/// A job the scheduler runs on the schedule of a cron expression.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RecurringJobResponse {
+    /// The identifier for the recurring job.
+    #[schema(example = "REFUND_WORKFLOW_ROUTER_SYNC_REFUND_ref_mbabizu24mvu3mela5njyhpit4")]
+    pub job_id: String,
+
+    /// The identifier for the Merchant Account the job runs for.
+    #[schema(max_length = 64, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: String,
+
+    /// The workflow the job runs.
+    #[schema(example = "REFUND_WORKFLOW_ROUTER")]
+    pub runner: Option<String>,
+
+    /// The name of the task the job runs.
+    pub name: Option<String>,
+
+    /// The status of the job, `paused` if the job is paused.
+    #[schema(example = "new")]
+    pub status: String,
+
+    /// The cron expression the job runs on the schedule of.
+    #[schema(example = "0 2 * * *")]
+    pub cron_expression: String,
+
+    /// The IANA timezone the cron expression is evaluated in.
+    #[schema(example = "Asia/Kolkata")]
+    pub timezone: String,
+
+    /// What happens to the runs of the job that are missed: `skip` or `catch_up`.
+    #[schema(example = "skip")]
+    pub missed_run_policy: String,
+
+    /// The run of the job it is scheduled for.
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub scheduled_for: PrimitiveDateTime,
+
+    /// Time at which the job is scheduled to run.
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub schedule_time: Option<PrimitiveDateTime>,
+
+    /// Time at which the job was created.
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+
+    /// Time at which the job was last updated.
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub updated_at: PrimitiveDateTime,
+}
+
+/// The response body when listing recurring jobs.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RecurringJobListResponse {
+    /// The number of recurring jobs included in the list.
+    pub count: usize,
+
+    /// The recurring jobs.
+    pub data: Vec<RecurringJobResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecurringJobListRequestInternal {
+    pub merchant_id: String,
+    pub constraints: RecurringJobListConstraints,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecurringJobCreateRequestInternal {
+    pub merchant_id: String,
+    pub request: RecurringJobCreateRequest,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecurringJobRequestInternal {
+    pub merchant_id: String,
+    pub job_id: String,
+}
+
+/// The constraints to apply when listing the tasks of the scheduler.
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ProcessTrackerTaskListConstraints {
+    /// Include only the tasks run by the specified workflow.
+    #[schema(example = "REFUND_WORKFLOW_ROUTER")]
+    pub runner: Option<String>,
+
+    /// Include only the tasks with the specified status.
+    #[schema(example = "process_started")]
+    pub status: Option<String>,
+
+    /// Include only the tasks of the specified Merchant Account.
+    #[schema(max_length = 64, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: Option<String>,
+
+    /// Include only the tasks scheduled to run at or after the specified time.
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub scheduled_after: Option<PrimitiveDateTime>,
+
+    /// Include only the tasks scheduled to run at or before the specified time.
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub scheduled_before: Option<PrimitiveDateTime>,
+
+    /// Include at most the specified number of tasks.
+    pub limit: Option<u16>,
+
+    /// Include tasks after the specified offset.
+    pub offset: Option<u16>,
+}
+
+/// A task of the scheduler.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ProcessTrackerTaskResponse {
+    /// The identifier for the task.
+    #[schema(example = "REFUND_WORKFLOW_ROUTER_SYNC_REFUND_ref_mbabizu24mvu3mela5njyhpit4")]
+    pub task_id: String,
+
+    /// The name of the task.
+    pub name: Option<String>,
+
+    /// The workflow the task is run by.
+    #[schema(example = "REFUND_WORKFLOW_ROUTER")]
+    pub runner: Option<String>,
+
+    /// The identifier for the Merchant Account the task runs for.
+    #[schema(max_length = 64, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: Option<String>,
+
+    /// The status of the task.
+    #[schema(example = "pending")]
+    pub status: String,
+
+    /// The outcome of the last run of the task, as reported by its workflow.
+    #[schema(example = "Pending")]
+    pub business_status: String,
+
+    /// The number of times the task was retried.
+    pub retry_count: i32,
+
+    /// Time at which the task is scheduled to run.
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub schedule_time: Option<PrimitiveDateTime>,
+
+    /// The tags of the task.
+    pub tag: Vec<String>,
+
+    /// The data the workflow runs the task with.
+    #[schema(value_type = Object)]
+    pub tracking_data: serde_json::Value,
+
+    /// Whether the task runs on the schedule of a cron expression.
+    pub recurring: bool,
+
+    /// The retries and operator actions of the task, oldest first. Only included when retrieving
+    /// a single task.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history: Option<Vec<ProcessTrackerTaskEvent>>,
+
+    /// Time at which the task was created.
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+
+    /// Time at which the task was last updated.
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub updated_at: PrimitiveDateTime,
+}
+
+/// An event in the history of a task of the scheduler.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ProcessTrackerTaskEvent {
+    /// What happened to the task: `retried`, `forced_run`, `rescheduled` or `cancelled`.
+    #[schema(example = "retried")]
+    pub kind: String,
+
+    /// The number of times the task was retried after the event.
+    pub retry_count: i32,
+
+    /// Time at which the task was scheduled to run after the event.
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub schedule_time: Option<PrimitiveDateTime>,
+
+    /// The business status of the task before the event.
+    pub previous_business_status: String,
+
+    /// Time at which the event happened.
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
+
+/// The response body when listing the tasks of the scheduler.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProcessTrackerTaskListResponse {
+    /// The number of tasks included in the list.
+    pub count: usize,
+
+    /// The tasks.
+    pub data: Vec<ProcessTrackerTaskResponse>,
+}
+
+/// The request body to reschedule a task of the scheduler.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ProcessTrackerTaskRescheduleRequest {
+    /// Time at which the task is to run.
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub schedule_time: PrimitiveDateTime,
+}
+
+/// The request body to reset the tasks of the scheduler stuck in limbo, which were picked by a
+/// consumer that never completed them.
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct LimboTasksResetRequest {
+    /// Reset only the tasks run by the specified workflow.
+    #[schema(example = "REFUND_WORKFLOW_ROUTER")]
+    pub runner: Option<String>,
+
+    /// Reset only the tasks last updated before the specified time, an hour ago by default.
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub started_before: Option<PrimitiveDateTime>,
+
+    /// Reset at most the specified number of tasks.
+    pub limit: Option<u16>,
+}
+
+/// The response body when resetting the tasks of the scheduler stuck in limbo.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LimboTasksResetResponse {
+    /// The number of tasks reset.
+    pub reset_count: usize,
+
+    /// The identifiers for the tasks reset.
+    pub task_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProcessTrackerTaskRescheduleRequestInternal {
+    pub task_id: String,
+    pub request: ProcessTrackerTaskRescheduleRequest,
+}
@@ -1,6 +1,11 @@
 use std::fmt::Debug;
 
 use common_utils::errors::ParsingError;
+#[cfg(feature = "ast_parser")]
+use euclid::frontend::ast::{
+    formatter,
+    parser::{self, EuclidParsable, ParseResult},
+};
 pub use euclid::{
     dssa::types::EuclidAnalysable,
     frontend::{
@@ -11,9 +16,9 @@ pub use euclid::{
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-use crate::enums::{RoutableConnectors, TransactionType};
+use crate::enums::{PaymentMethod, PaymentMethodType, RoutableConnectors, TransactionType};
 
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 #[serde(tag = "type", content = "data", rename_all = "snake_case")]
 pub enum ConnectorSelection {
     Priority(Vec<RoutableConnectorChoice>),
@@ -114,6 +119,9 @@ impl EuclidDirFilter for ConnectorSelection {
         DirKeyKind::CardRedirectType,
         DirKeyKind::BankTransferType,
         DirKeyKind::RealTimePaymentType,
+        DirKeyKind::UtcHour,
+        DirKeyKind::UtcWeekday,
+        DirKeyKind::UtcDate,
     ];
 }
 
@@ -144,7 +152,106 @@ impl EuclidAnalysable for ConnectorSelection {
     }
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ToSchema)]
+/// Textual syntax of a connector selection in the euclid DSL
+///
+/// ```text
+/// [stripe, adyen("mca_abc123")]
+/// volume_split[60% stripe, 40% adyen("mca_abc123")]
+/// ```
+#[cfg(feature = "ast_parser")]
+impl EuclidParsable for ConnectorSelection {
+    fn parse_output(input: &str) -> ParseResult<&str, Self> {
+        fn token<'a>(input: &'a str, token: &str) -> Option<&'a str> {
+            input.trim_start().strip_prefix(token)
+        }
+
+        fn connector_choice(input: &str) -> Option<(&str, RoutableConnectorChoice)> {
+            let input = input.trim_start();
+            let (connector, rest) = input.split_at(
+                input
+                    .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                    .unwrap_or(input.len()),
+            );
+            let connector = connector.parse::<RoutableConnectors>().ok()?;
+
+            let (rest, merchant_connector_id) = match rest.strip_prefix('(') {
+                Some(rest) => {
+                    let (rest, merchant_connector_id) = parser::string_str(rest).ok()?;
+                    (rest.strip_prefix(')')?, Some(merchant_connector_id))
+                }
+                None => (rest, None),
+            };
+
+            Some((
+                rest,
+                RoutableConnectorChoice {
+                    choice_kind: RoutableChoiceKind::FullStruct,
+                    connector,
+                    merchant_connector_id,
+                },
+            ))
+        }
+
+        fn volume_split(input: &str) -> Option<(&str, ConnectorVolumeSplit)> {
+            let (rest, split) = parser::percentage(input.trim_start()).ok()?;
+            let (rest, connector) = connector_choice(rest)?;
+            Some((rest, ConnectorVolumeSplit { connector, split }))
+        }
+
+        fn list<T>(input: &str, item: fn(&str) -> Option<(&str, T)>) -> Option<(&str, Vec<T>)> {
+            let rest = token(input, "[")?;
+            let mut items = Vec::new();
+            if let Some(rest) = token(rest, "]") {
+                return Some((rest, items));
+            }
+
+            let mut rest = rest;
+            loop {
+                let (next, parsed) = item(rest)?;
+                items.push(parsed);
+                match token(next, ",") {
+                    Some(next) => rest = next,
+                    None => return Some((token(next, "]")?, items)),
+                }
+            }
+        }
+
+        parser::parse_with(input, "connector_selection", |input| {
+            match token(input, "volume_split") {
+                Some(rest) => {
+                    list(rest, volume_split).map(|(rest, splits)| (rest, Self::VolumeSplit(splits)))
+                }
+                None => list(input, connector_choice)
+                    .map(|(rest, choices)| (rest, Self::Priority(choices))),
+            }
+        })
+    }
+
+    fn format_output(&self) -> String {
+        fn connector_choice(choice: &RoutableConnectorChoice) -> String {
+            match &choice.merchant_connector_id {
+                Some(mca_id) => format!("{}({})", choice.connector, formatter::string(mca_id)),
+                None => choice.connector.to_string(),
+            }
+        }
+
+        match self {
+            Self::Priority(choices) => {
+                let choices = choices.iter().map(connector_choice).collect::<Vec<_>>();
+                format!("[{}]", choices.join(", "))
+            }
+            Self::VolumeSplit(splits) => {
+                let splits = splits
+                    .iter()
+                    .map(|split| format!("{}% {}", split.split, connector_choice(&split.connector)))
+                    .collect::<Vec<_>>();
+                format!("volume_split[{}]", splits.join(", "))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, ToSchema)]
 pub struct ConnectorVolumeSplit {
     pub connector: RoutableConnectorChoice,
     pub split: u8,
@@ -242,6 +349,7 @@ pub enum RoutingAlgorithmKind {
     Priority,
     VolumeSplit,
     Advanced,
+    SuccessRateBased,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -265,6 +373,7 @@ pub enum RoutingAlgorithm {
     VolumeSplit(Vec<ConnectorVolumeSplit>),
     #[schema(value_type=ProgramConnectorSelection)]
     Advanced(ast::Program<ConnectorSelection>),
+    SuccessRateBased(SuccessRateBasedRoutingConfig),
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -274,6 +383,45 @@ pub enum RoutingAlgorithmSerde {
     Priority(Vec<RoutableConnectorChoice>),
     VolumeSplit(Vec<ConnectorVolumeSplit>),
     Advanced(ast::Program<ConnectorSelection>),
+    SuccessRateBased(SuccessRateBasedRoutingConfig),
+}
+
+/// Configuration of the success rate based routing algorithm.
+///
+/// The authorization outcomes of the last `window_size` attempts are tracked for every
+/// (connector, payment method type, currency, card network) bucket, and the connector with the
+/// highest success rate in the bucket of the current payment is tried first.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ToSchema)]
+pub struct SuccessRateBasedRoutingConfig {
+    /// The connectors among which the best performing one is chosen
+    pub connectors: Vec<RoutableConnectorChoice>,
+    /// The number of most recent attempt outcomes considered per bucket
+    #[serde(default = "SuccessRateBasedRoutingConfig::default_window_size")]
+    #[schema(default = 100, example = 100)]
+    pub window_size: u16,
+    /// The minimum number of outcomes a bucket must have before its success rate is trusted
+    #[serde(default = "SuccessRateBasedRoutingConfig::default_min_sample_size")]
+    #[schema(default = 10, example = 10)]
+    pub min_sample_size: u16,
+    /// The percentage of payments routed to a random connector, so that the success rates of
+    /// connectors other than the current best keep getting updated
+    #[serde(default = "SuccessRateBasedRoutingConfig::default_exploration_percent")]
+    #[schema(default = 10, maximum = 100, example = 10)]
+    pub exploration_percent: u8,
+}
+
+impl SuccessRateBasedRoutingConfig {
+    fn default_window_size() -> u16 {
+        100
+    }
+
+    fn default_min_sample_size() -> u16 {
+        10
+    }
+
+    fn default_exploration_percent() -> u8 {
+        10
+    }
 }
 
 impl TryFrom<RoutingAlgorithmSerde> for RoutingAlgorithm {
@@ -291,6 +439,21 @@ impl TryFrom<RoutingAlgorithmSerde> for RoutingAlgorithm {
                     "Connectors list can't be empty for Volume split Algorithm",
                 ))?
             }
+            RoutingAlgorithmSerde::SuccessRateBased(config) if config.connectors.is_empty() => {
+                Err(ParsingError::StructParseFailure(
+                    "Connectors list can't be empty for Success rate based Algorithm",
+                ))?
+            }
+            RoutingAlgorithmSerde::SuccessRateBased(config) if config.window_size == 0 => {
+                Err(ParsingError::StructParseFailure(
+                    "Window size must be greater than zero for Success rate based Algorithm",
+                ))?
+            }
+            RoutingAlgorithmSerde::SuccessRateBased(config) if config.exploration_percent > 100 => {
+                Err(ParsingError::StructParseFailure(
+                    "Exploration percent can't exceed 100 for Success rate based Algorithm",
+                ))?
+            }
             _ => {}
         };
         Ok(match value {
@@ -298,6 +461,7 @@ impl TryFrom<RoutingAlgorithmSerde> for RoutingAlgorithm {
             RoutingAlgorithmSerde::Priority(i) => Self::Priority(i),
             RoutingAlgorithmSerde::VolumeSplit(i) => Self::VolumeSplit(i),
             RoutingAlgorithmSerde::Advanced(i) => Self::Advanced(i),
+            RoutingAlgorithmSerde::SuccessRateBased(i) => Self::SuccessRateBased(i),
         })
     }
 }
@@ -400,6 +564,7 @@ impl RoutingAlgorithm {
             Self::Priority(_) => RoutingAlgorithmKind::Priority,
             Self::VolumeSplit(_) => RoutingAlgorithmKind::VolumeSplit,
             Self::Advanced(_) => RoutingAlgorithmKind::Advanced,
+            Self::SuccessRateBased(_) => RoutingAlgorithmKind::SuccessRateBased,
         }
     }
 }
@@ -461,3 +626,129 @@ pub enum RoutingKind {
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
 #[serde(transparent)]
 pub struct RoutingAlgorithmId(pub String);
+
+/// The routing algorithm to replay historical payments through
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ToSchema)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum RoutingSimulationAlgorithm {
+    /// The id of a routing algorithm stored for the merchant
+    AlgorithmId(String),
+    /// A routing algorithm which has not been stored yet
+    Inline(RoutingAlgorithm),
+}
+
+/// Request to replay the payments made in a time range through a routing algorithm without
+/// affecting any payment
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ToSchema)]
+pub struct RoutingSimulationRequest {
+    pub algorithm: RoutingSimulationAlgorithm,
+    /// The profile whose payments are replayed. Required for inline algorithms, defaults to the
+    /// profile of the algorithm otherwise
+    pub profile_id: Option<String>,
+    /// The time range in which the replayed payments were created
+    pub time_range: crate::payments::TimeRange,
+    /// The maximum number of payments to replay, most recent first
+    #[schema(default = 1000, maximum = 10000)]
+    pub limit: Option<u32>,
+}
+
+/// Volume and success rate of a connector in the replayed payments
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, ToSchema)]
+pub struct ConnectorSimulationResult {
+    pub connector: RoutableConnectors,
+    /// Number of payments that were actually processed through the connector
+    pub actual_volume: u64,
+    /// Number of payments the routing algorithm would have routed to the connector, once
+    /// filtered by the eligibility of the connectors like in live routing
+    pub simulated_volume: u64,
+    /// Difference between the simulated and the actual volume
+    pub volume_diff: i64,
+    /// Share of the payments processed through the connector that were successful, out of the
+    /// ones that reached a terminal authorization state
+    pub historical_success_rate: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, ToSchema)]
+pub struct RoutingSimulationResponse {
+    /// Number of payments replayed through the routing algorithm
+    pub total_payments: u64,
+    /// Number of payments the routing algorithm would have routed to a different connector than
+    /// the one that processed them, out of the payments that were processed by a connector
+    pub rerouted_payments: u64,
+    /// Number of payments for which the routing algorithm did not select any connector
+    pub unroutable_payments: u64,
+    pub connectors: Vec<ConnectorSimulationResult>,
+}
+
+/// A constraint of the configuration of a connector that a payment does not satisfy
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, ToSchema)]
+pub struct IneligibilityReason {
+    /// Human readable description of the unsatisfied constraint
+    #[schema(
+        example = "currency EUR is not one of GBP, USD for payment method type sofort on merchant connector account mca_1FkJ9dPYi7RYHi0Aw63Q"
+    )]
+    pub message: String,
+    /// Key constrained by the constraint, such as `currency`
+    pub key: Option<String>,
+    /// Value of the key in the payment, absent if the payment has no value for the key
+    pub found: Option<String>,
+    /// Values the constraint requires the key to have, or the ones it forbids if `excluded` is set
+    pub expected: Vec<String>,
+    /// Whether the `expected` values are forbidden rather than required
+    pub excluded: bool,
+    /// Merchant connector account whose configuration the constraint comes from
+    pub merchant_connector_id: Option<String>,
+    #[schema(value_type = Option<PaymentMethod>)]
+    pub payment_method: Option<PaymentMethod>,
+    #[schema(value_type = Option<PaymentMethodType>)]
+    pub payment_method_type: Option<PaymentMethodType>,
+}
+
+/// Why a connector was excluded while determining the connectors eligible for a payment
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, ToSchema)]
+pub struct ConnectorIneligibility {
+    pub connector: String,
+    pub reasons: Vec<IneligibilityReason>,
+}
+
+/// Request to explain which connectors are eligible for a payment, running the eligibility
+/// analysis of routing on the stored details of the payment
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RoutingEligibilityRequest {
+    /// The payment whose eligibility is analysed
+    #[schema(example = "pay_mbabizu24mvu3mela5njyhpit4")]
+    pub payment_id: String,
+    /// The connectors to analyse, defaults to the default fallback connectors of the profile of
+    /// the payment
+    pub connectors: Option<Vec<RoutableConnectorChoice>>,
+}
+
+/// Connectors that passed the eligibility analysis, along with why the other ones did not
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize, ToSchema)]
+pub struct EligibilityAnalysisResult {
+    pub eligible_connectors: Vec<RoutableConnectorChoice>,
+    pub ineligible_connectors: Vec<ConnectorIneligibility>,
+}
+
+#[cfg(all(test, feature = "ast_parser"))]
+mod connector_selection_dsl_tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_connector_selection_round_trip() {
+        let selections = [
+            "[stripe, adyen(\"mca_abc123\")]",
+            "volume_split[60% stripe, 40% adyen(\"mca_abc123\")]",
+            "volume_split[50% checkout, 50% stripe(\"mca_\\\"quoted\\\"\")]",
+            "[]",
+        ];
+
+        for text in selections {
+            let (rest, selection) = ConnectorSelection::parse_output(text).unwrap();
+            assert!(rest.is_empty());
+            assert_eq!(selection.format_output(), text);
+        }
+    }
+}
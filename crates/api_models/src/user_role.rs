@@ -36,6 +36,7 @@ pub enum Permission {
     PayoutRead,
     WebhookEventWrite,
     GenerateReport,
+    SchedulerManage,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -1,6 +1,8 @@
+pub mod network;
 pub mod validate;
 use std::ops::Deref;
 
+use common_enums::CardNetwork;
 use common_utils::{date_time, errors};
 use error_stack::report;
 use masking::{PeekInterface, StrongSecret};
@@ -12,6 +14,17 @@ pub use crate::validate::{CardNumber, CardNumberStrategy, CardNumberValidationEr
 #[derive(Serialize)]
 pub struct CardSecurityCode(StrongSecret<u16>);
 
+impl CardSecurityCode {
+    /// Whether a security code with as many digits is valid for cards of the network. Security
+    /// codes of cards of unknown networks can have three or four digits.
+    pub fn is_valid_length_for_network(length: usize, card_network: Option<&CardNetwork>) -> bool {
+        match card_network.and_then(network::get_card_network_rules) {
+            Some(rules) => rules.security_code_length == length,
+            None => (3..=4).contains(&length),
+        }
+    }
+}
+
 impl TryFrom<u16> for CardSecurityCode {
     type Error = error_stack::Report<errors::ValidationError>;
     fn try_from(csc: u16) -> Result<Self, Self::Error> {
@@ -0,0 +1,194 @@
+//! Card network detection
+//!
+//! Card networks are detected from the leading digits of the card number, using the BIN ranges
+//! of the networks. Several networks can match the same card number, as some networks issue
+//! co-badged cards within the ranges of other networks (such as Cartes Bancaires cards within the
+//! Visa and Mastercard ranges), or accept the cards of partner networks.
+
+use common_enums::CardNetwork;
+
+/// A range of leading digits of card numbers, both bounds having the same number of digits
+#[derive(Debug)]
+struct BinRange {
+    start: &'static str,
+    end: &'static str,
+}
+
+impl BinRange {
+    const fn prefix(prefix: &'static str) -> Self {
+        Self {
+            start: prefix,
+            end: prefix,
+        }
+    }
+
+    const fn range(start: &'static str, end: &'static str) -> Self {
+        Self { start, end }
+    }
+
+    fn contains(&self, card_number: &str) -> bool {
+        // Bounds and leading digits of the same length compare the same as their numeric values
+        card_number
+            .get(..self.start.len())
+            .is_some_and(|leading_digits| (self.start..=self.end).contains(&leading_digits))
+    }
+}
+
+/// Rules the card numbers and security codes of the cards of a network follow
+#[derive(Debug)]
+pub struct CardNetworkRules {
+    pub network: CardNetwork,
+    bin_ranges: &'static [BinRange],
+    /// Whether every card of the network is within `bin_ranges`. Co-badging networks issue their
+    /// cards within the ranges of other networks, and only some of their ranges are known.
+    complete_bin_ranges: bool,
+    /// Numbers of digits of the card numbers
+    pub lengths: &'static [usize],
+    /// Number of digits of the card security codes
+    pub security_code_length: usize,
+}
+
+impl CardNetworkRules {
+    fn matches(&self, card_number: &str) -> bool {
+        self.bin_ranges
+            .iter()
+            .any(|bin_range| bin_range.contains(card_number))
+    }
+
+    pub fn is_valid_length(&self, card_number_length: usize) -> bool {
+        self.lengths.contains(&card_number_length)
+    }
+}
+
+/// Rules of the networks, in order of precedence when several networks match a card number
+const CARD_NETWORK_RULES: &[CardNetworkRules] = &[
+    CardNetworkRules {
+        network: CardNetwork::Visa,
+        bin_ranges: &[BinRange::prefix("4")],
+        complete_bin_ranges: true,
+        lengths: &[13, 16, 19],
+        security_code_length: 3,
+    },
+    CardNetworkRules {
+        network: CardNetwork::Mastercard,
+        bin_ranges: &[BinRange::range("51", "55"), BinRange::range("2221", "2720")],
+        complete_bin_ranges: true,
+        lengths: &[16],
+        security_code_length: 3,
+    },
+    CardNetworkRules {
+        network: CardNetwork::AmericanExpress,
+        bin_ranges: &[BinRange::prefix("34"), BinRange::prefix("37")],
+        complete_bin_ranges: true,
+        lengths: &[15],
+        security_code_length: 4,
+    },
+    CardNetworkRules {
+        network: CardNetwork::Maestro,
+        bin_ranges: &[
+            BinRange::prefix("5018"),
+            BinRange::prefix("5020"),
+            BinRange::prefix("5038"),
+            BinRange::prefix("5893"),
+            BinRange::prefix("6304"),
+            BinRange::prefix("6759"),
+            BinRange::range("6761", "6763"),
+        ],
+        complete_bin_ranges: true,
+        lengths: &[12, 13, 14, 15, 16, 17, 18, 19],
+        security_code_length: 3,
+    },
+    CardNetworkRules {
+        network: CardNetwork::DinersClub,
+        bin_ranges: &[
+            BinRange::range("300", "305"),
+            BinRange::prefix("3095"),
+            BinRange::prefix("36"),
+            BinRange::range("38", "39"),
+        ],
+        complete_bin_ranges: true,
+        lengths: &[14, 15, 16, 17, 18, 19],
+        security_code_length: 3,
+    },
+    CardNetworkRules {
+        network: CardNetwork::JCB,
+        bin_ranges: &[BinRange::range("3528", "3589")],
+        complete_bin_ranges: true,
+        lengths: &[16, 17, 18, 19],
+        security_code_length: 3,
+    },
+    CardNetworkRules {
+        network: CardNetwork::UnionPay,
+        bin_ranges: &[BinRange::prefix("62"), BinRange::range("8100", "8171")],
+        complete_bin_ranges: true,
+        lengths: &[16, 17, 18, 19],
+        security_code_length: 3,
+    },
+    CardNetworkRules {
+        network: CardNetwork::Discover,
+        // The 65 range is shared with RuPay, whose part of it is left out
+        bin_ranges: &[
+            BinRange::prefix("6011"),
+            BinRange::range("644", "649"),
+            BinRange::range("650000", "652149"),
+            BinRange::range("653150", "659999"),
+            BinRange::range("622126", "622925"),
+        ],
+        complete_bin_ranges: true,
+        lengths: &[16, 17, 18, 19],
+        security_code_length: 3,
+    },
+    CardNetworkRules {
+        network: CardNetwork::RuPay,
+        // RuPay cards in the 353 and 356 ranges are co-badged with JCB
+        bin_ranges: &[
+            BinRange::range("508500", "508999"),
+            BinRange::range("606985", "607984"),
+            BinRange::range("608001", "608500"),
+            BinRange::range("652150", "653149"),
+            BinRange::range("817200", "819899"),
+            BinRange::range("820000", "822199"),
+            BinRange::prefix("353"),
+            BinRange::prefix("356"),
+        ],
+        complete_bin_ranges: true,
+        lengths: &[16],
+        security_code_length: 3,
+    },
+    // Cartes Bancaires ranges are not published, the ranges of the public test cards are listed
+    CardNetworkRules {
+        network: CardNetwork::CartesBancaires,
+        bin_ranges: &[
+            BinRange::prefix("40000025"),
+            BinRange::prefix("4035501"),
+            BinRange::prefix("55555525"),
+        ],
+        complete_bin_ranges: false,
+        lengths: &[16],
+        security_code_length: 3,
+    },
+];
+
+pub fn get_card_network_rules(network: &CardNetwork) -> Option<&'static CardNetworkRules> {
+    CARD_NETWORK_RULES
+        .iter()
+        .find(|rules| rules.network == *network)
+}
+
+/// Detects the networks of the card number from its leading digits. The first network is the one
+/// the card primarily belongs to, followed by the networks the card is co-badged with.
+pub fn detect_card_networks(card_number: &str) -> Vec<CardNetwork> {
+    CARD_NETWORK_RULES
+        .iter()
+        .filter(|rules| rules.matches(card_number))
+        .map(|rules| rules.network.clone())
+        .collect()
+}
+
+/// Whether the card number could belong to the network, which is the case for networks whose
+/// ranges are not all known even if the card number is not within any of their known ranges
+pub fn is_card_number_of_network(card_number: &str, network: &CardNetwork) -> bool {
+    get_card_network_rules(network).map_or(true, |rules| {
+        !rules.complete_bin_ranges || rules.matches(card_number)
+    })
+}
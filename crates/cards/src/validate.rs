@@ -1,11 +1,14 @@
 use std::{fmt, ops::Deref, str::FromStr};
 
+use common_enums::CardNetwork;
 use masking::{PeekInterface, Strategy, StrongSecret, WithType};
 #[cfg(not(target_arch = "wasm32"))]
 use router_env::{logger, which as router_env_which, Env};
 use serde::{Deserialize, Deserializer, Serialize};
 use thiserror::Error;
 
+use crate::network::{self, detect_card_networks, get_card_network_rules};
+
 ///
 /// Minimum limit of a card number will not be less than 8 by ISO standards
 ///
@@ -35,6 +38,57 @@ impl CardNumber {
     pub fn get_card_no(&self) -> String {
         self.0.peek().chars().collect::<String>()
     }
+    /// Networks of the card, the first being the one the card primarily belongs to and the others
+    /// the ones it is co-badged with
+    pub fn get_card_networks(&self) -> Vec<CardNetwork> {
+        detect_card_networks(self.0.peek())
+    }
+
+    pub fn get_card_network(&self) -> Option<CardNetwork> {
+        self.get_card_networks().into_iter().next()
+    }
+
+    /// Validates the card number against the rules of the network the card is claimed to be of,
+    /// or of the networks detected from the card number if none is claimed, returning the network
+    /// of the card, if known. A card detected to be of several networks only has to follow the
+    /// rules of one of them, and card numbers of unknown networks are only subject to the ISO
+    /// length limits checked on parsing.
+    pub fn validate_card_network(
+        &self,
+        card_network: Option<&CardNetwork>,
+    ) -> Result<Option<CardNetwork>, CardNumberValidationErr> {
+        let card_number = self.0.peek();
+
+        let candidates = match card_network {
+            Some(card_network)
+                if !network::is_card_number_of_network(card_number, card_network) =>
+            {
+                Err(CardNumberValidationErr(
+                    "card number does not belong to the card network",
+                ))?
+            }
+            Some(card_network) => vec![card_network.clone()],
+            None => self.get_card_networks(),
+        };
+
+        let is_valid_length = |card_network: &CardNetwork| {
+            get_card_network_rules(card_network)
+                .map_or(true, |rules| rules.is_valid_length(card_number.len()))
+        };
+
+        if candidates.is_empty() {
+            return Ok(None);
+        }
+
+        candidates
+            .into_iter()
+            .find(is_valid_length)
+            .map(Some)
+            .ok_or(CardNumberValidationErr(
+                "invalid card number length for the card network",
+            ))
+    }
+
     pub fn get_last4(&self) -> String {
         self.0
             .peek()
@@ -267,4 +321,82 @@ mod tests {
         let error_msg = card_number.unwrap_err().to_string();
         assert_eq!(error_msg, "card number invalid".to_string());
     }
+
+    #[test]
+    fn card_network_detection() {
+        use CardNetwork::*;
+
+        for (card_number, card_networks) in [
+            ("4242424242424242", vec![Visa]),
+            ("5555555555554444", vec![Mastercard]),
+            ("2223003122003222", vec![Mastercard]),
+            ("378282246310005", vec![AmericanExpress]),
+            ("6759649826438453", vec![Maestro]),
+            ("36227206271667", vec![DinersClub]),
+            ("3530111333300000", vec![JCB, RuPay]),
+            ("6200000000000005", vec![UnionPay]),
+            ("8100000000000002", vec![UnionPay]),
+            ("6011111111111117", vec![Discover]),
+            ("6500000000000002", vec![Discover]),
+            ("5085000000000007", vec![RuPay]),
+            ("6070000000000002", vec![RuPay]),
+            ("6521000000000007", vec![Discover]),
+            ("6522000000000006", vec![RuPay]),
+            ("8172000000000005", vec![RuPay]),
+            ("4000002500001001", vec![Visa, CartesBancaires]),
+            ("5555552500001001", vec![Mastercard, CartesBancaires]),
+        ] {
+            assert_eq!(
+                CardNumber::from_str(card_number)
+                    .unwrap()
+                    .get_card_networks(),
+                card_networks,
+                "{card_number}"
+            );
+        }
+
+        assert!(detect_card_networks("9123456789").is_empty());
+    }
+
+    #[test]
+    fn invalid_card_number_length_for_network() {
+        // Luhn valid, but too long for an American Express or a Visa card
+        for card_number in ["3700000000000000002", "40000000000000006"] {
+            let card_number = CardNumber::from_str(card_number).unwrap();
+            assert_eq!(
+                card_number
+                    .validate_card_network(None)
+                    .unwrap_err()
+                    .to_string(),
+                "invalid card number length for the card network".to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn card_number_validation_for_network() {
+        let visa = CardNumber::from_str("4242424242424242").unwrap();
+        let co_badged = CardNumber::from_str("4000002500001001").unwrap();
+
+        assert_eq!(
+            visa.validate_card_network(None).unwrap(),
+            Some(CardNetwork::Visa)
+        );
+        assert_eq!(
+            visa.validate_card_network(Some(&CardNetwork::Mastercard))
+                .unwrap_err()
+                .to_string(),
+            "card number does not belong to the card network".to_string()
+        );
+        assert_eq!(
+            co_badged
+                .validate_card_network(Some(&CardNetwork::CartesBancaires))
+                .unwrap(),
+            Some(CardNetwork::CartesBancaires)
+        );
+        // Cartes Bancaires ranges are not all known, so other cards cannot be ruled out
+        assert!(visa
+            .validate_card_network(Some(&CardNetwork::CartesBancaires))
+            .is_ok());
+    }
 }
@@ -1,6 +1,7 @@
 #![allow(clippy::unwrap_used, clippy::expect_used)]
 
 use cards::{CardExpiration, CardExpirationMonth, CardExpirationYear, CardSecurityCode};
+use common_enums::CardNetwork;
 use common_utils::date_time;
 use masking::PeekInterface;
 
@@ -21,6 +22,28 @@ fn test_card_security_code() {
     assert!(invalid_deserialization.is_err());
 }
 
+#[test]
+fn test_card_security_code_length_for_network() {
+    assert!(CardSecurityCode::is_valid_length_for_network(
+        4,
+        Some(&CardNetwork::AmericanExpress)
+    ));
+    assert!(!CardSecurityCode::is_valid_length_for_network(
+        3,
+        Some(&CardNetwork::AmericanExpress)
+    ));
+    assert!(CardSecurityCode::is_valid_length_for_network(
+        3,
+        Some(&CardNetwork::Visa)
+    ));
+    assert!(!CardSecurityCode::is_valid_length_for_network(
+        4,
+        Some(&CardNetwork::Visa)
+    ));
+    assert!(CardSecurityCode::is_valid_length_for_network(4, None));
+    assert!(!CardSecurityCode::is_valid_length_for_network(5, None));
+}
+
 #[test]
 fn test_card_expiration_month() {
     // no panic
@@ -2401,6 +2401,29 @@ pub enum PaymentSource {
     ExternalAuthenticator,
 }
 
+/// The cardholder's decision on an offer of dynamic currency conversion
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum DccDecision {
+    /// Pay in the currency of the card, at the offered amount
+    Accepted,
+    /// Pay in the currency of the payment
+    Declined,
+}
+
 #[derive(Default, Debug, Clone, serde::Deserialize, serde::Serialize, strum::EnumString)]
 pub enum BrowserName {
     #[default]
@@ -2713,6 +2736,7 @@ pub enum PermissionGroup {
     MerchantDetailsView,
     MerchantDetailsManage,
     OrganizationManage,
+    InternalManage,
 }
 
 /// Name of banks supported by Hyperswitch
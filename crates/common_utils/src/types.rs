@@ -705,3 +705,73 @@ pub struct ChargeRefunds {
 }
 
 crate::impl_to_sql_from_sql_json!(ChargeRefunds);
+
+/// Offer of dynamic currency conversion, to pay in the currency the card was issued in instead of
+/// the currency of the payment
+#[derive(
+    Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq, FromSqlRow, AsExpression, ToSchema,
+)]
+#[diesel(sql_type = Jsonb)]
+pub struct DccOffer {
+    /// Amount of the payment, in the currency of the payment
+    #[schema(value_type = i64, example = 6540)]
+    pub original_amount: MinorUnit,
+
+    /// Currency of the payment
+    #[schema(value_type = Currency, example = "USD")]
+    pub original_currency: enums::Currency,
+
+    /// Amount to be paid if the offer is accepted, in the currency of the card
+    #[schema(value_type = i64, example = 6018)]
+    pub converted_amount: MinorUnit,
+
+    /// Currency the card was issued in
+    #[schema(value_type = Currency, example = "EUR")]
+    pub converted_currency: enums::Currency,
+
+    /// Exchange rate between the currencies, before the markup
+    #[schema(value_type = String, example = "0.92")]
+    pub rate: Decimal,
+
+    /// Markup added to the exchange rate
+    #[schema(value_type = String, example = "0.0276")]
+    pub markup: Decimal,
+
+    /// Exchange rate the amount is converted at, including the markup
+    #[schema(value_type = String, example = "0.9476")]
+    pub applied_rate: Decimal,
+
+    /// The cardholder's decision on the offer, not set while the offer awaits it
+    #[schema(value_type = Option<DccDecision>)]
+    pub decision: Option<enums::DccDecision>,
+}
+
+impl DccOffer {
+    /// Whether the offer awaits the decision of the cardholder
+    pub fn is_pending(&self) -> bool {
+        self.decision.is_none()
+    }
+
+    /// Whether the payment is to be made in the currency of the card
+    pub fn is_accepted(&self) -> bool {
+        self.decision == Some(enums::DccDecision::Accepted)
+    }
+
+    /// Converts an amount in the currency of the payment, such as an amount to capture or to
+    /// refund, to the currency of the card, in proportion to the offered amounts. The amount of
+    /// the payment converts to the offered amount exactly.
+    pub fn convert_amount(&self, amount: MinorUnit) -> Option<MinorUnit> {
+        if amount == self.original_amount {
+            return Some(self.converted_amount);
+        }
+
+        Decimal::from(amount.get_amount_as_i64())
+            .checked_mul(Decimal::from(self.converted_amount.get_amount_as_i64()))?
+            .checked_div(Decimal::from(self.original_amount.get_amount_as_i64()))?
+            .round_dp_with_strategy(0, rust_decimal::RoundingStrategy::MidpointAwayFromZero)
+            .to_i64()
+            .map(MinorUnit::new)
+    }
+}
+
+crate::impl_to_sql_from_sql_json!(DccOffer);
@@ -1,10 +1,12 @@
 use common_enums::Currency;
-use rust_decimal::Decimal;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
 use rusty_money::Money;
 
 use crate::{
     error::CurrencyConversionError,
-    types::{currency_match, ExchangeRates},
+    types::{
+        currency_match, ConversionPolicy, ConversionResult, ExchangeRates, ExchangeRatesHistory,
+    },
 };
 
 pub fn convert(
@@ -26,12 +28,77 @@ pub fn convert(
     }
 }
 
+/// Converts the amount at the exchange rates that applied at `timestamp`, in unix seconds
+pub fn convert_at(
+    history: &ExchangeRatesHistory,
+    timestamp: i64,
+    from_currency: Currency,
+    to_currency: Currency,
+    amount: i64,
+) -> Result<Decimal, CurrencyConversionError> {
+    let ex_rates = history.get_rates_at(timestamp).ok_or(
+        CurrencyConversionError::ExchangeRatesNotAvailable(timestamp),
+    )?;
+    convert(ex_rates, from_currency, to_currency, amount)
+}
+
+/// Converts the amount, in minor units of `from_currency`, under the conversion policy. The
+/// amount is rounded to the minor units of `to_currency`.
+pub fn convert_with_policy(
+    ex_rates: &ExchangeRates,
+    policy: &ConversionPolicy,
+    from_currency: Currency,
+    to_currency: Currency,
+    amount: i64,
+) -> Result<ConversionResult, CurrencyConversionError> {
+    let rate = ex_rates.get_rate(from_currency, to_currency)?;
+    let spread = policy
+        .get_spread(from_currency, to_currency)
+        .map(|spread| spread.get_spread_for_rate(rate))
+        .transpose()?
+        .unwrap_or_default();
+    let applied_rate = rate
+        .checked_add(spread)
+        .ok_or(CurrencyConversionError::DecimalMultiplicationFailed)?;
+
+    let major_amount = Decimal::try_new(
+        amount,
+        u32::from(from_currency.number_of_digits_after_decimal_point()),
+    )
+    .map_err(|_| CurrencyConversionError::MinorUnitConversionFailed)?;
+    let converted_amount = major_amount
+        .checked_mul(applied_rate)
+        .ok_or(CurrencyConversionError::DecimalMultiplicationFailed)?;
+
+    let rounded_amount = converted_amount.round_dp_with_strategy(
+        u32::from(policy.get_rounding_digits(to_currency)),
+        policy.get_rounding_mode(from_currency, to_currency).into(),
+    );
+    let minor_amount = 10_i64
+        .checked_pow(u32::from(
+            to_currency.number_of_digits_after_decimal_point(),
+        ))
+        .and_then(|minor_units| rounded_amount.checked_mul(Decimal::from(minor_units)))
+        .and_then(|minor_amount| minor_amount.to_i64())
+        .ok_or(CurrencyConversionError::MinorUnitConversionFailed)?;
+
+    Ok(ConversionResult {
+        from_currency,
+        to_currency,
+        rate,
+        spread,
+        applied_rate,
+        converted_amount,
+        minor_amount,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::expect_used)]
     use std::collections::HashMap;
 
-    use crate::types::CurrencyFactors;
+    use crate::types::{CurrencyFactors, CurrencyPairPolicy, RoundingMode, Spread};
     #[test]
     fn currency_to_currency_conversion() {
         use super::*;
@@ -98,4 +165,140 @@ mod tests {
             amount, convert_from, convert_to, res
         );
     }
+
+    #[test]
+    fn historical_currency_conversion() {
+        use super::*;
+        let sample_rate = |inr_to_factor: Decimal| {
+            let mut conversion: HashMap<Currency, CurrencyFactors> = HashMap::new();
+            conversion.insert(
+                Currency::INR,
+                CurrencyFactors::new(inr_to_factor, Decimal::new(1214, 5)),
+            );
+            ExchangeRates::new(Currency::USD, conversion)
+        };
+        let history = ExchangeRatesHistory::from_iter([
+            (1_700_000_000, sample_rate(Decimal::new(823173, 4))),
+            (1_700_003_600, sample_rate(Decimal::new(83, 0))),
+        ]);
+
+        let res = convert_at(&history, 1_700_003_599, Currency::USD, Currency::INR, 2000)
+            .expect("converted_currency");
+        assert_eq!(res, Decimal::new(1646346, 3));
+
+        let res = convert_at(&history, 1_700_003_600, Currency::USD, Currency::INR, 2000)
+            .expect("converted_currency");
+        assert_eq!(res, Decimal::new(1660, 0));
+
+        assert!(matches!(
+            convert_at(&history, 1_699_999_999, Currency::USD, Currency::INR, 2000),
+            Err(CurrencyConversionError::ExchangeRatesNotAvailable(
+                1_699_999_999
+            ))
+        ));
+    }
+
+    fn sample_policy_rates() -> ExchangeRates {
+        use super::*;
+        let mut conversion: HashMap<Currency, CurrencyFactors> = HashMap::new();
+        conversion.insert(
+            Currency::JPY,
+            CurrencyFactors::new(Decimal::new(150, 0), Decimal::new(67, 4)),
+        );
+        conversion.insert(
+            Currency::KWD,
+            CurrencyFactors::new(Decimal::new(3075, 4), Decimal::new(325, 2)),
+        );
+        ExchangeRates::new(Currency::USD, conversion)
+    }
+
+    #[test]
+    fn zero_and_three_decimal_currency_conversion() {
+        use super::*;
+        let sample_rate = sample_policy_rates();
+        let policy = ConversionPolicy::default();
+        let cases = [
+            (Currency::USD, Currency::JPY, 1000, 1500),
+            (Currency::JPY, Currency::USD, 1500, 1005),
+            (Currency::USD, Currency::KWD, 1000, 3075),
+            (Currency::KWD, Currency::USD, 3075, 999),
+            (Currency::KWD, Currency::JPY, 12345, 6018),
+            (Currency::JPY, Currency::KWD, 1500, 3090),
+            (Currency::JPY, Currency::JPY, 1500, 1500),
+        ];
+        for (from_currency, to_currency, amount, expected) in cases {
+            let res =
+                convert_with_policy(&sample_rate, &policy, from_currency, to_currency, amount)
+                    .expect("converted_currency");
+            assert_eq!(
+                res.minor_amount, expected,
+                "{amount} {from_currency} to {to_currency}"
+            );
+        }
+    }
+
+    #[test]
+    fn currency_conversion_rounding_modes() {
+        use super::*;
+        let sample_rate = sample_policy_rates();
+        let cases = [
+            (RoundingMode::HalfEven, 4, 3136),
+            (RoundingMode::HalfUp, 5, 3137),
+            (RoundingMode::Down, 4, 3136),
+            (RoundingMode::Up, 5, 3137),
+        ];
+        for (rounding_mode, expected_jpy, expected_kwd) in cases {
+            let policy = ConversionPolicy {
+                rounding_mode,
+                ..Default::default()
+            };
+            // 0.03 USD is 4.5 JPY
+            let res = convert_with_policy(&sample_rate, &policy, Currency::USD, Currency::JPY, 3)
+                .expect("converted_currency");
+            assert_eq!(res.minor_amount, expected_jpy, "{rounding_mode:?}");
+            // 10.20 USD is 3.1365 KWD
+            let res =
+                convert_with_policy(&sample_rate, &policy, Currency::USD, Currency::KWD, 1020)
+                    .expect("converted_currency");
+            assert_eq!(res.minor_amount, expected_kwd, "{rounding_mode:?}");
+        }
+
+        let policy = ConversionPolicy {
+            rounding_digits: Some(2),
+            ..Default::default()
+        };
+        // 12.34 USD is 3.79455 KWD
+        let res = convert_with_policy(&sample_rate, &policy, Currency::USD, Currency::KWD, 1234)
+            .expect("converted_currency");
+        assert_eq!(res.minor_amount, 3790);
+    }
+
+    #[test]
+    fn currency_conversion_spreads() {
+        use super::*;
+        let sample_rate = sample_policy_rates();
+        let policy = ConversionPolicy {
+            spread: Some(Spread::Percentage(Decimal::new(2, 0))),
+            currency_pair_overrides: vec![CurrencyPairPolicy {
+                from_currency: Currency::USD,
+                to_currency: Currency::JPY,
+                rounding_mode: None,
+                spread: Some(Spread::Fixed(Decimal::new(5, 1))),
+            }],
+            ..Default::default()
+        };
+
+        let res = convert_with_policy(&sample_rate, &policy, Currency::USD, Currency::JPY, 1000)
+            .expect("converted_currency");
+        assert_eq!(res.rate, Decimal::new(150, 0));
+        assert_eq!(res.spread, Decimal::new(5, 1));
+        assert_eq!(res.applied_rate, Decimal::new(1505, 1));
+        assert_eq!(res.minor_amount, 1505);
+
+        let res = convert_with_policy(&sample_rate, &policy, Currency::USD, Currency::KWD, 1000)
+            .expect("converted_currency");
+        assert_eq!(res.spread, Decimal::new(615, 5));
+        assert_eq!(res.applied_rate, Decimal::new(31365, 5));
+        assert_eq!(res.minor_amount, 3136);
+    }
 }
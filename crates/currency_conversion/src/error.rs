@@ -5,4 +5,8 @@ pub enum CurrencyConversionError {
     DecimalMultiplicationFailed,
     #[error("Currency not supported: '{0}'")]
     ConversionNotSupported(String),
+    #[error("Exchange rates not available at timestamp: '{0}'")]
+    ExchangeRatesNotAvailable(i64),
+    #[error("Amount can't be represented in minor units")]
+    MinorUnitConversionFailed,
 }
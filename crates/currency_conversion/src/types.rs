@@ -1,7 +1,7 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use common_enums::Currency;
-use rust_decimal::Decimal;
+use rust_decimal::{Decimal, RoundingStrategy};
 use rusty_money::iso;
 
 use crate::error::CurrencyConversionError;
@@ -57,6 +57,27 @@ impl ExchangeRates {
             .ok_or(CurrencyConversionError::DecimalMultiplicationFailed)
     }
 
+    /// The rate amounts in `from_currency` are multiplied by to give amounts in `to_currency`
+    pub fn get_rate(
+        &self,
+        from_currency: Currency,
+        to_currency: Currency,
+    ) -> Result<Decimal, CurrencyConversionError> {
+        if from_currency == to_currency {
+            return Ok(Decimal::ONE);
+        }
+        let to_base_rate = if from_currency == self.base_currency {
+            Decimal::ONE
+        } else {
+            self.forward_conversion(Decimal::ONE, from_currency)?
+        };
+        if to_currency == self.base_currency {
+            Ok(to_base_rate)
+        } else {
+            self.backward_conversion(to_base_rate, to_currency)
+        }
+    }
+
     /// from base_currency -> to_currency
     pub fn backward_conversion(
         &self,
@@ -75,6 +96,162 @@ impl ExchangeRates {
     }
 }
 
+/// Snapshots of exchange rates by the unix timestamp from which they applied
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct ExchangeRatesHistory {
+    snapshots: BTreeMap<i64, ExchangeRates>,
+}
+
+impl ExchangeRatesHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the exchange rates that apply from `timestamp` until the next snapshot
+    pub fn insert(&mut self, timestamp: i64, ex_rates: ExchangeRates) {
+        self.snapshots.insert(timestamp, ex_rates);
+    }
+
+    /// The exchange rates that applied at `timestamp`, which are those of the latest snapshot
+    /// taken at or before it
+    pub fn get_rates_at(&self, timestamp: i64) -> Option<&ExchangeRates> {
+        self.snapshots
+            .range(..=timestamp)
+            .next_back()
+            .map(|(_, ex_rates)| ex_rates)
+    }
+}
+
+impl FromIterator<(i64, ExchangeRates)> for ExchangeRatesHistory {
+    fn from_iter<T: IntoIterator<Item = (i64, ExchangeRates)>>(iter: T) -> Self {
+        Self {
+            snapshots: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// How converted amounts are rounded to the minor units of the currency converted to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    /// Round to the nearest minor unit, and midpoints to the even minor unit
+    #[default]
+    HalfEven,
+    /// Round to the nearest minor unit, and midpoints away from zero
+    HalfUp,
+    /// Round towards zero
+    Down,
+    /// Round away from zero
+    Up,
+}
+
+impl From<RoundingMode> for RoundingStrategy {
+    fn from(value: RoundingMode) -> Self {
+        match value {
+            RoundingMode::HalfEven => Self::MidpointNearestEven,
+            RoundingMode::HalfUp => Self::MidpointAwayFromZero,
+            RoundingMode::Down => Self::ToZero,
+            RoundingMode::Up => Self::AwayFromZero,
+        }
+    }
+}
+
+/// Spread added to the exchange rate, which increases the converted amounts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum Spread {
+    /// Amount added to the exchange rate
+    Fixed(Decimal),
+    /// Percentage of the exchange rate added to it
+    Percentage(Decimal),
+}
+
+impl Spread {
+    /// The amount added to the exchange rate
+    pub fn get_spread_for_rate(&self, rate: Decimal) -> Result<Decimal, CurrencyConversionError> {
+        match self {
+            Self::Fixed(spread) => Ok(*spread),
+            Self::Percentage(percentage) => rate
+                .checked_mul(*percentage)
+                .and_then(|spread| spread.checked_div(Decimal::ONE_HUNDRED))
+                .ok_or(CurrencyConversionError::DecimalMultiplicationFailed),
+        }
+    }
+}
+
+/// Overrides of the conversion policy for conversions from one currency to another
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CurrencyPairPolicy {
+    pub from_currency: Currency,
+    pub to_currency: Currency,
+    pub rounding_mode: Option<RoundingMode>,
+    pub spread: Option<Spread>,
+}
+
+/// How amounts are converted at the exchange rates
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ConversionPolicy {
+    pub rounding_mode: RoundingMode,
+    /// Number of decimal digits converted amounts are rounded to, at most the number of digits of
+    /// the minor units of the currency converted to, which is the default
+    pub rounding_digits: Option<u8>,
+    pub spread: Option<Spread>,
+    pub currency_pair_overrides: Vec<CurrencyPairPolicy>,
+}
+
+impl ConversionPolicy {
+    fn get_currency_pair_policy(
+        &self,
+        from_currency: Currency,
+        to_currency: Currency,
+    ) -> Option<&CurrencyPairPolicy> {
+        self.currency_pair_overrides.iter().find(|pair_policy| {
+            pair_policy.from_currency == from_currency && pair_policy.to_currency == to_currency
+        })
+    }
+
+    pub fn get_rounding_mode(
+        &self,
+        from_currency: Currency,
+        to_currency: Currency,
+    ) -> RoundingMode {
+        self.get_currency_pair_policy(from_currency, to_currency)
+            .and_then(|pair_policy| pair_policy.rounding_mode)
+            .unwrap_or(self.rounding_mode)
+    }
+
+    pub fn get_spread(&self, from_currency: Currency, to_currency: Currency) -> Option<Spread> {
+        self.get_currency_pair_policy(from_currency, to_currency)
+            .and_then(|pair_policy| pair_policy.spread)
+            .or(self.spread)
+    }
+
+    pub fn get_rounding_digits(&self, to_currency: Currency) -> u8 {
+        let minor_units = to_currency.number_of_digits_after_decimal_point();
+        self.rounding_digits.map_or(minor_units, |rounding_digits| {
+            rounding_digits.min(minor_units)
+        })
+    }
+}
+
+/// Amount converted under a conversion policy
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ConversionResult {
+    pub from_currency: Currency,
+    pub to_currency: Currency,
+    /// Exchange rate before the spread
+    pub rate: Decimal,
+    /// Spread added to the exchange rate
+    pub spread: Decimal,
+    /// Exchange rate the amount was converted at
+    pub applied_rate: Decimal,
+    /// Converted amount before rounding, in major units
+    pub converted_amount: Decimal,
+    /// Rounded converted amount, in minor units
+    pub minor_amount: i64,
+}
+
 pub fn currency_match(currency: Currency) -> &'static iso::Currency {
     match currency {
         Currency::AED => iso::AED,
@@ -1,10 +1,17 @@
-use diesel::{Identifiable, Queryable, Selectable};
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable, Selectable};
 use time::PrimitiveDateTime;
 
 use crate::{enums as storage_enums, schema::cards_info};
 
 #[derive(
-    Clone, Debug, Queryable, Identifiable, Selectable, serde::Deserialize, serde::Serialize,
+    Clone,
+    Debug,
+    Queryable,
+    Identifiable,
+    Selectable,
+    Insertable,
+    serde::Deserialize,
+    serde::Serialize,
 )]
 #[diesel(table_name = cards_info, primary_key(card_iin), check_for_backend(diesel::pg::Pg))]
 pub struct CardInfo {
@@ -20,4 +27,24 @@ pub struct CardInfo {
     pub date_created: PrimitiveDateTime,
     pub last_updated: Option<PrimitiveDateTime>,
     pub last_updated_provider: Option<String>,
+    /// Last BIN of the range of BINs starting at `card_iin`, of the same length as `card_iin`.
+    /// The row applies to `card_iin` alone if not set.
+    pub card_iin_range_end: Option<String>,
+}
+
+/// Replaces every field of the card info, unset fields included
+#[derive(Clone, Debug, PartialEq, Eq, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = cards_info, treat_none_as_null = true)]
+pub struct UpdateCardInfo {
+    pub card_issuer: Option<String>,
+    pub card_network: Option<storage_enums::CardNetwork>,
+    pub card_type: Option<String>,
+    pub card_subtype: Option<String>,
+    pub card_issuing_country: Option<String>,
+    pub bank_code_id: Option<String>,
+    pub bank_code: Option<String>,
+    pub country_code: Option<String>,
+    pub last_updated: Option<PrimitiveDateTime>,
+    pub last_updated_provider: Option<String>,
+    pub card_iin_range_end: Option<String>,
 }
@@ -47,6 +47,7 @@ pub enum RoutingAlgorithmKind {
     Priority,
     VolumeSplit,
     Advanced,
+    SuccessRateBased,
 }
 
 #[derive(
@@ -96,6 +97,8 @@ pub enum ProcessTrackerStatus {
     ProcessStarted,
     // Finished by consumer
     Finish,
+    // Recurring task paused, not scheduled until it is resumed
+    Paused,
 }
 
 // Refund
@@ -0,0 +1,24 @@
+use diesel::{Identifiable, Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+
+use crate::{enums as storage_enums, schema::exchange_rates_history};
+
+#[derive(Clone, Debug, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = exchange_rates_history)]
+pub struct ExchangeRatesSnapshotNew {
+    pub base_currency: storage_enums::Currency,
+    pub conversion: serde_json::Value,
+    pub created_at: PrimitiveDateTime,
+}
+
+/// Exchange rates that applied from `created_at` until the next snapshot was taken
+#[derive(Clone, Debug, Identifiable, Queryable, Selectable, Serialize, Deserialize)]
+#[diesel(table_name = exchange_rates_history, check_for_backend(diesel::pg::Pg))]
+pub struct ExchangeRatesSnapshot {
+    pub id: i32,
+    pub base_currency: storage_enums::Currency,
+    /// Currency factors by currency, relative to `base_currency`
+    pub conversion: serde_json::Value,
+    pub created_at: PrimitiveDateTime,
+}
@@ -53,6 +53,8 @@ impl DBOperation {
                 Updateable::PayoutAttemptUpdate(_) => "payout_attempt",
                 Updateable::PaymentMethodUpdate(_) => "payment_method",
                 Updateable::MandateUpdate(_) => " mandate",
+                Updateable::PaymentIntentOverwrite(_) => "payment_intent",
+                Updateable::PaymentAttemptOverwrite(_) => "payment_attempt",
             },
         }
     }
@@ -147,6 +149,12 @@ impl DBOperation {
                     )
                     .await?,
                 )),
+                Updateable::PaymentIntentOverwrite(a) => {
+                    DBResult::PaymentIntent(Box::new(a.overwrite(conn).await?))
+                }
+                Updateable::PaymentAttemptOverwrite(a) => {
+                    DBResult::PaymentAttempt(Box::new(a.overwrite(conn).await?))
+                }
             },
         })
     }
@@ -200,6 +208,12 @@ pub enum Updateable {
     PayoutAttemptUpdate(PayoutAttemptUpdateMems),
     PaymentMethodUpdate(PaymentMethodUpdateMems),
     MandateUpdate(MandateUpdateMems),
+    /// Overwrites the row of the payment intent with the payment intent, to repair rows that
+    /// diverged from the KV store
+    PaymentIntentOverwrite(Box<PaymentIntent>),
+    /// Overwrites the row of the payment attempt with the payment attempt, to repair rows that
+    /// diverged from the KV store
+    PaymentAttemptOverwrite(Box<PaymentAttempt>),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -16,6 +16,7 @@ pub mod enums;
 pub mod ephemeral_key;
 pub mod errors;
 pub mod events;
+pub mod exchange_rates_history;
 pub mod file;
 #[allow(unused)]
 pub mod fraud_check;
@@ -1,4 +1,4 @@
-use common_utils::pii;
+use common_utils::{pii, types::DccOffer};
 use diesel::{AsChangeset, Identifiable, Insertable, Queryable, Selectable};
 use serde::{Deserialize, Serialize};
 use time::PrimitiveDateTime;
@@ -11,9 +11,20 @@ use crate::schema_v2::payment_attempt;
 
 #[cfg(all(feature = "v2", feature = "payment_v2"))]
 #[derive(
-    Clone, Debug, Eq, PartialEq, Identifiable, Queryable, Serialize, Deserialize, Selectable,
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    AsChangeset,
+    Identifiable,
+    Queryable,
+    Serialize,
+    Deserialize,
+    Selectable,
 )]
 #[diesel(table_name = payment_attempt, primary_key(attempt_id, merchant_id), check_for_backend(diesel::pg::Pg))]
+// Fields that are `None` are set to `NULL` when the row is overwritten, for repairs to clear them
+#[diesel(treat_none_as_null = true)]
 pub struct PaymentAttempt {
     pub payment_id: String,
     pub merchant_id: String,
@@ -78,13 +89,34 @@ pub struct PaymentAttempt {
     pub client_source: Option<String>,
     pub client_version: Option<String>,
     pub customer_acceptance: Option<pii::SecretSerdeValue>,
+    pub dcc_offer: Option<DccOffer>,
+}
+
+#[cfg(all(feature = "v2", feature = "payment_v2"))]
+impl PaymentAttempt {
+    /// Keeps the fields of the row that the entity of the KV store does not have, for the row to
+    /// be overwritten with the entity
+    pub fn keep_row_fields(self, _row: &Self) -> Self {
+        self
+    }
 }
 
 #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "payment_v2")))]
 #[derive(
-    Clone, Debug, Eq, PartialEq, Identifiable, Queryable, Serialize, Deserialize, Selectable,
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    AsChangeset,
+    Identifiable,
+    Queryable,
+    Serialize,
+    Deserialize,
+    Selectable,
 )]
 #[diesel(table_name = payment_attempt, primary_key(attempt_id, merchant_id), check_for_backend(diesel::pg::Pg))]
+// Fields that are `None` are set to `NULL` when the row is overwritten, for repairs to clear them
+#[diesel(treat_none_as_null = true)]
 pub struct PaymentAttempt {
     pub id: Option<i32>,
     pub payment_id: String,
@@ -150,6 +182,16 @@ pub struct PaymentAttempt {
     pub client_source: Option<String>,
     pub client_version: Option<String>,
     pub customer_acceptance: Option<pii::SecretSerdeValue>,
+    pub dcc_offer: Option<DccOffer>,
+}
+
+#[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "payment_v2")))]
+impl PaymentAttempt {
+    /// Keeps the fields of the row that the entity of the KV store does not have, for the row to
+    /// be overwritten with the entity
+    pub fn keep_row_fields(self, row: &Self) -> Self {
+        Self { id: row.id, ..self }
+    }
 }
 
 impl PaymentAttempt {
@@ -232,6 +274,7 @@ pub struct PaymentAttemptNew {
     pub client_source: Option<String>,
     pub client_version: Option<String>,
     pub customer_acceptance: Option<pii::SecretSerdeValue>,
+    pub dcc_offer: Option<DccOffer>,
 }
 
 impl PaymentAttemptNew {
@@ -318,6 +361,7 @@ pub enum PaymentAttemptUpdate {
         client_source: Option<String>,
         client_version: Option<String>,
         customer_acceptance: Option<pii::SecretSerdeValue>,
+        dcc_offer: Option<DccOffer>,
     },
     VoidUpdate {
         status: storage_enums::AttemptStatus,
@@ -489,6 +533,7 @@ pub struct PaymentAttemptUpdateInternal {
     client_source: Option<String>,
     client_version: Option<String>,
     customer_acceptance: Option<pii::SecretSerdeValue>,
+    dcc_offer: Option<DccOffer>,
 }
 
 impl PaymentAttemptUpdateInternal {
@@ -558,6 +603,7 @@ impl PaymentAttemptUpdate {
             client_source,
             client_version,
             customer_acceptance,
+            dcc_offer,
         } = PaymentAttemptUpdateInternal::from(self).populate_derived_fields(&source);
         PaymentAttempt {
             amount: amount.unwrap_or(source.amount),
@@ -610,6 +656,7 @@ impl PaymentAttemptUpdate {
             client_source: client_source.or(source.client_source),
             client_version: client_version.or(source.client_version),
             customer_acceptance: customer_acceptance.or(source.customer_acceptance),
+            dcc_offer: dcc_offer.or(source.dcc_offer),
             ..source
         }
     }
@@ -685,6 +732,7 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 client_source: None,
                 client_version: None,
                 customer_acceptance: None,
+                dcc_offer: None,
             },
             PaymentAttemptUpdate::AuthenticationTypeUpdate {
                 authentication_type,
@@ -736,6 +784,7 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 client_source: None,
                 client_version: None,
                 customer_acceptance: None,
+                dcc_offer: None,
             },
             PaymentAttemptUpdate::ConfirmUpdate {
                 amount,
@@ -768,6 +817,7 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 client_source,
                 client_version,
                 customer_acceptance,
+                dcc_offer,
             } => Self {
                 amount: Some(amount),
                 currency: Some(currency),
@@ -800,6 +850,7 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 client_source,
                 client_version,
                 customer_acceptance,
+                dcc_offer,
                 net_amount: None,
                 connector_transaction_id: None,
                 amount_to_capture: None,
@@ -867,6 +918,7 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 client_source: None,
                 client_version: None,
                 customer_acceptance: None,
+                dcc_offer: None,
             },
             PaymentAttemptUpdate::RejectUpdate {
                 status,
@@ -920,6 +972,7 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 client_source: None,
                 client_version: None,
                 customer_acceptance: None,
+                dcc_offer: None,
             },
             PaymentAttemptUpdate::BlocklistUpdate {
                 status,
@@ -973,6 +1026,7 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 client_source: None,
                 client_version: None,
                 customer_acceptance: None,
+                dcc_offer: None,
             },
             PaymentAttemptUpdate::PaymentMethodDetailsUpdate {
                 payment_method_id,
@@ -1024,6 +1078,7 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 client_source: None,
                 client_version: None,
                 customer_acceptance: None,
+                dcc_offer: None,
             },
             PaymentAttemptUpdate::ResponseUpdate {
                 status,
@@ -1093,6 +1148,7 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 client_source: None,
                 client_version: None,
                 customer_acceptance: None,
+                dcc_offer: None,
             },
             PaymentAttemptUpdate::ErrorUpdate {
                 connector,
@@ -1154,6 +1210,7 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 client_source: None,
                 client_version: None,
                 customer_acceptance: None,
+                dcc_offer: None,
             },
             PaymentAttemptUpdate::StatusUpdate { status, updated_by } => Self {
                 status: Some(status),
@@ -1202,6 +1259,7 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 client_source: None,
                 client_version: None,
                 customer_acceptance: None,
+                dcc_offer: None,
             },
             PaymentAttemptUpdate::UpdateTrackers {
                 payment_token,
@@ -1259,6 +1317,7 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 client_source: None,
                 client_version: None,
                 customer_acceptance: None,
+                dcc_offer: None,
             },
             PaymentAttemptUpdate::UnresolvedResponseUpdate {
                 status,
@@ -1317,6 +1376,7 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 client_source: None,
                 client_version: None,
                 customer_acceptance: None,
+                dcc_offer: None,
             },
             PaymentAttemptUpdate::PreprocessingUpdate {
                 status,
@@ -1373,6 +1433,7 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 client_source: None,
                 client_version: None,
                 customer_acceptance: None,
+                dcc_offer: None,
             },
             PaymentAttemptUpdate::CaptureUpdate {
                 multiple_capture_count,
@@ -1425,6 +1486,7 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 client_source: None,
                 client_version: None,
                 customer_acceptance: None,
+                dcc_offer: None,
             },
             PaymentAttemptUpdate::AmountToCaptureUpdate {
                 status,
@@ -1477,6 +1539,7 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 client_source: None,
                 client_version: None,
                 customer_acceptance: None,
+                dcc_offer: None,
             },
             PaymentAttemptUpdate::ConnectorResponse {
                 authentication_data,
@@ -1532,6 +1595,7 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 client_source: None,
                 client_version: None,
                 customer_acceptance: None,
+                dcc_offer: None,
             },
             PaymentAttemptUpdate::IncrementalAuthorizationAmountUpdate {
                 amount,
@@ -1583,6 +1647,7 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 client_source: None,
                 client_version: None,
                 customer_acceptance: None,
+                dcc_offer: None,
             },
             PaymentAttemptUpdate::AuthenticationUpdate {
                 status,
@@ -1637,6 +1702,7 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 client_source: None,
                 client_version: None,
                 customer_acceptance: None,
+                dcc_offer: None,
             },
             PaymentAttemptUpdate::ManualUpdate {
                 status,
@@ -1693,6 +1759,7 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 client_source: None,
                 client_version: None,
                 customer_acceptance: None,
+                dcc_offer: None,
             },
         }
     }
@@ -11,8 +11,20 @@ use crate::schema::payment_intent;
 use crate::schema_v2::payment_intent;
 
 #[cfg(all(feature = "v2", feature = "payment_v2"))]
-#[derive(Clone, Debug, PartialEq, Identifiable, Queryable, Serialize, Deserialize, Selectable)]
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    AsChangeset,
+    Identifiable,
+    Queryable,
+    Serialize,
+    Deserialize,
+    Selectable,
+)]
 #[diesel(table_name = payment_intent, primary_key(payment_id, merchant_id), check_for_backend(diesel::pg::Pg))]
+// Fields that are `None` are set to `NULL` when the row is overwritten, for repairs to clear them
+#[diesel(treat_none_as_null = true)]
 pub struct PaymentIntent {
     pub payment_id: String,
     pub merchant_id: String,
@@ -70,9 +82,30 @@ pub struct PaymentIntent {
     pub shipping_details: Option<Encryption>,
 }
 
+#[cfg(all(feature = "v2", feature = "payment_v2"))]
+impl PaymentIntent {
+    /// Keeps the fields of the row that the entity of the KV store does not have, for the row to
+    /// be overwritten with the entity
+    pub fn keep_row_fields(self, _row: &Self) -> Self {
+        self
+    }
+}
+
 #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "payment_v2")))]
-#[derive(Clone, Debug, PartialEq, Identifiable, Queryable, Serialize, Deserialize, Selectable)]
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    AsChangeset,
+    Identifiable,
+    Queryable,
+    Serialize,
+    Deserialize,
+    Selectable,
+)]
 #[diesel(table_name = payment_intent, primary_key(payment_id, merchant_id), check_for_backend(diesel::pg::Pg))]
+// Fields that are `None` are set to `NULL` when the row is overwritten, for repairs to clear them
+#[diesel(treat_none_as_null = true)]
 pub struct PaymentIntent {
     pub id: Option<i32>,
     pub payment_id: String,
@@ -131,6 +164,15 @@ pub struct PaymentIntent {
     pub shipping_details: Option<Encryption>,
 }
 
+#[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "payment_v2")))]
+impl PaymentIntent {
+    /// Keeps the fields of the row that the entity of the KV store does not have, for the row to
+    /// be overwritten with the entity
+    pub fn keep_row_fields(self, row: &Self) -> Self {
+        Self { id: row.id, ..self }
+    }
+}
+
 #[derive(
     Clone, Debug, PartialEq, Insertable, router_derive::DebugAsDisplay, Serialize, Deserialize,
 )]
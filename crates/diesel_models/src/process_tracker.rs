@@ -1,5 +1,8 @@
 use common_utils::ext_traits::Encode;
-use diesel::{AsChangeset, Identifiable, Insertable, Queryable, Selectable};
+use diesel::{
+    expression::AsExpression, AsChangeset, FromSqlRow, Identifiable, Insertable, Queryable,
+    Selectable,
+};
 use error_stack::ResultExt;
 use serde::{Deserialize, Serialize};
 use time::PrimitiveDateTime;
@@ -38,6 +41,8 @@ pub struct ProcessTracker {
     pub created_at: PrimitiveDateTime,
     #[serde(with = "common_utils::custom_serde::iso8601")]
     pub updated_at: PrimitiveDateTime,
+    pub merchant_id: Option<String>,
+    pub recurrence: Option<RecurringSchedule>,
 }
 
 impl ProcessTracker {
@@ -63,6 +68,8 @@ pub struct ProcessTrackerNew {
     pub event: Vec<String>,
     pub created_at: PrimitiveDateTime,
     pub updated_at: PrimitiveDateTime,
+    pub merchant_id: Option<String>,
+    pub recurrence: Option<RecurringSchedule>,
 }
 
 impl ProcessTrackerNew {
@@ -73,6 +80,7 @@ impl ProcessTrackerNew {
         tag: impl IntoIterator<Item = impl Into<String>>,
         tracking_data: T,
         schedule_time: PrimitiveDateTime,
+        merchant_id: Option<String>,
     ) -> StorageResult<Self>
     where
         T: Serialize + std::fmt::Debug,
@@ -95,8 +103,135 @@ impl ProcessTrackerNew {
             event: vec![],
             created_at: current_time,
             updated_at: current_time,
+            merchant_id,
+            recurrence: None,
         })
     }
+
+    /// Makes the task recurring, the schedule time of the task being the time of its first run
+    pub fn recurring(mut self, recurrence: RecurringSchedule) -> Self {
+        self.recurrence = Some(recurrence);
+        self
+    }
+}
+
+/// Schedule of a recurring task. The task is scheduled for its next run once a run finishes,
+/// whatever the outcome of the run.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, AsExpression, FromSqlRow)]
+#[diesel(sql_type = diesel::sql_types::Jsonb)]
+pub struct RecurringSchedule {
+    /// Cron expression of the runs, made of the minute, hour, day of month, month and day of week
+    /// fields
+    pub cron_expression: String,
+    /// IANA name of the timezone the cron expression is evaluated in, such as `Asia/Kolkata`
+    pub timezone: String,
+    pub missed_run_policy: MissedRunPolicy,
+    /// Time of the run the task is scheduled for, which is before the schedule time of the task
+    /// when catching up on missed runs
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub scheduled_for: PrimitiveDateTime,
+}
+
+common_utils::impl_to_sql_from_sql_json!(RecurringSchedule);
+
+/// What to do with the runs of a recurring task that were missed, because the scheduler was down,
+/// or the previous run finished after them
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, strum::Display, strum::EnumString,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum MissedRunPolicy {
+    /// The missed runs are skipped, the task is scheduled for the first run after the current time
+    Skip,
+    /// Every missed run is executed, one after the other, as soon as possible
+    CatchUp,
+}
+
+/// Filters of the processes to list, the processes matching all of them being listed
+#[derive(Clone, Debug, Default)]
+pub struct ProcessTrackerListConstraints {
+    pub runner: Option<ProcessTrackerRunner>,
+    pub statuses: Option<Vec<storage_enums::ProcessTrackerStatus>>,
+    pub merchant_id: Option<String>,
+    pub scheduled_after: Option<PrimitiveDateTime>,
+    pub scheduled_before: Option<PrimitiveDateTime>,
+    pub updated_before: Option<PrimitiveDateTime>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// Number of events kept in the history of a process, the oldest events being dropped first
+const MAX_PROCESS_EVENTS: usize = 50;
+
+impl ProcessTracker {
+    /// Events of the process, oldest first. Events that cannot be parsed are skipped.
+    pub fn events(&self) -> Vec<ProcessTrackerEvent> {
+        self.event
+            .iter()
+            .filter_map(|event| serde_json::from_str(event).ok())
+            .collect()
+    }
+
+    /// Events of the process once `event` is recorded, to be stored with the update the event is
+    /// recorded with
+    pub fn events_with(&self, event: ProcessTrackerEvent) -> Vec<String> {
+        let mut events = self.event.clone();
+        // Serializing the event cannot fail, as it only has string, number and time fields
+        events.extend(serde_json::to_string(&event).ok());
+        let excess_events = events.len().saturating_sub(MAX_PROCESS_EVENTS);
+        events.drain(..excess_events);
+        events
+    }
+}
+
+/// Event in the history of a process, recorded in the `event` column of the process as JSON
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ProcessTrackerEvent {
+    pub kind: ProcessTrackerEventKind,
+    /// Retry count of the process after the event
+    pub retry_count: i32,
+    /// Schedule time of the process after the event
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub schedule_time: Option<PrimitiveDateTime>,
+    /// Business status of the process before the event, which is the outcome of the run
+    /// before a retry
+    pub previous_business_status: String,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
+
+impl ProcessTrackerEvent {
+    pub fn new(
+        kind: ProcessTrackerEventKind,
+        process: &ProcessTracker,
+        retry_count: i32,
+        schedule_time: Option<PrimitiveDateTime>,
+    ) -> Self {
+        Self {
+            kind,
+            retry_count,
+            schedule_time,
+            previous_business_status: process.business_status.clone(),
+            created_at: common_utils::date_time::now(),
+        }
+    }
+}
+
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, strum::Display, strum::EnumString,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ProcessTrackerEventKind {
+    /// The run of the process failed, and the process was scheduled for a retry
+    Retried,
+    /// The process was scheduled to run right away by an operator
+    ForcedRun,
+    /// The process was rescheduled by an operator
+    Rescheduled,
+    /// The process was cancelled by an operator
+    Cancelled,
 }
 
 #[derive(Debug)]
@@ -119,6 +254,20 @@ pub enum ProcessTrackerUpdate {
         retry_count: i32,
         schedule_time: PrimitiveDateTime,
     },
+    /// Schedules a recurring task for a run
+    RecurrenceUpdate {
+        schedule_time: PrimitiveDateTime,
+        recurrence: RecurringSchedule,
+    },
+    /// Updates the status of the process, along with its events, which include the event the
+    /// update is recorded for
+    StatusEventUpdate {
+        status: storage_enums::ProcessTrackerStatus,
+        business_status: Option<String>,
+        retry_count: Option<i32>,
+        schedule_time: Option<PrimitiveDateTime>,
+        event: Vec<String>,
+    },
 }
 
 #[derive(Debug, Clone, AsChangeset, router_derive::DebugAsDisplay)]
@@ -131,6 +280,8 @@ pub struct ProcessTrackerUpdateInternal {
     business_status: Option<String>,
     status: Option<storage_enums::ProcessTrackerStatus>,
     updated_at: Option<PrimitiveDateTime>,
+    recurrence: Option<RecurringSchedule>,
+    event: Option<Vec<String>>,
 }
 
 impl Default for ProcessTrackerUpdateInternal {
@@ -143,6 +294,8 @@ impl Default for ProcessTrackerUpdateInternal {
             business_status: Option::default(),
             status: Option::default(),
             updated_at: Some(common_utils::date_time::now()),
+            recurrence: Option::default(),
+            event: Option::default(),
         }
     }
 }
@@ -166,6 +319,8 @@ impl From<ProcessTrackerUpdate> for ProcessTrackerUpdateInternal {
                 business_status,
                 status,
                 updated_at,
+                recurrence: None,
+                event: None,
             },
             ProcessTrackerUpdate::StatusUpdate {
                 status,
@@ -185,6 +340,59 @@ impl From<ProcessTrackerUpdate> for ProcessTrackerUpdateInternal {
                 schedule_time: Some(schedule_time),
                 ..Default::default()
             },
+            ProcessTrackerUpdate::RecurrenceUpdate {
+                schedule_time,
+                recurrence,
+            } => Self {
+                status: Some(storage_enums::ProcessTrackerStatus::New),
+                retry_count: Some(0),
+                schedule_time: Some(schedule_time),
+                business_status: Some(String::from(business_status::PENDING)),
+                recurrence: Some(recurrence),
+                ..Default::default()
+            },
+            ProcessTrackerUpdate::StatusEventUpdate {
+                status,
+                business_status,
+                retry_count,
+                schedule_time,
+                event,
+            } => Self {
+                status: Some(status),
+                business_status,
+                retry_count,
+                schedule_time,
+                event: Some(event),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl ProcessTrackerUpdate {
+    pub fn apply_changeset(self, source: ProcessTracker) -> ProcessTracker {
+        let ProcessTrackerUpdateInternal {
+            name,
+            retry_count,
+            schedule_time,
+            tracking_data,
+            business_status,
+            status,
+            updated_at,
+            recurrence,
+            event,
+        } = self.into();
+        ProcessTracker {
+            name: name.or(source.name),
+            retry_count: retry_count.unwrap_or(source.retry_count),
+            schedule_time: schedule_time.or(source.schedule_time),
+            tracking_data: tracking_data.unwrap_or(source.tracking_data),
+            business_status: business_status.unwrap_or(source.business_status),
+            status: status.unwrap_or(source.status),
+            updated_at: updated_at.unwrap_or_else(common_utils::date_time::now),
+            recurrence: recurrence.or(source.recurrence),
+            event: event.unwrap_or(source.event),
+            ..source
         }
     }
 }
@@ -197,6 +405,7 @@ impl From<ProcessTrackerUpdate> for ProcessTrackerUpdateInternal {
     Debug,
     PartialEq,
     Eq,
+    Hash,
     strum::EnumString,
     strum::Display,
 )]
@@ -265,4 +474,8 @@ pub mod business_status {
 
     /// Business status set for newly created tasks.
     pub const PENDING: &str = "Pending";
+
+    /// The task was cancelled by an operator.
+    /// A task that reaches this status should not be retried (rescheduled for execution) later.
+    pub const CANCELLED: &str = "CANCELLED";
 }
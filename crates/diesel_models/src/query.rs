@@ -14,6 +14,7 @@ pub mod customers;
 pub mod dashboard_metadata;
 pub mod dispute;
 pub mod events;
+pub mod exchange_rates_history;
 pub mod file;
 pub mod fraud_check;
 pub mod generic_link;
@@ -1,6 +1,15 @@
-use diesel::associations::HasTable;
+use async_bb8_diesel::AsyncRunQueryDsl;
+use diesel::{associations::HasTable, debug_query, upsert::excluded, ExpressionMethods};
+use error_stack::{report, ResultExt};
+use router_env::logger;
 
-use crate::{cards_info::CardInfo, query::generics, PgPooledConn, StorageResult};
+use crate::{
+    cards_info::{CardInfo, UpdateCardInfo},
+    errors,
+    query::generics,
+    schema::cards_info::dsl,
+    PgPooledConn, StorageResult,
+};
 
 impl CardInfo {
     pub async fn find_by_iin(conn: &PgPooledConn, card_iin: &str) -> StorageResult<Option<Self>> {
@@ -10,4 +19,70 @@ impl CardInfo {
         )
         .await
     }
+
+    pub async fn find_all(conn: &PgPooledConn) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::card_iin.is_not_null(),
+            None,
+            None,
+            Some(dsl::card_iin.asc()),
+        )
+        .await
+    }
+
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<Self> {
+        generics::generic_insert(conn, self).await
+    }
+
+    /// Inserts the card info of new BINs and replaces every field but `date_created` of the card
+    /// info of existing ones, in a single statement.
+    pub async fn upsert_batch(conn: &PgPooledConn, batch: Vec<Self>) -> StorageResult<Vec<Self>> {
+        let query = diesel::insert_into(<Self>::table())
+            .values(batch)
+            .on_conflict(dsl::card_iin)
+            .do_update()
+            .set((
+                dsl::card_issuer.eq(excluded(dsl::card_issuer)),
+                dsl::card_network.eq(excluded(dsl::card_network)),
+                dsl::card_type.eq(excluded(dsl::card_type)),
+                dsl::card_subtype.eq(excluded(dsl::card_subtype)),
+                dsl::card_issuing_country.eq(excluded(dsl::card_issuing_country)),
+                dsl::bank_code_id.eq(excluded(dsl::bank_code_id)),
+                dsl::bank_code.eq(excluded(dsl::bank_code)),
+                dsl::country_code.eq(excluded(dsl::country_code)),
+                dsl::last_updated.eq(excluded(dsl::last_updated)),
+                dsl::last_updated_provider.eq(excluded(dsl::last_updated_provider)),
+                dsl::card_iin_range_end.eq(excluded(dsl::card_iin_range_end)),
+            ));
+
+        logger::debug!(query = %debug_query::<diesel::pg::Pg, _>(&query).to_string());
+
+        generics::db_metrics::track_database_call::<<Self as HasTable>::Table, _, _>(
+            query.get_results_async(conn),
+            generics::db_metrics::DatabaseOperation::Insert,
+        )
+        .await
+        .change_context(errors::DatabaseError::Others)
+        .attach_printable("Error while upserting card info")
+    }
+
+    pub async fn update(
+        conn: &PgPooledConn,
+        card_iin: String,
+        data: UpdateCardInfo,
+    ) -> StorageResult<Self> {
+        generics::generic_update_with_results::<<Self as HasTable>::Table, UpdateCardInfo, _, _>(
+            conn,
+            dsl::card_iin.eq(card_iin),
+            data,
+        )
+        .await?
+        .first()
+        .cloned()
+        .ok_or_else(|| {
+            report!(errors::DatabaseError::NotFound)
+                .attach_printable("Error while updating card_info entry")
+        })
+    }
 }
@@ -0,0 +1,42 @@
+use async_bb8_diesel::AsyncRunQueryDsl;
+use diesel::{associations::HasTable, ExpressionMethods, QueryDsl};
+use error_stack::ResultExt;
+use time::PrimitiveDateTime;
+
+use super::generics;
+use crate::{
+    errors,
+    exchange_rates_history::{ExchangeRatesSnapshot, ExchangeRatesSnapshotNew},
+    schema::exchange_rates_history::dsl,
+    PgPooledConn, StorageResult,
+};
+
+impl ExchangeRatesSnapshotNew {
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<ExchangeRatesSnapshot> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl ExchangeRatesSnapshot {
+    pub async fn find_by_id(conn: &PgPooledConn, id: i32) -> StorageResult<Option<Self>> {
+        generics::generic_find_by_id_optional::<<Self as HasTable>::Table, _, _>(conn, id).await
+    }
+
+    /// Finds the id of the latest snapshot taken at or before `as_of`, without loading the
+    /// exchange rates of the snapshot
+    pub async fn find_latest_id_by_created_at(
+        conn: &PgPooledConn,
+        as_of: PrimitiveDateTime,
+    ) -> StorageResult<Option<i32>> {
+        <Self as HasTable>::table()
+            .select(dsl::id)
+            .filter(dsl::created_at.le(as_of))
+            .order(dsl::created_at.desc())
+            .limit(1)
+            .get_results_async::<i32>(conn)
+            .await
+            .change_context(errors::DatabaseError::Others)
+            .attach_printable("Error finding the latest exchange rates snapshot")
+            .map(|ids| ids.into_iter().next())
+    }
+}
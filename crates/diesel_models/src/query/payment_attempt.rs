@@ -56,6 +56,24 @@ impl PaymentAttempt {
         }
     }
 
+    /// Overwrites the row of the payment attempt with every field of the payment attempt, other
+    /// than its primary key. The fields that are `None` are set to `NULL`
+    pub async fn overwrite(self, conn: &PgPooledConn) -> StorageResult<Self> {
+        generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::attempt_id
+                .eq(self.attempt_id.to_owned())
+                .and(dsl::merchant_id.eq(self.merchant_id.to_owned())),
+            self,
+        )
+        .await
+    }
+
     pub async fn find_optional_by_payment_id_merchant_id(
         conn: &PgPooledConn,
         payment_id: &str,
@@ -44,6 +44,21 @@ impl PaymentIntent {
         }
     }
 
+    /// Overwrites the row of the payment intent with every field of the payment intent, other
+    /// than its primary key. The fields that are `None` are set to `NULL`
+    pub async fn overwrite(self, conn: &PgPooledConn) -> StorageResult<Self> {
+        generics::generic_update_with_results::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::payment_id
+                .eq(self.payment_id.to_owned())
+                .and(dsl::merchant_id.eq(self.merchant_id.to_owned())),
+            self,
+        )
+        .await?
+        .pop()
+        .ok_or(error_stack::report!(errors::DatabaseError::NotFound))
+    }
+
     pub async fn find_by_payment_id_merchant_id(
         conn: &PgPooledConn,
         payment_id: &str,
@@ -6,7 +6,8 @@ use super::generics;
 use crate::{
     enums, errors,
     process_tracker::{
-        ProcessTracker, ProcessTrackerNew, ProcessTrackerUpdate, ProcessTrackerUpdateInternal,
+        ProcessTracker, ProcessTrackerListConstraints, ProcessTrackerNew, ProcessTrackerUpdate,
+        ProcessTrackerUpdateInternal,
     },
     schema::process_tracker::dsl,
     PgPooledConn, StorageResult,
@@ -118,6 +119,125 @@ impl ProcessTracker {
         Ok(x)
     }
 
+    /// Updates the process only if its status is one of `statuses`, returning `None` otherwise
+    #[instrument(skip(conn))]
+    pub async fn update_process_if_status(
+        conn: &PgPooledConn,
+        id: &str,
+        statuses: Vec<enums::ProcessTrackerStatus>,
+        process: ProcessTrackerUpdate,
+    ) -> StorageResult<Option<Self>> {
+        match generics::generic_update_with_results::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::id.eq(id.to_owned()).and(dsl::status.eq_any(statuses)),
+            ProcessTrackerUpdateInternal::from(process),
+        )
+        .await
+        {
+            Ok(mut processes) => Ok(processes.pop()),
+            Err(error) => match error.current_context() {
+                errors::DatabaseError::NotFound => Ok(None),
+                _ => Err(error),
+            },
+        }
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn find_recurring_processes_by_merchant_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::recurrence.is_not_null()),
+            limit,
+            offset,
+            Some(dsl::created_at.asc()),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn find_recurring_processes_by_status(
+        conn: &PgPooledConn,
+        statuses: Vec<enums::ProcessTrackerStatus>,
+        time_upper_limit: PrimitiveDateTime,
+        limit: Option<i64>,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::status
+                .eq_any(statuses)
+                .and(dsl::schedule_time.lt(time_upper_limit))
+                .and(dsl::recurrence.is_not_null()),
+            limit,
+            None,
+            Some(dsl::schedule_time.asc()),
+        )
+        .await
+    }
+
+    /// Lists the processes matching the constraints, earliest scheduled first
+    #[instrument(skip(conn))]
+    pub async fn list_processes_by_constraints(
+        conn: &PgPooledConn,
+        constraints: ProcessTrackerListConstraints,
+    ) -> StorageResult<Vec<Self>> {
+        use async_bb8_diesel::AsyncRunQueryDsl;
+        use diesel::{debug_query, pg::Pg, QueryDsl};
+        use error_stack::ResultExt;
+        use router_env::logger;
+
+        use super::generics::db_metrics::{track_database_call, DatabaseOperation};
+
+        let mut query = <Self as HasTable>::table()
+            .order((dsl::schedule_time.asc(), dsl::id.asc()))
+            .into_boxed();
+
+        if let Some(runner) = constraints.runner {
+            query = query.filter(dsl::runner.eq(runner.to_string()));
+        }
+
+        if let Some(statuses) = constraints.statuses {
+            query = query.filter(dsl::status.eq_any(statuses));
+        }
+
+        if let Some(merchant_id) = constraints.merchant_id {
+            query = query.filter(dsl::merchant_id.eq(merchant_id));
+        }
+
+        if let Some(scheduled_after) = constraints.scheduled_after {
+            query = query.filter(dsl::schedule_time.ge(scheduled_after));
+        }
+
+        if let Some(scheduled_before) = constraints.scheduled_before {
+            query = query.filter(dsl::schedule_time.le(scheduled_before));
+        }
+
+        if let Some(updated_before) = constraints.updated_before {
+            query = query.filter(dsl::updated_at.lt(updated_before));
+        }
+
+        if let Some(limit) = constraints.limit {
+            query = query.limit(limit);
+        }
+
+        if let Some(offset) = constraints.offset {
+            query = query.offset(offset);
+        }
+
+        logger::debug!(query = %debug_query::<Pg, _>(&query).to_string());
+
+        track_database_call::<Self, _, _>(query.get_results_async(conn), DatabaseOperation::Filter)
+            .await
+            .change_context(errors::DatabaseError::Others) // Query returns empty Vec when no records are found
+            .attach_printable("Error filtering processes by constraints")
+    }
+
     #[instrument(skip(conn))]
     pub async fn reinitialize_limbo_processes(
         conn: &PgPooledConn,
@@ -264,6 +264,8 @@ diesel::table! {
         date_created -> Timestamp,
         last_updated -> Nullable<Timestamp>,
         last_updated_provider -> Nullable<Text>,
+        #[max_length = 16]
+        card_iin_range_end -> Nullable<Varchar>,
     }
 }
 
@@ -406,6 +408,18 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    exchange_rates_history (id) {
+        id -> Int4,
+        base_currency -> Currency,
+        conversion -> Jsonb,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use crate::enums::diesel_exports::*;
@@ -822,6 +836,7 @@ diesel::table! {
         #[max_length = 64]
         client_version -> Nullable<Varchar>,
         customer_acceptance -> Nullable<Jsonb>,
+        dcc_offer -> Nullable<Jsonb>,
     }
 }
 
@@ -1089,6 +1104,9 @@ diesel::table! {
         event -> Array<Nullable<Text>>,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        #[max_length = 64]
+        merchant_id -> Nullable<Varchar>,
+        recurrence -> Nullable<Jsonb>,
     }
 }
 
@@ -1309,6 +1327,7 @@ diesel::allow_tables_to_appear_in_same_query!(
     dashboard_metadata,
     dispute,
     events,
+    exchange_rates_history,
     file_metadata,
     fraud_check,
     gateway_status_map,
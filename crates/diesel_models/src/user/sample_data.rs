@@ -80,6 +80,7 @@ pub struct PaymentAttemptBatchNew {
     pub client_source: Option<String>,
     pub client_version: Option<String>,
     pub customer_acceptance: Option<common_utils::pii::SecretSerdeValue>,
+    pub dcc_offer: Option<common_utils::types::DccOffer>,
 }
 
 #[allow(dead_code)]
@@ -144,6 +145,7 @@ impl PaymentAttemptBatchNew {
             client_source: self.client_source,
             client_version: self.client_version,
             customer_acceptance: self.customer_acceptance,
+            dcc_offer: self.dcc_offer,
         }
     }
 }
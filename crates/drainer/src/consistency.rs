@@ -0,0 +1,467 @@
+//! Consistency checks between the KV store and the database.
+//!
+//! With the KV storage scheme, the payment intents and attempts of a payment are kept in the
+//! `mid_{merchant_id}_pid_{payment_id}` hash of the payment until the drainer writes them to the
+//! database. The `verify-kv` command of the drainer scans these hashes and compares every payment
+//! intent and attempt with its row in the database, reporting the rows that are missing, stale
+//! or different. The queries repairing the missing or stale rows can be re-enqueued onto the
+//! drainer streams, to be executed in order with the other queries of the payment.
+
+use std::{collections::HashMap, sync::Arc};
+
+use common_utils::errors::CustomResult;
+use diesel_models::{errors::DatabaseError, PaymentAttempt, PaymentIntent, PgPooledConn};
+use error_stack::{report, ResultExt};
+use futures::StreamExt;
+use redis_interface as redis;
+use router_env::{instrument, logger, tracing};
+
+use crate::{errors, kv, pg_connection, services::Store, settings::VerifyKvCommand};
+
+const PAYMENT_KEY_PATTERN: &str = "mid_*_pid_*";
+const REFUND_FIELD_INFIX: &str = "_ref_";
+/// Fields that are not expected to match between an entity and its row. The ID is generated by
+/// the database, and the modification time of the row is set when the drainer updates it.
+const UNCOMPARED_FIELDS: [&str; 2] = ["id", "modified_at"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InconsistencyKind {
+    /// The entity has no row in the database
+    MissingRow,
+    /// The row was last modified before the entity, so updates of the entity were not drained
+    StaleRow,
+    /// The row differs from the entity, although it was modified after the entity
+    FieldMismatch,
+}
+
+impl InconsistencyKind {
+    /// Whether the row can be repaired from the entity. Rows modified after their entity were
+    /// not written by the drainer, so overwriting them might lose data, and they are reported
+    /// only.
+    fn is_repairable(self) -> bool {
+        match self {
+            Self::MissingRow | Self::StaleRow => true,
+            Self::FieldMismatch => false,
+        }
+    }
+}
+
+/// Entity of the KV store that does not match its row in the database
+#[derive(Debug, serde::Serialize)]
+pub struct Inconsistency {
+    /// Key of the hash of the entity
+    pub key: String,
+    /// Field of the entity in the hash
+    pub field: String,
+    pub table: &'static str,
+    pub kind: InconsistencyKind,
+    /// Fields of the entity whose value differs in the row
+    pub differing_fields: Vec<String>,
+    pub kv_modified_at: Option<serde_json::Value>,
+    pub db_modified_at: Option<serde_json::Value>,
+    /// Whether the query repairing the row was enqueued onto the drainer stream
+    pub repaired: bool,
+}
+
+#[derive(Debug, Default, serde::Serialize)]
+pub struct VerificationReport {
+    pub scanned_keys: usize,
+    pub verified_entities: usize,
+    /// Entities modified too recently for their queries to be drained
+    pub skipped_entities: usize,
+    /// Fields of payment intents or attempts that could not be parsed
+    pub unparsable_fields: Vec<String>,
+    pub inconsistencies: Vec<Inconsistency>,
+}
+
+/// Entity of the KV store that is drained to a table of the database
+#[async_trait::async_trait]
+trait KvEntity: serde::Serialize + serde::de::DeserializeOwned + Send + Sync + Sized {
+    /// Prefix of the fields of the entity in the hash of the payment
+    const FIELD_PREFIX: &'static str;
+    const TABLE: &'static str;
+
+    fn is_modified_before(&self, other: &Self) -> bool;
+
+    fn age_in_seconds(&self) -> i64;
+
+    async fn find_row(&self, conn: &PgPooledConn) -> CustomResult<Option<Self>, DatabaseError>;
+
+    /// Query inserting the entity, for rows that are missing
+    fn into_insert_operation(self) -> errors::DrainerResult<kv::DBOperation>;
+
+    /// Query overwriting the row with the entity, for rows that are stale
+    fn into_overwrite_operation(self, row: &Self) -> kv::DBOperation;
+}
+
+#[async_trait::async_trait]
+impl KvEntity for PaymentIntent {
+    const FIELD_PREFIX: &'static str = "pi_";
+    const TABLE: &'static str = "payment_intent";
+
+    fn is_modified_before(&self, other: &Self) -> bool {
+        self.modified_at < other.modified_at
+    }
+
+    fn age_in_seconds(&self) -> i64 {
+        (common_utils::date_time::now() - self.modified_at).whole_seconds()
+    }
+
+    async fn find_row(&self, conn: &PgPooledConn) -> CustomResult<Option<Self>, DatabaseError> {
+        Self::find_optional_by_payment_id_merchant_id(conn, &self.payment_id, &self.merchant_id)
+            .await
+    }
+
+    fn into_insert_operation(self) -> errors::DrainerResult<kv::DBOperation> {
+        Ok(kv::DBOperation::Insert {
+            insertable: kv::Insertable::PaymentIntent(into_new(&self)?),
+        })
+    }
+
+    fn into_overwrite_operation(self, row: &Self) -> kv::DBOperation {
+        kv::DBOperation::Update {
+            updatable: kv::Updateable::PaymentIntentOverwrite(Box::new(self.keep_row_fields(row))),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl KvEntity for PaymentAttempt {
+    const FIELD_PREFIX: &'static str = "pa_";
+    const TABLE: &'static str = "payment_attempt";
+
+    fn is_modified_before(&self, other: &Self) -> bool {
+        self.modified_at < other.modified_at
+    }
+
+    fn age_in_seconds(&self) -> i64 {
+        (common_utils::date_time::now() - self.modified_at).whole_seconds()
+    }
+
+    async fn find_row(&self, conn: &PgPooledConn) -> CustomResult<Option<Self>, DatabaseError> {
+        match Self::find_by_merchant_id_attempt_id(conn, &self.merchant_id, &self.attempt_id).await
+        {
+            Ok(row) => Ok(Some(row)),
+            Err(error) if matches!(error.current_context(), DatabaseError::NotFound) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    fn into_insert_operation(self) -> errors::DrainerResult<kv::DBOperation> {
+        Ok(kv::DBOperation::Insert {
+            insertable: kv::Insertable::PaymentAttempt(into_new(&self)?),
+        })
+    }
+
+    fn into_overwrite_operation(self, row: &Self) -> kv::DBOperation {
+        kv::DBOperation::Update {
+            updatable: kv::Updateable::PaymentAttemptOverwrite(Box::new(self.keep_row_fields(row))),
+        }
+    }
+}
+
+/// Converts an entity into its insertable type, whose fields are a subset of those of the entity
+fn into_new<E, N>(entity: &E) -> errors::DrainerResult<N>
+where
+    E: serde::Serialize,
+    N: serde::de::DeserializeOwned,
+{
+    serde_json::to_value(entity)
+        .and_then(serde_json::from_value)
+        .map_err(|error| report!(errors::DrainerError::UnexpectedError(error.to_string())))
+}
+
+/// Classifies the inconsistency between the fields of an entity and the fields of its row, if
+/// they differ. Returns the kind of the inconsistency and the fields of the entity that differ.
+fn classify_inconsistency(
+    kv_fields: &serde_json::Map<String, serde_json::Value>,
+    db_fields: Option<&serde_json::Map<String, serde_json::Value>>,
+    is_row_modified_before: bool,
+) -> Option<(InconsistencyKind, Vec<String>)> {
+    let Some(db_fields) = db_fields else {
+        return Some((InconsistencyKind::MissingRow, Vec::new()));
+    };
+
+    let differing_fields = kv_fields
+        .iter()
+        .filter(|(name, _)| !UNCOMPARED_FIELDS.contains(&name.as_str()))
+        .filter(|(name, value)| db_fields.get(name.as_str()) != Some(value))
+        .map(|(name, _)| name.clone())
+        .collect::<Vec<_>>();
+
+    if differing_fields.is_empty() {
+        None
+    } else if is_row_modified_before {
+        Some((InconsistencyKind::StaleRow, differing_fields))
+    } else {
+        Some((InconsistencyKind::FieldMismatch, differing_fields))
+    }
+}
+
+fn to_json_map(
+    entity: &impl serde::Serialize,
+) -> errors::DrainerResult<serde_json::Map<String, serde_json::Value>> {
+    match serde_json::to_value(entity) {
+        Ok(serde_json::Value::Object(map)) => Ok(map),
+        Ok(_) => Err(report!(errors::DrainerError::UnexpectedError(
+            "Entity is not serialized as an object".to_owned()
+        ))),
+        Err(error) => Err(report!(errors::DrainerError::UnexpectedError(
+            error.to_string()
+        ))),
+    }
+}
+
+impl Store {
+    /// Scans the hashes of the payments in the KV store and verifies that their payment intents
+    /// and attempts match their rows in the database
+    #[instrument(skip_all)]
+    pub async fn verify_kv(
+        &self,
+        command: &VerifyKvCommand,
+    ) -> errors::DrainerResult<VerificationReport> {
+        let mut report = VerificationReport::default();
+
+        // The keys are verified one page at a time, for the keys of the whole KV store not to be
+        // held in memory
+        let mut pages = Box::pin(
+            self.redis_conn
+                .scan_keys(PAYMENT_KEY_PATTERN, Some(command.count)),
+        );
+        while let Some(keys) = pages.next().await {
+            let keys = keys.map_err(errors::DrainerError::from)?;
+            report.scanned_keys += keys.len();
+
+            for key in keys {
+                self.verify_key(&key, command, &mut report).await?;
+            }
+        }
+
+        Ok(report)
+    }
+
+    async fn verify_key(
+        &self,
+        key: &str,
+        command: &VerifyKvCommand,
+        report: &mut VerificationReport,
+    ) -> errors::DrainerResult<()> {
+        // Hashes that expired since they were scanned have no fields
+        let fields = self
+            .redis_conn
+            .get_all_hash_fields(key)
+            .await
+            .map_err(errors::DrainerError::from)?;
+
+        for (field, value) in fields {
+            if field.starts_with(PaymentIntent::FIELD_PREFIX) {
+                self.verify_entity::<PaymentIntent>(key, field, &value, command, report)
+                    .await?;
+            } else if field.starts_with(PaymentAttempt::FIELD_PREFIX) {
+                self.verify_entity::<PaymentAttempt>(key, field, &value, command, report)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn verify_entity<E: KvEntity>(
+        &self,
+        key: &str,
+        field: String,
+        value: &str,
+        command: &VerifyKvCommand,
+        report: &mut VerificationReport,
+    ) -> errors::DrainerResult<()> {
+        let entity = match serde_json::from_str::<E>(value) {
+            Ok(entity) => entity,
+            // Refunds are kept in the hash of the payment as `pa_{attempt_id}_ref_{refund_id}`
+            Err(_) if field.contains(REFUND_FIELD_INFIX) => return Ok(()),
+            Err(error) => {
+                logger::error!(key, field = %field, ?error, "Failed to parse KV entity");
+                report.unparsable_fields.push(format!("{key}:{field}"));
+                return Ok(());
+            }
+        };
+
+        if entity.age_in_seconds() < command.min_age {
+            report.skipped_entities += 1;
+            return Ok(());
+        }
+        report.verified_entities += 1;
+
+        let conn = pg_connection(&self.master_pool).await;
+        let row =
+            entity
+                .find_row(&conn)
+                .await
+                .change_context(errors::DrainerError::UnexpectedError(format!(
+                    "Failed to find the {} row of {key}:{field}",
+                    E::TABLE
+                )))?;
+
+        let kv_fields = to_json_map(&entity)?;
+        let mut db_fields = row.as_ref().map(to_json_map).transpose()?;
+        let Some((kind, differing_fields)) = classify_inconsistency(
+            &kv_fields,
+            db_fields.as_ref(),
+            row.as_ref()
+                .is_some_and(|row| row.is_modified_before(&entity)),
+        ) else {
+            return Ok(());
+        };
+        let db_modified_at = db_fields
+            .as_mut()
+            .and_then(|db_fields| db_fields.remove("modified_at"));
+
+        logger::warn!(key, field = %field, table = E::TABLE, ?kind, ?differing_fields);
+
+        let repaired = if command.repair && kind.is_repairable() {
+            let operation = match row {
+                None => entity.into_insert_operation()?,
+                Some(row) => entity.into_overwrite_operation(&row),
+            };
+            self.enqueue_repair(key, operation).await?;
+            true
+        } else {
+            false
+        };
+
+        report.inconsistencies.push(Inconsistency {
+            key: key.to_owned(),
+            field,
+            table: E::TABLE,
+            kind,
+            differing_fields,
+            kv_modified_at: kv_fields.get("modified_at").cloned(),
+            db_modified_at,
+            repaired,
+        });
+
+        Ok(())
+    }
+
+    /// Appends the query to the drainer stream of the shard of the key, for it to be executed
+    /// after the queries of the key that are not drained yet
+    async fn enqueue_repair(
+        &self,
+        key: &str,
+        operation: kv::DBOperation,
+    ) -> errors::DrainerResult<()> {
+        // Same partitioning of the keys as the one the application pushes the queries with
+        let partition =
+            crc32fast::hash(key.as_bytes()) % u32::from(self.config.drainer_num_partitions);
+        let stream_name = self.drainer_stream(&format!("shard_{partition}"));
+
+        let fields = kv::TypedSql { op: operation }
+            .to_field_value_pairs(self.request_id.clone().unwrap_or_default(), key.to_owned())
+            .change_context(errors::DrainerError::UnexpectedError(
+                "Failed to serialize the repairing query".to_owned(),
+            ))?;
+
+        self.redis_conn
+            .stream_append_entry(&stream_name, &redis::RedisEntryId::AutoGeneratedID, fields)
+            .await
+            .map_err(errors::DrainerError::from)?;
+
+        logger::info!(key, stream_name, "Enqueued repairing query");
+        Ok(())
+    }
+}
+
+/// Runs the verification against the store of its tenant, printing its report as JSON. Fails if
+/// inconsistencies were found and not repaired.
+pub async fn run_command(
+    stores: &HashMap<String, Arc<Store>>,
+    command: VerifyKvCommand,
+) -> errors::DrainerResult<()> {
+    let store = stores.get(&command.tenant).ok_or_else(|| {
+        report!(errors::DrainerError::UnexpectedError(format!(
+            "Tenant `{}` not found",
+            command.tenant
+        )))
+    })?;
+
+    let report = store.verify_kv(&command).await?;
+    let output = serde_json::to_string_pretty(&report)
+        .map_err(|error| errors::DrainerError::UnexpectedError(error.to_string()))?;
+    println!("{output}");
+
+    let unrepaired = report
+        .inconsistencies
+        .iter()
+        .filter(|inconsistency| !inconsistency.repaired)
+        .count();
+    common_utils::fp_utils::when(unrepaired > 0, || {
+        Err(report!(errors::DrainerError::InconsistentKvStore(
+            unrepaired
+        )))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::{classify_inconsistency, InconsistencyKind};
+
+    fn to_map(value: serde_json::Value) -> serde_json::Map<String, serde_json::Value> {
+        match value {
+            serde_json::Value::Object(map) => map,
+            _ => serde_json::Map::new(),
+        }
+    }
+
+    #[test]
+    fn test_classify_inconsistency() {
+        let kv_fields = to_map(serde_json::json!({
+            "id": null,
+            "status": "succeeded",
+            "connector": null,
+            "modified_at": "2024-01-01T00:00:10",
+        }));
+
+        assert_eq!(
+            classify_inconsistency(&kv_fields, None, false),
+            Some((InconsistencyKind::MissingRow, Vec::new()))
+        );
+
+        // The ID and the modification time are not compared
+        let matching_row = to_map(serde_json::json!({
+            "id": 1,
+            "status": "succeeded",
+            "connector": null,
+            "modified_at": "2024-01-01T00:00:20",
+        }));
+        assert_eq!(
+            classify_inconsistency(&kv_fields, Some(&matching_row), false),
+            None
+        );
+
+        // Fields that are empty in the KV store differ from the values of the row
+        let differing_row = to_map(serde_json::json!({
+            "id": 1,
+            "status": "succeeded",
+            "connector": "stripe",
+            "modified_at": "2024-01-01T00:00:00",
+        }));
+        let differing_fields = vec!["connector".to_string()];
+        assert_eq!(
+            classify_inconsistency(&kv_fields, Some(&differing_row), true),
+            Some((InconsistencyKind::StaleRow, differing_fields.clone()))
+        );
+        assert_eq!(
+            classify_inconsistency(&kv_fields, Some(&differing_row), false),
+            Some((InconsistencyKind::FieldMismatch, differing_fields))
+        );
+    }
+
+    #[test]
+    fn test_only_missing_and_stale_rows_are_repaired() {
+        assert!(InconsistencyKind::MissingRow.is_repairable());
+        assert!(InconsistencyKind::StaleRow.is_repairable());
+        assert!(!InconsistencyKind::FieldMismatch.is_repairable());
+    }
+}
@@ -0,0 +1,482 @@
+//! Dead-letter streams of the drainer.
+//!
+//! Entries of a drainer stream that cannot be parsed, or whose query keeps failing after the
+//! configured number of attempts, retried with an exponential backoff, are moved to the
+//! dead-letter stream of their shard along with the error, the number of attempts and their ID in
+//! the drainer stream, so that they neither block the shard nor get lost. Dead-letter streams are
+//! capped to the configured number of entries, the oldest entries being trimmed first.
+//! Dead-lettered entries are inspected, replayed into the drainer stream or discarded with the
+//! `dead-letter` command of the drainer.
+
+use std::{collections::HashMap, sync::Arc};
+
+use error_stack::report;
+use redis_interface as redis;
+use router_env::{instrument, logger, tracing};
+
+use crate::{errors, metrics, services::Store, settings::DeadLetterCommand};
+
+const ERROR_FIELD: &str = "dead_letter_error";
+const ATTEMPTS_FIELD: &str = "dead_letter_attempts";
+const STREAM_ID_FIELD: &str = "dead_letter_stream_id";
+const DEAD_LETTERED_AT_FIELD: &str = "dead_lettered_at";
+
+/// Failed attempts to execute the query of the entry at the head of a drainer stream. Only the
+/// entry at the head of the stream is retried, so it is the only one attempts are kept for.
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct FailedAttempts {
+    entry_id: String,
+    attempts: u8,
+    /// Unix timestamp the entry is not retried before
+    retry_at: i64,
+}
+
+impl FailedAttempts {
+    /// Adds a failed attempt of the entry to the previous failed attempts, which are discarded if
+    /// they are of another entry
+    fn record(previous: Option<Self>, entry_id: &str, now: i64, retry_backoff: u32) -> Self {
+        let attempts = previous
+            .filter(|previous| previous.entry_id == entry_id)
+            .map_or(1, |previous| previous.attempts.saturating_add(1));
+
+        Self {
+            entry_id: entry_id.to_owned(),
+            attempts,
+            retry_at: now.saturating_add(get_retry_delay(retry_backoff, attempts)),
+        }
+    }
+
+    fn is_retry_pending(&self, entry_id: &str, now: i64) -> bool {
+        self.entry_id == entry_id && now < self.retry_at
+    }
+}
+
+/// Seconds to wait for before retrying an entry whose query failed `attempts` times, doubling
+/// with each attempt
+fn get_retry_delay(retry_backoff: u32, attempts: u8) -> i64 {
+    let exponent = u32::from(attempts.saturating_sub(1));
+    i64::from(retry_backoff.saturating_mul(2u32.saturating_pow(exponent)))
+}
+
+/// Fields of the entry of the dead-letter stream the entry of the drainer stream is moved to
+fn get_dead_letter_fields(
+    entry_id: &str,
+    entry: HashMap<String, String>,
+    error: String,
+    attempts: u8,
+    dead_lettered_at: i64,
+) -> Vec<(String, String)> {
+    let mut fields = entry.into_iter().collect::<Vec<_>>();
+    fields.extend([
+        (ERROR_FIELD.to_owned(), error),
+        (ATTEMPTS_FIELD.to_owned(), attempts.to_string()),
+        (STREAM_ID_FIELD.to_owned(), entry_id.to_owned()),
+        (
+            DEAD_LETTERED_AT_FIELD.to_owned(),
+            dead_lettered_at.to_string(),
+        ),
+    ]);
+    fields
+}
+
+/// Entry of a dead-letter stream
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DeadLetterEntry {
+    /// ID of the entry in the dead-letter stream
+    pub id: String,
+    /// ID the entry had in the drainer stream
+    pub stream_id: String,
+    pub error: String,
+    pub attempts: u8,
+    /// Unix timestamp the entry was dead-lettered at
+    pub dead_lettered_at: i64,
+    /// Fields of the entry in the drainer stream
+    pub entry: HashMap<String, String>,
+}
+
+impl DeadLetterEntry {
+    fn from_stream_entry(id: String, mut entry: HashMap<String, String>) -> Self {
+        let mut take_field = |field| entry.remove(field).unwrap_or_default();
+        let error = take_field(ERROR_FIELD);
+        let attempts = take_field(ATTEMPTS_FIELD).parse().unwrap_or_default();
+        let stream_id = take_field(STREAM_ID_FIELD);
+        let dead_lettered_at = take_field(DEAD_LETTERED_AT_FIELD)
+            .parse()
+            .unwrap_or_default();
+
+        Self {
+            id,
+            stream_id,
+            error,
+            attempts,
+            dead_lettered_at,
+            entry,
+        }
+    }
+}
+
+impl Store {
+    #[inline(always)]
+    pub(crate) fn get_dead_letter_stream_name(&self, stream_name: &str) -> String {
+        // Shares the hash tag of the drainer stream, for both to be in the same hash slot
+        format!("{stream_name}_dead_letter")
+    }
+
+    #[inline(always)]
+    fn get_failed_attempts_key(&self, stream_name: &str) -> String {
+        format!("{stream_name}_failed_attempts")
+    }
+
+    async fn get_failed_attempts(&self, stream_name: &str) -> Option<FailedAttempts> {
+        self.redis_conn
+            .get_and_deserialize_key::<FailedAttempts>(
+                &self.get_failed_attempts_key(stream_name),
+                "FailedAttempts",
+            )
+            .await
+            .ok()
+    }
+
+    /// Whether the query of the entry at the head of the stream failed and is not to be retried
+    /// yet
+    pub async fn is_retry_pending(&self, stream_name: &str, entry_id: &str) -> bool {
+        self.get_failed_attempts(stream_name)
+            .await
+            .is_some_and(|failed_attempts| {
+                failed_attempts
+                    .is_retry_pending(entry_id, common_utils::date_time::now_unix_timestamp())
+            })
+    }
+
+    /// Records a failed attempt to execute the query of the entry at the head of the stream, and
+    /// moves the entry to the dead-letter stream once it has been attempted `max_attempts` times.
+    /// The entry is not retried before `retry_backoff` seconds, doubled with each failed attempt,
+    /// have passed. Returns whether the entry was dead-lettered.
+    #[instrument(skip_all)]
+    pub async fn record_failed_attempt(
+        &self,
+        stream_name: &str,
+        entry_id: &str,
+        entry: HashMap<String, String>,
+        error: String,
+        max_attempts: u8,
+        retry_backoff: u32,
+    ) -> errors::DrainerResult<bool> {
+        let failed_attempts = FailedAttempts::record(
+            self.get_failed_attempts(stream_name).await,
+            entry_id,
+            common_utils::date_time::now_unix_timestamp(),
+            retry_backoff,
+        );
+        let attempts = failed_attempts.attempts;
+
+        metrics::FAILED_QUERY_ATTEMPTS.add(
+            &metrics::CONTEXT,
+            1,
+            &[metrics::KeyValue::new("stream", stream_name.to_owned())],
+        );
+
+        if attempts < max_attempts {
+            logger::warn!(
+                entry_id,
+                attempts,
+                retry_at = failed_attempts.retry_at,
+                "Failed to drain stream entry, will retry"
+            );
+            self.redis_conn
+                .serialize_and_set_key(&self.get_failed_attempts_key(stream_name), failed_attempts)
+                .await
+                .map_err(errors::DrainerError::from)?;
+            return Ok(false);
+        }
+
+        self.dead_letter_entry(stream_name, entry_id, entry, error, attempts)
+            .await?;
+        Ok(true)
+    }
+
+    /// Appends the entry of the stream to the dead-letter stream of the stream, trimming the oldest
+    /// entries of the dead-letter stream beyond its maximum length
+    #[instrument(skip_all)]
+    pub async fn dead_letter_entry(
+        &self,
+        stream_name: &str,
+        entry_id: &str,
+        entry: HashMap<String, String>,
+        error: String,
+        attempts: u8,
+    ) -> errors::DrainerResult<()> {
+        let fields = get_dead_letter_fields(
+            entry_id,
+            entry,
+            error,
+            attempts,
+            common_utils::date_time::now_unix_timestamp(),
+        );
+
+        let dead_letter_stream = self.get_dead_letter_stream_name(stream_name);
+        self.redis_conn
+            .stream_append_entry(
+                &dead_letter_stream,
+                &redis::RedisEntryId::AutoGeneratedID,
+                fields,
+            )
+            .await
+            .map_err(errors::DrainerError::from)?;
+
+        // The entry is already dead-lettered, so failing to trim the stream is only logged
+        self.redis_conn
+            .stream_trim_entries(
+                &dead_letter_stream,
+                (
+                    redis::StreamCapKind::MaxLen,
+                    redis::StreamCapTrim::AlmostExact,
+                    self.config.dead_letter_max_len.to_string(),
+                ),
+            )
+            .await
+            .map_err(|error| logger::error!(?error, "Failed to trim the dead-letter stream"))
+            .ok();
+
+        logger::error!(
+            entry_id,
+            attempts,
+            "Moved stream entry to the dead-letter stream"
+        );
+        metrics::DEAD_LETTERED_ENTRIES.add(
+            &metrics::CONTEXT,
+            1,
+            &[metrics::KeyValue::new("stream", stream_name.to_owned())],
+        );
+
+        Ok(())
+    }
+
+    /// Lists the first `count` entries of the dead-letter stream of the shard
+    pub async fn list_dead_letter_entries(
+        &self,
+        stream_index: u8,
+        count: u64,
+    ) -> errors::DrainerResult<Vec<DeadLetterEntry>> {
+        let dead_letter_stream =
+            self.get_dead_letter_stream_name(&self.get_drainer_stream_name(stream_index));
+
+        Ok(self
+            .redis_conn
+            .stream_read_range(&dead_letter_stream, "-", "+", Some(count))
+            .await
+            .map_err(errors::DrainerError::from)?
+            .into_iter()
+            .map(|(id, entry)| DeadLetterEntry::from_stream_entry(id, entry))
+            .collect())
+    }
+
+    async fn read_dead_letter_entry(
+        &self,
+        dead_letter_stream: &str,
+        id: &str,
+    ) -> errors::DrainerResult<DeadLetterEntry> {
+        let (id, entry) = self
+            .redis_conn
+            .stream_read_range(dead_letter_stream, id, id, Some(1))
+            .await
+            .map_err(errors::DrainerError::from)?
+            .pop()
+            .ok_or_else(|| report!(errors::DrainerError::DeadLetterEntryNotFound(id.to_owned())))?;
+
+        Ok(DeadLetterEntry::from_stream_entry(id, entry))
+    }
+
+    async fn delete_dead_letter_entry(
+        &self,
+        dead_letter_stream: &str,
+        id: &str,
+    ) -> errors::DrainerResult<()> {
+        self.redis_conn
+            .stream_delete_entries(dead_letter_stream, id)
+            .await
+            .map_err(errors::DrainerError::from)?;
+
+        Ok(())
+    }
+
+    /// Moves the entry of the dead-letter stream of the shard back to the end of the drainer
+    /// stream of the shard, for its query to be executed again.
+    ///
+    /// The entry is drained after the entries that were appended to the drainer stream since it
+    /// was dead-lettered, which the query of the entry might conflict with. It is only removed
+    /// from the dead-letter stream once appended to the drainer stream, for a failure not to lose
+    /// it.
+    #[instrument(skip_all)]
+    pub async fn replay_dead_letter_entry(
+        &self,
+        stream_index: u8,
+        id: &str,
+    ) -> errors::DrainerResult<DeadLetterEntry> {
+        let stream_name = self.get_drainer_stream_name(stream_index);
+        let dead_letter_stream = self.get_dead_letter_stream_name(&stream_name);
+        let dead_letter_entry = self.read_dead_letter_entry(&dead_letter_stream, id).await?;
+
+        self.redis_conn
+            .stream_append_entry(
+                &stream_name,
+                &redis::RedisEntryId::AutoGeneratedID,
+                dead_letter_entry
+                    .entry
+                    .clone()
+                    .into_iter()
+                    .collect::<Vec<_>>(),
+            )
+            .await
+            .map_err(errors::DrainerError::from)?;
+
+        self.delete_dead_letter_entry(&dead_letter_stream, &dead_letter_entry.id)
+            .await
+            .map_err(|error| {
+                // The entry was already appended to the drainer stream, so replaying it again
+                // would execute its query twice
+                logger::error!(
+                    ?dead_letter_entry,
+                    "Failed to remove replayed entry from the dead-letter stream"
+                );
+                error
+            })?;
+
+        metrics::DEAD_LETTER_ENTRIES_REPLAYED.add(
+            &metrics::CONTEXT,
+            1,
+            &[metrics::KeyValue::new("stream", stream_name)],
+        );
+
+        Ok(dead_letter_entry)
+    }
+
+    /// Removes the entry of the dead-letter stream of the shard, without executing its query
+    #[instrument(skip_all)]
+    pub async fn discard_dead_letter_entry(
+        &self,
+        stream_index: u8,
+        id: &str,
+    ) -> errors::DrainerResult<DeadLetterEntry> {
+        let stream_name = self.get_drainer_stream_name(stream_index);
+        let dead_letter_stream = self.get_dead_letter_stream_name(&stream_name);
+        let dead_letter_entry = self.read_dead_letter_entry(&dead_letter_stream, id).await?;
+        self.delete_dead_letter_entry(&dead_letter_stream, &dead_letter_entry.id)
+            .await?;
+
+        logger::warn!(?dead_letter_entry, "Discarded dead-lettered entry");
+        metrics::DEAD_LETTER_ENTRIES_DISCARDED.add(
+            &metrics::CONTEXT,
+            1,
+            &[metrics::KeyValue::new("stream", stream_name)],
+        );
+
+        Ok(dead_letter_entry)
+    }
+}
+
+/// Runs the dead-letter command against the store of its tenant, printing its result as JSON
+pub async fn run_command(
+    stores: &HashMap<String, Arc<Store>>,
+    command: DeadLetterCommand,
+) -> errors::DrainerResult<()> {
+    let get_store = |tenant: &str| {
+        stores.get(tenant).ok_or_else(|| {
+            report!(errors::DrainerError::UnexpectedError(format!(
+                "Tenant `{tenant}` not found"
+            )))
+        })
+    };
+
+    let output = match command {
+        DeadLetterCommand::List {
+            tenant,
+            shard,
+            count,
+        } => serde_json::to_string_pretty(
+            &get_store(&tenant)?
+                .list_dead_letter_entries(shard, count)
+                .await?,
+        ),
+        DeadLetterCommand::Replay { tenant, shard, id } => serde_json::to_string_pretty(
+            &get_store(&tenant)?
+                .replay_dead_letter_entry(shard, &id)
+                .await?,
+        ),
+        DeadLetterCommand::Discard { tenant, shard, id } => serde_json::to_string_pretty(
+            &get_store(&tenant)?
+                .discard_dead_letter_entry(shard, &id)
+                .await?,
+        ),
+    }
+    .map_err(|error| errors::DrainerError::UnexpectedError(error.to_string()))?;
+
+    println!("{output}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::collections::HashMap;
+
+    use super::{get_dead_letter_fields, get_retry_delay, DeadLetterEntry, FailedAttempts};
+
+    #[test]
+    fn test_retry_delay_doubles_with_each_attempt() {
+        assert_eq!(get_retry_delay(5, 0), 5);
+        assert_eq!(get_retry_delay(5, 1), 5);
+        assert_eq!(get_retry_delay(5, 2), 10);
+        assert_eq!(get_retry_delay(5, 4), 40);
+        assert_eq!(get_retry_delay(5, u8::MAX), i64::from(u32::MAX));
+        assert_eq!(get_retry_delay(0, 3), 0);
+    }
+
+    #[test]
+    fn test_record_failed_attempt() {
+        let first = FailedAttempts::record(None, "1-0", 100, 5);
+        assert_eq!(
+            first,
+            FailedAttempts {
+                entry_id: "1-0".to_string(),
+                attempts: 1,
+                retry_at: 105,
+            }
+        );
+        assert!(first.is_retry_pending("1-0", 104));
+        assert!(!first.is_retry_pending("1-0", 105));
+        assert!(!first.is_retry_pending("2-0", 104));
+
+        let second = FailedAttempts::record(Some(first), "1-0", 105, 5);
+        assert_eq!(second.attempts, 2);
+        assert_eq!(second.retry_at, 115);
+
+        // The attempts of another entry are not counted
+        let other = FailedAttempts::record(Some(second), "2-0", 120, 5);
+        assert_eq!(other.attempts, 1);
+        assert_eq!(other.retry_at, 125);
+    }
+
+    #[test]
+    fn test_dead_letter_entry_keeps_stream_entry() {
+        let entry = HashMap::from([
+            ("request_id".to_string(), "req_1".to_string()),
+            ("typed_sql".to_string(), "{}".to_string()),
+        ]);
+
+        let dead_letter_entry = DeadLetterEntry::from_stream_entry(
+            "2-0".to_string(),
+            get_dead_letter_fields("1-0", entry.clone(), "error".to_string(), 5, 100)
+                .into_iter()
+                .collect(),
+        );
+
+        assert_eq!(dead_letter_entry.id, "2-0");
+        assert_eq!(dead_letter_entry.stream_id, "1-0");
+        assert_eq!(dead_letter_entry.error, "error");
+        assert_eq!(dead_letter_entry.attempts, 5);
+        assert_eq!(dead_letter_entry.dead_lettered_at, 100);
+        assert_eq!(dead_letter_entry.entry, entry);
+    }
+}
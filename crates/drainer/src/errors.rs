@@ -17,6 +17,10 @@ pub enum DrainerError {
     UnexpectedError(String),
     #[error("I/O: {0}")]
     IoError(std::io::Error),
+    #[error("Dead-lettered entry not found: {0}")]
+    DeadLetterEntryNotFound(String),
+    #[error("KV store and database are inconsistent: {0} rows were not repaired")]
+    InconsistentKvStore(usize),
 }
 
 #[derive(Debug, Error, Clone, serde::Serialize)]
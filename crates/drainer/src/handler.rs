@@ -78,6 +78,8 @@ impl Handler {
                             store.clone(),
                             stream_index,
                             self.conf.max_read_count,
+                            self.conf.max_query_attempts,
+                            self.conf.query_retry_backoff,
                             self.active_tasks.clone(),
                             jobs_picked.clone(),
                         )
@@ -170,6 +172,8 @@ async fn drainer_handler(
     store: Arc<Store>,
     stream_index: u8,
     max_read_count: u64,
+    max_query_attempts: u8,
+    query_retry_backoff: u32,
     active_tasks: Arc<atomic::AtomicU64>,
     jobs_picked: Arc<atomic::AtomicU8>,
 ) -> errors::DrainerResult<()> {
@@ -180,6 +184,8 @@ async fn drainer_handler(
     let drainer_result = Box::pin(drainer(
         store.clone(),
         max_read_count,
+        max_query_attempts,
+        query_retry_backoff,
         stream_name.as_str(),
         jobs_picked,
     ))
@@ -203,6 +209,8 @@ async fn drainer_handler(
 async fn drainer(
     store: Arc<Store>,
     max_read_count: u64,
+    max_query_attempts: u8,
+    query_retry_backoff: u32,
     stream_name: &str,
     jobs_picked: Arc<atomic::AtomicU8>,
 ) -> errors::DrainerResult<()> {
@@ -234,6 +242,14 @@ async fn drainer(
     )?;
     let read_count = entries.len();
 
+    // Only the entry at the head of the stream can be awaiting the retry of its query
+    if let Some((entry_id, _)) = entries.first() {
+        if store.is_retry_pending(stream_name, entry_id).await {
+            logger::debug!(entry_id, "Awaiting the retry of the stream entry");
+            return Ok(());
+        }
+    }
+
     metrics::JOBS_PICKED_PER_STREAM.add(
         &metrics::CONTEXT,
         u64::try_from(read_count).unwrap_or(u64::MIN),
@@ -248,7 +264,7 @@ async fn drainer(
     let mut last_processed_id = String::new();
 
     for (entry_id, entry) in entries.clone() {
-        let data = match StreamData::from_hashmap(entry) {
+        let data = match StreamData::from_hashmap(entry.clone()) {
             Ok(data) => data,
             Err(err) => {
                 logger::error!(operation = "deserialization", err=?err);
@@ -261,8 +277,21 @@ async fn drainer(
                     }],
                 );
 
-                // break from the loop in case of a deser error
-                break;
+                // Entries that cannot be parsed never will be, so they are not retried
+                match store
+                    .dead_letter_entry(stream_name, &entry_id, entry, format!("{err:?}"), 1)
+                    .await
+                {
+                    Ok(()) => {
+                        last_processed_id = entry_id;
+                        continue;
+                    }
+                    // break from the loop in case of a deser error that could not be dead-lettered
+                    Err(error) => {
+                        logger::error!(operation = "dead_letter", ?error);
+                        break;
+                    }
+                }
             }
         };
 
@@ -280,8 +309,30 @@ async fn drainer(
                 diesel_models::errors::DatabaseError::UniqueViolation => {
                     last_processed_id = entry_id;
                 }
-                // break from the loop in case of an error in query
-                _ => break,
+                // The query is not at fault when the database cannot be connected to, so the
+                // attempt does not count towards dead-lettering the entry
+                diesel_models::errors::DatabaseError::DatabaseConnectionError => break,
+                // Retry the entry once its backoff has passed, or move it to the dead-letter stream
+                // once it has been attempted `max_query_attempts` times, for it not to block the
+                // stream
+                _ => match store
+                    .record_failed_attempt(
+                        stream_name,
+                        &entry_id,
+                        entry,
+                        format!("{err:?}"),
+                        max_query_attempts,
+                        query_retry_backoff,
+                    )
+                    .await
+                {
+                    Ok(true) => last_processed_id = entry_id,
+                    Ok(false) => break,
+                    Err(error) => {
+                        logger::error!(operation = "dead_letter", ?error);
+                        break;
+                    }
+                },
             },
         }
     }
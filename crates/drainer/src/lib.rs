@@ -1,4 +1,6 @@
 mod connection;
+pub mod consistency;
+pub mod dead_letter;
 pub mod errors;
 mod handler;
 mod health_check;
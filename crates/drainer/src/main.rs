@@ -1,7 +1,8 @@
 use std::collections::HashMap;
 
 use drainer::{
-    errors::DrainerResult, logger::logger, services, settings, start_drainer, start_web_server,
+    consistency, dead_letter, errors::DrainerResult, logger::logger, services, settings,
+    start_drainer, start_web_server,
 };
 use router_env::tracing::Instrument;
 
@@ -34,6 +35,16 @@ async fn main() -> DrainerResult<()> {
         [router_env::service_name!()],
     );
 
+    match cmd_line.command {
+        Some(settings::Command::DeadLetter(command)) => {
+            return dead_letter::run_command(&stores, command).await;
+        }
+        Some(settings::Command::VerifyKv(command)) => {
+            return consistency::run_command(&stores, command).await;
+        }
+        None => {}
+    }
+
     #[allow(clippy::expect_used)]
     let web_server = Box::pin(start_web_server(
         state.conf.as_ref().clone(),
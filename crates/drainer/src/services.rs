@@ -22,6 +22,7 @@ pub struct Store {
 pub struct StoreConfig {
     pub drainer_stream_name: String,
     pub drainer_num_partitions: u8,
+    pub dead_letter_max_len: u64,
 }
 
 impl Store {
@@ -46,6 +47,7 @@ impl Store {
             config: StoreConfig {
                 drainer_stream_name: config.drainer.stream_name.clone(),
                 drainer_num_partitions: config.drainer.num_partitions,
+                dead_letter_max_len: config.drainer.dead_letter_max_len,
             },
             request_id: None,
         }
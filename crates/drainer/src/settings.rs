@@ -26,6 +26,68 @@ pub struct CmdLineConf {
     /// Application will look for "config/config.toml" if this option isn't specified.
     #[arg(short = 'f', long, value_name = "FILE")]
     pub config_path: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(clap::Subcommand)]
+pub enum Command {
+    /// Inspect, replay or discard the entries of the dead-letter streams, instead of draining
+    #[command(subcommand)]
+    DeadLetter(DeadLetterCommand),
+    /// Verify that the payment intents and attempts of the KV store match their rows in the
+    /// database, instead of draining
+    VerifyKv(VerifyKvCommand),
+}
+
+#[derive(clap::Args)]
+pub struct VerifyKvCommand {
+    #[arg(long, default_value = "public")]
+    pub tenant: String,
+    /// Skip the entities modified less than this many seconds ago, whose queries may not be
+    /// drained yet
+    #[arg(long, default_value_t = 300)]
+    pub min_age: i64,
+    /// Enqueue the queries repairing the missing or stale rows onto the drainer streams
+    #[arg(long)]
+    pub repair: bool,
+    /// Number of keys to scan per call to Redis
+    #[arg(long, default_value_t = 1000)]
+    pub count: u32,
+}
+
+#[derive(clap::Subcommand)]
+pub enum DeadLetterCommand {
+    /// List the entries of the dead-letter stream of a shard
+    List {
+        #[arg(long, default_value = "public")]
+        tenant: String,
+        #[arg(long)]
+        shard: u8,
+        #[arg(long, default_value_t = 100)]
+        count: u64,
+    },
+    /// Move an entry of the dead-letter stream of a shard back to the drainer stream
+    Replay {
+        #[arg(long, default_value = "public")]
+        tenant: String,
+        #[arg(long)]
+        shard: u8,
+        /// ID of the entry in the dead-letter stream
+        #[arg(long)]
+        id: String,
+    },
+    /// Remove an entry of the dead-letter stream of a shard without draining it
+    Discard {
+        #[arg(long, default_value = "public")]
+        tenant: String,
+        #[arg(long)]
+        shard: u8,
+        /// ID of the entry in the dead-letter stream
+        #[arg(long)]
+        id: String,
+    },
 }
 
 #[derive(Clone)]
@@ -112,6 +174,15 @@ pub struct DrainerSettings {
     pub stream_name: String,
     pub num_partitions: u8,
     pub max_read_count: u64,
+    /// Number of times the query of a stream entry is attempted before the entry is moved to the
+    /// dead-letter stream of its shard
+    pub max_query_attempts: u8,
+    /// Time to wait for before retrying the query of a stream entry after its first failed
+    /// attempt, doubled with each further failed attempt
+    pub query_retry_backoff: u32, // in seconds
+    /// Number of entries the dead-letter stream of a shard is capped to, beyond which its oldest
+    /// entries are trimmed
+    pub dead_letter_max_len: u64,
     pub shutdown_interval: u32, // in milliseconds
     pub loop_interval: u32,     // in milliseconds
 }
@@ -184,6 +255,9 @@ impl Default for DrainerSettings {
             stream_name: "DRAINER_STREAM".into(),
             num_partitions: 64,
             max_read_count: 100,
+            max_query_attempts: 5,
+            query_retry_backoff: 5, // in seconds
+            dead_letter_max_len: 10000,
             shutdown_interval: 1000, // in milliseconds
             loop_interval: 100,      // in milliseconds
         }
@@ -236,6 +310,18 @@ impl DrainerSettings {
             Err(errors::DrainerError::ConfigParsingError(
                 "drainer stream name must not be empty".into(),
             ))
+        })?;
+
+        common_utils::fp_utils::when(self.max_query_attempts == 0, || {
+            Err(errors::DrainerError::ConfigParsingError(
+                "drainer max query attempts must be greater than 0".into(),
+            ))
+        })?;
+
+        common_utils::fp_utils::when(self.dead_letter_max_len == 0, || {
+            Err(errors::DrainerError::ConfigParsingError(
+                "drainer dead letter max len must be greater than 0".into(),
+            ))
         })
     }
 }
@@ -269,7 +355,8 @@ impl Settings<SecuredSecret> {
                     .try_parsing(true)
                     .separator("__")
                     .list_separator(",")
-                    .with_list_parse_key("redis.cluster_urls"),
+                    .with_list_parse_key("redis.cluster_urls")
+                    .with_list_parse_key("redis.sentinel_nodes"),
             )
             .build()?;
 
@@ -30,10 +30,64 @@ pub struct PaymentInput {
     pub setup_future_usage: Option<enums::SetupFutureUsage>,
 }
 
+/// Time at which the payment is routed, in UTC
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeInput {
+    pub hour: u8,
+    pub weekday: enums::Weekday,
+    /// The date as a `YYYYMMDD` number, so that date ranges can be expressed as comparisons
+    pub date: i64,
+}
+
+impl TimeInput {
+    pub fn from_datetime(datetime: time::PrimitiveDateTime) -> Self {
+        let date = datetime.date();
+
+        Self {
+            hour: datetime.hour(),
+            weekday: date.weekday().into(),
+            date: i64::from(date.year()) * 10000
+                + i64::from(u8::from(date.month())) * 100
+                + i64::from(date.day()),
+        }
+    }
+
+    pub fn now(clock: &impl Clock) -> Self {
+        Self::from_datetime(clock.now())
+    }
+}
+
+/// Source of the current time for the time based keys, which can be replaced in tests to make
+/// the evaluation of time based rules deterministic
+pub trait Clock {
+    fn now(&self) -> time::PrimitiveDateTime;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> time::PrimitiveDateTime {
+        common_utils::date_time::now()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub time::PrimitiveDateTime);
+
+impl Clock for FixedClock {
+    fn now(&self) -> time::PrimitiveDateTime {
+        self.0
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BackendInput {
     pub metadata: Option<FxHashMap<String, String>>,
     pub payment: PaymentInput,
     pub payment_method: PaymentMethodInput,
     pub mandate: MandateData,
+    /// The time based keys are not present in the context if this is not provided
+    #[serde(default)]
+    pub time: Option<TimeInput>,
 }
@@ -41,6 +41,7 @@ where
     fn eval_comparison(
         comparison: &ast::Comparison,
         ctx: &types::Context,
+        in_not_group: bool,
     ) -> Result<bool, types::InterpreterError> {
         use ast::{ComparisonType::*, ValueType::*};
 
@@ -75,7 +76,13 @@ where
                     metadata: comparison.metadata.clone(),
                 }),
             }
+        } else if in_not_group {
+            // Within `!`, a missing value is never equal to, nor compares against, any value,
+            // which is how the VIR evaluates a missing key, so that `!` agrees across both backends
+            Ok(matches!(comparison.comparison, NotEqual))
         } else {
+            // Comparisons outside of `!` keep matching nothing for a missing value, for the
+            // results of the existing programs not to change
             Ok(false)
         }
     }
@@ -83,9 +90,10 @@ where
     fn eval_if_condition(
         condition: &ast::IfCondition,
         ctx: &types::Context,
+        in_not_group: bool,
     ) -> Result<bool, types::InterpreterError> {
-        for comparison in condition {
-            let res = Self::eval_comparison(comparison, ctx)?;
+        for term in condition {
+            let res = Self::eval_condition_term(term, ctx, in_not_group)?;
 
             if !res {
                 return Ok(false);
@@ -95,11 +103,37 @@ where
         Ok(true)
     }
 
+    fn eval_condition_term(
+        term: &ast::Condition,
+        ctx: &types::Context,
+        in_not_group: bool,
+    ) -> Result<bool, types::InterpreterError> {
+        match term {
+            ast::Condition::Comparison(comparison) => {
+                Self::eval_comparison(comparison, ctx, in_not_group)
+            }
+
+            ast::Condition::Group(ast::ConditionGroup::Or(alternatives)) => {
+                for alternative in alternatives {
+                    if Self::eval_if_condition(alternative, ctx, in_not_group)? {
+                        return Ok(true);
+                    }
+                }
+
+                Ok(false)
+            }
+
+            ast::Condition::Group(ast::ConditionGroup::Not(condition)) => {
+                Ok(!Self::eval_if_condition(condition, ctx, true)?)
+            }
+        }
+    }
+
     fn eval_if_statement(
         stmt: &ast::IfStatement,
         ctx: &types::Context,
     ) -> Result<bool, types::InterpreterError> {
-        let cond_res = Self::eval_if_condition(&stmt.condition, ctx)?;
+        let cond_res = Self::eval_if_condition(&stmt.condition, ctx, false)?;
 
         if !cond_res {
             return Ok(false);
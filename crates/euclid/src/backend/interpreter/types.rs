@@ -1,5 +1,6 @@
 use std::{collections::HashMap, fmt, ops::Deref, string::ToString};
 
+use common_utils::types::MinorUnit;
 use serde::Serialize;
 
 use crate::{backend::inputs, frontend::ast::ValueType, types::EuclidKey};
@@ -74,6 +75,27 @@ impl From<inputs::BackendInput> for Context {
                 EuclidKey::PaymentCurrency.to_string(),
                 Some(ValueType::EnumVariant(input.payment.currency.to_string())),
             ),
+            (
+                EuclidKey::UtcHour.to_string(),
+                input
+                    .time
+                    .as_ref()
+                    .map(|time| ValueType::Number(MinorUnit::new(i64::from(time.hour)))),
+            ),
+            (
+                EuclidKey::UtcWeekday.to_string(),
+                input
+                    .time
+                    .as_ref()
+                    .map(|time| ValueType::EnumVariant(time.weekday.to_string())),
+            ),
+            (
+                EuclidKey::UtcDate.to_string(),
+                input
+                    .time
+                    .as_ref()
+                    .map(|time| ValueType::Number(MinorUnit::new(time.date))),
+            ),
         ]);
 
         Self(ctx)
@@ -151,6 +151,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            time: None,
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -191,6 +192,7 @@ mod test {
                 mandate_type: None,
                 payment_type: Some(enums::PaymentType::SetupMandate),
             },
+            time: None,
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -232,6 +234,7 @@ mod test {
                 mandate_type: Some(enums::MandateType::SingleUse),
                 payment_type: None,
             },
+            time: None,
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -273,6 +276,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            time: None,
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -314,6 +318,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            time: None,
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -355,6 +360,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            time: None,
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -396,6 +402,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            time: None,
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -437,6 +444,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            time: None,
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -480,6 +488,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            time: None,
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -521,6 +530,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            time: None,
         };
         let mut inp_equal = inp_greater.clone();
         inp_equal.payment.amount = MinorUnit::new(123);
@@ -571,6 +581,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            time: None,
         };
         let mut inp_equal = inp_lower.clone();
         inp_equal.payment.amount = MinorUnit::new(123);
@@ -586,4 +597,266 @@ mod test {
             "rule_1"
         );
     }
+
+    #[test]
+    fn test_or_and_not_condition_groups() {
+        let program_str = r#"
+        default: ["stripe"]
+        rule_1: ["adyen"]
+        {
+            payment_method = card
+                & (card_network = Visa | amount > 10000)
+                & !billing_country = UnitedStatesOfAmerica
+        }
+        "#;
+
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        let make_input = |amount: i64,
+                          card_network: Option<enums::CardNetwork>,
+                          billing_country: enums::Country| {
+            inputs::BackendInput {
+                metadata: None,
+                payment: inputs::PaymentInput {
+                    amount: MinorUnit::new(amount),
+                    card_bin: None,
+                    currency: enums::Currency::USD,
+                    authentication_type: Some(enums::AuthenticationType::NoThreeDs),
+                    capture_method: Some(enums::CaptureMethod::Automatic),
+                    business_country: Some(enums::Country::UnitedStatesOfAmerica),
+                    billing_country: Some(billing_country),
+                    business_label: None,
+                    setup_future_usage: None,
+                },
+                payment_method: inputs::PaymentMethodInput {
+                    payment_method: Some(enums::PaymentMethod::Card),
+                    payment_method_type: Some(enums::PaymentMethodType::Credit),
+                    card_network,
+                },
+                mandate: inputs::MandateData {
+                    mandate_acceptance_type: None,
+                    mandate_type: None,
+                    payment_type: None,
+                },
+                time: None,
+            }
+        };
+
+        let cases = [
+            (
+                make_input(100, Some(enums::CardNetwork::Visa), enums::Country::France),
+                Some("rule_1"),
+            ),
+            (
+                make_input(
+                    20000,
+                    Some(enums::CardNetwork::Mastercard),
+                    enums::Country::France,
+                ),
+                Some("rule_1"),
+            ),
+            (
+                make_input(
+                    100,
+                    Some(enums::CardNetwork::Mastercard),
+                    enums::Country::France,
+                ),
+                None,
+            ),
+            (
+                make_input(
+                    100,
+                    Some(enums::CardNetwork::Visa),
+                    enums::Country::UnitedStatesOfAmerica,
+                ),
+                None,
+            ),
+        ];
+
+        let vir_backend =
+            VirInterpreterBackend::<DummyOutput>::with_program(program.clone()).expect("Program");
+        let ast_backend =
+            backend::InterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
+
+        for (input, expected_rule) in cases {
+            let vir_result = vir_backend.execute(input.clone()).expect("Execution");
+            let ast_result = ast_backend.execute(input).expect("Execution");
+
+            assert_eq!(vir_result.rule_name.as_deref(), expected_rule);
+            assert_eq!(ast_result.rule_name.as_deref(), expected_rule);
+        }
+    }
+
+    #[test]
+    fn test_negated_comparisons_of_missing_values() {
+        let make_input = |billing_country: Option<enums::Country>| inputs::BackendInput {
+            metadata: None,
+            payment: inputs::PaymentInput {
+                amount: MinorUnit::new(100),
+                card_bin: None,
+                currency: enums::Currency::USD,
+                authentication_type: Some(enums::AuthenticationType::NoThreeDs),
+                capture_method: Some(enums::CaptureMethod::Automatic),
+                business_country: Some(enums::Country::UnitedStatesOfAmerica),
+                billing_country,
+                business_label: None,
+                setup_future_usage: None,
+            },
+            payment_method: inputs::PaymentMethodInput {
+                payment_method: Some(enums::PaymentMethod::Card),
+                payment_method_type: Some(enums::PaymentMethodType::Credit),
+                card_network: None,
+            },
+            mandate: inputs::MandateData {
+                mandate_acceptance_type: None,
+                mandate_type: None,
+                payment_type: None,
+            },
+            time: None,
+        };
+
+        let cases = [
+            ("billing_country = UnitedStatesOfAmerica", None, false),
+            ("!billing_country = UnitedStatesOfAmerica", None, true),
+            ("!(billing_country /= UnitedStatesOfAmerica)", None, false),
+            (
+                "!(billing_country /= UnitedStatesOfAmerica)",
+                Some(enums::Country::UnitedStatesOfAmerica),
+                true,
+            ),
+            ("!(billing_country = (France, Germany))", None, true),
+            (
+                "!(billing_country = France | payment_method = wallet)",
+                None,
+                true,
+            ),
+        ];
+
+        for (condition, billing_country, expected_match) in cases {
+            let program_str = format!(
+                r#"
+                default: ["stripe"]
+                rule_1: ["adyen"]
+                {{
+                    {condition}
+                }}
+                "#
+            );
+            let (_, program) = ast::parser::program::<DummyOutput>(&program_str).expect("Program");
+
+            let vir_backend = VirInterpreterBackend::<DummyOutput>::with_program(program.clone())
+                .expect("Program");
+            let ast_backend =
+                backend::InterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
+
+            let input = make_input(billing_country);
+            let vir_result = vir_backend.execute(input.clone()).expect("Execution");
+            let ast_result = ast_backend.execute(input).expect("Execution");
+
+            let expected_rule = expected_match.then_some("rule_1");
+            assert_eq!(
+                vir_result.rule_name.as_deref(),
+                expected_rule,
+                "{condition}"
+            );
+            assert_eq!(
+                ast_result.rule_name.as_deref(),
+                expected_rule,
+                "{condition}"
+            );
+        }
+
+        // Outside of `!`, the backends keep their results of the existing programs for a missing
+        // value, the AST interpreter not matching `/=` while the VIR does
+        let program_str = r#"
+        default: ["stripe"]
+        rule_1: ["adyen"]
+        {
+            billing_country /= UnitedStatesOfAmerica
+        }
+        "#;
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        let vir_backend =
+            VirInterpreterBackend::<DummyOutput>::with_program(program.clone()).expect("Program");
+        let ast_backend =
+            backend::InterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
+
+        let vir_result = vir_backend.execute(make_input(None)).expect("Execution");
+        let ast_result = ast_backend.execute(make_input(None)).expect("Execution");
+
+        assert_eq!(vir_result.rule_name.as_deref(), Some("rule_1"));
+        assert_eq!(ast_result.rule_name, None);
+    }
+
+    #[test]
+    fn test_time_based_keys() {
+        let program_str = r#"
+        default: ["stripe"]
+
+        maintenance: ["adyen"]
+        {
+            utc_date = 2024-12-31 & utc_hour >= 22
+        }
+
+        weekend: ["checkout"]
+        {
+            utc_weekday = (saturday, sunday)
+        }
+        "#;
+
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        let make_input = |clock: Option<inputs::FixedClock>| inputs::BackendInput {
+            metadata: None,
+            payment: inputs::PaymentInput {
+                amount: MinorUnit::new(100),
+                card_bin: None,
+                currency: enums::Currency::USD,
+                authentication_type: None,
+                capture_method: None,
+                business_country: None,
+                billing_country: None,
+                business_label: None,
+                setup_future_usage: None,
+            },
+            payment_method: inputs::PaymentMethodInput {
+                payment_method: Some(enums::PaymentMethod::Card),
+                payment_method_type: None,
+                card_network: None,
+            },
+            mandate: inputs::MandateData {
+                mandate_acceptance_type: None,
+                mandate_type: None,
+                payment_type: None,
+            },
+            time: clock.as_ref().map(inputs::TimeInput::now),
+        };
+        let clock_at = |year: i32, month: time::Month, day: u8, hour: u8| {
+            let datetime = time::Date::from_calendar_date(year, month, day)
+                .and_then(|date| date.with_hms(hour, 0, 0))
+                .expect("Date time");
+            Some(inputs::FixedClock(datetime))
+        };
+
+        let cases = [
+            (
+                clock_at(2024, time::Month::December, 31, 23),
+                Some("maintenance"),
+            ),
+            (clock_at(2024, time::Month::December, 31, 10), None),
+            (clock_at(2025, time::Month::January, 4, 23), Some("weekend")),
+            (None, None),
+        ];
+
+        let vir_backend =
+            VirInterpreterBackend::<DummyOutput>::with_program(program.clone()).expect("Program");
+        let ast_backend =
+            backend::InterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
+
+        for (clock, expected_rule) in cases {
+            let vir_result = vir_backend.execute(make_input(clock)).expect("Execution");
+            let ast_result = ast_backend.execute(make_input(clock)).expect("Execution");
+
+            assert_eq!(vir_result.rule_name.as_deref(), expected_rule);
+            assert_eq!(ast_result.rule_name.as_deref(), expected_rule);
+        }
+    }
 }
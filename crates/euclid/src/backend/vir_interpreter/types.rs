@@ -1,3 +1,4 @@
+use common_utils::types::MinorUnit;
 use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::{
@@ -114,7 +115,7 @@ impl Context {
             enum_values.insert(EuclidValue::MandateAcceptanceType(mandate_acceptance_type));
         }
 
-        let numeric_values: FxHashMap<EuclidKey, EuclidValue> = FxHashMap::from_iter([(
+        let mut numeric_values: FxHashMap<EuclidKey, EuclidValue> = FxHashMap::from_iter([(
             EuclidKey::PaymentAmount,
             EuclidValue::PaymentAmount(types::NumValue {
                 number: payment.amount,
@@ -122,6 +123,24 @@ impl Context {
             }),
         )]);
 
+        if let Some(time) = input.time {
+            enum_values.insert(EuclidValue::UtcWeekday(time.weekday));
+            numeric_values.insert(
+                EuclidKey::UtcHour,
+                EuclidValue::UtcHour(types::NumValue {
+                    number: MinorUnit::new(i64::from(time.hour)),
+                    refinement: None,
+                }),
+            );
+            numeric_values.insert(
+                EuclidKey::UtcDate,
+                EuclidValue::UtcDate(types::NumValue {
+                    number: MinorUnit::new(time.date),
+                    refinement: None,
+                }),
+            );
+        }
+
         Self {
             atomic_values: enum_values,
             numeric_values,
@@ -18,7 +18,7 @@ use crate::{
         dir::{self, EuclidDirFilter},
         vir,
     },
-    types::{DataType, Metadata},
+    types::{DataType, Metadata, NumValue, NumValueRefinement},
 };
 
 /// Analyses conflicting assertions on the same key in a conjunctive context.
@@ -220,21 +220,408 @@ fn perform_context_analyses(
     Ok(())
 }
 
-pub fn analyze<O: EuclidAnalysable + EuclidDirFilter>(
+/// A conjunction of comparisons under which a rule matches, one for every leaf statement of the
+/// rule.
+type RulePath<'a> = Vec<&'a dir::DirComparison>;
+
+/// The maximum number of key value combinations [`analyze_coverage`] checks
+const MAX_COVERAGE_COMBINATIONS: usize = 10_000;
+
+fn collect_rule_paths<'a>(
+    statements: &'a [dir::DirIfStatement],
+    prefix: &mut RulePath<'a>,
+    paths: &mut Vec<RulePath<'a>>,
+) {
+    for stmt in statements {
+        let prefix_len = prefix.len();
+        prefix.extend(stmt.condition.iter());
+
+        match &stmt.nested {
+            None => paths.push(prefix.clone()),
+            Some(nested) => collect_rule_paths(nested, prefix, paths),
+        }
+
+        prefix.truncate(prefix_len);
+    }
+}
+
+fn rule_paths<O>(rule: &dir::DirRule<O>) -> Vec<RulePath<'_>> {
+    let mut paths = Vec::new();
+    collect_rule_paths(&rule.statements, &mut Vec::new(), &mut paths);
+    paths
+}
+
+/// The inclusive range of amounts a number value matches, `None` for `/=` refinements
+fn num_value_range(value: &NumValue) -> Option<(i64, i64)> {
+    let number = value.number.get_amount_as_i64();
+
+    match value.refinement {
+        None => Some((number, number)),
+        Some(NumValueRefinement::GreaterThan) => Some((number.saturating_add(1), i64::MAX)),
+        Some(NumValueRefinement::GreaterThanEqual) => Some((number, i64::MAX)),
+        Some(NumValueRefinement::LessThan) => Some((i64::MIN, number.saturating_sub(1))),
+        Some(NumValueRefinement::LessThanEqual) => Some((i64::MIN, number)),
+        Some(NumValueRefinement::NotEqual) => None,
+    }
+}
+
+/// Whether every payment having the `narrower` value also has the `wider` value
+fn value_implies(narrower: &dir::DirValue, wider: &dir::DirValue) -> bool {
+    match (narrower.get_num_value(), wider.get_num_value()) {
+        (Some(narrower_num), Some(wider_num)) if narrower.get_key() == wider.get_key() => {
+            match (num_value_range(&narrower_num), num_value_range(&wider_num)) {
+                (Some((low, high)), Some((wider_low, wider_high))) => {
+                    wider_low <= low && high <= wider_high
+                }
+                _ => narrower_num == wider_num,
+            }
+        }
+        _ => narrower == wider,
+    }
+}
+
+/// Whether every payment satisfying the `narrower` comparison also satisfies the `wider` one.
+///
+/// This errs on the side of `false`, so that a rule is only reported as shadowed when it
+/// certainly is. `key_present` tells whether the payments satisfying `narrower` are known to have
+/// a value for its key, since a negative comparison also holds for payments without one.
+fn comparison_implies(
+    narrower: &dir::DirComparison,
+    wider: &dir::DirComparison,
+    key_present: bool,
+) -> bool {
+    use dir::DirComparisonLogic::{NegativeConjunction, PositiveDisjunction};
+
+    match (&narrower.logic, &wider.logic) {
+        (PositiveDisjunction, PositiveDisjunction) => narrower.values.iter().all(|narrower_val| {
+            wider
+                .values
+                .iter()
+                .any(|wider_val| value_implies(narrower_val, wider_val))
+        }),
+
+        (PositiveDisjunction, NegativeConjunction) => narrower.values.iter().all(|narrower_val| {
+            narrower_val.get_num_value().is_none()
+                && wider.values.iter().all(|wider_val| {
+                    narrower_val.get_key() == wider_val.get_key() && narrower_val != wider_val
+                })
+        }),
+
+        (NegativeConjunction, NegativeConjunction) => wider
+            .values
+            .iter()
+            .all(|wider_val| narrower.values.contains(wider_val)),
+
+        (NegativeConjunction, PositiveDisjunction) => {
+            key_present
+                && narrower
+                    .values
+                    .first()
+                    .and_then(|val| val.get_key().kind.get_value_set())
+                    .is_some_and(|value_set| {
+                        value_set
+                            .iter()
+                            .filter(|val| !narrower.values.contains(val))
+                            .all(|val| wider.values.contains(val))
+                    })
+        }
+    }
+}
+
+fn comparison_key(comparison: &dir::DirComparison) -> Option<dir::DirKey> {
+    comparison.values.first().map(dir::DirValue::get_key)
+}
+
+/// Whether every payment the path matches has a value for the key of the comparison, which is the
+/// case when the path asserts some value of the key
+fn path_asserts_key(path: &RulePath<'_>, comparison: &dir::DirComparison) -> bool {
+    let key = comparison_key(comparison);
+
+    key.is_some()
+        && path.iter().any(|path_comparison| {
+            matches!(
+                path_comparison.logic,
+                dir::DirComparisonLogic::PositiveDisjunction
+            ) && comparison_key(path_comparison) == key
+        })
+}
+
+/// Whether every payment the `narrower` path matches is also matched by the `wider` path
+fn path_subsumes(wider: &RulePath<'_>, narrower: &RulePath<'_>) -> bool {
+    wider.iter().all(|wider_comparison| {
+        narrower.iter().any(|narrower_comparison| {
+            comparison_implies(
+                narrower_comparison,
+                wider_comparison,
+                path_asserts_key(narrower, narrower_comparison),
+            )
+        })
+    })
+}
+
+/// Analyses rules which are never reached because the rules before them already match every
+/// payment they match.
+///
+/// For example,
+/// ```notrust
+/// rule_1: ["stripe"]
+/// {
+///     payment_method = (card, wallet)
+/// }
+///
+/// rule_2: ["adyen"]
+/// {
+///     payment_method = card & amount > 500
+/// }
+/// ```notrust
+/// Every payment matching `rule_2` is routed by `rule_1`, so `rule_2` has no effect. This is a
+/// warning rather than an error, so it is not part of [`analyze`], see [`analyze_shadowing`]. Every
+/// shadowed rule of the program is reported, with an error each.
+pub fn analyze_shadowed_rules<O>(
+    program: &dir::DirProgram<O>,
+) -> Result<(), Vec<types::AnalysisError>> {
+    let mut errors = Vec::new();
+    let rules_with_paths = program
+        .rules
+        .iter()
+        .map(|rule| (rule, rule_paths(rule)))
+        .collect::<Vec<_>>();
+
+    for (index, (rule, paths)) in rules_with_paths.iter().enumerate() {
+        // A rule without any path never matches in the first place
+        if paths.is_empty() {
+            continue;
+        }
+
+        let mut shadowed_by: Vec<String> = Vec::new();
+        let is_shadowed = paths.iter().all(|path| {
+            let shadowing_rule = rules_with_paths
+                .iter()
+                .take(index)
+                .find(|(_, earlier_paths)| {
+                    earlier_paths
+                        .iter()
+                        .any(|earlier_path| path_subsumes(earlier_path, path))
+                });
+
+            if let Some((earlier_rule, _)) = shadowing_rule {
+                if !shadowed_by.contains(&earlier_rule.name) {
+                    shadowed_by.push(earlier_rule.name.clone());
+                }
+            }
+
+            shadowing_rule.is_some()
+        });
+
+        if is_shadowed {
+            errors.push(types::AnalysisError {
+                error_type: types::AnalysisErrorType::ShadowedRule {
+                    rule: rule.name.clone(),
+                    shadowed_by,
+                },
+                metadata: Default::default(),
+            });
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Whether a payment having the given values can be matched by the path, assuming that the keys
+/// not among the values can take any value.
+fn path_admits(path: &RulePath<'_>, values: &[dir::DirValue]) -> bool {
+    path.iter().all(|comparison| {
+        let comparison_key = comparison.values.first().map(dir::DirValue::get_key);
+
+        values
+            .iter()
+            .find(|val| Some(val.get_key()) == comparison_key)
+            .map_or(true, |val| {
+                let is_present = comparison.values.contains(val);
+
+                match comparison.logic {
+                    dir::DirComparisonLogic::PositiveDisjunction => is_present,
+                    dir::DirComparisonLogic::NegativeConjunction => !is_present,
+                }
+            })
+    })
+}
+
+/// Analyses the rules of the program which are shadowed by the rules before them, with
+/// [`analyze_shadowed_rules`].
+pub fn analyze_shadowing<O: EuclidDirFilter>(
     program: ast::Program<O>,
-    knowledge_graph: Option<&ConstraintGraph<dir::DirValue>>,
-) -> Result<vir::ValuedProgram<O>, types::AnalysisError> {
+) -> Result<(), Vec<types::AnalysisError>> {
+    analyze_shadowed_rules(&ast::lowering::lower_program(program).map_err(|error| vec![error])?)
+}
+
+/// Analyses the combinations of values of the given keys for which no rule can match, so that
+/// every payment with such a combination falls through to the default selection.
+///
+/// For example, with the keys `payment_method` and `currency`, a program whose rules all assert
+/// `currency = USD` leaves every payment method in every other currency uncovered. The uncovered
+/// combinations are reported with [`types::AnalysisErrorType::IncompleteCoverage`].
+pub fn analyze_coverage<O: EuclidDirFilter>(
+    program: ast::Program<O>,
+    keys: &[dir::DirKeyKind],
+) -> Result<(), types::AnalysisError> {
     let dir_program = ast::lowering::lower_program(program)?;
 
-    let selection_data = state_machine::make_connector_selection_data(&dir_program);
-    let mut ctx_manager = state_machine::AnalysisContextManager::new(&dir_program, &selection_data);
+    let mut combinations: Vec<Vec<dir::DirValue>> = vec![Vec::new()];
+    for key in keys {
+        let value_set = key
+            .get_value_set()
+            .filter(|_| matches!(key.get_type(), DataType::EnumVariant))
+            .ok_or_else(|| types::AnalysisError {
+                error_type: types::AnalysisErrorType::UnsupportedCoverageKey(key.clone()),
+                metadata: Default::default(),
+            })?;
+
+        if combinations.len().saturating_mul(value_set.len()) > MAX_COVERAGE_COMBINATIONS {
+            Err(types::AnalysisError {
+                error_type: types::AnalysisErrorType::CoverageTooLarge {
+                    limit: MAX_COVERAGE_COMBINATIONS,
+                },
+                metadata: Default::default(),
+            })?;
+        }
+
+        combinations = combinations
+            .iter()
+            .flat_map(|combination| {
+                value_set.iter().map(move |val| {
+                    let mut combination = combination.clone();
+                    combination.push(val.clone());
+                    combination
+                })
+            })
+            .collect();
+    }
+
+    let paths = dir_program
+        .rules
+        .iter()
+        .flat_map(rule_paths)
+        .collect::<Vec<_>>();
+
+    let uncovered = combinations
+        .into_iter()
+        .filter(|combination| !paths.iter().any(|path| path_admits(path, combination)))
+        .collect::<Vec<_>>();
+
+    if uncovered.is_empty() {
+        Ok(())
+    } else {
+        Err(types::AnalysisError {
+            error_type: types::AnalysisErrorType::IncompleteCoverage {
+                keys: keys.to_vec(),
+                uncovered,
+            },
+            metadata: Default::default(),
+        })
+    }
+}
+
+/// Analyses a single path of a rule, i.e. a purely conjunctive condition along with the values
+/// asserted by the connector selection of the rule.
+fn analyze_path(
+    condition: dir::DirIfCondition,
+    connector_selection_data: &[(dir::DirValue, Metadata)],
+    knowledge_graph: &ConstraintGraph<dir::DirValue>,
+) -> Result<(), types::AnalysisError> {
+    let program = dir::DirProgram {
+        default_selection: (),
+        rules: vec![dir::DirRule {
+            name: String::new(),
+            connector_selection: (),
+            statements: vec![dir::DirIfStatement {
+                condition,
+                nested: None,
+            }],
+        }],
+        metadata: Default::default(),
+    };
+    let selection_data = [connector_selection_data.to_vec()];
+
+    let mut ctx_manager = state_machine::AnalysisContextManager::new(&program, &selection_data);
     while let Some(ctx) = ctx_manager.advance().map_err(|err| types::AnalysisError {
         metadata: Default::default(),
         error_type: types::AnalysisErrorType::StateMachine(err),
     })? {
-        perform_context_analyses(ctx, knowledge_graph.unwrap_or(&truth::ANALYSIS_GRAPH))?;
+        perform_context_analyses(ctx, knowledge_graph)?;
     }
 
+    Ok(())
+}
+
+/// Analyses the paths through an if statement, under the conjunctive `prefix` asserted by the
+/// enclosing if statements.
+///
+/// `|` and `!` groups make a condition expand to several alternatives, so the if statement is
+/// only flagged when every one of its alternatives is flagged, in which case the error of the
+/// first alternative is returned. Within an alternative, every nested if statement has to pass.
+fn analyze_if_statement<O: EuclidDirFilter>(
+    statement: &ast::IfStatement,
+    prefix: &dir::DirIfCondition,
+    connector_selection_data: &[(dir::DirValue, Metadata)],
+    knowledge_graph: &ConstraintGraph<dir::DirValue>,
+) -> Result<(), types::AnalysisError> {
+    let mut first_error = None;
+
+    for alternative in ast::lowering::lower_condition::<O>(statement.condition.clone())? {
+        let mut condition = prefix.clone();
+        condition.extend(alternative);
+
+        let result = match &statement.nested {
+            None => analyze_path(condition, connector_selection_data, knowledge_graph),
+            Some(nested) => nested.iter().try_for_each(|nested_statement| {
+                analyze_if_statement::<O>(
+                    nested_statement,
+                    &condition,
+                    connector_selection_data,
+                    knowledge_graph,
+                )
+            }),
+        };
+
+        match result {
+            Ok(()) => return Ok(()),
+            Err(error) => {
+                first_error.get_or_insert(error);
+            }
+        }
+    }
+
+    first_error.map_or(Ok(()), Err)
+}
+
+pub fn analyze<O: EuclidAnalysable + EuclidDirFilter>(
+    program: ast::Program<O>,
+    knowledge_graph: Option<&ConstraintGraph<dir::DirValue>>,
+) -> Result<vir::ValuedProgram<O>, types::AnalysisError> {
+    let knowledge_graph = knowledge_graph.unwrap_or(&truth::ANALYSIS_GRAPH);
+
+    for rule in &program.rules {
+        let connector_selection_data = rule
+            .connector_selection
+            .get_dir_value_for_analysis(rule.name.clone());
+
+        for statement in &rule.statements {
+            analyze_if_statement::<O>(
+                statement,
+                &Vec::new(),
+                &connector_selection_data,
+                knowledge_graph,
+            )?;
+        }
+    }
+
+    let dir_program = ast::lowering::lower_program(program)?;
+
     dir::lowering::lower_program(dir_program)
 }
 
@@ -376,6 +763,271 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_negated_assertion_in_condition_group_detection() {
+        let program_str = r#"
+            default: ["stripe"]
+
+            rule_1: ["adyen"]
+            {
+                payment_method = wallet & (capture_method = automatic | !payment_method = wallet)
+            }
+        "#;
+
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        let analysis_result = analyze(program, None);
+
+        assert!(
+            analysis_result.is_ok(),
+            "Expected the satisfiable `capture_method = automatic` alternative to pass"
+        );
+    }
+
+    #[test]
+    fn test_negated_assertion_in_every_condition_group_alternative_detection() {
+        let program_str = r#"
+            default: ["stripe"]
+
+            rule_1: ["adyen"]
+            {
+                payment_method = wallet & (!payment_method = wallet | payment_method = card)
+            }
+        "#;
+
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        let analysis_result = analyze(program, None);
+
+        if let Err(types::AnalysisError {
+            error_type: types::AnalysisErrorType::NegatedAssertion { value, .. },
+            ..
+        }) = analysis_result
+        {
+            assert_eq!(
+                value,
+                dirval!(PaymentMethod = Wallet),
+                "Expected to catch payment_method = wallet as conflict"
+            );
+        } else {
+            panic!("Expected negated assertion error");
+        }
+    }
+
+    #[test]
+    fn test_shadowed_rule_detection() {
+        let program_str = r#"
+            default: ["stripe"]
+
+            cards_and_wallets: ["stripe"]
+            {
+                payment_method = (card, wallet)
+            }
+
+            large_card_payments: ["adyen"]
+            {
+                payment_method = card & amount > 500 {
+                    card_network = Visa
+                    currency /= USD
+                }
+            }
+        "#;
+
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        assert!(
+            analyze(program.clone(), None).is_ok(),
+            "Expected a shadowed rule not to fail the analysis"
+        );
+        let analysis_result = analyze_shadowing(program);
+
+        if let Err(errors) = analysis_result {
+            let shadowed_rules = errors
+                .into_iter()
+                .map(|error| match error.error_type {
+                    types::AnalysisErrorType::ShadowedRule { rule, shadowed_by } => {
+                        (rule, shadowed_by)
+                    }
+                    error_type => panic!("Unexpected analysis error {error_type:?}"),
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                shadowed_rules,
+                vec![(
+                    "large_card_payments".to_string(),
+                    vec!["cards_and_wallets".to_string()]
+                )]
+            );
+        } else {
+            panic!("Did not receive shadowed rule error");
+        }
+
+        let program_str = r#"
+            default: ["stripe"]
+
+            cards_and_wallets: ["stripe"]
+            {
+                payment_method = (card, wallet)
+            }
+
+            large_card_payments: ["adyen"]
+            {
+                payment_method = card & amount > 500
+            }
+
+            usd_payments: ["adyen"]
+            {
+                currency = USD
+            }
+
+            wallet_payments: ["checkout"]
+            {
+                payment_method = wallet
+            }
+        "#;
+
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        let shadowed_rules = analyze_shadowing(program)
+            .expect_err("Expected shadowed rules")
+            .into_iter()
+            .map(|error| match error.error_type {
+                types::AnalysisErrorType::ShadowedRule { rule, shadowed_by } => (rule, shadowed_by),
+                error_type => panic!("Unexpected analysis error {error_type:?}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            shadowed_rules,
+            vec![
+                (
+                    "large_card_payments".to_string(),
+                    vec!["cards_and_wallets".to_string()]
+                ),
+                (
+                    "wallet_payments".to_string(),
+                    vec!["cards_and_wallets".to_string()]
+                ),
+            ]
+        );
+
+        let program_str = r#"
+            default: ["stripe"]
+
+            large_card_payments: ["adyen"]
+            {
+                payment_method = card & amount > 500
+            }
+
+            cards: ["stripe"]
+            {
+                payment_method = card
+            }
+        "#;
+
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        assert!(analyze_shadowing(program).is_ok());
+    }
+
+    #[test]
+    fn test_negative_comparison_shadowing_requires_key() {
+        // Payments without an authentication type match `authentication_type /= three_ds` but not
+        // `authentication_type = no_three_ds`
+        let program_str = r#"
+            default: ["stripe"]
+
+            no_three_ds: ["stripe"]
+            {
+                authentication_type = no_three_ds
+            }
+
+            not_three_ds: ["adyen"]
+            {
+                authentication_type /= three_ds
+            }
+        "#;
+
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        assert!(analyze_shadowing(program).is_ok());
+
+        let program_str = r#"
+            default: ["stripe"]
+
+            no_three_ds: ["stripe"]
+            {
+                authentication_type = no_three_ds
+            }
+
+            not_three_ds: ["adyen"]
+            {
+                authentication_type = (three_ds, no_three_ds) & authentication_type /= three_ds
+            }
+        "#;
+
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        assert!(matches!(
+            analyze_shadowing(program).as_deref(),
+            Err([types::AnalysisError {
+                error_type: types::AnalysisErrorType::ShadowedRule { .. },
+                ..
+            }])
+        ));
+    }
+
+    #[test]
+    fn test_coverage_analysis() {
+        let program_str = r#"
+            default: ["stripe"]
+
+            usd_payments: ["adyen"]
+            {
+                currency = USD
+            }
+
+            non_card_payments: ["stripe"]
+            {
+                payment_method /= card
+            }
+        "#;
+
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        let analysis_result = analyze_coverage(
+            program,
+            &[
+                dir::DirKeyKind::PaymentMethod,
+                dir::DirKeyKind::PaymentCurrency,
+            ],
+        );
+
+        if let Err(types::AnalysisError {
+            error_type: types::AnalysisErrorType::IncompleteCoverage { uncovered, .. },
+            ..
+        }) = analysis_result
+        {
+            assert!(uncovered
+                .iter()
+                .all(|combination| combination.contains(&dirval!(PaymentMethod = Card))));
+            assert!(uncovered.contains(&vec![
+                dirval!(PaymentMethod = Card),
+                dirval!(PaymentCurrency = EUR)
+            ]));
+            assert!(!uncovered.contains(&vec![
+                dirval!(PaymentMethod = Card),
+                dirval!(PaymentCurrency = USD)
+            ]));
+        } else {
+            panic!("Did not receive incomplete coverage error");
+        }
+
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        let analysis_result = analyze_coverage(program, &[dir::DirKeyKind::PaymentAmount]);
+
+        assert!(matches!(
+            analysis_result,
+            Err(types::AnalysisError {
+                error_type: types::AnalysisErrorType::UnsupportedCoverageKey(
+                    dir::DirKeyKind::PaymentAmount
+                ),
+                ..
+            })
+        ));
+    }
+
     #[test]
     fn test_negation_graph_analysis() {
         let graph = knowledge! {
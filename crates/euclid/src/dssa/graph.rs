@@ -70,6 +70,9 @@ impl cgraph::NodeViz for dir::DirValue {
             Self::CardRedirectType(crt) => crt.to_string(),
             Self::RealTimePaymentType(rtpt) => rtpt.to_string(),
             Self::OpenBankingType(ob) => ob.to_string(),
+            Self::UtcHour(hour) => hour.number.to_string(),
+            Self::UtcWeekday(weekday) => weekday.to_string(),
+            Self::UtcDate(date) => date.number.to_string(),
         }
     }
 }
@@ -243,6 +246,18 @@ pub trait CgraphExt {
         domains: Option<&[String]>,
     ) -> Result<bool, cgraph::GraphError<dir::DirValue>>;
 
+    /// Performs the same analysis as [`Self::check_value_validity`], but fails with the error
+    /// explaining why the value is invalid instead. The analysis trace of the error is only
+    /// available as long as `memo` is.
+    fn value_validity_analysis(
+        &self,
+        val: dir::DirValue,
+        analysis_ctx: &AnalysisContext,
+        memo: &mut cgraph::Memoization<dir::DirValue>,
+        cycle_map: &mut cgraph::CycleCheck,
+        domains: Option<&[String]>,
+    ) -> Result<(), cgraph::GraphError<dir::DirValue>>;
+
     fn key_value_analysis(
         &self,
         val: dir::DirValue,
@@ -333,26 +348,11 @@ impl CgraphExt for cgraph::ConstraintGraph<dir::DirValue> {
         cycle_map: &mut cgraph::CycleCheck,
         domains: Option<&[String]>,
     ) -> Result<bool, cgraph::GraphError<dir::DirValue>> {
-        let maybe_node_id = self.value_map.get(&cgraph::NodeValue::Value(val));
-
-        let node_id = if let Some(nid) = maybe_node_id {
-            nid
-        } else {
-            return Ok(false);
-        };
-
-        let result = self.check_node(
-            analysis_ctx,
-            *node_id,
-            cgraph::Relation::Positive,
-            cgraph::Strength::Weak,
-            memo,
-            cycle_map,
-            domains,
-        );
+        let result = self.value_validity_analysis(val, analysis_ctx, memo, cycle_map, domains);
 
         match result {
             Ok(_) => Ok(true),
+            Err(cgraph::GraphError::ValueNodeNotFound(_)) => Ok(false),
             Err(e) => {
                 e.get_analysis_trace()?;
                 Ok(false)
@@ -360,6 +360,30 @@ impl CgraphExt for cgraph::ConstraintGraph<dir::DirValue> {
         }
     }
 
+    fn value_validity_analysis(
+        &self,
+        val: dir::DirValue,
+        analysis_ctx: &AnalysisContext,
+        memo: &mut cgraph::Memoization<dir::DirValue>,
+        cycle_map: &mut cgraph::CycleCheck,
+        domains: Option<&[String]>,
+    ) -> Result<(), cgraph::GraphError<dir::DirValue>> {
+        let node_id = *self
+            .value_map
+            .get(&cgraph::NodeValue::Value(val.clone()))
+            .ok_or(cgraph::GraphError::ValueNodeNotFound(val))?;
+
+        self.check_node(
+            analysis_ctx,
+            node_id,
+            cgraph::Relation::Positive,
+            cgraph::Strength::Weak,
+            memo,
+            cycle_map,
+            domains,
+        )
+    }
+
     fn key_value_analysis(
         &self,
         val: dir::DirValue,
@@ -152,6 +152,26 @@ pub enum AnalysisErrorType {
     NotImplemented,
     #[error("The payment method type is not supported under the payment method")]
     NotSupported,
+    #[error("Condition group is empty")]
+    EmptyConditionGroup,
+    #[error("Condition expands to more than {limit} alternatives")]
+    ConditionTooComplex { limit: usize },
+    #[error(
+        "Rule '{rule}' is never reached, as it only matches payments matched by {shadowed_by:?}"
+    )]
+    ShadowedRule {
+        rule: String,
+        shadowed_by: Vec<String>,
+    },
+    #[error("Key '{0}' does not have a finite set of values to analyze coverage over")]
+    UnsupportedCoverageKey(dir::DirKeyKind),
+    #[error("Coverage analysis spans more than {limit} combinations")]
+    CoverageTooLarge { limit: usize },
+    #[error("{} combinations of {keys:?} always fall through to the default selection", .uncovered.len())]
+    IncompleteCoverage {
+        keys: Vec<dir::DirKeyKind>,
+        uncovered: Vec<Vec<dir::DirValue>>,
+    },
 }
 
 #[derive(Debug, Clone)]
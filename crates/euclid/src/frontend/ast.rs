@@ -1,3 +1,5 @@
+#[cfg(feature = "ast_parser")]
+pub mod formatter;
 pub mod lowering;
 #[cfg(feature = "ast_parser")]
 pub mod parser;
@@ -80,7 +82,7 @@ pub enum ComparisonType {
 }
 
 /// Represents a single comparison condition.
-#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Comparison {
     /// The left hand side which will always be a domain input identifier like "payment.method.cardtype"
@@ -96,13 +98,39 @@ pub struct Comparison {
     pub metadata: Metadata,
 }
 
+/// Represents a single term of the conjunction making up an IF statement's condition, which is
+/// either a plain comparison or a group of conditions
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum Condition {
+    Comparison(Comparison),
+    Group(ConditionGroup),
+}
+
+/// Represents a group of conditions combined with a boolean operator other than AND
+///
+/// ```text
+/// (payment.method.network = visa | amount > 10000)
+/// !billing_country = UnitedStatesOfAmerica
+/// !(payment.method = card & payment.method.cardtype = credit)
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConditionGroup {
+    /// Holds if any of the contained conditions hold
+    Or(Vec<Vec<Condition>>),
+    /// Holds if the contained condition does not hold
+    Not(Vec<Condition>),
+}
+
 /// Represents all the conditions of an IF statement
 /// eg:
 ///
 /// ```text
 /// payment.method = card & payment.method.cardtype = debit & payment.method.network = diners
+/// payment.method = card & (payment.method.network = visa | amount > 10000)
 /// ```
-pub type IfCondition = Vec<Comparison>;
+pub type IfCondition = Vec<Condition>;
 
 /// Represents an IF statement with conditions and optional nested IF statements
 ///
@@ -113,10 +141,10 @@ pub type IfCondition = Vec<Comparison>;
 ///     }
 /// }
 /// ```
-#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct IfStatement {
-    #[schema(value_type=Vec<Comparison>)]
+    #[schema(value_type=Vec<Condition>)]
     pub condition: IfCondition,
     pub nested: Option<Vec<IfStatement>>,
 }
@@ -136,7 +164,7 @@ pub struct IfStatement {
 /// }
 /// ```
 
-#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[aliases(RuleConnectorSelection = Rule<ConnectorSelection>)]
 pub struct Rule<O> {
@@ -148,7 +176,7 @@ pub struct Rule<O> {
 
 /// The program, having a default connector selection and
 /// a bunch of rules. Also can hold arbitrary metadata.
-#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[aliases(ProgramConnectorSelection = Program<ConnectorSelection>)]
 pub struct Program<O> {
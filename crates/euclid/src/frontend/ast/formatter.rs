@@ -0,0 +1,309 @@
+//! Formatter for the textual euclid DSL
+//!
+//! Emits the canonical DSL text of an [`ast::Program`], which [`super::parser::program`] parses
+//! back into the same program. Metadata is meant for the frontend and is not part of the textual
+//! format, so it is left empty when the text is parsed again.
+
+use common_utils::types::MinorUnit;
+
+use super::parser::{self, EuclidParsable};
+use crate::frontend::ast;
+
+const INDENT: &str = "    ";
+
+pub fn comparison_type(comparison_type: &ast::ComparisonType) -> &'static str {
+    match comparison_type {
+        ast::ComparisonType::Equal => "=",
+        ast::ComparisonType::NotEqual => "/=",
+        ast::ComparisonType::LessThan => "<",
+        ast::ComparisonType::LessThanEqual => "<=",
+        ast::ComparisonType::GreaterThan => ">",
+        ast::ComparisonType::GreaterThanEqual => ">=",
+    }
+}
+
+/// Formats a string literal, escaping the `"` and `\` it contains
+pub fn string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn list<T>(items: &[T], format_item: impl Fn(&T) -> String) -> String {
+    let items = items.iter().map(format_item).collect::<Vec<_>>();
+    format!("({})", items.join(", "))
+}
+
+/// Formats a number of the `YYYYMMDD` form as the `YYYY-MM-DD` date it was parsed from
+fn date(date: &MinorUnit) -> String {
+    let date = date.get_amount_as_i64();
+    format!(
+        "{:04}-{:02}-{:02}",
+        date / 10000,
+        date / 100 % 100,
+        date % 100
+    )
+}
+
+pub fn value_type(value: &ast::ValueType) -> String {
+    value_type_with(value, ToString::to_string)
+}
+
+/// Formats the value of the `utc_date` key, whose numbers are written as dates
+pub fn date_value_type(value: &ast::ValueType) -> String {
+    value_type_with(value, date)
+}
+
+fn value_type_with(value: &ast::ValueType, number: fn(&MinorUnit) -> String) -> String {
+    match value {
+        ast::ValueType::Number(value) => number(value),
+        ast::ValueType::EnumVariant(variant) => variant.clone(),
+        ast::ValueType::MetadataVariant(metadata) => string(&metadata.value),
+        ast::ValueType::StrValue(value) => string(value),
+        ast::ValueType::NumberArray(numbers) => list(numbers, number),
+        ast::ValueType::EnumVariantArray(variants) => list(variants, Clone::clone),
+        ast::ValueType::NumberComparisonArray(comparisons) => list(comparisons, |comparison| {
+            format!(
+                "{}{}",
+                comparison_type(&comparison.comparison_type),
+                number(&comparison.number)
+            )
+        }),
+    }
+}
+
+pub fn comparison(comparison: &ast::Comparison) -> String {
+    let lhs = match &comparison.value {
+        ast::ValueType::MetadataVariant(metadata) => string(&metadata.key),
+        _ => comparison.lhs.clone(),
+    };
+    let value = if parser::is_date_key(&comparison.lhs) {
+        date_value_type(&comparison.value)
+    } else {
+        value_type(&comparison.value)
+    };
+
+    format!("{lhs} {} {value}", comparison_type(&comparison.comparison))
+}
+
+fn condition_term(term: &ast::Condition) -> String {
+    match term {
+        ast::Condition::Comparison(cmp) => comparison(cmp),
+
+        ast::Condition::Group(ast::ConditionGroup::Or(alternatives)) => {
+            let alternatives = alternatives.iter().map(condition).collect::<Vec<_>>();
+            format!("({})", alternatives.join(" | "))
+        }
+
+        ast::Condition::Group(ast::ConditionGroup::Not(negated)) => match negated.as_slice() {
+            [ast::Condition::Comparison(cmp)] => format!("!{}", comparison(cmp)),
+            _ => format!("!({})", condition(negated)),
+        },
+    }
+}
+
+pub fn condition(condition: &ast::IfCondition) -> String {
+    condition
+        .iter()
+        .map(condition_term)
+        .collect::<Vec<_>>()
+        .join(" & ")
+}
+
+fn write_if_statement(out: &mut String, stmt: &ast::IfStatement, depth: usize) {
+    let indent = INDENT.repeat(depth);
+    out.push_str(&indent);
+    out.push_str(&condition(&stmt.condition));
+
+    if let Some(nested) = &stmt.nested {
+        out.push_str(" {\n");
+        for nested_stmt in nested {
+            write_if_statement(out, nested_stmt, depth + 1);
+        }
+        out.push_str(&indent);
+        out.push('}');
+    }
+
+    out.push('\n');
+}
+
+pub fn rule<O: EuclidParsable>(rule: &ast::Rule<O>) -> String {
+    let mut out = format!(
+        "{}: {}\n{{\n",
+        rule.name,
+        rule.connector_selection.format_output()
+    );
+
+    for stmt in &rule.statements {
+        write_if_statement(&mut out, stmt, 1);
+    }
+    out.push('}');
+
+    out
+}
+
+/// Formats the program as canonical DSL text, such that parsing the text with
+/// [`super::parser::program`] yields back the same program (except for metadata).
+///
+/// ```text
+/// default: ["stripe"]
+///
+/// rule_1: ["adyen"]
+/// {
+///     payment_method = card & (card_network = Visa | amount > 10000) {
+///         !billing_country = UnitedStatesOfAmerica
+///     }
+/// }
+/// ```
+pub fn program<O: EuclidParsable>(program: &ast::Program<O>) -> String {
+    let mut out = format!("default: {}\n", program.default_selection.format_output());
+
+    for program_rule in &program.rules {
+        out.push('\n');
+        out.push_str(&rule(program_rule));
+        out.push('\n');
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+
+    use super::*;
+    use crate::{frontend::ast::parser, types::DummyOutput};
+
+    fn assert_round_trip(program_str: &str) {
+        let (_, parsed) = parser::program::<DummyOutput>(program_str).expect("Program");
+        let formatted = program(&parsed);
+        let (rest, reparsed) = parser::program::<DummyOutput>(&formatted).expect("Formatted");
+
+        assert!(rest.trim().is_empty(), "Unparsed formatted text: {rest}");
+        assert_eq!(parsed, reparsed);
+        assert_eq!(formatted, program(&reparsed), "Formatting is not canonical");
+    }
+
+    #[test]
+    fn test_round_trip_values() {
+        assert_round_trip(
+            r#"
+            default: ["stripe", "adyen"]
+
+            rule_1: ["stripe"]
+            {
+                payment_method = card & card_network = (Visa, Mastercard) & amount >= 100
+                amount = (100, 200) & amount = (>500, <=1000) & currency /= USD
+                business_label = "label one" & "tier" = "gold"
+            }
+            rule_2: ["adyen", "checkout"]
+            {
+                capture_method = manual
+            }
+            "#,
+        );
+    }
+
+    #[test]
+    fn test_round_trip_nested_and_groups() {
+        assert_round_trip(
+            r#"
+            default: ["stripe"]
+
+            rule_1: ["adyen"]
+            {
+                payment_method = card & (card_network = Visa | amount > 10000) {
+                    !billing_country = UnitedStatesOfAmerica {
+                        !(capture_method = manual & authentication_type = three_ds)
+                    }
+                    !(payment_method = wallet | payment_method = pay_later) & ((amount < 5))
+                }
+            }
+            "#,
+        );
+    }
+
+    #[test]
+    fn test_round_trip_dates() {
+        assert_round_trip(
+            r#"
+            default: ["stripe"]
+
+            rule_1: ["adyen"]
+            {
+                utc_date = 2024-12-31 & amount > 20241231
+                utc_date = (2024-01-01, 2024-06-30) & utc_hour >= 22
+                utc_date = (>=2024-01-01, <2025-01-01)
+            }
+            "#,
+        );
+    }
+
+    #[test]
+    fn test_date_literals_only_for_date_key() {
+        assert!(parser::program::<DummyOutput>(
+            r#"default: ["stripe"] rule_1: ["adyen"] { amount > 2024-12-31 }"#,
+        )
+        .map_or(true, |(rest, _)| !rest.trim().is_empty()));
+        assert!(parser::program::<DummyOutput>(
+            r#"default: ["stripe"] rule_1: ["adyen"] { utc_date = 20241231 }"#,
+        )
+        .map_or(true, |(rest, _)| !rest.trim().is_empty()));
+
+        let (_, parsed) = parser::program::<DummyOutput>(
+            r#"default: ["stripe"] rule_1: ["adyen"] { utc_date = 2024-12-31 }"#,
+        )
+        .expect("Program");
+        assert!(program(&parsed).contains("utc_date = 2024-12-31"));
+    }
+
+    #[test]
+    fn test_round_trip_escaped_strings() {
+        assert_round_trip(
+            r#"
+            default: ["stripe"]
+
+            rule_1: ["adyen"]
+            {
+                business_label = "say \"hello\"" & "back\\slash" = "\"quoted\" \\ value"
+            }
+            "#,
+        );
+    }
+
+    #[test]
+    fn test_formatted_escaped_string() {
+        let (_, parsed) = parser::program::<DummyOutput>(
+            r#"default: ["stripe"] rule_1: ["adyen"] { business_label = "a \"b\" \\ c" }"#,
+        )
+        .expect("Program");
+        let condition = parsed
+            .rules
+            .first()
+            .and_then(|parsed_rule| parsed_rule.statements.first())
+            .map(|statement| &statement.condition)
+            .expect("Condition");
+
+        assert_eq!(
+            condition,
+            &vec![ast::Condition::Comparison(ast::Comparison {
+                lhs: "business_label".to_string(),
+                comparison: ast::ComparisonType::Equal,
+                value: ast::ValueType::StrValue("a \"b\" \\ c".to_string()),
+                metadata: Default::default(),
+            })]
+        );
+        assert!(program(&parsed).contains(r#"business_label = "a \"b\" \\ c""#));
+    }
+
+    #[test]
+    fn test_formatted_text() {
+        let (_, parsed) = parser::program::<DummyOutput>(
+            r#"default: ["stripe"] rule_1: ["adyen"] { payment_method = card & amount > 100 { card_network = Visa } }"#,
+        )
+        .expect("Program");
+
+        assert_eq!(
+            program(&parsed),
+            "default: [\"stripe\"]\n\nrule_1: [\"adyen\"]\n{\n    payment_method = card & amount > 100 {\n        card_network = Visa\n    }\n}\n"
+        );
+    }
+}
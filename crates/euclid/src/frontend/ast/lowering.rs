@@ -5,6 +5,8 @@
 
 use std::str::FromStr;
 
+use common_utils::types::MinorUnit;
+
 use crate::{
     dssa::types::{AnalysisError, AnalysisErrorType},
     enums::CollectVariants,
@@ -68,20 +70,23 @@ macro_rules! lower_enum {
 /// payment_amount = 17052001
 /// ```notrust
 /// This is for the cases in which there are numerical values involved and they are lowered
-/// accordingly on basis of the supplied key, currently payment_amount is the only key having this
-/// use case
+/// accordingly on basis of the supplied key, with an optional validation of every number
 
 macro_rules! lower_number {
-    ($key:ident, $value:ident, $comp:ident) => {
+    ($key:ident, $value:ident, $comp:ident $(, $validation_closure:expr)?) => {
         match $value {
-            ast::ValueType::Number(num) => Ok(vec![dir::DirValue::$key(types::NumValue {
-                number: num,
-                refinement: $comp.into(),
-            })]),
+            ast::ValueType::Number(num) => {
+                $($validation_closure(num)?;)?
+                Ok(vec![dir::DirValue::$key(types::NumValue {
+                    number: num,
+                    refinement: $comp.into(),
+                })])
+            }
 
             ast::ValueType::NumberArray(na) => na
                 .into_iter()
                 .map(|num| {
+                    $($validation_closure(num)?;)?
                     Ok(dir::DirValue::$key(types::NumValue {
                         number: num,
                         refinement: $comp.clone().into(),
@@ -92,6 +97,7 @@ macro_rules! lower_number {
             ast::ValueType::NumberComparisonArray(nca) => nca
                 .into_iter()
                 .map(|nc| {
+                    $($validation_closure(nc.number)?;)?
                     Ok(dir::DirValue::$key(types::NumValue {
                         number: nc.number,
                         refinement: nc.comparison_type.into(),
@@ -151,6 +157,22 @@ macro_rules! lower_metadata {
         }
     };
 }
+
+/// checks that a number in the `YYYYMMDD` form is a valid calendar date
+fn is_valid_date(date: i64) -> bool {
+    let (Ok(year), Ok(month), Ok(day)) = (
+        i32::try_from(date / 10000),
+        u8::try_from(date / 100 % 100),
+        u8::try_from(date % 100),
+    ) else {
+        return false;
+    };
+
+    time::Month::try_from(month)
+        .and_then(|month| time::Date::from_calendar_date(year, month, day))
+        .is_ok()
+}
+
 /// lowers the comparison operators for different subtle value types present
 /// by throwing required errors for comparisons that can't be performed for a certain value type
 /// for example
@@ -297,6 +319,38 @@ fn lower_comparison_inner<O: EuclidDirFilter>(
 
         dir::DirKeyKind::PaymentAmount => lower_number!(PaymentAmount, value, comparison),
 
+        dir::DirKeyKind::UtcWeekday => lower_enum!(UtcWeekday, value),
+
+        dir::DirKeyKind::UtcHour => {
+            let validation_closure = |hour: MinorUnit| -> Result<(), AnalysisErrorType> {
+                if (0..24).contains(&hour.get_amount_as_i64()) {
+                    Ok(())
+                } else {
+                    Err(AnalysisErrorType::InvalidValue {
+                        key: dir::DirKeyKind::UtcHour,
+                        value: hour.to_string(),
+                        message: Some("Expected an hour from 0 to 23".to_string()),
+                    })
+                }
+            };
+            lower_number!(UtcHour, value, comparison, validation_closure)
+        }
+
+        dir::DirKeyKind::UtcDate => {
+            let validation_closure = |date: MinorUnit| -> Result<(), AnalysisErrorType> {
+                if is_valid_date(date.get_amount_as_i64()) {
+                    Ok(())
+                } else {
+                    Err(AnalysisErrorType::InvalidValue {
+                        key: dir::DirKeyKind::UtcDate,
+                        value: date.to_string(),
+                        message: Some("Expected a date as YYYY-MM-DD".to_string()),
+                    })
+                }
+            };
+            lower_number!(UtcDate, value, comparison, validation_closure)
+        }
+
         dir::DirKeyKind::Connector => Err(AnalysisErrorType::InvalidKey(
             dir::DirKeyKind::Connector.to_string(),
         )),
@@ -329,22 +383,157 @@ fn lower_comparison<O: EuclidDirFilter>(
     })
 }
 
+/// Upper bound on the number of conjunctive alternatives a single IF condition may expand to once
+/// its `|` and `!` groups are distributed into disjunctive normal form
+const MAX_CONDITION_ALTERNATIVES: usize = 256;
+
+fn negate_comparison(comparison: dir::DirComparison) -> dir::DirComparison {
+    let logic = match comparison.logic {
+        dir::DirComparisonLogic::PositiveDisjunction => {
+            dir::DirComparisonLogic::NegativeConjunction
+        }
+        dir::DirComparisonLogic::NegativeConjunction => {
+            dir::DirComparisonLogic::PositiveDisjunction
+        }
+    };
+
+    dir::DirComparison {
+        logic,
+        ..comparison
+    }
+}
+
+/// Computes the conjunction of two conditions in disjunctive normal form, by pairing up every
+/// alternative of the left hand side with every alternative of the right hand side
+fn conjoin(
+    lhs: Vec<dir::DirIfCondition>,
+    rhs: Vec<dir::DirIfCondition>,
+) -> Result<Vec<dir::DirIfCondition>, AnalysisError> {
+    if lhs.len().saturating_mul(rhs.len()) > MAX_CONDITION_ALTERNATIVES {
+        return Err(AnalysisError {
+            error_type: AnalysisErrorType::ConditionTooComplex {
+                limit: MAX_CONDITION_ALTERNATIVES,
+            },
+            metadata: Default::default(),
+        });
+    }
+
+    Ok(lhs
+        .iter()
+        .flat_map(|lhs_alternative| {
+            rhs.iter().map(move |rhs_alternative| {
+                lhs_alternative
+                    .iter()
+                    .chain(rhs_alternative.iter())
+                    .cloned()
+                    .collect::<dir::DirIfCondition>()
+            })
+        })
+        .collect())
+}
+
+/// lowers a single term of an IF condition to the list of conjunctive alternatives it is
+/// equivalent to.
+///
+/// `!` is pushed down to the comparisons by flipping their [`dir::DirComparisonLogic`], using
+/// `!(a & b) = !a | !b` and `!(a | b) = !a & !b`. This holds for missing keys as well, since a
+/// comparison of a missing key only holds for [`dir::DirComparisonLogic::NegativeConjunction`],
+/// exactly like the AST interpreter only holds `/=` for a missing key.
+fn lower_condition_term<O: EuclidDirFilter>(
+    term: ast::Condition,
+) -> Result<Vec<dir::DirIfCondition>, AnalysisError> {
+    match term {
+        ast::Condition::Comparison(comparison) => {
+            Ok(vec![vec![lower_comparison::<O>(comparison)?]])
+        }
+
+        ast::Condition::Group(ast::ConditionGroup::Or(alternatives)) => {
+            if alternatives.is_empty() {
+                return Err(AnalysisError {
+                    error_type: AnalysisErrorType::EmptyConditionGroup,
+                    metadata: Default::default(),
+                });
+            }
+
+            let mut lowered = Vec::new();
+            for alternative in alternatives {
+                lowered.extend(lower_condition::<O>(alternative)?);
+                if lowered.len() > MAX_CONDITION_ALTERNATIVES {
+                    return Err(AnalysisError {
+                        error_type: AnalysisErrorType::ConditionTooComplex {
+                            limit: MAX_CONDITION_ALTERNATIVES,
+                        },
+                        metadata: Default::default(),
+                    });
+                }
+            }
+            Ok(lowered)
+        }
+
+        ast::Condition::Group(ast::ConditionGroup::Not(condition)) => {
+            if condition.is_empty() {
+                return Err(AnalysisError {
+                    error_type: AnalysisErrorType::EmptyConditionGroup,
+                    metadata: Default::default(),
+                });
+            }
+
+            lower_condition::<O>(condition)?.into_iter().try_fold(
+                vec![Vec::new()],
+                |negated, alternative| {
+                    conjoin(
+                        negated,
+                        alternative
+                            .into_iter()
+                            .map(|comparison| vec![negate_comparison(comparison)])
+                            .collect(),
+                    )
+                },
+            )
+        }
+    }
+}
+
+/// lowers an IF condition to disjunctive normal form, i.e. the list of purely conjunctive
+/// conditions any one of which has to hold for the IF condition to hold
+pub(crate) fn lower_condition<O: EuclidDirFilter>(
+    condition: ast::IfCondition,
+) -> Result<Vec<dir::DirIfCondition>, AnalysisError> {
+    condition
+        .into_iter()
+        .try_fold(vec![Vec::new()], |lowered, term| {
+            conjoin(lowered, lower_condition_term::<O>(term)?)
+        })
+}
+
 /// lowers the if statement accordingly with a condition and following nested if statements (if
-/// present)
+/// present).
+///
+/// Since the DIR only has conjunctive conditions, an if statement whose condition contains `|` or
+/// `!` groups is lowered to one if statement per alternative of its condition in disjunctive normal
+/// form, each of them carrying the same nested if statements.
 fn lower_if_statement<O: EuclidDirFilter>(
     stmt: ast::IfStatement,
-) -> Result<dir::DirIfStatement, AnalysisError> {
-    Ok(dir::DirIfStatement {
-        condition: stmt
-            .condition
-            .into_iter()
-            .map(lower_comparison::<O>)
-            .collect::<Result<_, _>>()?,
-        nested: stmt
-            .nested
-            .map(|n| n.into_iter().map(lower_if_statement::<O>).collect())
-            .transpose()?,
-    })
+) -> Result<Vec<dir::DirIfStatement>, AnalysisError> {
+    let nested = stmt.nested.map(lower_if_statements::<O>).transpose()?;
+
+    Ok(lower_condition::<O>(stmt.condition)?
+        .into_iter()
+        .map(|condition| dir::DirIfStatement {
+            condition,
+            nested: nested.clone(),
+        })
+        .collect())
+}
+
+fn lower_if_statements<O: EuclidDirFilter>(
+    statements: Vec<ast::IfStatement>,
+) -> Result<Vec<dir::DirIfStatement>, AnalysisError> {
+    let mut lowered = Vec::with_capacity(statements.len());
+    for statement in statements {
+        lowered.extend(lower_if_statement::<O>(statement)?);
+    }
+    Ok(lowered)
 }
 
 /// lowers the rules supplied accordingly to DirRule struct by specifying the rule_name,
@@ -355,11 +544,7 @@ pub fn lower_rule<O: EuclidDirFilter>(
     Ok(dir::DirRule {
         name: rule.name,
         connector_selection: rule.connector_selection,
-        statements: rule
-            .statements
-            .into_iter()
-            .map(lower_if_statement::<O>)
-            .collect::<Result<_, _>>()?,
+        statements: lower_if_statements::<O>(rule.statements)?,
     })
 }
 
@@ -1,9 +1,14 @@
+use std::str::FromStr;
+
 use common_utils::types::MinorUnit;
 use nom::{
     branch, bytes::complete, character::complete as pchar, combinator, error, multi, sequence,
 };
 
-use crate::{frontend::ast, types::DummyOutput};
+use crate::{
+    frontend::{ast, dir},
+    types::DummyOutput,
+};
 pub type ParseResult<T, U> = nom::IResult<T, U, error::VerboseError<T>>;
 
 pub enum EuclidError {
@@ -15,6 +20,9 @@ pub enum EuclidError {
 
 pub trait EuclidParsable: Sized {
     fn parse_output(input: &str) -> ParseResult<&str, Self>;
+
+    /// Formats the output in the syntax accepted by [`EuclidParsable::parse_output`]
+    fn format_output(&self) -> String;
 }
 
 impl EuclidParsable for DummyOutput {
@@ -50,7 +58,29 @@ impl EuclidParsable for DummyOutput {
             ),
         )(input)
     }
+    fn format_output(&self) -> String {
+        let outputs = self
+            .outputs
+            .iter()
+            .map(|output| format!("\"{output}\""))
+            .collect::<Vec<_>>();
+        format!("[{}]", outputs.join(", "))
+    }
+}
+/// Runs a parser written without the parser combinators, which returns the remaining input along
+/// with its output, or `None` if the input could not be parsed
+pub fn parse_with<'a, O>(
+    input: &'a str,
+    context: &'static str,
+    parse: impl FnOnce(&'a str) -> Option<(&'a str, O)>,
+) -> ParseResult<&'a str, O> {
+    parse(input).ok_or_else(|| {
+        nom::Err::Error(error::VerboseError {
+            errors: vec![(input, error::VerboseErrorKind::Context(context))],
+        })
+    })
 }
+
 pub fn skip_ws<'a, F, O>(inner: F) -> impl FnMut(&'a str) -> ParseResult<&str, O>
 where
     F: FnMut(&'a str) -> ParseResult<&str, O> + 'a,
@@ -71,16 +101,46 @@ pub fn num_i64(input: &str) -> ParseResult<&str, i64> {
     )(input)
 }
 
+/// Parses a `YYYY-MM-DD` date into the number `YYYYMMDD`, so that dates can be compared. Date
+/// literals are only accepted as the values of the `utc_date` key.
+pub fn date_i64(input: &str) -> ParseResult<&str, i64> {
+    let digits = |count| complete::take_while_m_n(count, count, |c: char| c.is_ascii_digit());
+
+    error::context(
+        "date",
+        combinator::map_res(
+            sequence::tuple((
+                digits(4),
+                sequence::preceded(complete::tag("-"), digits(2)),
+                sequence::preceded(complete::tag("-"), digits(2)),
+            )),
+            |(year, month, day): (&str, &str, &str)| {
+                format!("{year}{month}{day}")
+                    .parse::<i64>()
+                    .map_err(|_| EuclidError::InvalidNumber(format!("{year}-{month}-{day}")))
+            },
+        ),
+    )(input)
+}
+
+/// Parses a non empty double quoted string, in which `"` and `\` are escaped with a `\`
 pub fn string_str(input: &str) -> ParseResult<&str, String> {
     error::context(
         "String",
-        combinator::map(
+        combinator::verify(
             sequence::delimited(
                 complete::tag("\""),
-                complete::take_while1(|c: char| c != '"'),
+                complete::escaped_transform(
+                    complete::is_not("\\\""),
+                    '\\',
+                    branch::alt((
+                        combinator::value("\\", complete::tag("\\")),
+                        combinator::value("\"", complete::tag("\"")),
+                    )),
+                ),
                 complete::tag("\""),
             ),
-            |val: &str| val.to_string(),
+            |val: &String| !val.is_empty(),
         ),
     )(input)
 }
@@ -120,6 +180,13 @@ pub fn number_value(input: &str) -> ParseResult<&str, ast::ValueType> {
     )(input)
 }
 
+pub fn date_value(input: &str) -> ParseResult<&str, ast::ValueType> {
+    error::context(
+        "date_value",
+        combinator::map(date_i64, |n| ast::ValueType::Number(MinorUnit::new(n))),
+    )(input)
+}
+
 pub fn str_value(input: &str) -> ParseResult<&str, ast::ValueType> {
     error::context(
         "str_value",
@@ -144,9 +211,18 @@ pub fn enum_variant_value(input: &str) -> ParseResult<&str, ast::ValueType> {
 }
 
 pub fn number_array_value(input: &str) -> ParseResult<&str, ast::ValueType> {
-    fn num_minor_unit(input: &str) -> ParseResult<&str, MinorUnit> {
-        combinator::map(num_i64, MinorUnit::new)(input)
-    }
+    number_array_of(num_i64, "number_array_value")(input)
+}
+
+pub fn date_array_value(input: &str) -> ParseResult<&str, ast::ValueType> {
+    number_array_of(date_i64, "date_array_value")(input)
+}
+
+fn number_array_of<'a>(
+    number: fn(&'a str) -> ParseResult<&'a str, i64>,
+    context: &'static str,
+) -> impl FnMut(&'a str) -> ParseResult<&'a str, ast::ValueType> {
+    let num_minor_unit = move |input: &'a str| combinator::map(number, MinorUnit::new)(input);
     let many_with_comma = multi::many0(sequence::preceded(
         skip_ws(complete::tag(",")),
         skip_ws(num_minor_unit),
@@ -155,7 +231,7 @@ pub fn number_array_value(input: &str) -> ParseResult<&str, ast::ValueType> {
     let full_sequence = sequence::pair(skip_ws(num_minor_unit), many_with_comma);
 
     error::context(
-        "number_array_value",
+        context,
         combinator::map(
             sequence::delimited(
                 skip_ws(complete::tag("(")),
@@ -168,7 +244,7 @@ pub fn number_array_value(input: &str) -> ParseResult<&str, ast::ValueType> {
                 ast::ValueType::NumberArray(rest)
             },
         ),
-    )(input)
+    )
 }
 
 pub fn enum_variant_array_value(input: &str) -> ParseResult<&str, ast::ValueType> {
@@ -197,6 +273,12 @@ pub fn enum_variant_array_value(input: &str) -> ParseResult<&str, ast::ValueType
 }
 
 pub fn number_comparison(input: &str) -> ParseResult<&str, ast::NumberComparison> {
+    number_comparison_of(num_i64)(input)
+}
+
+fn number_comparison_of<'a>(
+    number: fn(&'a str) -> ParseResult<&'a str, i64>,
+) -> impl FnMut(&'a str) -> ParseResult<&'a str, ast::NumberComparison> {
     let operator = combinator::map_res(
         branch::alt((
             complete::tag(">="),
@@ -216,25 +298,36 @@ pub fn number_comparison(input: &str) -> ParseResult<&str, ast::NumberComparison
     error::context(
         "number_comparison",
         combinator::map(
-            sequence::pair(operator, num_i64),
+            sequence::pair(operator, number),
             |tup: (ast::ComparisonType, i64)| ast::NumberComparison {
                 comparison_type: tup.0,
                 number: MinorUnit::new(tup.1),
             },
         ),
-    )(input)
+    )
 }
 
 pub fn number_comparison_array_value(input: &str) -> ParseResult<&str, ast::ValueType> {
+    number_comparison_array_of(num_i64, "number_comparison_array_value")(input)
+}
+
+pub fn date_comparison_array_value(input: &str) -> ParseResult<&str, ast::ValueType> {
+    number_comparison_array_of(date_i64, "date_comparison_array_value")(input)
+}
+
+fn number_comparison_array_of<'a>(
+    number: fn(&'a str) -> ParseResult<&'a str, i64>,
+    context: &'static str,
+) -> impl FnMut(&'a str) -> ParseResult<&'a str, ast::ValueType> {
     let many_with_comma = multi::many0(sequence::preceded(
         skip_ws(complete::tag(",")),
-        skip_ws(number_comparison),
+        skip_ws(number_comparison_of(number)),
     ));
 
-    let full_sequence = sequence::pair(skip_ws(number_comparison), many_with_comma);
+    let full_sequence = sequence::pair(skip_ws(number_comparison_of(number)), many_with_comma);
 
     error::context(
-        "number_comparison_array_value",
+        context,
         combinator::map(
             sequence::delimited(
                 skip_ws(complete::tag("(")),
@@ -247,7 +340,7 @@ pub fn number_comparison_array_value(input: &str) -> ParseResult<&str, ast::Valu
                 ast::ValueType::NumberComparisonArray(rest)
             },
         ),
-    )(input)
+    )
 }
 
 pub fn value_type(input: &str) -> ParseResult<&str, ast::ValueType> {
@@ -264,6 +357,14 @@ pub fn value_type(input: &str) -> ParseResult<&str, ast::ValueType> {
     )(input)
 }
 
+/// Parses the value of the `utc_date` key, whose numbers are written as `YYYY-MM-DD` dates
+pub fn date_value_type(input: &str) -> ParseResult<&str, ast::ValueType> {
+    error::context(
+        "date_value_type",
+        branch::alt((date_value, date_array_value, date_comparison_array_value)),
+    )(input)
+}
+
 pub fn comparison_type(input: &str) -> ParseResult<&str, ast::ComparisonType> {
     error::context(
         "comparison_operator",
@@ -289,16 +390,32 @@ pub fn comparison_type(input: &str) -> ParseResult<&str, ast::ComparisonType> {
     )(input)
 }
 
+/// Whether the key is `utc_date`, the values of which are written as dates
+pub(crate) fn is_date_key(key: &str) -> bool {
+    matches!(dir::DirKeyKind::from_str(key), Ok(dir::DirKeyKind::UtcDate))
+}
+
 pub fn comparison(input: &str) -> ParseResult<&str, ast::Comparison> {
+    fn key(input: &str) -> ParseResult<&str, &str> {
+        skip_ws(complete::take_while1(|c: char| {
+            c.is_ascii_alphabetic() || c == '.' || c == '_'
+        }))(input)
+    }
+
     error::context(
         "condition",
         combinator::map(
-            sequence::tuple((
-                skip_ws(complete::take_while1(|c: char| {
-                    c.is_ascii_alphabetic() || c == '.' || c == '_'
-                })),
-                skip_ws(comparison_type),
-                skip_ws(value_type),
+            branch::alt((
+                sequence::tuple((
+                    combinator::verify(key, |key: &str| is_date_key(key)),
+                    skip_ws(comparison_type),
+                    skip_ws(date_value_type),
+                )),
+                sequence::tuple((
+                    combinator::verify(key, |key: &str| !is_date_key(key)),
+                    skip_ws(comparison_type),
+                    skip_ws(value_type),
+                )),
             )),
             |tup: (&str, ast::ComparisonType, ast::ValueType)| ast::Comparison {
                 lhs: tup.0.to_string(),
@@ -332,22 +449,90 @@ pub fn arbitrary_comparison(input: &str) -> ParseResult<&str, ast::Comparison> {
     )(input)
 }
 
-pub fn comparison_array(input: &str) -> ParseResult<&str, Vec<ast::Comparison>> {
-    let many_with_ampersand = error::context(
-        "many_with_amp",
-        multi::many0(sequence::preceded(skip_ws(complete::tag("&")), comparison)),
+/// parses a parenthesized list of conditions separated by `|`
+///
+/// ```text
+/// (payment_method = card & card_network = visa | amount > 10000)
+/// ```
+pub fn condition_group(input: &str) -> ParseResult<&str, Vec<ast::IfCondition>> {
+    let many_with_pipe = error::context(
+        "many_with_pipe",
+        multi::many0(sequence::preceded(
+            skip_ws(complete::tag("|")),
+            comparison_array,
+        )),
     );
 
-    let full_sequence = sequence::pair(
+    error::context(
+        "condition_group",
+        sequence::delimited(
+            skip_ws(complete::tag("(")),
+            combinator::map(
+                sequence::pair(comparison_array, many_with_pipe),
+                |tup: (ast::IfCondition, Vec<ast::IfCondition>)| {
+                    let mut rest = tup.1;
+                    rest.insert(0, tup.0);
+                    rest
+                },
+            ),
+            skip_ws(complete::tag(")")),
+        ),
+    )(input)
+}
+
+/// parses a `!` followed by either a single comparison or a condition group
+pub fn negated_condition(input: &str) -> ParseResult<&str, ast::ConditionGroup> {
+    let negated_group = combinator::map(condition_group, |mut alternatives| {
+        if alternatives.len() == 1 {
+            ast::ConditionGroup::Not(alternatives.remove(0))
+        } else {
+            ast::ConditionGroup::Not(vec![ast::Condition::Group(ast::ConditionGroup::Or(
+                alternatives,
+            ))])
+        }
+    });
+
+    let negated_comparison = combinator::map(
         skip_ws(branch::alt((comparison, arbitrary_comparison))),
-        many_with_ampersand,
+        |comparison| ast::ConditionGroup::Not(vec![ast::Condition::Comparison(comparison)]),
+    );
+
+    error::context(
+        "negated_condition",
+        sequence::preceded(
+            skip_ws(complete::tag("!")),
+            branch::alt((negated_group, negated_comparison)),
+        ),
+    )(input)
+}
+
+pub fn condition(input: &str) -> ParseResult<&str, ast::Condition> {
+    error::context(
+        "condition_term",
+        skip_ws(branch::alt((
+            combinator::map(comparison, ast::Condition::Comparison),
+            combinator::map(arbitrary_comparison, ast::Condition::Comparison),
+            combinator::map(negated_condition, ast::Condition::Group),
+            combinator::map(condition_group, |alternatives| {
+                ast::Condition::Group(ast::ConditionGroup::Or(alternatives))
+            }),
+        ))),
+    )(input)
+}
+
+pub fn comparison_array(input: &str) -> ParseResult<&str, ast::IfCondition> {
+    let many_with_ampersand = error::context(
+        "many_with_amp",
+        multi::many0(sequence::preceded(skip_ws(complete::tag("&")), condition)),
     );
 
+    let full_sequence = sequence::pair(condition, many_with_ampersand);
+
     error::context(
         "comparison_array",
         combinator::map(
             full_sequence,
-            |tup: (ast::Comparison, Vec<ast::Comparison>)| {
+            |tup: (ast::Condition, Vec<ast::Condition>)| {
                 let mut rest = tup.1;
                 rest.insert(0, tup.0);
                 rest
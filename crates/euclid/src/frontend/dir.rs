@@ -45,7 +45,7 @@ macro_rules! dirval {
     }};
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, serde::Serialize)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct DirKey {
     pub kind: DirKeyKind,
     pub value: Option<String>,
@@ -64,6 +64,7 @@ impl DirKey {
     PartialEq,
     Eq,
     serde::Serialize,
+    serde::Deserialize,
     strum::Display,
     strum::EnumIter,
     strum::VariantNames,
@@ -241,7 +242,7 @@ pub enum DirKeyKind {
     )]
     #[serde(rename = "billing_country")]
     BillingCountry,
-    #[serde(skip_deserializing, rename = "connector")]
+    #[serde(rename = "connector")]
     Connector,
     #[strum(
         serialize = "business_label",
@@ -278,6 +279,27 @@ pub enum DirKeyKind {
         props(Category = "Payment Method Types")
     )]
     OpenBankingType,
+    #[strum(
+        serialize = "utc_hour",
+        detailed_message = "Hour of the day (0 to 23) in UTC at which the payment is routed",
+        props(Category = "Time")
+    )]
+    #[serde(rename = "utc_hour")]
+    UtcHour,
+    #[strum(
+        serialize = "utc_weekday",
+        detailed_message = "Day of the week in UTC at which the payment is routed",
+        props(Category = "Time")
+    )]
+    #[serde(rename = "utc_weekday")]
+    UtcWeekday,
+    #[strum(
+        serialize = "utc_date",
+        detailed_message = "Date in UTC at which the payment is routed - eg. 2024-12-31",
+        props(Category = "Time")
+    )]
+    #[serde(rename = "utc_date")]
+    UtcDate,
 }
 
 pub trait EuclidDirFilter: Sized
@@ -327,6 +349,9 @@ impl DirKeyKind {
             Self::CardRedirectType => types::DataType::EnumVariant,
             Self::RealTimePaymentType => types::DataType::EnumVariant,
             Self::OpenBankingType => types::DataType::EnumVariant,
+            Self::UtcHour => types::DataType::Number,
+            Self::UtcWeekday => types::DataType::EnumVariant,
+            Self::UtcDate => types::DataType::Number,
         }
     }
     pub fn get_value_set(&self) -> Option<Vec<DirValue>> {
@@ -459,12 +484,27 @@ impl DirKeyKind {
                     .map(DirValue::OpenBankingType)
                     .collect(),
             ),
+            Self::UtcHour => None,
+            Self::UtcWeekday => Some(
+                enums::UtcWeekday::iter()
+                    .map(DirValue::UtcWeekday)
+                    .collect(),
+            ),
+            Self::UtcDate => None,
         }
     }
 }
 
 #[derive(
-    Debug, Clone, Hash, PartialEq, Eq, serde::Serialize, strum::Display, strum::VariantNames,
+    Debug,
+    Clone,
+    Hash,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum::Display,
+    strum::VariantNames,
 )]
 #[serde(tag = "key", content = "value")]
 pub enum DirValue {
@@ -516,7 +556,7 @@ pub enum DirValue {
     BusinessCountry(enums::Country),
     #[serde(rename = "billing_country")]
     BillingCountry(enums::Country),
-    #[serde(skip_deserializing, rename = "connector")]
+    #[serde(rename = "connector")]
     Connector(Box<ast::ConnectorChoice>),
     #[serde(rename = "business_label")]
     BusinessLabel(types::StrValue),
@@ -528,6 +568,12 @@ pub enum DirValue {
     RealTimePaymentType(enums::RealTimePaymentType),
     #[serde(rename = "open_banking")]
     OpenBankingType(enums::OpenBankingType),
+    #[serde(rename = "utc_hour")]
+    UtcHour(types::NumValue),
+    #[serde(rename = "utc_weekday")]
+    UtcWeekday(enums::UtcWeekday),
+    #[serde(rename = "utc_date")]
+    UtcDate(types::NumValue),
 }
 
 impl DirValue {
@@ -563,6 +609,9 @@ impl DirValue {
             Self::GiftCardType(_) => (DirKeyKind::GiftCardType, None),
             Self::RealTimePaymentType(_) => (DirKeyKind::RealTimePaymentType, None),
             Self::OpenBankingType(_) => (DirKeyKind::OpenBankingType, None),
+            Self::UtcHour(_) => (DirKeyKind::UtcHour, None),
+            Self::UtcWeekday(_) => (DirKeyKind::UtcWeekday, None),
+            Self::UtcDate(_) => (DirKeyKind::UtcDate, None),
         };
 
         DirKey::new(kind, data)
@@ -599,6 +648,9 @@ impl DirValue {
             Self::CardRedirectType(_) => None,
             Self::RealTimePaymentType(_) => None,
             Self::OpenBankingType(_) => None,
+            Self::UtcHour(_) => None,
+            Self::UtcWeekday(_) => None,
+            Self::UtcDate(_) => None,
         }
     }
 
@@ -611,7 +663,7 @@ impl DirValue {
 
     pub fn get_num_value(&self) -> Option<types::NumValue> {
         match self {
-            Self::PaymentAmount(val) => Some(val.clone()),
+            Self::PaymentAmount(val) | Self::UtcHour(val) | Self::UtcDate(val) => Some(val.clone()),
             _ => None,
         }
     }
@@ -645,6 +697,7 @@ impl DirValue {
             (Self::UpiType(ut1), Self::UpiType(ut2)) => ut1 == ut2,
             (Self::VoucherType(vt1), Self::VoucherType(vt2)) => vt1 == vt2,
             (Self::CardRedirectType(crt1), Self::CardRedirectType(crt2)) => crt1 == crt2,
+            (Self::UtcWeekday(wd1), Self::UtcWeekday(wd2)) => wd1 == wd2,
             _ => false,
         }
     }
@@ -829,6 +882,9 @@ mod test {
             dirval!(CaptureMethod = Manual),
             dirval!(BillingCountry = UnitedStatesOfAmerica),
             dirval!(BusinessCountry = France),
+            dirval!(UtcHour = 22),
+            dirval!(UtcWeekday = Sunday),
+            dirval!(UtcDate = 20241231),
         ];
 
         for val in values {
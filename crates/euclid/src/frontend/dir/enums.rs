@@ -5,6 +5,7 @@ pub use crate::enums::{
     AuthenticationType, CaptureMethod, CardNetwork, Country, Country as BusinessCountry,
     Country as BillingCountry, CountryAlpha2, Currency as PaymentCurrency, MandateAcceptanceType,
     MandateType, PaymentMethod, PaymentType, RoutableConnectors, SetupFutureUsage,
+    Weekday as UtcWeekday,
 };
 #[cfg(feature = "payouts")]
 pub use crate::enums::{PayoutBankTransferType, PayoutType, PayoutWalletType};
@@ -247,6 +247,9 @@ fn lower_value(dir_value: dir::DirValue) -> Result<EuclidValue, AnalysisErrorTyp
         dir::DirValue::BusinessLabel(bl) => EuclidValue::BusinessLabel(bl),
         dir::DirValue::SetupFutureUsage(sfu) => EuclidValue::SetupFutureUsage(sfu),
         dir::DirValue::OpenBankingType(ob) => EuclidValue::PaymentMethodType(ob.into()),
+        dir::DirValue::UtcHour(hour) => EuclidValue::UtcHour(hour),
+        dir::DirValue::UtcWeekday(weekday) => EuclidValue::UtcWeekday(weekday),
+        dir::DirValue::UtcDate(date) => EuclidValue::UtcDate(date),
     })
 }
 
@@ -173,11 +173,43 @@ pub fn get_valid_connectors_for_rule(rule: JsValue) -> JsResult {
     Ok(serde_wasm_bindgen::to_value(&valid_connectors)?)
 }
 
+/// Throws if the program is invalid. Rules shadowed by the rules before them, and payment method
+/// and currency combinations that always fall through to the default selection, are not an error,
+/// and are returned as a list of warnings instead, `null` being returned if there are none.
 #[wasm_bindgen(js_name = analyzeProgram)]
 pub fn analyze_program(js_program: JsValue) -> JsResult {
     let program: ast::Program<ConnectorSelection> = serde_wasm_bindgen::from_value(js_program)?;
-    analyzer::analyze(program, SEED_DATA.get().map(|sd| &sd.cgraph)).err_to_js()?;
-    Ok(JsValue::NULL)
+    analyzer::analyze(program.clone(), SEED_DATA.get().map(|sd| &sd.cgraph)).err_to_js()?;
+
+    let warnings = analyzer::analyze_shadowing(program.clone())
+        .err()
+        .unwrap_or_default()
+        .into_iter()
+        .chain(
+            analyzer::analyze_coverage(
+                program,
+                &[
+                    dir::DirKeyKind::PaymentMethod,
+                    dir::DirKeyKind::PaymentCurrency,
+                ],
+            )
+            .err(),
+        )
+        .collect::<Vec<_>>();
+
+    if warnings.is_empty() {
+        Ok(JsValue::NULL)
+    } else {
+        Ok(serde_wasm_bindgen::to_value(&warnings)?)
+    }
+}
+
+#[wasm_bindgen(js_name = formatProgram)]
+pub fn format_program(js_program: JsValue) -> JsResult {
+    let program: ast::Program<ConnectorSelection> = serde_wasm_bindgen::from_value(js_program)?;
+    Ok(serde_wasm_bindgen::to_value(&ast::formatter::program(
+        &program,
+    ))?)
 }
 
 #[wasm_bindgen(js_name = runProgram)]
@@ -262,8 +294,11 @@ pub fn get_variant_values(key: &str) -> Result<JsValue, JsValue> {
         dir::DirKeyKind::BankDebitType => dir_enums::BankDebitType::VARIANTS,
         dir::DirKeyKind::RealTimePaymentType => dir_enums::RealTimePaymentType::VARIANTS,
         dir::DirKeyKind::OpenBankingType => dir_enums::OpenBankingType::VARIANTS,
+        dir::DirKeyKind::UtcWeekday => dir_enums::UtcWeekday::VARIANTS,
 
         dir::DirKeyKind::PaymentAmount
+        | dir::DirKeyKind::UtcHour
+        | dir::DirKeyKind::UtcDate
         | dir::DirKeyKind::Connector
         | dir::DirKeyKind::CardBin
         | dir::DirKeyKind::BusinessLabel
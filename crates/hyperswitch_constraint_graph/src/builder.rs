@@ -7,8 +7,8 @@ use crate::{
     error::GraphError,
     graph::ConstraintGraph,
     types::{
-        DomainId, DomainIdentifier, DomainInfo, Edge, EdgeId, Metadata, Node, NodeId, NodeType,
-        NodeValue, Relation, Strength, ValueNode,
+        DomainContribution, DomainId, DomainIdentifier, DomainInfo, Edge, EdgeId, Metadata, Node,
+        NodeId, NodeType, NodeValue, Relation, Strength, ValueNode,
     },
 };
 
@@ -44,6 +44,8 @@ pub struct ConstraintGraphBuilder<V: ValueNode> {
     edges_map: FxHashMap<(NodeId, NodeId, Option<DomainId>), EdgeId>,
     node_info: DenseMap<NodeId, Option<&'static str>>,
     node_metadata: DenseMap<NodeId, Option<Arc<dyn Metadata>>>,
+    domain_contributions: FxHashMap<DomainId, DomainContribution>,
+    contributing_domain: Option<DomainId>,
 }
 
 #[allow(clippy::new_without_default)]
@@ -61,6 +63,31 @@ where
             edges_map: FxHashMap::default(),
             node_info: DenseMap::new(),
             node_metadata: DenseMap::new(),
+            domain_contributions: FxHashMap::default(),
+            contributing_domain: None,
+        }
+    }
+
+    /// Creates a builder that extends an already built graph, with the same node, edge and domain
+    /// ids as the graph.
+    pub fn from_graph(graph: ConstraintGraph<V>) -> Self {
+        let edges_map = graph
+            .edges
+            .iter()
+            .map(|(edge_id, edge)| ((edge.pred, edge.succ, edge.domain), edge_id))
+            .collect();
+
+        Self {
+            domain: graph.domain,
+            nodes: graph.nodes,
+            edges: graph.edges,
+            domain_identifier_map: graph.domain_identifier_map,
+            value_map: graph.value_map,
+            edges_map,
+            node_info: graph.node_info,
+            node_metadata: graph.node_metadata,
+            domain_contributions: graph.domain_contributions,
+            contributing_domain: None,
         }
     }
 
@@ -73,6 +100,48 @@ where
             value_map: self.value_map,
             node_info: self.node_info,
             node_metadata: self.node_metadata,
+            domain_contributions: self.domain_contributions,
+        }
+    }
+
+    /// Records the nodes and edges made by `contribute` as the contribution of the domain, so that
+    /// they can later be removed from the built graph with
+    /// [`ConstraintGraph::without_domain_contribution`]. The edges made are not scoped to the
+    /// domain unless they are made with it.
+    pub fn contribute_to_domain<T, E>(
+        &mut self,
+        domain_id: DomainId,
+        contribute: impl FnOnce(&mut Self) -> Result<T, E>,
+    ) -> Result<T, E>
+    where
+        E: From<GraphError<V>>,
+    {
+        self.ensure_domain_exists(domain_id)?;
+
+        let previous_domain = self.contributing_domain.replace(domain_id);
+        let result = contribute(self);
+        self.contributing_domain = previous_domain;
+
+        result
+    }
+
+    fn record_node_contribution(&mut self, node_id: NodeId) {
+        if let Some(domain_id) = self.contributing_domain {
+            self.domain_contributions
+                .entry(domain_id)
+                .or_default()
+                .nodes
+                .insert(node_id);
+        }
+    }
+
+    fn record_edge_contribution(&mut self, edge_id: EdgeId) {
+        if let Some(domain_id) = self.contributing_domain {
+            self.domain_contributions
+                .entry(domain_id)
+                .or_default()
+                .edges
+                .insert(edge_id);
         }
     }
 
@@ -116,7 +185,7 @@ where
         info: Option<&'static str>,
         metadata: Option<M>,
     ) -> NodeId {
-        self.value_map.get(&value).copied().unwrap_or_else(|| {
+        let node_id = self.value_map.get(&value).copied().unwrap_or_else(|| {
             let node_id = self.nodes.push(Node::new(NodeType::Value(value.clone())));
             let _node_info_id = self.node_info.push(info);
 
@@ -126,7 +195,10 @@ where
 
             self.value_map.insert(value, node_id);
             node_id
-        })
+        });
+
+        self.record_node_contribution(node_id);
+        node_id
     }
 
     pub fn make_edge<T: Into<DomainIdOrIdentifier>>(
@@ -149,7 +221,8 @@ where
                 }
             })
             .transpose()?;
-        self.edges_map
+        let edge_id = self
+            .edges_map
             .get(&(pred_id, succ_id, domain_id))
             .copied()
             .and_then(|edge_id| self.edges.get(edge_id).cloned().map(|edge| (edge_id, edge)))
@@ -186,7 +259,10 @@ where
                         Err(GraphError::ConflictingEdgeCreated)
                     }
                 },
-            )
+            )?;
+
+        self.record_edge_contribution(edge_id);
+        Ok(edge_id)
     }
 
     pub fn make_all_aggregator<M: Metadata>(
@@ -206,6 +282,7 @@ where
         let _node_metadata_id = self
             .node_metadata
             .push(metadata.map(|meta| -> Arc<dyn Metadata> { Arc::new(meta) }));
+        self.record_node_contribution(aggregator_id);
 
         for (node_id, relation, strength) in nodes {
             self.make_edge(*node_id, aggregator_id, *strength, *relation, domain_id)?;
@@ -231,6 +308,7 @@ where
         let _node_metadata_id = self
             .node_metadata
             .push(metadata.map(|meta| -> Arc<dyn Metadata> { Arc::new(meta) }));
+        self.record_node_contribution(aggregator_id);
 
         for (node_id, relation, strength) in nodes {
             self.make_edge(*node_id, aggregator_id, *strength, *relation, domain_id)?;
@@ -267,6 +345,7 @@ where
         let _node_metadata_id = self
             .node_metadata
             .push(metadata.map(|meta| -> Arc<dyn Metadata> { Arc::new(meta) }));
+        self.record_node_contribution(node_id);
 
         Ok(node_id)
     }
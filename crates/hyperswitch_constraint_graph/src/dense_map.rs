@@ -23,6 +23,7 @@ macro_rules! impl_entity {
 
 pub(crate) use impl_entity;
 
+#[derive(Clone)]
 pub struct DenseMap<K, V> {
     data: Vec<V>,
     _marker: PhantomData<K>,
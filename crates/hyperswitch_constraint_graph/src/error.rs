@@ -65,6 +65,8 @@ pub enum GraphError<V: ValueNode> {
     NoInAggregatorValues,
     #[error("Error during analysis: {0:#?}")]
     AnalysisError(Weak<AnalysisTrace<V>>),
+    #[error("Unsupported graph snapshot version {found}, expected version {expected}")]
+    UnsupportedSnapshotVersion { expected: u32, found: u32 },
 }
 
 impl<V: ValueNode> GraphError<V> {
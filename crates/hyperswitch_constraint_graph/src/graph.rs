@@ -7,9 +7,9 @@ use crate::{
     dense_map::DenseMap,
     error::{self, AnalysisTrace, GraphError},
     types::{
-        CheckingContext, CycleCheck, DomainId, DomainIdentifier, DomainInfo, Edge, EdgeId,
-        Memoization, Metadata, Node, NodeId, NodeType, NodeValue, Relation, RelationResolution,
-        Strength, ValueNode,
+        CheckingContext, CycleCheck, DomainContribution, DomainId, DomainIdentifier, DomainInfo,
+        Edge, EdgeId, Memoization, Metadata, Node, NodeId, NodeType, NodeValue, Relation,
+        RelationResolution, Strength, ValueNode,
     },
 };
 
@@ -25,7 +25,7 @@ struct CheckNodeContext<'a, V: ValueNode, C: CheckingContext<Value = V>> {
     domains: Option<&'a [DomainId]>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ConstraintGraph<V: ValueNode> {
     pub domain: DenseMap<DomainId, DomainInfo>,
     pub domain_identifier_map: FxHashMap<DomainIdentifier, DomainId>,
@@ -34,6 +34,9 @@ pub struct ConstraintGraph<V: ValueNode> {
     pub value_map: FxHashMap<NodeValue<V>, NodeId>,
     pub node_info: DenseMap<NodeId, Option<&'static str>>,
     pub node_metadata: DenseMap<NodeId, Option<Arc<dyn Metadata>>>,
+    /// Nodes and edges made while contributing to a domain, see
+    /// [`builder::ConstraintGraphBuilder::contribute_to_domain`]
+    pub domain_contributions: FxHashMap<DomainId, DomainContribution>,
 }
 
 impl<V> ConstraintGraph<V>
@@ -482,6 +485,178 @@ where
         Ok(())
     }
 
+    /// Collects the nodes relevant to the given ones: the nodes they depend on, the nodes that
+    /// depend on them, and the immediate predecessors of the latter, which are the other
+    /// constraints those nodes are subject to.
+    pub fn get_relevant_nodes(&self, nodes: &[NodeId]) -> FxHashSet<NodeId> {
+        let mut relevant: FxHashSet<NodeId> = nodes.iter().copied().collect();
+
+        let mut stack = nodes.to_vec();
+        let mut visited = FxHashSet::default();
+        while let Some(node_id) = stack.pop() {
+            if !visited.insert(node_id) {
+                continue;
+            }
+
+            if let Some(node) = self.nodes.get(node_id) {
+                for pred in node
+                    .preds
+                    .iter()
+                    .filter_map(|edge_id| self.edges.get(*edge_id))
+                {
+                    relevant.insert(pred.pred);
+                    stack.push(pred.pred);
+                }
+            }
+        }
+
+        let mut stack = nodes.to_vec();
+        let mut visited = FxHashSet::default();
+        while let Some(node_id) = stack.pop() {
+            if !visited.insert(node_id) {
+                continue;
+            }
+
+            if let Some(node) = self.nodes.get(node_id) {
+                for succ in node
+                    .succs
+                    .iter()
+                    .filter_map(|edge_id| self.edges.get(*edge_id))
+                {
+                    relevant.insert(succ.succ);
+                    stack.push(succ.succ);
+                }
+
+                relevant.extend(
+                    node.preds
+                        .iter()
+                        .filter_map(|edge_id| self.edges.get(*edge_id))
+                        .map(|edge| edge.pred),
+                );
+            }
+        }
+
+        relevant
+    }
+
+    /// Copies the graph without the nodes and edges contributed to the domain, keeping the ones
+    /// that were also contributed to other domains. Edges of the removed nodes are removed as
+    /// well. The domain itself is kept, with an empty contribution, so that it can be contributed
+    /// to again by a builder created with [`builder::ConstraintGraphBuilder::from_graph`].
+    ///
+    /// Node and edge ids are compacted, and so are not stable across removals.
+    pub fn without_domain_contribution(&self, domain_id: DomainId) -> Result<Self, GraphError<V>> {
+        if !self.domain.contains_key(domain_id) {
+            Err(GraphError::DomainNotFound)?;
+        }
+
+        let removed = self
+            .domain_contributions
+            .get(&domain_id)
+            .cloned()
+            .unwrap_or_default();
+        let retained = self
+            .domain_contributions
+            .iter()
+            .filter(|(contributor, _)| **contributor != domain_id)
+            .fold(DomainContribution::default(), |mut retained, (_, other)| {
+                retained.nodes.extend(other.nodes.iter().copied());
+                retained.edges.extend(other.edges.iter().copied());
+                retained
+            });
+
+        let mut node_map = FxHashMap::<NodeId, NodeId>::default();
+        let mut nodes = DenseMap::new();
+        let mut node_info = DenseMap::new();
+        let mut node_metadata = DenseMap::new();
+        let mut value_map = FxHashMap::default();
+        for (old_node_id, node) in self.nodes.iter() {
+            if removed.nodes.contains(&old_node_id) && !retained.nodes.contains(&old_node_id) {
+                continue;
+            }
+
+            let node_id: NodeId = nodes.push(Node::new(node.node_type.clone()));
+            if let NodeType::Value(value) = &node.node_type {
+                value_map.insert(value.clone(), node_id);
+            }
+            let _node_info_id: NodeId =
+                node_info.push(self.node_info.get(old_node_id).copied().flatten());
+            let _node_metadata_id: NodeId =
+                node_metadata.push(self.node_metadata.get(old_node_id).cloned().flatten());
+
+            node_map.insert(old_node_id, node_id);
+        }
+
+        let mut edge_map = FxHashMap::<EdgeId, EdgeId>::default();
+        let mut edges = DenseMap::new();
+        for (old_edge_id, edge) in self.edges.iter() {
+            if removed.edges.contains(&old_edge_id) && !retained.edges.contains(&old_edge_id) {
+                continue;
+            }
+
+            let (Some(&pred), Some(&succ)) = (node_map.get(&edge.pred), node_map.get(&edge.succ))
+            else {
+                continue;
+            };
+
+            let edge_id: EdgeId = edges.push(Edge {
+                strength: edge.strength,
+                relation: edge.relation,
+                pred,
+                succ,
+                domain: edge.domain,
+            });
+            nodes
+                .get_mut(pred)
+                .ok_or(GraphError::NodeNotFound)?
+                .succs
+                .push(edge_id);
+            nodes
+                .get_mut(succ)
+                .ok_or(GraphError::NodeNotFound)?
+                .preds
+                .push(edge_id);
+
+            edge_map.insert(old_edge_id, edge_id);
+        }
+
+        let domain_contributions = self
+            .domain_contributions
+            .iter()
+            .filter(|(contributor, _)| **contributor != domain_id)
+            .map(|(contributor, contribution)| {
+                (
+                    *contributor,
+                    DomainContribution {
+                        nodes: contribution
+                            .nodes
+                            .iter()
+                            .filter_map(|node_id| node_map.get(node_id).copied())
+                            .collect(),
+                        edges: contribution
+                            .edges
+                            .iter()
+                            .filter_map(|edge_id| edge_map.get(edge_id).copied())
+                            .collect(),
+                    },
+                )
+            })
+            .collect();
+
+        Ok(Self {
+            domain: self.domain.values().cloned().collect(),
+            domain_identifier_map: self.domain_identifier_map.clone(),
+            nodes,
+            edges,
+            value_map,
+            node_info,
+            node_metadata,
+            domain_contributions,
+        })
+    }
+
+    /// Combines the two graphs into one. Domain contributions are not carried over to the
+    /// combined graph.
     pub fn combine(g1: &Self, g2: &Self) -> Result<Self, GraphError<V>> {
         let mut node_builder = builder::ConstraintGraphBuilder::new();
         let mut g1_old2new_id = DenseMap::<NodeId, NodeId>::new();
@@ -610,26 +785,7 @@ mod viz {
         <V as ValueNode>::Key: NodeViz,
     {
         fn get_node_label(node: &types::Node<V>) -> String {
-            let label = match &node.node_type {
-                types::NodeType::Value(types::NodeValue::Key(key)) => format!("any {}", key.viz()),
-                types::NodeType::Value(types::NodeValue::Value(val)) => {
-                    format!("{} = {}", val.get_key().viz(), val.viz())
-                }
-                types::NodeType::AllAggregator => "&&".to_string(),
-                types::NodeType::AnyAggregator => "| |".to_string(),
-                types::NodeType::InAggregator(agg) => {
-                    let key = if let Some(val) = agg.iter().next() {
-                        val.get_key().viz()
-                    } else {
-                        return "empty in".to_string();
-                    };
-
-                    let nodes = agg.iter().map(NodeViz::viz).collect::<Vec<_>>();
-                    format!("{key} in [{}]", nodes.join(", "))
-                }
-            };
-
-            format!("\"{label}\"")
+            format!("\"{}\"", Self::get_node_text(node))
         }
 
         fn build_node(cg_node_id: types::NodeId, cg_node: &types::Node<V>) -> Node {
@@ -678,3 +834,147 @@ mod viz {
         }
     }
 }
+
+/// Text renderings of the graph which do not need the `viz` feature
+mod render {
+    use std::fmt::Write;
+
+    use rustc_hash::FxHashSet;
+
+    use crate::{dense_map::EntityId, types, ConstraintGraph, NodeViz, ValueNode};
+
+    fn get_node_id(node_id: types::NodeId) -> String {
+        format!("N{}", node_id.get_id())
+    }
+
+    fn escape_dot(text: &str) -> String {
+        text.replace('\\', "\\\\").replace('"', "\\\"")
+    }
+
+    impl<V> ConstraintGraph<V>
+    where
+        V: ValueNode + NodeViz,
+        <V as ValueNode>::Key: NodeViz,
+    {
+        pub(crate) fn get_node_text(node: &types::Node<V>) -> String {
+            match &node.node_type {
+                types::NodeType::Value(types::NodeValue::Key(key)) => format!("any {}", key.viz()),
+                types::NodeType::Value(types::NodeValue::Value(val)) => {
+                    format!("{} = {}", val.get_key().viz(), val.viz())
+                }
+                types::NodeType::AllAggregator => "&&".to_string(),
+                types::NodeType::AnyAggregator => "| |".to_string(),
+                types::NodeType::InAggregator(agg) => {
+                    let key = if let Some(val) = agg.iter().next() {
+                        val.get_key().viz()
+                    } else {
+                        return "empty in".to_string();
+                    };
+
+                    let nodes = agg.iter().map(NodeViz::viz).collect::<Vec<_>>();
+                    format!("{key} in [{}]", nodes.join(", "))
+                }
+            }
+        }
+
+        fn included_edges<'a>(
+            &'a self,
+            include: &'a impl Fn(types::NodeId) -> bool,
+        ) -> impl Iterator<Item = &'a types::Edge> {
+            self.edges
+                .values()
+                .filter(move |edge| include(edge.pred) && include(edge.succ))
+        }
+
+        fn build_dot_digraph(&self, include: impl Fn(types::NodeId) -> bool) -> String {
+            let mut digraph = String::from("strict digraph constraint_graph {\n");
+
+            for (node_id, node) in self.nodes.iter().filter(|(node_id, _)| include(*node_id)) {
+                let _ = writeln!(
+                    digraph,
+                    "    {} [label=\"{}\"]",
+                    get_node_id(node_id),
+                    escape_dot(&Self::get_node_text(node))
+                );
+            }
+
+            for edge in self.included_edges(&include) {
+                let arrowhead = match edge.strength {
+                    types::Strength::Weak => "onormal",
+                    types::Strength::Normal => "normal",
+                    types::Strength::Strong => "normalnormal",
+                };
+                let color = match edge.relation {
+                    types::Relation::Positive => "blue",
+                    types::Relation::Negative => "red",
+                };
+
+                let _ = writeln!(
+                    digraph,
+                    "    {} -> {} [arrowhead={arrowhead}, color={color}]",
+                    get_node_id(edge.pred),
+                    get_node_id(edge.succ)
+                );
+            }
+
+            digraph.push_str("}\n");
+            digraph
+        }
+
+        /// Renders the graph in the Graphviz DOT language, in which negative edges are red and
+        /// the arrowhead of an edge shows its strength
+        pub fn get_dot_digraph(&self) -> String {
+            self.build_dot_digraph(|_| true)
+        }
+
+        /// DOT digraph of only the given nodes and the edges between them
+        pub fn get_dot_subgraph_digraph(&self, nodes: &FxHashSet<types::NodeId>) -> String {
+            self.build_dot_digraph(|node_id| nodes.contains(&node_id))
+        }
+
+        fn build_mermaid_flowchart(&self, include: impl Fn(types::NodeId) -> bool) -> String {
+            let mut flowchart = String::from("flowchart LR\n");
+
+            for (node_id, node) in self.nodes.iter().filter(|(node_id, _)| include(*node_id)) {
+                let _ = writeln!(
+                    flowchart,
+                    "    {}[\"{}\"]",
+                    get_node_id(node_id),
+                    Self::get_node_text(node).replace('"', "#quot;")
+                );
+            }
+
+            for edge in self.included_edges(&include) {
+                let arrow = match edge.strength {
+                    types::Strength::Weak => "-.->",
+                    types::Strength::Normal => "-->",
+                    types::Strength::Strong => "==>",
+                };
+                let label = match edge.relation {
+                    types::Relation::Positive => "",
+                    types::Relation::Negative => "|not|",
+                };
+
+                let _ = writeln!(
+                    flowchart,
+                    "    {} {arrow}{label} {}",
+                    get_node_id(edge.pred),
+                    get_node_id(edge.succ)
+                );
+            }
+
+            flowchart
+        }
+
+        /// Renders the graph as a Mermaid flowchart, in which strong edges are thick, weak edges
+        /// are dotted and negative edges are labelled `not`
+        pub fn get_mermaid_flowchart(&self) -> String {
+            self.build_mermaid_flowchart(|_| true)
+        }
+
+        /// Mermaid flowchart of only the given nodes and the edges between them
+        pub fn get_mermaid_subgraph_flowchart(&self, nodes: &FxHashSet<types::NodeId>) -> String {
+            self.build_mermaid_flowchart(|node_id| nodes.contains(&node_id))
+        }
+    }
+}
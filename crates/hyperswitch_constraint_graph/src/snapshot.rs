@@ -0,0 +1,336 @@
+//! Serialized form of a [`ConstraintGraph`]
+//!
+//! Building a graph can be expensive, so built graphs can be converted to a
+//! [`ConstraintGraphSnapshot`], stored in a cache and converted back to a graph without running
+//! the builder again. Snapshots are versioned, and a snapshot taken by a different version of the
+//! format is rejected when loaded rather than being misread.
+//!
+//! Node metadata is type erased in the graph, so it is snapshotted in its serialized form, and the
+//! metadata of a loaded graph serializes the same way as that of the original graph.
+//!
+//! Domain contributions are part of the snapshot, so that loaded graphs can still be updated
+//! incrementally.
+
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    dense_map::{DenseMap, EntityId},
+    error::GraphError,
+    graph::ConstraintGraph,
+    types::{
+        DomainContribution, DomainId, DomainIdentifier, DomainInfo, Edge, EdgeId, Metadata, Node,
+        NodeId, NodeType, NodeValue, Relation, Strength, ValueNode,
+    },
+};
+
+/// Version of the snapshot format, to be bumped on any change to the snapshot types
+pub const SNAPSHOT_VERSION: u32 = 3;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DomainSnapshot {
+    pub identifier: String,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(
+    tag = "type",
+    content = "value",
+    rename_all = "snake_case",
+    bound(deserialize = "V: serde::Deserialize<'de>, V::Key: serde::Deserialize<'de>")
+)]
+pub enum NodeTypeSnapshot<V: ValueNode> {
+    AllAggregator,
+    AnyAggregator,
+    InAggregator(Vec<V>),
+    Value(NodeValue<V>),
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(bound(deserialize = "V: serde::Deserialize<'de>, V::Key: serde::Deserialize<'de>"))]
+pub struct NodeSnapshot<V: ValueNode> {
+    pub node_type: NodeTypeSnapshot<V>,
+    pub info: Option<String>,
+    pub metadata: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct EdgeSnapshot {
+    pub strength: Strength,
+    pub relation: Relation,
+    /// Index of the predecessor in [`ConstraintGraphSnapshot::nodes`]
+    pub pred: usize,
+    /// Index of the successor in [`ConstraintGraphSnapshot::nodes`]
+    pub succ: usize,
+    /// Index of the domain in [`ConstraintGraphSnapshot::domains`]
+    pub domain: Option<usize>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ContributionSnapshot {
+    /// Index of the domain in [`ConstraintGraphSnapshot::domains`]
+    pub domain: usize,
+    /// Indices of the contributed nodes in [`ConstraintGraphSnapshot::nodes`]
+    pub nodes: Vec<usize>,
+    /// Indices of the contributed edges in [`ConstraintGraphSnapshot::edges`]
+    pub edges: Vec<usize>,
+}
+
+/// Serializable form of a [`ConstraintGraph`], in which nodes, edges and domains refer to each
+/// other by their index in the graph.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(bound(deserialize = "V: serde::Deserialize<'de>, V::Key: serde::Deserialize<'de>"))]
+pub struct ConstraintGraphSnapshot<V: ValueNode> {
+    pub version: u32,
+    pub domains: Vec<DomainSnapshot>,
+    pub nodes: Vec<NodeSnapshot<V>>,
+    pub edges: Vec<EdgeSnapshot>,
+    pub contributions: Vec<ContributionSnapshot>,
+}
+
+/// Node info is borrowed for `'static` by the graph, so the info of loaded snapshots is leaked.
+/// Every distinct info string is leaked only once, as graphs reuse a small set of them.
+fn intern_info(info: String) -> &'static str {
+    static INTERNED_INFO: OnceLock<Mutex<FxHashSet<&'static str>>> = OnceLock::new();
+
+    let mut interned = INTERNED_INFO
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+
+    if let Some(&existing) = interned.get(info.as_str()) {
+        existing
+    } else {
+        let leaked: &'static str = Box::leak(info.into_boxed_str());
+        interned.insert(leaked);
+        leaked
+    }
+}
+
+impl<V> ConstraintGraph<V>
+where
+    V: ValueNode,
+{
+    pub fn to_snapshot(&self) -> Result<ConstraintGraphSnapshot<V>, GraphError<V>> {
+        let domains = self
+            .domain
+            .values()
+            .map(|domain_info| DomainSnapshot {
+                identifier: domain_info.domain_identifier.into_inner(),
+                description: domain_info.domain_description.clone(),
+            })
+            .collect();
+
+        let nodes = self
+            .nodes
+            .iter()
+            .map(|(node_id, node)| {
+                let metadata = self
+                    .node_metadata
+                    .get(node_id)
+                    .and_then(Option::as_ref)
+                    .map(|metadata| serde_json::to_value(metadata.as_ref()))
+                    .transpose()
+                    .map_err(|err| GraphError::MalformedGraph {
+                        reason: format!("Unable to serialize node metadata: {err}"),
+                    })?;
+
+                Ok(NodeSnapshot {
+                    node_type: match &node.node_type {
+                        NodeType::AllAggregator => NodeTypeSnapshot::AllAggregator,
+                        NodeType::AnyAggregator => NodeTypeSnapshot::AnyAggregator,
+                        NodeType::InAggregator(values) => {
+                            NodeTypeSnapshot::InAggregator(values.iter().cloned().collect())
+                        }
+                        NodeType::Value(value) => NodeTypeSnapshot::Value(value.clone()),
+                    },
+                    info: self
+                        .node_info
+                        .get(node_id)
+                        .copied()
+                        .flatten()
+                        .map(ToString::to_string),
+                    metadata,
+                })
+            })
+            .collect::<Result<_, GraphError<V>>>()?;
+
+        let edges = self
+            .edges
+            .values()
+            .map(|edge| EdgeSnapshot {
+                strength: edge.strength,
+                relation: edge.relation,
+                pred: edge.pred.get_id(),
+                succ: edge.succ.get_id(),
+                domain: edge.domain.map(|domain_id| domain_id.get_id()),
+            })
+            .collect();
+
+        let mut contributions = self
+            .domain_contributions
+            .iter()
+            .map(|(domain_id, contribution)| {
+                let mut nodes = contribution
+                    .nodes
+                    .iter()
+                    .map(EntityId::get_id)
+                    .collect::<Vec<_>>();
+                nodes.sort_unstable();
+                let mut edges = contribution
+                    .edges
+                    .iter()
+                    .map(EntityId::get_id)
+                    .collect::<Vec<_>>();
+                edges.sort_unstable();
+
+                ContributionSnapshot {
+                    domain: domain_id.get_id(),
+                    nodes,
+                    edges,
+                }
+            })
+            .collect::<Vec<_>>();
+        contributions.sort_unstable_by_key(|contribution| contribution.domain);
+
+        Ok(ConstraintGraphSnapshot {
+            version: SNAPSHOT_VERSION,
+            domains,
+            nodes,
+            edges,
+            contributions,
+        })
+    }
+
+    /// Rebuilds the graph from a snapshot, yielding the same node, edge and domain ids as the
+    /// graph the snapshot was taken from.
+    pub fn from_snapshot(snapshot: ConstraintGraphSnapshot<V>) -> Result<Self, GraphError<V>> {
+        if snapshot.version != SNAPSHOT_VERSION {
+            Err(GraphError::UnsupportedSnapshotVersion {
+                expected: SNAPSHOT_VERSION,
+                found: snapshot.version,
+            })?;
+        }
+
+        let mut domain = DenseMap::new();
+        let mut domain_identifier_map = FxHashMap::default();
+        for domain_snapshot in snapshot.domains {
+            let domain_identifier = DomainIdentifier::new(domain_snapshot.identifier);
+            let domain_id: DomainId = domain.push(DomainInfo {
+                domain_identifier: domain_identifier.clone(),
+                domain_description: domain_snapshot.description,
+            });
+            domain_identifier_map.insert(domain_identifier, domain_id);
+        }
+
+        let mut nodes = DenseMap::new();
+        let mut value_map = FxHashMap::default();
+        let mut node_info = DenseMap::new();
+        let mut node_metadata = DenseMap::new();
+        for node_snapshot in snapshot.nodes {
+            let node_type = match node_snapshot.node_type {
+                NodeTypeSnapshot::AllAggregator => NodeType::AllAggregator,
+                NodeTypeSnapshot::AnyAggregator => NodeType::AnyAggregator,
+                NodeTypeSnapshot::InAggregator(values) => {
+                    NodeType::InAggregator(FxHashSet::from_iter(values))
+                }
+                NodeTypeSnapshot::Value(value) => NodeType::Value(value),
+            };
+
+            let value = match &node_type {
+                NodeType::Value(value) => Some(value.clone()),
+                _ => None,
+            };
+            let node_id: NodeId = nodes.push(Node::new(node_type));
+            if let Some(value) = value {
+                value_map.insert(value, node_id);
+            }
+
+            let _node_info_id: NodeId = node_info.push(node_snapshot.info.map(intern_info));
+            let _node_metadata_id: NodeId = node_metadata.push(
+                node_snapshot
+                    .metadata
+                    .map(|metadata| -> Arc<dyn Metadata> { Arc::new(metadata) }),
+            );
+        }
+
+        let mut edges = DenseMap::new();
+        for edge_snapshot in snapshot.edges {
+            let pred = NodeId::with_id(edge_snapshot.pred);
+            let succ = NodeId::with_id(edge_snapshot.succ);
+            let domain_id = edge_snapshot.domain.map(DomainId::with_id);
+
+            if let Some(domain_id) = domain_id {
+                if !domain.contains_key(domain_id) {
+                    Err(GraphError::DomainNotFound)?;
+                }
+            }
+
+            let edge_id: EdgeId = edges.push(Edge {
+                strength: edge_snapshot.strength,
+                relation: edge_snapshot.relation,
+                pred,
+                succ,
+                domain: domain_id,
+            });
+
+            nodes
+                .get_mut(pred)
+                .ok_or(GraphError::NodeNotFound)?
+                .succs
+                .push(edge_id);
+            nodes
+                .get_mut(succ)
+                .ok_or(GraphError::NodeNotFound)?
+                .preds
+                .push(edge_id);
+        }
+
+        let mut domain_contributions = FxHashMap::default();
+        for contribution_snapshot in snapshot.contributions {
+            let domain_id = DomainId::with_id(contribution_snapshot.domain);
+            if !domain.contains_key(domain_id) {
+                Err(GraphError::DomainNotFound)?;
+            }
+
+            let contribution = DomainContribution {
+                nodes: contribution_snapshot
+                    .nodes
+                    .into_iter()
+                    .map(|node_id| {
+                        let node_id = NodeId::with_id(node_id);
+                        nodes
+                            .contains_key(node_id)
+                            .then_some(node_id)
+                            .ok_or(GraphError::NodeNotFound)
+                    })
+                    .collect::<Result<_, _>>()?,
+                edges: contribution_snapshot
+                    .edges
+                    .into_iter()
+                    .map(|edge_id| {
+                        let edge_id = EdgeId::with_id(edge_id);
+                        edges
+                            .contains_key(edge_id)
+                            .then_some(edge_id)
+                            .ok_or(GraphError::EdgeNotFound)
+                    })
+                    .collect::<Result<_, _>>()?,
+            };
+            domain_contributions.insert(domain_id, contribution);
+        }
+
+        Ok(Self {
+            domain,
+            domain_identifier_map,
+            nodes,
+            edges,
+            value_map,
+            node_info,
+            node_metadata,
+            domain_contributions,
+        })
+    }
+}
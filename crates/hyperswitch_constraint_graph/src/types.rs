@@ -17,7 +17,6 @@ pub trait ValueNode: fmt::Debug + Clone + hash::Hash + serde::Serialize + Partia
     fn get_key(&self) -> Self::Key;
 }
 
-#[cfg(feature = "viz")]
 pub trait NodeViz {
     fn viz(&self) -> String;
 }
@@ -28,7 +27,7 @@ pub struct NodeId(usize);
 
 impl_entity!(NodeId);
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Node<V: ValueNode> {
     pub node_type: NodeType<V>,
     pub preds: Vec<EdgeId>,
@@ -45,7 +44,7 @@ impl<V: ValueNode> Node<V> {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum NodeType<V: ValueNode> {
     AllAggregator,
     AnyAggregator,
@@ -53,8 +52,13 @@ pub enum NodeType<V: ValueNode> {
     Value(NodeValue<V>),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize)]
-#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(
+    tag = "type",
+    content = "value",
+    rename_all = "snake_case",
+    bound(deserialize = "V: serde::Deserialize<'de>, V::Key: serde::Deserialize<'de>")
+)]
 pub enum NodeValue<V: ValueNode> {
     Key(<V as ValueNode>::Key),
     Value(V),
@@ -72,7 +76,17 @@ pub struct EdgeId(usize);
 impl_entity!(EdgeId);
 
 #[derive(
-    Debug, Clone, Copy, serde::Serialize, PartialEq, Eq, Hash, strum::Display, PartialOrd, Ord,
+    Debug,
+    Clone,
+    Copy,
+    serde::Serialize,
+    serde::Deserialize,
+    PartialEq,
+    Eq,
+    Hash,
+    strum::Display,
+    PartialOrd,
+    Ord,
 )]
 pub enum Strength {
     Weak,
@@ -86,7 +100,9 @@ impl Strength {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize,
+)]
 #[serde(rename_all = "snake_case")]
 pub enum Relation {
     Positive,
@@ -164,6 +180,16 @@ pub struct DomainInfo {
     pub domain_description: String,
 }
 
+/// The nodes and edges that were made while contributing to a domain.
+///
+/// Nodes and edges are deduplicated by the builder, so the same node or edge can be part of the
+/// contributions of several domains.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DomainContribution {
+    pub nodes: FxHashSet<NodeId>,
+    pub edges: FxHashSet<EdgeId>,
+}
+
 pub trait CheckingContext {
     type Value: ValueNode;
 
@@ -4,7 +4,7 @@ use common_utils::{
     encryption::Encryption,
     errors::{CustomResult, ValidationError},
     pii,
-    types::{keymanager::KeyManagerState, MinorUnit},
+    types::{keymanager::KeyManagerState, DccOffer, MinorUnit},
 };
 use error_stack::ResultExt;
 use masking::PeekInterface;
@@ -178,6 +178,7 @@ pub struct PaymentAttempt {
     pub client_source: Option<String>,
     pub client_version: Option<String>,
     pub customer_acceptance: Option<pii::SecretSerdeValue>,
+    pub dcc_offer: Option<DccOffer>,
 }
 
 impl PaymentAttempt {
@@ -268,6 +269,7 @@ pub struct PaymentAttemptNew {
     pub client_source: Option<String>,
     pub client_version: Option<String>,
     pub customer_acceptance: Option<pii::SecretSerdeValue>,
+    pub dcc_offer: Option<DccOffer>,
 }
 
 impl PaymentAttemptNew {
@@ -351,6 +353,7 @@ pub enum PaymentAttemptUpdate {
         client_source: Option<String>,
         client_version: Option<String>,
         customer_acceptance: Option<pii::SecretSerdeValue>,
+        dcc_offer: Option<DccOffer>,
     },
     RejectUpdate {
         status: storage_enums::AttemptStatus,
@@ -124,5 +124,27 @@ fn evaluation(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, evaluation);
+fn snapshot(c: &mut Criterion) {
+    let big_graph = build_test_data(20, 20);
+    let snapshot = serde_json::to_vec(&big_graph.to_snapshot().expect("Snapshot"))
+        .expect("Serialized snapshot");
+
+    c.bench_function("MCA Big Graph Build", |b| {
+        b.iter(|| build_test_data(black_box(20), black_box(20)));
+    });
+
+    c.bench_function("MCA Big Graph Snapshot Load", |b| {
+        b.iter(|| {
+            hyperswitch_constraint_graph::ConstraintGraph::from_snapshot(
+                serde_json::from_slice::<
+                    hyperswitch_constraint_graph::ConstraintGraphSnapshot<dir::DirValue>,
+                >(black_box(&snapshot))
+                .expect("Deserialized snapshot"),
+            )
+            .expect("Loaded graph")
+        });
+    });
+}
+
+criterion_group!(benches, evaluation, snapshot);
 criterion_main!(benches);
@@ -0,0 +1,281 @@
+//! Human readable explanations of failed constraint graph analyses
+//!
+//! Walks the [`cgraph::AnalysisTrace`] of a failed analysis down to the constraints that were not
+//! satisfied, attributing each of them to the merchant connector account, payment method and
+//! payment method type it was configured for, using the metadata of the nodes along the way.
+
+use api_models::routing::IneligibilityReason;
+use euclid::frontend::dir;
+use hyperswitch_constraint_graph as cgraph;
+
+use crate::types as kgraph_types;
+
+/// Splits a value into its key and the textual form of its value, such as `currency` and `EUR`
+fn describe_value(value: &dir::DirValue) -> (String, String) {
+    let serialized = serde_json::to_value(value).unwrap_or_default();
+
+    let key = serialized
+        .get("key")
+        .and_then(serde_json::Value::as_str)
+        .map_or_else(|| value.get_key().kind.to_string(), ToString::to_string);
+
+    let value = match (value.get_num_value(), serialized.get("value")) {
+        (Some(num_value), _) => match num_value.refinement {
+            Some(refinement) => format!(
+                "{} {}",
+                match refinement {
+                    euclid::types::NumValueRefinement::NotEqual => "/=",
+                    euclid::types::NumValueRefinement::GreaterThan => ">",
+                    euclid::types::NumValueRefinement::LessThan => "<",
+                    euclid::types::NumValueRefinement::GreaterThanEqual => ">=",
+                    euclid::types::NumValueRefinement::LessThanEqual => "<=",
+                },
+                num_value.number
+            ),
+            None => num_value.number.to_string(),
+        },
+        (None, Some(serde_json::Value::String(variant))) => variant.clone(),
+        (None, Some(serde_json::Value::Object(fields))) => fields
+            .get("connector")
+            .or_else(|| fields.get("value"))
+            .and_then(serde_json::Value::as_str)
+            .map_or_else(
+                || serde_json::Value::Object(fields.clone()).to_string(),
+                ToString::to_string,
+            ),
+        (None, other) => other.map(ToString::to_string).unwrap_or_default(),
+    };
+
+    (key, value)
+}
+
+fn describe_node_value(value: &cgraph::NodeValue<dir::DirValue>) -> (String, Option<String>) {
+    match value {
+        cgraph::NodeValue::Key(key) => (key.kind.to_string(), None),
+        cgraph::NodeValue::Value(value) => {
+            let (key, value) = describe_value(value);
+            (key, Some(value))
+        }
+    }
+}
+
+/// Attribution of the constraints below a node, accumulated from the metadata of its ancestors
+#[derive(Clone)]
+struct ReasonContext {
+    metadata: kgraph_types::MerchantConnectorNodeMetadata,
+}
+
+impl ReasonContext {
+    fn with_metadata(
+        &self,
+        metadata: Option<&std::sync::Arc<dyn cgraph::types::Metadata>>,
+    ) -> Self {
+        let node_metadata = metadata
+            .and_then(|metadata| serde_json::to_value(metadata.as_ref()).ok())
+            .and_then(|metadata| {
+                serde_json::from_value::<kgraph_types::MerchantConnectorNodeMetadata>(metadata).ok()
+            })
+            .unwrap_or_default();
+
+        Self {
+            metadata: kgraph_types::MerchantConnectorNodeMetadata {
+                merchant_connector_id: node_metadata
+                    .merchant_connector_id
+                    .or_else(|| self.metadata.merchant_connector_id.clone()),
+                payment_method: node_metadata
+                    .payment_method
+                    .or(self.metadata.payment_method),
+                payment_method_type: node_metadata
+                    .payment_method_type
+                    .or(self.metadata.payment_method_type),
+            },
+        }
+    }
+
+    fn make_reason(
+        &self,
+        description: String,
+        key: Option<String>,
+        found: Option<String>,
+        expected: Vec<String>,
+        excluded: bool,
+    ) -> IneligibilityReason {
+        let mut message = description;
+        if let Some(payment_method_type) = self.metadata.payment_method_type {
+            message.push_str(&format!(" for payment method type {payment_method_type}"));
+        } else if let Some(payment_method) = self.metadata.payment_method {
+            message.push_str(&format!(" for payment method {payment_method}"));
+        }
+        if let Some(merchant_connector_id) = &self.metadata.merchant_connector_id {
+            message.push_str(&format!(
+                " on merchant connector account {merchant_connector_id}"
+            ));
+        }
+
+        IneligibilityReason {
+            message,
+            key,
+            found,
+            expected,
+            excluded,
+            merchant_connector_id: self.metadata.merchant_connector_id.clone(),
+            payment_method: self.metadata.payment_method,
+            payment_method_type: self.metadata.payment_method_type,
+        }
+    }
+}
+
+fn walk_trace(
+    trace: &cgraph::AnalysisTrace<dir::DirValue>,
+    context: &ReasonContext,
+    reasons: &mut Vec<IneligibilityReason>,
+) {
+    match trace {
+        cgraph::AnalysisTrace::Value {
+            value,
+            relation,
+            predecessors,
+            metadata,
+            ..
+        } => {
+            let context = context.with_metadata(metadata.as_ref());
+
+            match predecessors {
+                Some(cgraph::error::ValueTracePredecessor::Mandatory(predecessor)) => {
+                    walk_weak_traces(std::iter::once(predecessor.as_ref()), &context, reasons)
+                }
+                Some(cgraph::error::ValueTracePredecessor::OneOf(predecessors)) => {
+                    walk_weak_traces(predecessors.iter(), &context, reasons)
+                }
+                None => {
+                    let (key, value) = describe_node_value(value);
+                    let excluded = matches!(relation, cgraph::Relation::Negative);
+                    let description = match (&value, excluded) {
+                        (Some(value), false) => format!("{key} {value} is required"),
+                        (Some(value), true) => format!("{key} {value} is not supported"),
+                        (None, false) => format!("{key} is required"),
+                        (None, true) => format!("{key} is not supported"),
+                    };
+
+                    reasons.push(context.make_reason(
+                        description,
+                        Some(key),
+                        None,
+                        value.into_iter().collect(),
+                        excluded,
+                    ));
+                }
+            }
+        }
+
+        cgraph::AnalysisTrace::AllAggregation {
+            unsatisfied,
+            metadata,
+            ..
+        }
+        | cgraph::AnalysisTrace::AnyAggregation {
+            unsatisfied,
+            metadata,
+            ..
+        } => {
+            let context = context.with_metadata(metadata.as_ref());
+            walk_weak_traces(unsatisfied.iter(), &context, reasons);
+        }
+
+        cgraph::AnalysisTrace::InAggregation {
+            expected,
+            found,
+            relation,
+            metadata,
+            ..
+        } => {
+            let context = context.with_metadata(metadata.as_ref());
+            let mut expected = expected.iter().map(describe_value).collect::<Vec<_>>();
+            expected.sort();
+            let found = found.as_ref().map(describe_value);
+
+            let key = found
+                .as_ref()
+                .or(expected.first())
+                .map(|(key, _)| key.clone());
+            let expected = expected
+                .into_iter()
+                .map(|(_, value)| value)
+                .collect::<Vec<_>>();
+            let found = found.map(|(_, value)| value);
+            let key_name = key.clone().unwrap_or_default();
+            let excluded = matches!(relation, cgraph::Relation::Negative);
+
+            let description = match (&found, excluded) {
+                (Some(found), false) => {
+                    format!("{key_name} {found} is not one of {}", expected.join(", "))
+                }
+                (Some(found), true) => format!("{key_name} {found} is not supported"),
+                (None, _) => format!("{key_name} is required"),
+            };
+
+            reasons.push(context.make_reason(description, key, found, expected, excluded));
+        }
+
+        cgraph::AnalysisTrace::Contradiction { .. } => {
+            reasons.push(context.make_reason(
+                "The configuration has contradicting constraints".to_string(),
+                None,
+                None,
+                Vec::new(),
+                false,
+            ));
+        }
+    }
+}
+
+fn walk_weak_traces<'a>(
+    traces: impl Iterator<Item = &'a std::sync::Weak<cgraph::AnalysisTrace<dir::DirValue>>>,
+    context: &ReasonContext,
+    reasons: &mut Vec<IneligibilityReason>,
+) {
+    for trace in traces.filter_map(std::sync::Weak::upgrade) {
+        walk_trace(&trace, context, reasons);
+    }
+}
+
+/// Explains the error of a failed analysis with the constraints that were not satisfied.
+///
+/// Constraints are attributed to the merchant connector account, payment method and payment method
+/// type found in the metadata of the graph nodes, falling back to the ones in `attribution` for
+/// graphs built without metadata.
+///
+/// The analysis trace of the error is only available as long as the memoization the analysis was
+/// performed with, so this is to be called before the memoization is dropped.
+pub fn explain_graph_error(
+    error: &cgraph::GraphError<dir::DirValue>,
+    attribution: kgraph_types::MerchantConnectorNodeMetadata,
+) -> Vec<IneligibilityReason> {
+    let context = ReasonContext {
+        metadata: attribution,
+    };
+    let mut reasons = Vec::new();
+
+    match error {
+        cgraph::GraphError::AnalysisError(trace) => {
+            walk_weak_traces(std::iter::once(trace), &context, &mut reasons);
+        }
+        cgraph::GraphError::ValueNodeNotFound(value) => {
+            let (key, value) = describe_value(value);
+            reasons.push(context.make_reason(
+                format!("{key} {value} is not enabled"),
+                Some(key),
+                None,
+                vec![value],
+                false,
+            ));
+        }
+        _ => {}
+    }
+
+    if reasons.is_empty() {
+        reasons.push(context.make_reason(error.to_string(), None, None, Vec::new(), false));
+    }
+
+    reasons
+}
@@ -16,6 +16,10 @@ use crate::{error::KgraphError, transformers::IntoDirValue, types as kgraph_type
 
 pub const DOMAIN_IDENTIFIER: &str = "payment_methods_enabled_for_merchantconnectoraccount";
 
+/// Description of the domains the merchant connector accounts are compiled into, which are
+/// identified by the merchant connector id of the account
+const MCA_DOMAIN_DESCRIPTION: &str = "Constraints of a MerchantConnectorAccount";
+
 fn get_dir_value_payment_method(
     from: api_enums::PaymentMethodType,
 ) -> Result<dir::DirValue, KgraphError> {
@@ -153,7 +157,14 @@ fn compile_request_pm_types(
     builder: &mut cgraph::ConstraintGraphBuilder<dir::DirValue>,
     pm_types: RequestPaymentMethodTypes,
     pm: api_enums::PaymentMethod,
+    merchant_connector_id: &str,
 ) -> Result<cgraph::NodeId, KgraphError> {
+    let metadata = kgraph_types::MerchantConnectorNodeMetadata {
+        merchant_connector_id: Some(merchant_connector_id.to_string()),
+        payment_method: Some(pm),
+        payment_method_type: Some(pm_types.payment_method_type),
+    };
+
     let mut agg_nodes: Vec<(cgraph::NodeId, cgraph::Relation, cgraph::Strength)> = Vec::new();
 
     let pmt_info = "PaymentMethodType";
@@ -330,13 +341,19 @@ fn compile_request_pm_types(
 
     let pmt_all_aggregator_info = "All Aggregator for PaymentMethodType";
     builder
-        .make_all_aggregator(&agg_nodes, Some(pmt_all_aggregator_info), None::<()>, None)
+        .make_all_aggregator(
+            &agg_nodes,
+            Some(pmt_all_aggregator_info),
+            Some(metadata),
+            None,
+        )
         .map_err(KgraphError::GraphConstructionError)
 }
 
 fn compile_payment_method_enabled(
     builder: &mut cgraph::ConstraintGraphBuilder<dir::DirValue>,
     enabled: admin_api::PaymentMethodsEnabled,
+    merchant_connector_id: &str,
 ) -> Result<Option<cgraph::NodeId>, KgraphError> {
     let agg_id = if !enabled
         .payment_method_types
@@ -355,7 +372,12 @@ fn compile_payment_method_enabled(
 
         if let Some(pm_types) = enabled.payment_method_types {
             for pm_type in pm_types {
-                let node_id = compile_request_pm_types(builder, pm_type, enabled.payment_method)?;
+                let node_id = compile_request_pm_types(
+                    builder,
+                    pm_type,
+                    enabled.payment_method,
+                    merchant_connector_id,
+                )?;
                 agg_nodes.push((
                     node_id,
                     cgraph::Relation::Positive,
@@ -381,7 +403,11 @@ fn compile_payment_method_enabled(
                     ),
                 ],
                 Some(all_aggregator_info),
-                None::<()>,
+                Some(kgraph_types::MerchantConnectorNodeMetadata {
+                    merchant_connector_id: Some(merchant_connector_id.to_string()),
+                    payment_method: Some(enabled.payment_method),
+                    payment_method_type: None,
+                }),
                 None,
             )
             .map_err(KgraphError::GraphConstructionError)?;
@@ -612,7 +638,8 @@ fn compile_merchant_connector_graph(
 
     if let Some(pms_enabled) = mca.payment_methods_enabled.clone() {
         for pm_enabled in pms_enabled {
-            let maybe_pm_enabled_id = compile_payment_method_enabled(builder, pm_enabled)?;
+            let maybe_pm_enabled_id =
+                compile_payment_method_enabled(builder, pm_enabled, &mca.merchant_connector_id)?;
             if let Some(pm_enabled_id) = maybe_pm_enabled_id {
                 agg_nodes.push((
                     pm_enabled_id,
@@ -647,7 +674,11 @@ fn compile_merchant_connector_graph(
                 ),
             ],
             Some(config_info),
-            None::<()>,
+            Some(kgraph_types::MerchantConnectorNodeMetadata {
+                merchant_connector_id: Some(mca.merchant_connector_id.clone()),
+                payment_method: None,
+                payment_method_type: None,
+            }),
             None,
         )
         .map_err(KgraphError::GraphConstructionError)?;
@@ -680,7 +711,55 @@ pub fn make_mca_graph(
         "Payment methods enabled for MerchantConnectorAccount",
     );
     for acct in accts {
-        compile_merchant_connector_graph(&mut builder, acct, config)?;
+        compile_merchant_connector_graph_in_domain(&mut builder, acct, config)?;
+    }
+
+    Ok(builder.build())
+}
+
+/// Compiles the account as the contribution of its own domain, so that it can be replaced without
+/// rebuilding the graph. The edges of the account are not scoped to the domain, so the domain has
+/// no bearing on the analysis of the graph.
+fn compile_merchant_connector_graph_in_domain(
+    builder: &mut cgraph::ConstraintGraphBuilder<dir::DirValue>,
+    acct: admin_api::MerchantConnectorResponse,
+    config: &kgraph_types::CountryCurrencyFilter,
+) -> Result<(), KgraphError> {
+    let domain_id =
+        builder.make_domain(acct.merchant_connector_id.clone(), MCA_DOMAIN_DESCRIPTION)?;
+
+    builder.contribute_to_domain(domain_id, |builder| {
+        compile_merchant_connector_graph(builder, acct, config)
+    })
+}
+
+/// Updates a graph made by [`make_mca_graph`] for a change to one of its merchant connector
+/// accounts, replacing the part of the graph compiled from the account instead of compiling all the
+/// accounts again. The account is removed from the graph if `acct` is `None`, and added to it if it
+/// was not compiled into the graph before.
+///
+/// The updated graph is equivalent to the one [`make_mca_graph`] would make with the updated
+/// accounts, although its node and edge ids differ.
+pub fn update_mca_graph(
+    graph: &cgraph::ConstraintGraph<dir::DirValue>,
+    merchant_connector_id: &str,
+    acct: Option<admin_api::MerchantConnectorResponse>,
+    config: &kgraph_types::CountryCurrencyFilter,
+) -> Result<cgraph::ConstraintGraph<dir::DirValue>, KgraphError> {
+    let domain_id = graph
+        .domain_identifier_map
+        .get(&cgraph::DomainIdentifier::new(
+            merchant_connector_id.to_string(),
+        ))
+        .copied();
+
+    let mut builder = cgraph::ConstraintGraphBuilder::from_graph(match domain_id {
+        Some(domain_id) => graph.without_domain_contribution(domain_id)?,
+        None => graph.clone(),
+    });
+
+    if let Some(acct) = acct {
+        compile_merchant_connector_graph_in_domain(&mut builder, acct, config)?;
     }
 
     Ok(builder.build())
@@ -871,6 +950,44 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_single_mismatch_failure_explanation() {
+        let graph = build_test_data();
+        let mut memo = Memoization::new();
+
+        let error = graph
+            .key_value_analysis(
+                dirval!(Connector = Stripe),
+                &AnalysisContext::from_dir_values([
+                    dirval!(Connector = Stripe),
+                    dirval!(PaymentMethod = Card),
+                    dirval!(CardType = Debit),
+                    dirval!(CardNetwork = Maestro),
+                    dirval!(PaymentCurrency = PHP),
+                    dirval!(PaymentAmount = 100),
+                ]),
+                &mut memo,
+                &mut CycleCheck::new(),
+                None,
+            )
+            .expect_err("Currency mismatch");
+
+        let reasons = crate::explanation::explain_graph_error(
+            &error,
+            kgraph_types::MerchantConnectorNodeMetadata::default(),
+        );
+
+        assert!(
+            reasons.iter().any(|reason| {
+                reason.key.as_deref() == Some("currency")
+                    && reason.found.as_deref() == Some("PHP")
+                    && reason.merchant_connector_id.as_deref() == Some("something")
+                    && reason.payment_method_type == Some(api_enums::PaymentMethodType::Debit)
+            }),
+            "Unexpected reasons: {reasons:?}"
+        );
+    }
+
     #[test]
     fn test_amount_mismatch_failure_case() {
         let graph = build_test_data();
@@ -1147,4 +1264,284 @@ mod tests {
         );
         assert!(result.is_err(), "bluesnap validation failed");
     }
+
+    #[test]
+    fn test_relevant_subgraph() {
+        let graph = build_test_data();
+
+        let debit_node = graph
+            .value_map
+            .get(&cgraph::NodeValue::Value(dirval!(CardType = Debit)))
+            .copied()
+            .expect("Debit node");
+        let nodes = graph.get_relevant_nodes(&[debit_node]);
+        let flowchart = graph.get_mermaid_subgraph_flowchart(&nodes);
+
+        assert!(flowchart.starts_with("flowchart LR\n"));
+        assert!(flowchart.contains("Maestro"));
+        assert!(flowchart.contains("= stripe"));
+        assert!(!flowchart.contains("Visa"));
+        assert!(graph.get_mermaid_flowchart().contains("Visa"));
+
+        let digraph = graph.get_dot_subgraph_digraph(&nodes);
+        assert!(digraph.starts_with("strict digraph constraint_graph {\n"));
+        assert!(digraph.contains("Maestro"));
+        assert!(!digraph.contains("Visa"));
+        assert!(graph.get_dot_digraph().contains("Visa"));
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let graph = build_test_data();
+
+        let snapshot = serde_json::to_string(&graph.to_snapshot().expect("Snapshot"))
+            .expect("Serialized snapshot");
+        let loaded = ConstraintGraph::from_snapshot(
+            serde_json::from_str::<cgraph::ConstraintGraphSnapshot<dir::DirValue>>(&snapshot)
+                .expect("Deserialized snapshot"),
+        )
+        .expect("Loaded graph");
+
+        for (card_type, card_network, currency) in [
+            (
+                dirval!(CardType = Credit),
+                dirval!(CardNetwork = Visa),
+                dirval!(PaymentCurrency = INR),
+            ),
+            (
+                dirval!(CardType = Debit),
+                dirval!(CardNetwork = Maestro),
+                dirval!(PaymentCurrency = GBP),
+            ),
+            (
+                dirval!(CardType = Credit),
+                dirval!(CardNetwork = Visa),
+                dirval!(PaymentCurrency = GBP),
+            ),
+        ] {
+            let context = AnalysisContext::from_dir_values([
+                dirval!(Connector = Stripe),
+                dirval!(PaymentMethod = Card),
+                card_type,
+                card_network,
+                currency,
+                dirval!(PaymentAmount = 100),
+            ]);
+            let analyze = |graph: &ConstraintGraph<dir::DirValue>| {
+                graph
+                    .key_value_analysis(
+                        dirval!(Connector = Stripe),
+                        &context,
+                        &mut Memoization::new(),
+                        &mut CycleCheck::new(),
+                        None,
+                    )
+                    .is_ok()
+            };
+
+            assert_eq!(analyze(&graph), analyze(&loaded));
+        }
+    }
+
+    const CONNECTORS: [api_enums::RoutableConnectors; 3] = [
+        api_enums::RoutableConnectors::Stripe,
+        api_enums::RoutableConnectors::Adyen,
+        api_enums::RoutableConnectors::Checkout,
+    ];
+
+    proptest::prop_compose! {
+        fn card_payment_method_type()(
+            payment_method_type in proptest::sample::select(vec![
+                api_enums::PaymentMethodType::Credit,
+                api_enums::PaymentMethodType::Debit,
+            ]),
+            card_networks in proptest::sample::subsequence(
+                vec![
+                    api_enums::CardNetwork::Visa,
+                    api_enums::CardNetwork::Mastercard,
+                    api_enums::CardNetwork::Maestro,
+                ],
+                0..=3,
+            ),
+            currencies in proptest::sample::subsequence(
+                vec![
+                    api_enums::Currency::USD,
+                    api_enums::Currency::EUR,
+                    api_enums::Currency::GBP,
+                ],
+                0..=3,
+            ),
+            minimum_amount in proptest::option::of(proptest::sample::select(vec![10_i64, 500])),
+            maximum_amount in proptest::option::of(proptest::sample::select(vec![1000_i64, 5000])),
+        ) -> RequestPaymentMethodTypes {
+            RequestPaymentMethodTypes {
+                payment_method_type,
+                payment_experience: None,
+                card_networks: (!card_networks.is_empty()).then_some(card_networks),
+                accepted_currencies: (!currencies.is_empty()).then_some(
+                    api_models::admin::AcceptedCurrencies::EnableOnly(currencies),
+                ),
+                accepted_countries: None,
+                minimum_amount: minimum_amount.map(MinorUnit::new),
+                maximum_amount: maximum_amount.map(MinorUnit::new),
+                recurring_enabled: true,
+                installment_payment_enabled: true,
+            }
+        }
+    }
+
+    proptest::prop_compose! {
+        fn connector_account()(
+            connector in proptest::sample::select(CONNECTORS.to_vec()),
+            payment_method_types in proptest::collection::vec(card_payment_method_type(), 0..3),
+        ) -> (api_enums::RoutableConnectors, Vec<RequestPaymentMethodTypes>) {
+            (connector, payment_method_types)
+        }
+    }
+
+    fn make_account(
+        merchant_connector_id: String,
+        (connector, payment_method_types): (
+            api_enums::RoutableConnectors,
+            Vec<RequestPaymentMethodTypes>,
+        ),
+    ) -> admin_api::MerchantConnectorResponse {
+        admin_api::MerchantConnectorResponse {
+            connector_type: api_enums::ConnectorType::FizOperations,
+            connector_name: connector.to_string(),
+            merchant_connector_id,
+            business_country: None,
+            connector_label: None,
+            business_label: None,
+            business_sub_label: None,
+            connector_account_details: masking::Secret::new(serde_json::json!({})),
+            test_mode: None,
+            disabled: None,
+            metadata: None,
+            payment_methods_enabled: Some(vec![admin_api::PaymentMethodsEnabled {
+                payment_method: api_enums::PaymentMethod::Card,
+                payment_method_types: Some(payment_method_types),
+            }]),
+            frm_configs: None,
+            connector_webhook_details: None,
+            profile_id: None,
+            applepay_verified_domains: None,
+            pm_auth_config: None,
+            status: api_enums::ConnectorStatus::Active,
+            additional_merchant_data: None,
+        }
+    }
+
+    /// Analyses every connector in a grid of card payments, yielding whether it is eligible
+    fn analyze_card_payments(graph: &ConstraintGraph<dir::DirValue>) -> Vec<bool> {
+        let mut results = Vec::new();
+        for connector in CONNECTORS {
+            for card_type in [dirval!(CardType = Credit), dirval!(CardType = Debit)] {
+                for card_network in [
+                    dirval!(CardNetwork = Visa),
+                    dirval!(CardNetwork = Mastercard),
+                    dirval!(CardNetwork = Maestro),
+                ] {
+                    for currency in [
+                        dirval!(PaymentCurrency = USD),
+                        dirval!(PaymentCurrency = EUR),
+                        dirval!(PaymentCurrency = GBP),
+                    ] {
+                        for amount in [5, 100, 2000] {
+                            let connector_value =
+                                dir::DirValue::Connector(Box::new(ast::ConnectorChoice {
+                                    connector,
+                                }));
+                            let context = AnalysisContext::from_dir_values([
+                                connector_value.clone(),
+                                dirval!(PaymentMethod = Card),
+                                card_type.clone(),
+                                card_network.clone(),
+                                currency.clone(),
+                                dir::DirValue::PaymentAmount(NumValue {
+                                    number: MinorUnit::new(amount),
+                                    refinement: None,
+                                }),
+                            ]);
+
+                            results.push(
+                                graph
+                                    .key_value_analysis(
+                                        connector_value,
+                                        &context,
+                                        &mut Memoization::new(),
+                                        &mut CycleCheck::new(),
+                                        None,
+                                    )
+                                    .is_ok(),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        results
+    }
+
+    proptest::proptest! {
+        #![proptest_config(proptest::test_runner::Config::with_cases(64))]
+
+        #[test]
+        fn proptest_incremental_update_matches_fresh_build(
+            accounts in proptest::collection::vec(connector_account(), 1..4),
+            updated_index in proptest::arbitrary::any::<proptest::sample::Index>(),
+            updated_account in proptest::option::of(connector_account()),
+        ) {
+            let config = kgraph_types::CountryCurrencyFilter {
+                connector_configs: HashMap::new(),
+                default_configs: None,
+            };
+            let accounts = accounts
+                .into_iter()
+                .enumerate()
+                .map(|(index, account)| make_account(format!("mca_{index}"), account))
+                .collect::<Vec<_>>();
+            let updated_index = updated_index.index(accounts.len());
+            let merchant_connector_id = format!("mca_{updated_index}");
+            let updated_account = updated_account
+                .map(|account| make_account(merchant_connector_id.clone(), account));
+
+            // Cached graphs are loaded from snapshots, so the update is applied to a loaded graph
+            let graph = make_mca_graph(accounts.clone(), &config).expect("Graph");
+            let graph = ConstraintGraph::from_snapshot(graph.to_snapshot().expect("Snapshot"))
+                .expect("Loaded graph");
+            let updated = update_mca_graph(
+                &graph,
+                &merchant_connector_id,
+                updated_account.clone(),
+                &config,
+            )
+            .expect("Updated graph");
+
+            let fresh = make_mca_graph(
+                accounts
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(|(index, account)| {
+                        if index == updated_index {
+                            updated_account.clone()
+                        } else {
+                            Some(account)
+                        }
+                    })
+                    .collect(),
+                &config,
+            )
+            .expect("Fresh graph");
+
+            proptest::prop_assert_eq!(updated.nodes.values().count(), fresh.nodes.values().count());
+            proptest::prop_assert_eq!(updated.edges.values().count(), fresh.edges.values().count());
+            proptest::prop_assert_eq!(
+                updated.value_map.keys().collect::<HashSet<_>>(),
+                fresh.value_map.keys().collect::<HashSet<_>>()
+            );
+            proptest::prop_assert_eq!(analyze_card_payments(&updated), analyze_card_payments(&fresh));
+        }
+    }
 }
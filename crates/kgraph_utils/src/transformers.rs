@@ -72,6 +72,17 @@ impl IntoContext for BackendInput {
         if let Some(payment_type) = self.mandate.payment_type {
             ctx.push(dir::DirValue::PaymentType(payment_type));
         }
+        if let Some(time) = self.time {
+            ctx.push(dir::DirValue::UtcHour(NumValue {
+                number: common_utils::types::MinorUnit::new(i64::from(time.hour)),
+                refinement: None,
+            }));
+            ctx.push(dir::DirValue::UtcWeekday(time.weekday));
+            ctx.push(dir::DirValue::UtcDate(NumValue {
+                number: common_utils::types::MinorUnit::new(time.date),
+                refinement: None,
+            }));
+        }
 
         Ok(ctx)
     }
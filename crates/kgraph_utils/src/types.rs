@@ -33,3 +33,12 @@ pub struct CurrencyCountryFlowFilter {
 pub struct NotAvailableFlows {
     pub capture_method: Option<api_enums::CaptureMethod>,
 }
+
+/// Metadata of the aggregator nodes of a merchant connector account graph, identifying the part of
+/// the merchant connector account configuration the constraints of the node come from
+#[derive(Debug, serde::Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct MerchantConnectorNodeMetadata {
+    pub merchant_connector_id: Option<String>,
+    pub payment_method: Option<api_enums::PaymentMethod>,
+    pub payment_method_type: Option<api_enums::PaymentMethodType>,
+}
@@ -27,8 +27,14 @@ where
     S: Clone,
     I: crate::Strategy<S>,
 {
+    // Not mapped with a closure, for the location of the caller to be tracked
+    #[allow(clippy::manual_map)]
+    #[cfg_attr(feature = "audit", track_caller)]
     fn expose_option(self) -> Option<S> {
-        self.map(ExposeInterface::expose)
+        match self {
+            Some(secret) => Some(secret.expose()),
+            None => None,
+        }
     }
 }
 
@@ -36,7 +42,10 @@ impl<S, I> ExposeInterface<S> for Secret<S, I>
 where
     I: crate::Strategy<S>,
 {
+    #[cfg_attr(feature = "audit", track_caller)]
     fn expose(self) -> S {
+        #[cfg(feature = "audit")]
+        crate::audit::record::<Self, I>(crate::audit::ExposureKind::Expose);
         self.inner_secret
     }
 }
@@ -58,7 +67,10 @@ where
 {
     type Output = Secret<S, ToStrategy>;
 
+    #[cfg_attr(feature = "audit", track_caller)]
     fn switch_strategy(self) -> Self::Output {
+        #[cfg(feature = "audit")]
+        crate::audit::record::<Self, FromStrategy>(crate::audit::ExposureKind::SwitchStrategy);
         Secret::new(self.inner_secret)
     }
 }
@@ -0,0 +1,229 @@
+//!
+//! Audit trail of the exposure of secrets.
+//!
+//! Every call to [`PeekInterface::peek`](crate::PeekInterface::peek),
+//! [`ExposeInterface::expose`](crate::ExposeInterface::expose), [`Secret::map`](crate::Secret::map),
+//! [`Secret::zip`](crate::Secret::zip) and
+//! [`SwitchStrategy::switch_strategy`](crate::SwitchStrategy::switch_strategy) on the secrets of
+//! this crate is recorded as an [`ExposureEvent`], along with the location of the call, into the
+//! sink set with [`set_exposure_sink`] and into the [`RecordingSink`]s installed on the current
+//! thread or task. So are the serialization of secrets and their writing to the database, the
+//! location of which is that of the code calling into the secret to serialize or write it, which
+//! is the code of the serializer or of the database driver unless the secret is serialized or
+//! written directly.
+//!
+
+use std::{
+    any::type_name,
+    cell::RefCell,
+    fmt,
+    future::Future,
+    panic::Location,
+    sync::{Arc, Mutex, OnceLock, PoisonError},
+};
+
+/// How a secret was exposed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExposureKind {
+    /// A reference to the secret was obtained with `peek()`
+    Peek,
+    /// The secret was consumed with `expose()`
+    Expose,
+    /// The secret was transformed with `map()`
+    Map,
+    /// The secret was combined with another secret with `zip()`
+    Zip,
+    /// The masking strategy of the secret was switched with `switch_strategy()`
+    SwitchStrategy,
+    /// The secret was written to the database
+    ToSql,
+}
+
+impl fmt::Display for ExposureKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Peek => f.write_str("peek"),
+            Self::Expose => f.write_str("expose"),
+            Self::Map => f.write_str("map"),
+            Self::Zip => f.write_str("zip"),
+            Self::SwitchStrategy => f.write_str("switch_strategy"),
+            Self::ToSql => f.write_str("to_sql"),
+        }
+    }
+}
+
+/// Exposure of a secret
+#[derive(Debug, Clone)]
+pub struct ExposureEvent {
+    /// How the secret was exposed
+    pub kind: ExposureKind,
+    /// Type name of the secret, such as `masking::Secret<alloc::string::String>`
+    pub secret_type: &'static str,
+    /// Type name of the masking strategy of the secret, `()` for secrets without one
+    pub strategy: &'static str,
+    /// Location of the code the secret was exposed by
+    pub location: &'static Location<'static>,
+}
+
+impl ExposureEvent {
+    /// Whether the exposed secret is of type `T`
+    pub fn is<T: ?Sized>(&self) -> bool {
+        self.secret_type == type_name::<T>()
+    }
+}
+
+/// Sink the exposure events are recorded into
+pub trait ExposureSink: Send + Sync {
+    /// Records the exposure of a secret. Called on every exposure, so it should be cheap.
+    fn record(&self, event: &ExposureEvent);
+}
+
+static EXPOSURE_SINK: OnceLock<Box<dyn ExposureSink>> = OnceLock::new();
+
+thread_local! {
+    static RECORDING_SINKS: RefCell<Vec<RecordingSink>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Sets the sink the exposure events of every thread are recorded into. The sink can only be set
+/// once, and is returned as the error if it was already set.
+pub fn set_exposure_sink(sink: impl ExposureSink + 'static) -> Result<(), Box<dyn ExposureSink>> {
+    EXPOSURE_SINK.set(Box::new(sink))
+}
+
+/// Records the exposure of a secret of type `S` with masking strategy `M` at the location of the
+/// caller
+#[track_caller]
+pub(crate) fn record<S: ?Sized, M: ?Sized>(kind: ExposureKind) {
+    let event = ExposureEvent {
+        kind,
+        secret_type: type_name::<S>(),
+        strategy: type_name::<M>(),
+        location: Location::caller(),
+    };
+
+    if let Some(sink) = EXPOSURE_SINK.get() {
+        sink.record(&event);
+    }
+    RECORDING_SINKS.with(|sinks| {
+        sinks
+            .borrow()
+            .iter()
+            .for_each(|sink| ExposureSink::record(sink, &event))
+    });
+}
+
+/// Sink that keeps the exposure events in memory, to assert on the secrets a flow exposes in
+/// tests.
+///
+/// The sink only records the events of the thread it is installed on, for as long as the guard
+/// returned by [`RecordingSink::install`] is alive, or of the future it scopes with
+/// [`RecordingSink::scope`], whichever thread polls it, so that tests running in parallel do not
+/// record each other's events.
+///
+/// ```
+/// use masking::{audit::RecordingSink, PeekInterface, Secret};
+///
+/// let sink = RecordingSink::new();
+/// let guard = sink.install();
+/// let secret: Secret<String> = Secret::new("secret".to_string());
+/// assert_eq!(secret.peek(), "secret");
+/// drop(guard);
+///
+/// assert_eq!(sink.exposures_of::<Secret<String>>().len(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RecordingSink {
+    events: Arc<Mutex<Vec<ExposureEvent>>>,
+}
+
+/// Guard of a [`RecordingSink`] installed on the current thread, which uninstalls it when dropped
+#[derive(Debug)]
+#[must_use = "the sink is uninstalled when the guard is dropped"]
+pub struct RecordingGuard {
+    // The guard has to be dropped on the thread the sink was installed on
+    _not_send: std::marker::PhantomData<*const ()>,
+}
+
+impl Drop for RecordingGuard {
+    fn drop(&mut self) {
+        RECORDING_SINKS.with(|sinks| sinks.borrow_mut().pop());
+    }
+}
+
+impl RecordingSink {
+    /// Creates a sink with no events
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the exposure events of the current thread into the sink until the guard is dropped
+    pub fn install(&self) -> RecordingGuard {
+        RECORDING_SINKS.with(|sinks| sinks.borrow_mut().push(self.clone()));
+        RecordingGuard {
+            _not_send: std::marker::PhantomData,
+        }
+    }
+
+    /// Records the exposure events of the future into the sink, on whichever thread the future is
+    /// polled, such as the worker threads of a multi-threaded runtime. The events of the tasks the
+    /// future spawns are not recorded, unless they are scoped too.
+    pub async fn scope<F: Future>(&self, future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        std::future::poll_fn(|cx| {
+            let _guard = self.install();
+            future.as_mut().poll(cx)
+        })
+        .await
+    }
+
+    /// Recorded exposure events
+    pub fn events(&self) -> Vec<ExposureEvent> {
+        self.events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Recorded exposure events of the secrets of type `T`
+    pub fn exposures_of<T: ?Sized>(&self) -> Vec<ExposureEvent> {
+        self.events()
+            .into_iter()
+            .filter(ExposureEvent::is::<T>)
+            .collect()
+    }
+
+    /// Asserts that secrets of type `T` were only exposed in the source files of `allowed_paths`,
+    /// given as paths relative to the root of the workspace or prefixes of them.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the locations of the exposures outside of `allowed_paths`, if any.
+    #[track_caller]
+    pub fn assert_not_exposed_outside<T: ?Sized>(&self, allowed_paths: &[&str]) {
+        let exposures = self
+            .exposures_of::<T>()
+            .into_iter()
+            .filter(|event| {
+                !allowed_paths
+                    .iter()
+                    .any(|path| event.location.file().starts_with(path))
+            })
+            .map(|event| format!("{} at {}", event.kind, event.location))
+            .collect::<Vec<_>>();
+
+        assert!(
+            exposures.is_empty(),
+            "`{}` was exposed outside of {allowed_paths:?}: {exposures:#?}",
+            type_name::<T>()
+        );
+    }
+}
+
+impl ExposureSink for RecordingSink {
+    fn record(&self, event: &ExposureEvent) {
+        self.events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(event.clone());
+    }
+}
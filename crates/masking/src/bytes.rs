@@ -25,7 +25,10 @@ impl SecretBytesMut {
 }
 
 impl PeekInterface<BytesMut> for SecretBytesMut {
+    #[cfg_attr(feature = "audit", track_caller)]
     fn peek(&self) -> &BytesMut {
+        #[cfg(feature = "audit")]
+        crate::audit::record::<Self, ()>(crate::audit::ExposureKind::Peek);
         &self.0
     }
 }
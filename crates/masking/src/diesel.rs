@@ -41,7 +41,10 @@ where
     S: ToSql<T, DB>,
     I: Strategy<S>,
 {
+    #[cfg_attr(feature = "audit", track_caller)]
     fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        #[cfg(feature = "audit")]
+        crate::audit::record::<Self, I>(crate::audit::ExposureKind::ToSql);
         ToSql::<T, DB>::to_sql(&self.inner_secret, out)
     }
 }
@@ -111,7 +114,10 @@ where
     S: ToSql<T, DB> + ZeroizableSecret,
     I: Strategy<S>,
 {
+    #[cfg_attr(feature = "audit", track_caller)]
     fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        #[cfg(feature = "audit")]
+        crate::audit::record::<Self, I>(crate::audit::ExposureKind::ToSql);
         ToSql::<T, DB>::to_sql(&self.inner_secret, out)
     }
 }
@@ -14,7 +14,10 @@ pub use zeroize::{self, DefaultIsZeroes, Zeroize as ZeroizableSecret};
 
 mod strategy;
 
-pub use strategy::{Strategy, WithType, WithoutType};
+pub use strategy::{
+    set_hashing_key, HashToken, KeepFirst, KeepFirstAndLast, KeepLast, MaskEmailLocalPart,
+    MaskIban, MaskPhoneNumber, Strategy, WithType, WithoutType,
+};
 mod abs;
 pub use abs::{ExposeInterface, ExposeOptionInterface, PeekInterface, SwitchStrategy};
 
@@ -59,4 +62,7 @@ mod diesel;
 
 pub mod maskable;
 
+#[cfg(feature = "audit")]
+pub mod audit;
+
 pub use maskable::*;
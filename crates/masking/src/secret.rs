@@ -61,6 +61,7 @@ where
     }
 
     /// Zip 2 secrets with the same masking strategy into one
+    #[cfg_attr(feature = "audit", track_caller)]
     pub fn zip<OtherSecretValue>(
         self,
         other: Secret<OtherSecretValue, MaskingStrategy>,
@@ -68,10 +69,13 @@ where
     where
         MaskingStrategy: Strategy<OtherSecretValue> + Strategy<(SecretValue, OtherSecretValue)>,
     {
+        #[cfg(feature = "audit")]
+        crate::audit::record::<Self, MaskingStrategy>(crate::audit::ExposureKind::Zip);
         (self.inner_secret, other.inner_secret).into()
     }
 
     /// consume self and modify the inner value
+    #[cfg_attr(feature = "audit", track_caller)]
     pub fn map<OtherSecretValue>(
         self,
         f: impl FnOnce(SecretValue) -> OtherSecretValue,
@@ -79,6 +83,8 @@ where
     where
         MaskingStrategy: Strategy<OtherSecretValue>,
     {
+        #[cfg(feature = "audit")]
+        crate::audit::record::<Self, MaskingStrategy>(crate::audit::ExposureKind::Map);
         f(self.inner_secret).into()
     }
 }
@@ -88,7 +94,10 @@ impl<SecretValue, MaskingStrategy> PeekInterface<SecretValue>
 where
     MaskingStrategy: Strategy<SecretValue>,
 {
+    #[cfg_attr(feature = "audit", track_caller)]
     fn peek(&self) -> &SecretValue {
+        #[cfg(feature = "audit")]
+        crate::audit::record::<Self, MaskingStrategy>(crate::audit::ExposureKind::Peek);
         &self.inner_secret
     }
 }
@@ -122,7 +131,7 @@ where
     MaskingStrategy: Strategy<SecretValue>,
 {
     fn eq(&self, other: &Self) -> bool {
-        self.peek().eq(other.peek())
+        self.inner_secret.eq(&other.inner_secret)
     }
 }
 
@@ -52,6 +52,8 @@ where
     T: SerializableSecret + Serialize + Sized,
     I: Strategy<T>,
 {
+    // Tracked for the exposure of the secret to be recorded at the code serializing it
+    #[cfg_attr(feature = "audit", track_caller)]
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
@@ -78,6 +80,7 @@ where
     T: SerializableSecret + Serialize + ZeroizableSecret + Sized,
     I: Strategy<T>,
 {
+    #[cfg_attr(feature = "audit", track_caller)]
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
@@ -140,6 +143,7 @@ use pii_serializer::PIISerializer;
 mod pii_serializer {
     use std::fmt::Display;
 
+    #[cfg_attr(feature = "audit", track_caller)]
     pub(super) fn pii_serialize<
         V: Serialize,
         T: std::fmt::Debug + PeekInterface<V>,
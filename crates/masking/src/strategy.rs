@@ -1,4 +1,5 @@
-use core::fmt;
+use core::fmt::{self, Write};
+use std::sync::OnceLock;
 
 /// Debugging trait which is specialized for handling secret values
 pub trait Strategy<T> {
@@ -27,3 +28,273 @@ impl<T> Strategy<T> for WithoutType {
         fmt.write_str("*** ***")
     }
 }
+
+/// Character the masked characters of a value are replaced with
+const MASK_CHARACTER: char = '*';
+
+/// Writes the value with every character but the first `first` and the last `last` characters
+/// masked. The value is masked entirely if it is not longer than the revealed characters.
+fn fmt_partially_masked(
+    value: &str,
+    first: usize,
+    last: usize,
+    fmt: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    let length = value.chars().count();
+    let masked = if length > first.saturating_add(last) {
+        first..length - last
+    } else {
+        0..length
+    };
+
+    value
+        .chars()
+        .enumerate()
+        .try_for_each(|(index, character)| {
+            if masked.contains(&index) {
+                fmt.write_char(MASK_CHARACTER)
+            } else {
+                fmt.write_char(character)
+            }
+        })
+}
+
+/// Reveals the first `N` characters of the value
+///
+/// ```
+/// use masking::{KeepFirst, Secret};
+///
+/// let account_id: Secret<String, KeepFirst<4>> = Secret::new("acct_1234567890".to_string());
+/// assert_eq!(format!("{account_id:?}"), "acct***********");
+/// ```
+pub enum KeepFirst<const N: usize> {}
+
+impl<T, const N: usize> Strategy<T> for KeepFirst<N>
+where
+    T: AsRef<str>,
+{
+    fn fmt(value: &T, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_partially_masked(value.as_ref(), N, 0, fmt)
+    }
+}
+
+/// Reveals the last `N` characters of the value
+///
+/// ```
+/// use masking::{KeepLast, Secret};
+///
+/// let card_number: Secret<String, KeepLast<4>> = Secret::new("4242424242424242".to_string());
+/// assert_eq!(format!("{card_number:?}"), "************4242");
+/// ```
+pub enum KeepLast<const N: usize> {}
+
+impl<T, const N: usize> Strategy<T> for KeepLast<N>
+where
+    T: AsRef<str>,
+{
+    fn fmt(value: &T, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_partially_masked(value.as_ref(), 0, N, fmt)
+    }
+}
+
+/// Reveals the first `FIRST` and the last `LAST` characters of the value
+///
+/// ```
+/// use masking::{KeepFirstAndLast, Secret};
+///
+/// let card_number: Secret<String, KeepFirstAndLast<6, 4>> =
+///     Secret::new("4242424242424242".to_string());
+/// assert_eq!(format!("{card_number:?}"), "424242******4242");
+/// ```
+pub enum KeepFirstAndLast<const FIRST: usize, const LAST: usize> {}
+
+impl<T, const FIRST: usize, const LAST: usize> Strategy<T> for KeepFirstAndLast<FIRST, LAST>
+where
+    T: AsRef<str>,
+{
+    fn fmt(value: &T, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_partially_masked(value.as_ref(), FIRST, LAST, fmt)
+    }
+}
+
+/// Reveals the domain and the first character of the local part of an email address
+///
+/// ```
+/// use masking::{MaskEmailLocalPart, Secret};
+///
+/// let email: Secret<String, MaskEmailLocalPart> = Secret::new("john.doe@example.com".to_string());
+/// assert_eq!(format!("{email:?}"), "j*******@example.com");
+/// ```
+pub enum MaskEmailLocalPart {}
+
+impl<T> Strategy<T> for MaskEmailLocalPart
+where
+    T: AsRef<str>,
+{
+    fn fmt(value: &T, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match value.as_ref().rsplit_once('@') {
+            Some((local_part, domain)) if !local_part.is_empty() => {
+                fmt_partially_masked(local_part, 1, 0, fmt)?;
+                fmt.write_char('@')?;
+                fmt.write_str(domain)
+            }
+            _ => WithType::fmt(value, fmt),
+        }
+    }
+}
+
+/// Reveals the last 4 digits of a phone number, keeping its formatting
+///
+/// ```
+/// use masking::{MaskPhoneNumber, Secret};
+///
+/// let phone: Secret<String, MaskPhoneNumber> = Secret::new("+1 (415) 555-2671".to_string());
+/// assert_eq!(format!("{phone:?}"), "+* (***) ***-2671");
+/// ```
+pub enum MaskPhoneNumber {}
+
+/// Number of digits of phone numbers that are revealed
+const PHONE_NUMBER_REVEALED_DIGITS: usize = 4;
+
+/// Numbers with fewer digits are masked entirely, as revealing 4 of their digits reveals most of
+/// the number
+const PHONE_NUMBER_MIN_DIGITS: usize = 8;
+
+impl<T> Strategy<T> for MaskPhoneNumber
+where
+    T: AsRef<str>,
+{
+    fn fmt(value: &T, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = value.as_ref();
+        let digits = value.chars().filter(char::is_ascii_digit).count();
+        let masked_digits = if digits < PHONE_NUMBER_MIN_DIGITS {
+            digits
+        } else {
+            digits - PHONE_NUMBER_REVEALED_DIGITS
+        };
+
+        let mut digit_index = 0;
+        value.chars().try_for_each(|character| {
+            if !character.is_ascii_digit() {
+                return fmt.write_char(character);
+            }
+            digit_index += 1;
+            if digit_index <= masked_digits {
+                fmt.write_char(MASK_CHARACTER)
+            } else {
+                fmt.write_char(character)
+            }
+        })
+    }
+}
+
+/// Reveals the country code, the check digits and the last 4 characters of an IBAN, keeping its
+/// formatting
+///
+/// ```
+/// use masking::{MaskIban, Secret};
+///
+/// let iban: Secret<String, MaskIban> = Secret::new("DE89 3704 0044 0532 0130 00".to_string());
+/// assert_eq!(format!("{iban:?}"), "DE89 **** **** **** **30 00");
+/// ```
+pub enum MaskIban {}
+
+/// Number of characters of the country code and the check digits of IBANs
+const IBAN_PREFIX_LENGTH: usize = 4;
+
+/// Number of trailing characters of IBANs that are revealed
+const IBAN_REVEALED_SUFFIX_LENGTH: usize = 4;
+
+/// Length of the shortest IBANs, those of Norway
+const IBAN_MIN_LENGTH: usize = 15;
+
+impl<T> Strategy<T> for MaskIban
+where
+    T: AsRef<str>,
+{
+    fn fmt(value: &T, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let iban = value.as_ref();
+        let length = iban.chars().filter(char::is_ascii_alphanumeric).count();
+        if length < IBAN_MIN_LENGTH {
+            return WithType::fmt(value, fmt);
+        }
+
+        let masked = IBAN_PREFIX_LENGTH..length - IBAN_REVEALED_SUFFIX_LENGTH;
+        let mut index = 0;
+        iban.chars().try_for_each(|character| {
+            if !character.is_ascii_alphanumeric() {
+                return fmt.write_char(character);
+            }
+            let is_masked = masked.contains(&index);
+            index += 1;
+            if is_masked {
+                fmt.write_char(MASK_CHARACTER)
+            } else {
+                fmt.write_char(character)
+            }
+        })
+    }
+}
+
+/// Key the tokens of [`HashToken`] are derived with
+static HASHING_KEY: OnceLock<[u8; 32]> = OnceLock::new();
+
+/// Sets the key the tokens of [`HashToken`] are derived with, which can only be set once.
+///
+/// The same key has to be set by every application whose tokens are to be correlated. The key
+/// already set is kept, and the key passed is returned, if a key was set before.
+pub fn set_hashing_key(key: [u8; 32]) -> Result<(), [u8; 32]> {
+    HASHING_KEY.set(key)
+}
+
+/// Replaces the value by a token derived from a keyed hash of it, of the same format as the value.
+///
+/// Every ASCII digit of the value is replaced by a digit and every ASCII letter by a letter of the
+/// same case, other characters being kept. The token of a value is the same as long as the
+/// hashing key is, so that the occurrences of a value can be correlated across logs without the
+/// value being revealed. Values are masked entirely until the hashing key is set with
+/// [`set_hashing_key`].
+///
+/// ```
+/// use masking::{HashToken, Secret};
+///
+/// masking::set_hashing_key([7; 32]).ok();
+///
+/// let card_number: Secret<String, HashToken> = Secret::new("4242 4242 4242 4242".to_string());
+/// let token = format!("{card_number:?}");
+/// assert_eq!(token.len(), 19);
+/// assert_ne!(token, "4242 4242 4242 4242");
+/// assert_eq!(token, format!("{:?}", card_number.clone()));
+/// ```
+pub enum HashToken {}
+
+impl<T> Strategy<T> for HashToken
+where
+    T: AsRef<str>,
+{
+    fn fmt(value: &T, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(key) = HASHING_KEY.get() else {
+            return WithType::fmt(value, fmt);
+        };
+
+        let value = value.as_ref();
+        let mut hash = blake3::Hasher::new_keyed(key)
+            .update(value.as_bytes())
+            .finalize_xof();
+        let mut next_byte = || {
+            let mut byte = [0; 1];
+            hash.fill(&mut byte);
+            byte[0]
+        };
+
+        value.chars().try_for_each(|character| {
+            let character = match character {
+                '0'..='9' => char::from(b'0' + next_byte() % 10),
+                'a'..='z' => char::from(b'a' + next_byte() % 26),
+                'A'..='Z' => char::from(b'A' + next_byte() % 26),
+                _ => character,
+            };
+            fmt.write_char(character)
+        })
+    }
+}
@@ -33,7 +33,10 @@ impl<Secret: ZeroizableSecret, MaskingStrategy> StrongSecret<Secret, MaskingStra
 impl<Secret: ZeroizableSecret, MaskingStrategy> PeekInterface<Secret>
     for StrongSecret<Secret, MaskingStrategy>
 {
+    #[cfg_attr(feature = "audit", track_caller)]
     fn peek(&self) -> &Secret {
+        #[cfg(feature = "audit")]
+        crate::audit::record::<Self, MaskingStrategy>(crate::audit::ExposureKind::Peek);
         &self.inner_secret
     }
 }
@@ -63,7 +66,7 @@ where
     Secret: ZeroizableSecret + StrongEq,
 {
     fn eq(&self, other: &Self) -> bool {
-        StrongEq::strong_eq(self.peek(), other.peek())
+        StrongEq::strong_eq(&self.inner_secret, &other.inner_secret)
     }
 }
 
@@ -0,0 +1,174 @@
+#![cfg(feature = "audit")]
+#![allow(clippy::unwrap_used)]
+
+use masking::{
+    audit::{ExposureKind, RecordingSink},
+    ExposeInterface, ExposeOptionInterface, PeekInterface, Secret, StrongSecret,
+};
+
+enum CardNumberStrategy {}
+
+impl<T> masking::Strategy<T> for CardNumberStrategy {
+    fn fmt(_: &T, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.write_str("****")
+    }
+}
+
+type CardNumber = StrongSecret<String, CardNumberStrategy>;
+
+#[test]
+fn records_exposures_at_the_call_site() {
+    let sink = RecordingSink::new();
+    let guard = sink.install();
+
+    let card_number = CardNumber::new("4242424242424242".to_string());
+    let line = line!() + 1;
+    let _ = card_number.peek();
+    let _ = Some(Secret::<String>::new("secret".to_string())).expose_option();
+
+    // Comparisons and formatting do not expose the secret
+    assert_eq!(card_number, CardNumber::new("4242424242424242".to_string()));
+    assert_eq!(format!("{card_number:?}"), "****");
+    drop(guard);
+
+    let events = sink.events();
+    assert_eq!(events.len(), 2);
+
+    let exposure = sink.exposures_of::<CardNumber>().pop().unwrap();
+    assert_eq!(exposure.kind, ExposureKind::Peek);
+    assert_eq!(
+        exposure.strategy,
+        std::any::type_name::<CardNumberStrategy>()
+    );
+    assert_eq!(exposure.location.file(), file!());
+    assert_eq!(exposure.location.line(), line);
+
+    let exposure = sink.exposures_of::<Secret<String>>().pop().unwrap();
+    assert_eq!(exposure.kind, ExposureKind::Expose);
+    assert_eq!(exposure.location.line(), line + 1);
+}
+
+#[test]
+fn records_only_while_installed() {
+    let sink = RecordingSink::new();
+    let secret = Secret::<String>::new("secret".to_string());
+
+    let _ = secret.peek();
+    {
+        let _guard = sink.install();
+        let _ = secret.peek();
+    }
+    let _ = secret.expose();
+
+    assert_eq!(sink.events().len(), 1);
+}
+
+#[test]
+fn asserts_exposures_outside_allowed_paths() {
+    let sink = RecordingSink::new();
+    let guard = sink.install();
+    let _ = CardNumber::new("4242424242424242".to_string()).peek();
+    drop(guard);
+
+    sink.assert_not_exposed_outside::<CardNumber>(&["crates/masking/tests/audit.rs"]);
+    // Exposures of other secrets are not asserted on
+    sink.assert_not_exposed_outside::<Secret<String>>(&[]);
+
+    let result = std::panic::catch_unwind(|| {
+        sink.assert_not_exposed_outside::<CardNumber>(&["crates/router/src/core/payment_methods"])
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+fn records_transformations_and_serialization_at_the_call_site() {
+    use masking::{Serialize, SwitchStrategy};
+
+    let sink = RecordingSink::new();
+    let guard = sink.install();
+
+    let line = line!() + 1;
+    let secret = Secret::<String>::new("secret".to_string()).map(|secret| secret.len());
+    let secret = secret.zip(Secret::new(1_usize));
+    let _: Secret<(usize, usize), CardNumberStrategy> = secret.switch_strategy();
+    let _ = Secret::<i32>::new(42).serialize(serde_json::value::Serializer);
+
+    // Masked serialization does not expose the secret
+    let _ = masking::masked_serialize(&Secret::<i32>::new(42));
+    drop(guard);
+
+    let events = sink
+        .events()
+        .into_iter()
+        .map(|event| (event.kind, event.location.file(), event.location.line()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        events,
+        [
+            (ExposureKind::Map, file!(), line),
+            (ExposureKind::Zip, file!(), line + 1),
+            (ExposureKind::SwitchStrategy, file!(), line + 2),
+            (ExposureKind::Peek, file!(), line + 3),
+        ]
+    );
+}
+
+#[test]
+fn records_exposures_of_scoped_futures_on_every_thread() {
+    use std::{
+        future::Future,
+        pin::Pin,
+        sync::Arc,
+        task::{Context, Poll, Wake, Waker},
+    };
+
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    /// Future that is pending on its first poll
+    #[derive(Default)]
+    struct YieldNow(bool);
+
+    impl Future for YieldNow {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                Poll::Ready(())
+            } else {
+                self.0 = true;
+                Poll::Pending
+            }
+        }
+    }
+
+    let sink = RecordingSink::new();
+    let secret = Secret::<String>::new("secret".to_string());
+    let waker = Waker::from(Arc::new(NoopWaker));
+
+    let mut future = Box::pin(sink.scope(async {
+        let _ = secret.peek();
+        YieldNow::default().await;
+        let _ = secret.peek();
+    }));
+
+    // The future is polled on a thread the sink is not installed on after it yields
+    assert!(future
+        .as_mut()
+        .poll(&mut Context::from_waker(&waker))
+        .is_pending());
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            assert!(future
+                .as_mut()
+                .poll(&mut Context::from_waker(&waker))
+                .is_ready());
+        });
+    });
+    let _ = secret.peek();
+
+    assert_eq!(sink.exposures_of::<Secret<String>>().len(), 2);
+}
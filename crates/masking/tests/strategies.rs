@@ -0,0 +1,116 @@
+#![allow(clippy::unwrap_used)]
+
+use masking::{
+    HashToken, KeepFirst, KeepFirstAndLast, KeepLast, MaskEmailLocalPart, MaskIban,
+    MaskPhoneNumber, Secret, Strategy,
+};
+
+fn masked<S: Strategy<String>>(value: &str) -> String {
+    format!("{:?}", Secret::<String, S>::new(value.to_string()))
+}
+
+#[test]
+fn partial_reveal() {
+    assert_eq!(masked::<KeepFirst<4>>("acct_1234"), "acct*****");
+    assert_eq!(
+        masked::<KeepLast<4>>("4242424242424242"),
+        "************4242"
+    );
+    assert_eq!(
+        masked::<KeepFirstAndLast<6, 4>>("4242424242424242"),
+        "424242******4242"
+    );
+
+    // Values that would be revealed entirely are masked entirely
+    assert_eq!(masked::<KeepLast<4>>("4242"), "****");
+    assert_eq!(masked::<KeepFirstAndLast<2, 2>>("abcd"), "****");
+
+    // Characters are masked rather than bytes
+    assert_eq!(masked::<KeepLast<2>>("héllo"), "***lo");
+}
+
+#[test]
+fn email_phone_number_and_iban() {
+    assert_eq!(
+        masked::<MaskEmailLocalPart>("john.doe@example.com"),
+        "j*******@example.com"
+    );
+    assert_eq!(
+        masked::<MaskEmailLocalPart>("not an email"),
+        "*** alloc::string::String ***"
+    );
+
+    assert_eq!(
+        masked::<MaskPhoneNumber>("+1 (415) 555-2671"),
+        "+* (***) ***-2671"
+    );
+    assert_eq!(masked::<MaskPhoneNumber>("4155552671"), "******2671");
+    // Numbers too short for 4 digits to be revealed are masked entirely
+    assert_eq!(masked::<MaskPhoneNumber>("555-2671"), "***-****");
+
+    assert_eq!(
+        masked::<MaskIban>("DE89 3704 0044 0532 0130 00"),
+        "DE89 **** **** **** **30 00"
+    );
+    assert_eq!(
+        masked::<MaskIban>("GB82WEST12345698765432"),
+        "GB82**************5432"
+    );
+    assert_eq!(masked::<MaskIban>("DE89"), "*** alloc::string::String ***");
+}
+
+#[test]
+fn hash_token() {
+    masking::set_hashing_key([42; 32]).ok();
+
+    let token = masked::<HashToken>("4242 4242 4242 4242");
+    assert_eq!(token.len(), 19);
+    assert_ne!(token, "4242 4242 4242 4242");
+    assert!(token
+        .split(' ')
+        .all(|group| group.len() == 4 && group.chars().all(|c| c.is_ascii_digit())));
+
+    // Tokens of the same value are the same, and of different values differ
+    assert_eq!(token, masked::<HashToken>("4242 4242 4242 4242"));
+    assert_ne!(token, masked::<HashToken>("4000 0566 5566 5556"));
+
+    let token = masked::<HashToken>("John.Doe@example.com");
+    assert_eq!(token.len(), 20);
+    assert_eq!(token.find('@'), Some(8));
+    assert!(token.starts_with(|c: char| c.is_ascii_uppercase()));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn masked_serialize_with_strategies() {
+    #[derive(serde::Serialize)]
+    struct Customer {
+        email: Secret<String, MaskEmailLocalPart>,
+        phone: Option<Secret<String, MaskPhoneNumber>>,
+        iban: Secret<String, MaskIban>,
+        card_number: Secret<String, KeepFirstAndLast<6, 4>>,
+    }
+
+    let customer = Customer {
+        email: Secret::new("john.doe@example.com".to_string()),
+        phone: Some(Secret::new("+1 (415) 555-2671".to_string())),
+        iban: Secret::new("DE89 3704 0044 0532 0130 00".to_string()),
+        card_number: Secret::new("4242424242424242".to_string()),
+    };
+
+    assert_eq!(
+        masking::masked_serialize(&customer).unwrap(),
+        serde_json::json!({
+            "email": "j*******@example.com",
+            "phone": "+* (***) ***-2671",
+            "iban": "DE89 **** **** **** **30 00",
+            "card_number": "424242******4242",
+        })
+    );
+
+    // Values are serialized as they are otherwise
+    assert_eq!(
+        serde_json::to_value(&customer).unwrap()["card_number"],
+        "4242424242424242"
+    );
+}
@@ -100,6 +100,7 @@ Never share your secret api keys. Keep them guarded and secure.
         routes::merchant_connector_account::payment_connector_create,
         routes::merchant_connector_account::payment_connector_retrieve,
         routes::merchant_connector_account::payment_connector_list,
+        routes::merchant_connector_account::payment_connector_graph,
         routes::merchant_connector_account::payment_connector_update,
         routes::merchant_connector_account::payment_connector_delete,
 
@@ -146,6 +147,9 @@ Never share your secret api keys. Keep them guarded and secure.
         routes::routing::routing_create_config,
         routes::routing::routing_link_config,
         routes::routing::routing_retrieve_config,
+        routes::routing::routing_retrieve_config_dsl,
+        routes::routing::routing_simulate_config,
+        routes::routing::routing_analyze_eligibility,
         routes::routing::list_routing_configs,
         routes::routing::routing_unlink_config,
         routes::routing::routing_update_default_config,
@@ -186,6 +190,7 @@ Never share your secret api keys. Keep them guarded and secure.
     ),
     components(schemas(
         common_utils::types::MinorUnit,
+        common_utils::types::DccOffer,
         common_utils::link_utils::GenericLinkUiConfig,
         common_utils::link_utils::EnabledPaymentMethod,
         api_models::refunds::RefundRequest,
@@ -198,6 +203,7 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::admin::MerchantAccountDeleteResponse,
         api_models::admin::MerchantConnectorDeleteResponse,
         api_models::admin::MerchantConnectorResponse,
+        api_models::admin::MerchantConnectorGraphResponse,
         api_models::admin::AuthenticationConnectorDetails,
         api_models::admin::ExtendedCardInfoConfig,
         api_models::admin::BusinessGenericLinkConfig,
@@ -232,6 +238,7 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::enums::AuthenticationConnectors,
         api_models::enums::Currency,
         api_models::enums::IntentStatus,
+        api_models::enums::DccDecision,
         api_models::enums::CaptureMethod,
         api_models::enums::FutureUsage,
         api_models::enums::AuthenticationType,
@@ -519,6 +526,10 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::routing::RoutingDictionaryRecord,
         api_models::routing::RoutingKind,
         api_models::routing::RoutableConnectorChoice,
+        api_models::routing::IneligibilityReason,
+        api_models::routing::ConnectorIneligibility,
+        api_models::routing::RoutingEligibilityRequest,
+        api_models::routing::EligibilityAnalysisResult,
         api_models::routing::LinkedRoutingConfigRetrieveResponse,
         api_models::routing::RoutingRetrieveResponse,
         api_models::routing::ProfileDefaultRoutingConfig,
@@ -528,12 +539,19 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::routing::RoutingAlgorithm,
         api_models::routing::StraightThroughAlgorithm,
         api_models::routing::ConnectorVolumeSplit,
+        api_models::routing::SuccessRateBasedRoutingConfig,
         api_models::routing::ConnectorSelection,
+        api_models::routing::RoutingSimulationAlgorithm,
+        api_models::routing::RoutingSimulationRequest,
+        api_models::routing::RoutingSimulationResponse,
+        api_models::routing::ConnectorSimulationResult,
         api_models::routing::ast::RoutableChoiceKind,
         api_models::enums::RoutableConnectors,
         api_models::routing::ast::ProgramConnectorSelection,
         api_models::routing::ast::RuleConnectorSelection,
         api_models::routing::ast::IfStatement,
+        api_models::routing::ast::Condition,
+        api_models::routing::ast::ConditionGroup,
         api_models::routing::ast::Comparison,
         api_models::routing::ast::ComparisonType,
         api_models::routing::ast::ValueType,
@@ -48,6 +48,7 @@ pub async fn create_payment_method_api() {}
         ("maximum_amount" = i64, Query, description = "The maximum amount accepted for processing by the particular payment method."),
         ("recurring_payment_enabled" = bool, Query, description = "Indicates whether the payment method is eligible for recurring payments"),
         ("installment_payment_enabled" = bool, Query, description = "Indicates whether the payment method is eligible for installment payments"),
+        ("explain_ineligibility" = bool, Query, description = "Explain why connectors were excluded from the list, only honoured when authenticated with an API key"),
     ),
     responses(
         (status = 200, description = "Payment Methods retrieved", body = PaymentMethodListResponse),
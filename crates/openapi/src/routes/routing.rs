@@ -62,6 +62,72 @@ pub async fn routing_link_config() {}
 )]
 pub async fn routing_retrieve_config() {}
 
+/// Routing - Retrieve as DSL
+///
+/// Retrieve an advanced routing algorithm as text in the routing DSL
+
+#[utoipa::path(
+    get,
+    path = "/routing/{algorithm_id}/dsl",
+    params(
+        ("algorithm_id" = String, Path, description = "The unique identifier for a config"),
+    ),
+    responses(
+        (status = 200, description = "Successfully fetched routing config as DSL text", content_type = "text/plain", body = String),
+        (status = 500, description = "Internal server error"),
+        (status = 404, description = "Resource missing"),
+        (status = 412, description = "Routing config is not an advanced routing config"),
+        (status = 403, description = "Forbidden")
+    ),
+   tag = "Routing",
+   operation_id = "Retrieve a routing config as DSL",
+   security(("api_key" = []), ("jwt_key" = []))
+)]
+pub async fn routing_retrieve_config_dsl() {}
+
+/// Routing - Simulate
+///
+/// Replay the payments made in a time range through a routing config, without affecting any
+/// payment, and compare the simulated connector distribution with the actual one
+#[utoipa::path(
+    post,
+    path = "/routing/simulate",
+    request_body = RoutingSimulationRequest,
+    responses(
+        (status = 200, description = "Routing config simulated", body = RoutingSimulationResponse),
+        (status = 400, description = "Request body is malformed"),
+        (status = 500, description = "Internal server error"),
+        (status = 404, description = "Resource missing"),
+        (status = 422, description = "Unprocessable request"),
+        (status = 403, description = "Forbidden"),
+    ),
+   tag = "Routing",
+   operation_id = "Simulate a routing config",
+   security(("api_key" = []), ("jwt_key" = []))
+)]
+pub async fn routing_simulate_config() {}
+
+/// Routing - Analyze Eligibility
+///
+/// Run the eligibility analysis of routing on a payment, and explain why the connectors which are
+/// not eligible for the payment were excluded
+#[utoipa::path(
+    post,
+    path = "/routing/eligibility",
+    request_body = RoutingEligibilityRequest,
+    responses(
+        (status = 200, description = "Connector eligibility analyzed", body = EligibilityAnalysisResult),
+        (status = 400, description = "Request body is malformed"),
+        (status = 500, description = "Internal server error"),
+        (status = 404, description = "Payment not found"),
+        (status = 403, description = "Forbidden"),
+    ),
+   tag = "Routing",
+   operation_id = "Analyze the eligibility of connectors for a payment",
+   security(("api_key" = []), ("jwt_key" = []))
+)]
+pub async fn routing_analyze_eligibility() {}
+
 /// Routing - List
 ///
 /// List all routing configs
@@ -6,7 +6,7 @@
 //!
 //!
 
-use std::fmt::Debug;
+use std::{collections::HashMap, fmt::Debug};
 
 use common_utils::{
     errors::CustomResult,
@@ -15,7 +15,10 @@ use common_utils::{
 };
 use error_stack::{report, ResultExt};
 use fred::{
-    interfaces::{HashesInterface, KeysInterface, SetsInterface, StreamsInterface},
+    interfaces::{
+        HashesInterface, KeysInterface, ListInterface, LuaInterface, SetsInterface,
+        StreamsInterface,
+    },
     prelude::RedisErrorKind,
     types::{
         Expiration, FromRedis, MultipleIDs, MultipleKeys, MultipleOrderedPairs, MultipleStrings,
@@ -31,12 +34,36 @@ use crate::{
 };
 
 impl super::RedisConnectionPool {
+    /// Prefixes the key with the key prefix of the connection.
+    ///
+    /// Keys with a hash tag keep it as the hash tag of the prefixed key, for the keys sharing a
+    /// hash tag to remain in the same hash slot, unless the prefix has a hash tag of its own, which
+    /// takes precedence. With `hash_tag_aware_key_prefix` enabled, the hash tag of the key is then
+    /// repeated ahead of the prefix.
     pub fn add_prefix(&self, key: &str) -> String {
-        if self.key_prefix.is_empty() {
-            key.to_string()
-        } else {
-            format!("{}:{}", self.key_prefix, key)
+        add_prefix(&self.key_prefix, key, self.config.hash_tag_aware_key_prefix)
+    }
+
+    /// Ensures that the keys of a multi-key command are in the same hash slot when connected to
+    /// a Redis Cluster, which rejects commands on keys of different hash slots.
+    fn ensure_same_hash_slot<'a>(
+        &self,
+        keys: impl IntoIterator<Item = &'a [u8]>,
+    ) -> CustomResult<(), errors::RedisError> {
+        if !self.pool.is_clustered() {
+            return Ok(());
         }
+
+        let mut hash_slots = keys.into_iter().map(fred::util::redis_keyslot);
+        let first_hash_slot = hash_slots.next();
+        fp_utils::when(
+            hash_slots.any(|hash_slot| Some(hash_slot) != first_hash_slot),
+            || {
+                Err(report!(errors::RedisError::CrossSlotKeys)).attach_printable(
+                    "Keys of multi-key commands need a common hash tag with Redis Cluster",
+                )
+            },
+        )
     }
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn set_key<V>(&self, key: &str, value: V) -> CustomResult<(), errors::RedisError>
@@ -79,6 +106,17 @@ impl super::RedisConnectionPool {
         V: TryInto<RedisMap> + Debug + Send + Sync,
         V::Error: Into<fred::error::RedisError> + Send + Sync,
     {
+        let value: RedisMap = value
+            .try_into()
+            .map_err(Into::<fred::error::RedisError>::into)
+            .change_context(errors::RedisError::SetFailed)?;
+        let value = value
+            .inner()
+            .into_iter()
+            .map(|(key, value)| (self.add_prefix(&key.as_str_lossy()), value))
+            .collect::<HashMap<_, _>>();
+        self.ensure_same_hash_slot(value.keys().map(String::as_bytes))?;
+
         self.pool
             .msetnx(value)
             .await
@@ -422,6 +460,43 @@ impl super::RedisConnectionPool {
             .collect())
     }
 
+    /// Scans the keys matching the pattern, on every primary node when connected to a Redis
+    /// Cluster, one page of keys at a time. The keys are returned without the key prefix of the
+    /// connection, for them to be used with the other commands.
+    pub fn scan_keys(
+        &self,
+        pattern: &str,
+        count: Option<u32>,
+    ) -> impl futures::Stream<Item = CustomResult<Vec<String>, errors::RedisError>> + '_ {
+        let client = self.pool.next();
+        let pattern = self.add_prefix(pattern);
+        let scanner = if self.pool.is_clustered() {
+            client.scan_cluster(pattern, count, None).boxed()
+        } else {
+            client.scan(pattern, count, None).boxed()
+        };
+
+        scanner.map(
+            move |page| -> CustomResult<Vec<String>, errors::RedisError> {
+                let page = page
+                    .change_context(errors::RedisError::ScanFailed)?
+                    .take_results()
+                    .unwrap_or_default();
+                Ok(page
+                    .iter()
+                    .filter_map(|key| {
+                        strip_prefix(
+                            &self.key_prefix,
+                            key.as_str()?,
+                            self.config.hash_tag_aware_key_prefix,
+                        )
+                        .map(ToString::to_string)
+                    })
+                    .collect())
+            },
+        )
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn get_hash_field<V>(
         &self,
@@ -458,6 +533,17 @@ impl super::RedisConnectionPool {
             .change_context(errors::RedisError::JsonDeserializationFailed)
     }
 
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn get_all_hash_fields(
+        &self,
+        key: &str,
+    ) -> CustomResult<HashMap<String, String>, errors::RedisError> {
+        self.pool
+            .hgetall(self.add_prefix(key))
+            .await
+            .change_context(errors::RedisError::GetHashFieldFailed)
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn sadd<V>(
         &self,
@@ -474,6 +560,50 @@ impl super::RedisConnectionPool {
             .change_context(errors::RedisError::SetAddMembersFailed)
     }
 
+    /// Prepends the element to the list, keeping at most `max_length` elements and setting the
+    /// expiry of the list, atomically
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn prepend_to_capped_list(
+        &self,
+        key: &str,
+        element: &str,
+        max_length: i64,
+        seconds: i64,
+    ) -> CustomResult<(), errors::RedisError> {
+        const SCRIPT: &str = r#"
+            redis.call('LPUSH', KEYS[1], ARGV[1])
+            redis.call('LTRIM', KEYS[1], 0, tonumber(ARGV[2]) - 1)
+            redis.call('EXPIRE', KEYS[1], ARGV[3])
+            return 1
+        "#;
+
+        self.pool
+            .eval::<(), _, _, _>(
+                SCRIPT,
+                self.add_prefix(key),
+                vec![
+                    element.to_string(),
+                    max_length.to_string(),
+                    seconds.to_string(),
+                ],
+            )
+            .await
+            .change_context(errors::RedisError::AppendElementsToListFailed)
+    }
+
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn get_list_elements(
+        &self,
+        key: &str,
+        start: i64,
+        stop: i64,
+    ) -> CustomResult<Vec<String>, errors::RedisError> {
+        self.pool
+            .lrange(self.add_prefix(key), start, stop)
+            .await
+            .change_context(errors::RedisError::GetListElementsFailed)
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn stream_append_entry<F>(
         &self,
@@ -546,6 +676,22 @@ impl super::RedisConnectionPool {
             .change_context(errors::RedisError::GetLengthFailed)
     }
 
+    /// Reads at most `count` entries of the stream with IDs from `start` to `end`, both
+    /// inclusive. The special IDs `-` and `+` are the first and the last IDs of the stream.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn stream_read_range(
+        &self,
+        stream: &str,
+        start: &str,
+        end: &str,
+        count: Option<u64>,
+    ) -> CustomResult<Vec<(String, HashMap<String, String>)>, errors::RedisError> {
+        self.pool
+            .xrange_values(self.add_prefix(stream), start, end, count)
+            .await
+            .change_context(errors::RedisError::StreamReadFailed)
+    }
+
     pub fn get_keys_with_prefix<K>(&self, keys: K) -> MultipleKeys
     where
         K: Into<MultipleKeys> + Debug + Send + Sync,
@@ -573,6 +719,8 @@ impl super::RedisConnectionPool {
         Ids: Into<MultipleIDs> + Debug + Send + Sync,
     {
         let strms = self.get_keys_with_prefix(streams);
+        self.ensure_same_hash_slot(strms.inner().iter().map(RedisKey::as_bytes))
+            .change_context(errors::RedisError::StreamReadFailed)?;
         self.pool
             .xread_map(
                 Some(read_count.unwrap_or(self.config.default_stream_read_count)),
@@ -602,25 +750,17 @@ impl super::RedisConnectionPool {
         K: Into<MultipleKeys> + Debug + Send + Sync,
         Ids: Into<MultipleIDs> + Debug + Send + Sync,
     {
+        let streams = self.get_keys_with_prefix(streams);
+        self.ensure_same_hash_slot(streams.inner().iter().map(RedisKey::as_bytes))
+            .change_context(errors::RedisError::StreamReadFailed)?;
+
         match group {
             Some((group_name, consumer_name)) => {
                 self.pool
-                    .xreadgroup_map(
-                        group_name,
-                        consumer_name,
-                        count,
-                        block,
-                        false,
-                        self.get_keys_with_prefix(streams),
-                        ids,
-                    )
-                    .await
-            }
-            None => {
-                self.pool
-                    .xread_map(count, block, self.get_keys_with_prefix(streams), ids)
+                    .xreadgroup_map(group_name, consumer_name, count, block, false, streams, ids)
                     .await
             }
+            None => self.pool.xread_map(count, block, streams, ids).await,
         }
         .map_err(|err| match err.kind() {
             RedisErrorKind::NotFound | RedisErrorKind::Parse => {
@@ -723,12 +863,124 @@ impl super::RedisConnectionPool {
     }
 }
 
+/// Finds the hash tag of a key, the part of the key the hash slot of the key is computed from in
+/// a Redis Cluster: the non-empty substring between the first `{` and the first `}` following it.
+fn find_hash_tag(key: &str) -> Option<&str> {
+    let (_, rest) = key.split_once('{')?;
+    let (hash_tag, _) = rest.split_once('}')?;
+    (!hash_tag.is_empty()).then_some(hash_tag)
+}
+
+fn add_prefix(prefix: &str, key: &str, hash_tag_aware: bool) -> String {
+    match (find_hash_tag(prefix), find_hash_tag(key)) {
+        _ if prefix.is_empty() => key.to_string(),
+        (Some(_), Some(hash_tag)) if hash_tag_aware => format!("{{{hash_tag}}}:{prefix}:{key}"),
+        _ => format!("{prefix}:{key}"),
+    }
+}
+
+/// Removes the key prefix added by [`add_prefix`] from the key, if the key has it
+fn strip_prefix<'a>(prefix: &str, prefixed_key: &'a str, hash_tag_aware: bool) -> Option<&'a str> {
+    if prefix.is_empty() {
+        return Some(prefixed_key);
+    }
+
+    let key = match find_hash_tag(prefixed_key) {
+        Some(hash_tag) if hash_tag_aware && find_hash_tag(prefix).is_some() => prefixed_key
+            .strip_prefix(&format!("{{{hash_tag}}}:{prefix}:"))
+            .filter(|key| find_hash_tag(key) == Some(hash_tag)),
+        _ => None,
+    };
+
+    key.or_else(|| prefixed_key.strip_prefix(&format!("{prefix}:")))
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::expect_used, clippy::unwrap_used)]
 
+    use super::{add_prefix, find_hash_tag, strip_prefix};
     use crate::{errors::RedisError, RedisConnectionPool, RedisEntryId, RedisSettings};
 
+    #[test]
+    fn test_add_prefix_keeps_key_layout_by_default() {
+        assert_eq!(
+            add_prefix("", "{shard_5}_stream", false),
+            "{shard_5}_stream"
+        );
+        assert_eq!(add_prefix("public", "key", false), "public:key");
+        assert_eq!(
+            add_prefix("{tenant}", "{shard_5}_stream", false),
+            "{tenant}:{shard_5}_stream"
+        );
+
+        assert_eq!(
+            strip_prefix("{tenant}", "{tenant}:{shard_5}_stream", false),
+            Some("{shard_5}_stream")
+        );
+        assert_eq!(strip_prefix("public", "other:key", false), None);
+    }
+
+    #[test]
+    fn test_add_prefix_keeps_hash_tag() {
+        assert_eq!(find_hash_tag("{shard_5}_drainer_stream"), Some("shard_5"));
+        assert_eq!(find_hash_tag("a{}b{c}"), None);
+        assert_eq!(find_hash_tag("key"), None);
+
+        assert_eq!(add_prefix("", "{shard_5}_stream", true), "{shard_5}_stream");
+        assert_eq!(add_prefix("public", "key", true), "public:key");
+        assert_eq!(
+            add_prefix("public", "{shard_5}_stream", true),
+            "public:{shard_5}_stream"
+        );
+        assert_eq!(
+            add_prefix("{tenant}", "{shard_5}_stream", true),
+            "{shard_5}:{tenant}:{shard_5}_stream"
+        );
+        assert_eq!(add_prefix("{tenant}", "key", true), "{tenant}:key");
+
+        for key in ["key", "{shard_5}_stream"] {
+            for prefix in ["", "public", "{tenant}"] {
+                assert_eq!(
+                    strip_prefix(prefix, &add_prefix(prefix, key, true), true),
+                    Some(key)
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_multiple_keys_if_not_exist_with_prefix() {
+        let is_success = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool")
+                    .clone("test_msetnx");
+                let _ = pool.delete_key("{user}:first").await;
+                let _ = pool.delete_key("{user}:second").await;
+
+                // Act
+                let result = pool
+                    .set_multiple_keys_if_not_exist(vec![
+                        ("{user}:first", "1".to_string()),
+                        ("{user}:second", "2".to_string()),
+                    ])
+                    .await;
+
+                // Assert setup
+                let value = pool.get_key::<String>("{user}:first").await;
+                result.is_ok_and(|reply| reply == crate::MsetnxReply::KeysSet)
+                    && value.is_ok_and(|value| value == "1")
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert!(is_success);
+    }
+
     #[tokio::test]
     async fn test_consumer_group_create() {
         let is_invalid_redis_entry_error = tokio::task::spawn_blocking(move || {
@@ -122,6 +122,21 @@ impl RedisConnectionPool {
         let mut config = fred::types::RedisConfig::from_url(&redis_connection_url)
             .change_context(errors::RedisError::RedisConnectionError)?;
 
+        // The master is discovered through the sentinels on every connection, so that the
+        // clients reconnect to the new master when the sentinels fail over, which they signal by
+        // disconnecting the clients of the former master
+        if conf.sentinel_enabled {
+            let sentinel_nodes = conf
+                .sentinel_nodes
+                .iter()
+                .map(|node| types::parse_node_address(node))
+                .collect::<Result<Vec<_>, _>>()?;
+            config.server = fred::types::ServerConfig::new_sentinel(
+                sentinel_nodes,
+                conf.sentinel_service_name.clone(),
+            );
+        }
+
         let perf = fred::types::PerformanceConfig {
             auto_pipeline: conf.auto_pipeline,
             default_command_timeout: std::time::Duration::from_secs(conf.default_command_timeout),
@@ -204,7 +219,9 @@ impl RedisConnectionPool {
         loop {
             if let Some(Ok(error)) = error_rx.next().await {
                 tracing::error!(?error, "Redis protocol or connection error");
-                if self.pool.state() == fred::types::ClientState::Disconnected {
+                if self.pool.state() == fred::types::ClientState::Disconnected
+                    && !self.wait_for_failover().await
+                {
                     if tx.send(()).is_err() {
                         tracing::error!("The redis shutdown signal sender failed to signal");
                     }
@@ -216,6 +233,30 @@ impl RedisConnectionPool {
         }
     }
 
+    /// Waits for the clients to reconnect to the master after a failover, returning whether they
+    /// did. The master is only failed over with Redis Sentinel, so this returns `false` right away
+    /// otherwise.
+    async fn wait_for_failover(&self) -> bool {
+        let Some(failover_timeout) = self.config.failover_timeout else {
+            return false;
+        };
+
+        match tokio::time::timeout(failover_timeout, self.pool.wait_for_connect()).await {
+            Ok(Ok(())) => {
+                tracing::info!("Reconnected to the Redis master discovered through the sentinels");
+                true
+            }
+            Ok(Err(error)) => {
+                tracing::error!(?error, "Failed to reconnect to the Redis master");
+                false
+            }
+            Err(_) => {
+                tracing::error!("Timed out waiting to reconnect to the Redis master");
+                false
+            }
+        }
+    }
+
     pub async fn on_unresponsive(&self) {
         let _ = self.pool.clients().iter().map(|client| {
             client.on_unresponsive(|server| {
@@ -230,6 +271,9 @@ pub struct RedisConfig {
     default_ttl: u32,
     default_stream_read_count: u64,
     default_hash_ttl: u32,
+    /// Time allowed to reconnect to the master after a failover, with Redis Sentinel
+    failover_timeout: Option<std::time::Duration>,
+    hash_tag_aware_key_prefix: bool,
 }
 
 impl From<&RedisSettings> for RedisConfig {
@@ -238,6 +282,14 @@ impl From<&RedisSettings> for RedisConfig {
             default_ttl: config.default_ttl,
             default_stream_read_count: config.stream_read_count,
             default_hash_ttl: config.default_hash_ttl,
+            // Every reconnection attempt can take up to the connection timeout, after the
+            // reconnection delay
+            failover_timeout: config.sentinel_enabled.then(|| {
+                (fred::types::ConnectionConfig::default().connection_timeout
+                    + std::time::Duration::from_millis(config.reconnect_delay.into()))
+                    * config.reconnect_max_attempts
+            }),
+            hash_tag_aware_key_prefix: config.hash_tag_aware_key_prefix,
         }
     }
 }
@@ -252,4 +304,64 @@ mod test {
 
         assert_eq!(x, "Failed to set Redis stream message owner".to_string())
     }
+
+    #[test]
+    fn test_sentinel_settings_validation() {
+        let settings = RedisSettings {
+            sentinel_enabled: true,
+            sentinel_nodes: vec!["127.0.0.1:26379".to_string()],
+            sentinel_service_name: "mymaster".to_string(),
+            ..RedisSettings::default()
+        };
+        assert!(settings.validate().is_ok());
+
+        for settings in [
+            RedisSettings {
+                sentinel_nodes: vec![],
+                ..settings.clone()
+            },
+            RedisSettings {
+                sentinel_service_name: String::new(),
+                ..settings.clone()
+            },
+            RedisSettings {
+                sentinel_nodes: vec!["127.0.0.1".to_string()],
+                ..settings.clone()
+            },
+            RedisSettings {
+                cluster_enabled: true,
+                cluster_urls: vec!["127.0.0.1:7000".to_string()],
+                ..settings
+            },
+        ] {
+            assert!(settings.validate().is_err());
+        }
+    }
+
+    /// Requires a master monitored as `mymaster` by a sentinel listening on port 26379, such as
+    /// started with `redis-server --port 6379` and `redis-sentinel sentinel.conf`, with the
+    /// configuration `sentinel monitor mymaster 127.0.0.1 6379 1`
+    #[tokio::test]
+    #[ignore = "requires a local redis sentinel"]
+    #[allow(clippy::expect_used)]
+    async fn test_sentinel_connection() {
+        let pool = RedisConnectionPool::new(&RedisSettings {
+            sentinel_enabled: true,
+            sentinel_nodes: vec!["127.0.0.1:26379".to_string()],
+            sentinel_service_name: "mymaster".to_string(),
+            ..RedisSettings::default()
+        })
+        .await
+        .expect("failed to connect through the sentinel");
+
+        pool.set_key("sentinel_key", "value".to_string())
+            .await
+            .expect("failed to set key on the master");
+        let value = pool
+            .get_key::<String>("sentinel_key")
+            .await
+            .expect("failed to get key from the master");
+
+        assert_eq!(value, "value");
+    }
 }
@@ -41,6 +41,17 @@ pub struct RedisSettings {
     pub port: u16,
     pub cluster_enabled: bool,
     pub cluster_urls: Vec<String>,
+    /// Whether the master is discovered through Redis Sentinel, rather than connected to at
+    /// `host` and `port`
+    pub sentinel_enabled: bool,
+    /// Addresses of the sentinels, in the `host:port` format
+    pub sentinel_nodes: Vec<String>,
+    /// Name the sentinels monitor the master under
+    pub sentinel_service_name: String,
+    /// Whether keys with a hash tag keep it as the hash tag of the key prefixed with a key prefix
+    /// that has a hash tag of its own, for multi-key commands on them to be possible with Redis
+    /// Cluster. Enabling it renames such keys, so existing ones have to be migrated.
+    pub hash_tag_aware_key_prefix: bool,
     pub use_legacy_version: bool,
     pub pool_size: usize,
     pub reconnect_max_attempts: u32,
@@ -76,6 +87,36 @@ impl RedisSettings {
             ))
         })?;
 
+        when(self.cluster_enabled && self.sentinel_enabled, || {
+            Err(errors::RedisError::InvalidConfiguration(
+                "Redis `cluster_enabled` and `sentinel_enabled` cannot both be `true`".into(),
+            ))
+        })?;
+
+        when(
+            self.sentinel_enabled && self.sentinel_nodes.is_empty(),
+            || {
+                Err(errors::RedisError::InvalidConfiguration(
+                    "Redis `sentinel_nodes` must be specified if `sentinel_enabled` is `true`"
+                        .into(),
+                ))
+            },
+        )?;
+
+        when(
+            self.sentinel_enabled && self.sentinel_service_name.is_default_or_empty(),
+            || {
+                Err(errors::RedisError::InvalidConfiguration(
+                    "Redis `sentinel_service_name` must be specified if `sentinel_enabled` is `true`"
+                        .into(),
+                ))
+            },
+        )?;
+
+        self.sentinel_nodes
+            .iter()
+            .try_for_each(|node| parse_node_address(node).map(|_| ()))?;
+
         when(
             self.default_command_timeout < self.unresponsive_timeout,
             || {
@@ -88,6 +129,19 @@ impl RedisSettings {
     }
 }
 
+/// Parses the address of a node in the `host:port` format
+pub(crate) fn parse_node_address(node: &str) -> CustomResult<(&str, u16), errors::RedisError> {
+    node.rsplit_once(':')
+        .and_then(|(host, port)| Some((host, port.parse().ok()?)))
+        .filter(|(host, _)| !host.is_empty())
+        .ok_or_else(|| {
+            errors::RedisError::InvalidConfiguration(format!(
+                "Redis node address `{node}` is not in the `host:port` format"
+            ))
+            .into()
+        })
+}
+
 impl Default for RedisSettings {
     fn default() -> Self {
         Self {
@@ -95,6 +149,10 @@ impl Default for RedisSettings {
             port: 6379,
             cluster_enabled: false,
             cluster_urls: vec![],
+            sentinel_enabled: false,
+            sentinel_nodes: vec![],
+            sentinel_service_name: String::new(),
+            hash_tag_aware_key_prefix: false,
             use_legacy_version: false,
             pool_size: 5,
             reconnect_max_attempts: 5,
@@ -1,4 +1,6 @@
 use error_stack::ResultExt;
+#[cfg(feature = "olap")]
+use router::configs::settings::Subcommand;
 use router::{
     configs::settings::{CmdLineConf, Settings},
     core::errors::{ApplicationError, ApplicationResult},
@@ -28,6 +30,21 @@ async fn main() -> ApplicationResult<()> {
     )
     .change_context(ApplicationError::ConfigurationError)?;
 
+    #[cfg(feature = "olap")]
+    match cmd_line.subcommand {
+        Some(Subcommand::ImportCardsInfo {
+            file,
+            provider,
+            tenant,
+        }) => {
+            return Ok(Box::pin(router::import_cards_info(conf, file, provider, tenant)).await?);
+        }
+        Some(Subcommand::Scheduler { tenant, command }) => {
+            return Ok(Box::pin(router::run_scheduler_command(conf, tenant, command)).await?);
+        }
+        _ => {}
+    }
+
     logger::info!("Application started [{:?}] [{:?}]", conf.server, conf.log);
 
     // Spawn a thread for collecting metrics at fixed intervals
@@ -837,6 +837,9 @@ pub enum StripeNextAction {
     InvokeSdkClient {
         next_action_data: payments::SdkNextActionData,
     },
+    DisplayDccOffer {
+        dcc_offer: common_utils::types::DccOffer,
+    },
 }
 
 pub(crate) fn into_stripe_next_action(
@@ -892,6 +895,9 @@ pub(crate) fn into_stripe_next_action(
         payments::NextActionData::InvokeSdkClient { next_action_data } => {
             StripeNextAction::InvokeSdkClient { next_action_data }
         }
+        payments::NextActionData::DisplayDccOffer { dcc_offer } => {
+            StripeNextAction::DisplayDccOffer { dcc_offer }
+        }
     })
 }
 
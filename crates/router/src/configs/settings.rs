@@ -55,6 +55,113 @@ pub enum Subcommand {
     #[cfg(feature = "openapi")]
     /// Generate the OpenAPI specification file from code.
     GenerateOpenapiSpec,
+
+    #[cfg(feature = "olap")]
+    /// Import card info of BINs and BIN ranges from a CSV file, instead of starting the server.
+    ImportCardsInfo {
+        /// CSV file with a header row, having the fields of the card info import records as columns.
+        #[arg(long, value_name = "FILE")]
+        file: PathBuf,
+
+        /// Provider of the card info, recorded as the last provider to have updated the records.
+        #[arg(long)]
+        provider: Option<String>,
+
+        /// Tenant to import the card info for.
+        #[arg(long, default_value = "public")]
+        tenant: String,
+    },
+
+    #[cfg(feature = "olap")]
+    /// Inspect and manage the tasks of the scheduler, instead of starting the server.
+    Scheduler {
+        /// Tenant to manage the tasks of.
+        #[arg(long, default_value = "public")]
+        tenant: String,
+
+        #[command(subcommand)]
+        command: SchedulerCommand,
+    },
+}
+
+#[cfg(feature = "olap")]
+#[derive(clap::Subcommand)]
+pub enum SchedulerCommand {
+    /// List the tasks matching all the specified filters, earliest scheduled first.
+    List {
+        /// Workflow the tasks are run by, such as `REFUND_WORKFLOW_ROUTER`.
+        #[arg(long)]
+        runner: Option<String>,
+
+        /// Status of the tasks, such as `pending` or `process_started`.
+        #[arg(long)]
+        status: Option<String>,
+
+        /// Merchant Account the tasks run for.
+        #[arg(long)]
+        merchant_id: Option<String>,
+
+        /// Earliest time the tasks are scheduled to run at, in ISO 8601 format.
+        #[arg(long, value_parser = parse_iso8601)]
+        scheduled_after: Option<time::PrimitiveDateTime>,
+
+        /// Latest time the tasks are scheduled to run at, in ISO 8601 format.
+        #[arg(long, value_parser = parse_iso8601)]
+        scheduled_before: Option<time::PrimitiveDateTime>,
+
+        #[arg(long)]
+        limit: Option<u16>,
+
+        #[arg(long)]
+        offset: Option<u16>,
+    },
+
+    /// Show a task along with its retries and the actions taken on it.
+    Show { task_id: String },
+
+    /// Run a task right away.
+    Run { task_id: String },
+
+    /// Schedule a task to run at the specified time.
+    Reschedule {
+        task_id: String,
+
+        /// Time to run the task at, in ISO 8601 format.
+        #[arg(value_parser = parse_iso8601)]
+        schedule_time: time::PrimitiveDateTime,
+    },
+
+    /// Cancel a task which is not running.
+    Cancel { task_id: String },
+
+    /// Hand the tasks picked by a consumer which never completed them over to the consumers
+    /// again.
+    ResetLimbo {
+        /// Workflow the tasks are run by, such as `REFUND_WORKFLOW_ROUTER`.
+        #[arg(long)]
+        runner: Option<String>,
+
+        /// Reset only the tasks picked before this time, in ISO 8601 format. An hour ago by
+        /// default.
+        #[arg(long, value_parser = parse_iso8601)]
+        started_before: Option<time::PrimitiveDateTime>,
+
+        #[arg(long)]
+        limit: Option<u16>,
+    },
+}
+
+#[cfg(feature = "olap")]
+fn parse_iso8601(value: &str) -> Result<time::PrimitiveDateTime, time::error::Parse> {
+    let date_time = time::OffsetDateTime::parse(
+        value,
+        &time::format_description::well_known::Iso8601::DEFAULT,
+    )?
+    .to_offset(time::UtcOffset::UTC);
+    Ok(time::PrimitiveDateTime::new(
+        date_time.date(),
+        date_time.time(),
+    ))
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -742,6 +849,7 @@ impl Settings<SecuredSecret> {
                     .list_separator(",")
                     .with_list_parse_key("log.telemetry.route_to_trace")
                     .with_list_parse_key("redis.cluster_urls")
+                    .with_list_parse_key("redis.sentinel_nodes")
                     .with_list_parse_key("events.kafka.brokers")
                     .with_list_parse_key("proxy.bypass_proxy_urls")
                     .with_list_parse_key("connectors.supported.wallets")
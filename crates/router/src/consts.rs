@@ -67,6 +67,16 @@ pub(crate) const METRICS_HOST_TAG_NAME: &str = "host";
 pub const MAX_ROUTING_CONFIGS_PER_MERCHANT: usize = 100;
 pub const ROUTING_CONFIG_ID_LENGTH: usize = 10;
 
+/// Default number of payments replayed in a routing simulation
+pub const ROUTING_SIMULATION_DEFAULT_LIMIT: u32 = 1000;
+/// Maximum number of payments replayed in a routing simulation
+pub const ROUTING_SIMULATION_MAX_LIMIT: u32 = 10000;
+/// Number of payments fetched at a time for a routing simulation
+pub const ROUTING_SIMULATION_PAGE_SIZE: u32 = 100;
+
+/// Time for which a constraint graph snapshot is retained in redis
+pub const CGRAPH_SNAPSHOT_TTL_IN_SECONDS: i64 = 30 * 60; // 30 minutes
+
 pub const LOCKER_REDIS_PREFIX: &str = "LOCKER_PM_TOKEN";
 pub const LOCKER_REDIS_EXPIRY_SECONDS: u32 = 60 * 15; // 15 minutes
 
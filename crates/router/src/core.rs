@@ -33,6 +33,10 @@ pub mod payout_link;
 pub mod payouts;
 pub mod pm_auth;
 pub mod poll;
+#[cfg(feature = "olap")]
+pub mod process_tracker;
+#[cfg(feature = "olap")]
+pub mod recurring_jobs;
 pub mod refunds;
 pub mod routing;
 pub mod surcharge_decision_config;
@@ -12,7 +12,10 @@ use common_utils::{
 };
 use diesel_models::configs;
 use error_stack::{report, FutureExt, ResultExt};
+use euclid::frontend::dir;
 use futures::future::try_join_all;
+use hyperswitch_constraint_graph as cgraph;
+use kgraph_utils::transformers::IntoDirValue;
 use masking::{PeekInterface, Secret};
 use pm_auth::{connector::plaid::transformers::PlaidAuthType, types as pm_auth_types};
 use regex::Regex;
@@ -22,10 +25,11 @@ use uuid::Uuid;
 use crate::{
     consts,
     core::{
+        api_locking,
         encryption::transfer_encryption_key,
         errors::{self, RouterResponse, RouterResult, StorageErrorExt},
         payment_methods::{cards, cards::create_encrypted_data, transformers},
-        payments::helpers,
+        payments::{self, helpers},
         pm_auth::helpers::PaymentAuthConnectorDataExt,
         routing::helpers as routing_helpers,
         utils as core_utils,
@@ -1418,20 +1422,63 @@ pub async fn create_payment_connector(
     )
     .await?;
 
-    let mca = state
-        .store
-        .insert_merchant_connector_account(
-            key_manager_state,
-            merchant_connector_account,
-            &key_store,
+    // The lock is held from loading the graph of the profile until the graph with the created
+    // account is stored, the same as for updates of the accounts of the profile
+    let cgraph_lock = payments::routing::get_cgraph_update_lock(&profile_id);
+    cgraph_lock
+        .clone()
+        .perform_locking_action(&state, merchant_id.to_string())
+        .await?;
+
+    let create_result = async {
+        let cached_cgraph = payments::routing::load_merchant_cgraph_snapshot(
+            &state,
+            merchant_id,
+            &profile_id,
+            &transaction_type,
         )
+        .await;
+
+        let mca = state
+            .store
+            .insert_merchant_connector_account(
+                key_manager_state,
+                merchant_connector_account,
+                &key_store,
+            )
+            .await
+            .to_duplicate_response(
+                errors::ApiErrorResponse::DuplicateMerchantConnectorAccount {
+                    profile_id: profile_id.clone(),
+                    connector_label,
+                },
+            )?;
+
+        // Adding the account to the cached graph keeps the graph from missing the account until
+        // it is rebuilt
+        if let Some(cgraph) = cached_cgraph {
+            payments::routing::update_cgraph_for_merchant_connector_account(
+                &state,
+                &cgraph,
+                mca.clone(),
+                &transaction_type,
+            )
+            .await
+            .map_err(|error| crate::logger::error!(?error, "Failed to update constraint graph"))
+            .ok();
+        }
+
+        Ok::<_, error_stack::Report<errors::ApiErrorResponse>>(mca)
+    }
+    .await;
+
+    cgraph_lock
+        .free_lock_action(&state, merchant_id.to_string())
         .await
-        .to_duplicate_response(
-            errors::ApiErrorResponse::DuplicateMerchantConnectorAccount {
-                profile_id: profile_id.clone(),
-                connector_label,
-            },
-        )?;
+        .map_err(|error| crate::logger::error!(?error, "Failed to free constraint graph lock"))
+        .ok();
+
+    let mca = create_result?;
 
     if let Some(routable_connector_val) = routable_connector {
         let choice = routing_types::RoutableConnectorChoice {
@@ -1598,6 +1645,87 @@ pub async fn list_payment_connectors(
     Ok(service_api::ApplicationResponse::Json(response))
 }
 
+pub async fn get_merchant_connector_graph(
+    state: SessionState,
+    merchant_id: String,
+    query: admin_types::MerchantConnectorGraphQuery,
+) -> RouterResponse<admin_types::MerchantConnectorGraphResponse> {
+    let store = state.store.as_ref();
+    let key_manager_state = &(&state).into();
+    let key_store = store
+        .get_merchant_key_store_by_merchant_id(
+            key_manager_state,
+            &merchant_id,
+            &store.get_master_key().to_vec().into(),
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    // Validate merchant account
+    store
+        .find_merchant_account_by_merchant_id(key_manager_state, &merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let graph = payments::routing::build_merchant_cgraph(
+        &state,
+        &key_store,
+        query.profile_id,
+        &query.transaction_type.unwrap_or_default(),
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to build the knowledge graph of the merchant connector accounts")?;
+
+    let mut focus = Vec::new();
+    if let Some(connector) = query.connector {
+        focus.push(dir::DirValue::Connector(Box::new(
+            routing_types::ast::ConnectorChoice { connector },
+        )));
+    }
+    if let Some(payment_method_type) = query.payment_method_type {
+        focus.push(
+            (
+                payment_method_type,
+                api_enums::PaymentMethod::foreign_from(payment_method_type),
+            )
+                .into_dir_value()
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to convert the payment method type to a graph value")?,
+        );
+    }
+
+    let response = if focus.is_empty() {
+        admin_types::MerchantConnectorGraphResponse {
+            dot: graph.get_dot_digraph(),
+            mermaid: graph.get_mermaid_flowchart(),
+            node_count: graph.nodes.iter().count(),
+        }
+    } else {
+        // A value missing from the graph is not configured on any connector, so nothing in the
+        // graph is relevant to it
+        let nodes = focus
+            .into_iter()
+            .map(|value| {
+                graph
+                    .value_map
+                    .get(&cgraph::NodeValue::Value(value))
+                    .map(|node_id| graph.get_relevant_nodes(&[*node_id]))
+                    .unwrap_or_default()
+            })
+            .reduce(|relevant, other| relevant.intersection(&other).copied().collect())
+            .unwrap_or_default();
+
+        admin_types::MerchantConnectorGraphResponse {
+            dot: graph.get_dot_subgraph_digraph(&nodes),
+            mermaid: graph.get_mermaid_subgraph_flowchart(&nodes),
+            node_count: nodes.len(),
+        }
+    };
+
+    Ok(service_api::ApplicationResponse::Json(response))
+}
+
 pub async fn update_payment_connector(
     state: SessionState,
     merchant_id: &str,
@@ -1726,25 +1854,71 @@ pub async fn update_payment_connector(
 
     let request_connector_label = req.connector_label;
 
-    let updated_mca = db
-        .update_merchant_connector_account(
-            key_manager_state,
-            mca,
-            payment_connector.into(),
-            &key_store,
+    // The lock is held from loading the graph, which the update invalidates, until the updated
+    // graph is stored, so that concurrent changes to the accounts of the profile do not patch a
+    // graph missing the other changes
+    let cgraph_lock = payments::routing::get_cgraph_update_lock(&profile_id);
+    cgraph_lock
+        .clone()
+        .perform_locking_action(&state, merchant_id.to_string())
+        .await?;
+
+    let update_result = async {
+        let cached_cgraph = payments::routing::load_merchant_cgraph_snapshot(
+            &state,
+            merchant_id,
+            &profile_id,
+            &api_enums::TransactionType::Payment,
         )
+        .await;
+
+        let updated_mca = db
+            .update_merchant_connector_account(
+                key_manager_state,
+                mca,
+                payment_connector.into(),
+                &key_store,
+            )
+            .await
+            .change_context(
+                errors::ApiErrorResponse::DuplicateMerchantConnectorAccount {
+                    profile_id: profile_id.clone(),
+                    connector_label: request_connector_label.unwrap_or_default(),
+                },
+            )
+            .attach_printable_lazy(|| {
+                format!(
+                    "Failed while updating MerchantConnectorAccount: id: {merchant_connector_id}"
+                )
+            })?;
+
+        // Updating the graph spares large merchants a full rebuild of the graph on the next
+        // payment. The invalidation published by the update is handled asynchronously by the
+        // other instances and may still evict the updated graph from their memory, in which case
+        // they load it from its snapshot.
+        if let Some(cgraph) = cached_cgraph {
+            payments::routing::update_cgraph_for_merchant_connector_account(
+                &state,
+                &cgraph,
+                updated_mca.clone(),
+                &api_enums::TransactionType::Payment,
+            )
+            .await
+            .map_err(|error| crate::logger::error!(?error, "Failed to update constraint graph"))
+            .ok();
+        }
+
+        Ok::<_, error_stack::Report<errors::ApiErrorResponse>>(updated_mca)
+    }
+    .await;
+
+    cgraph_lock
+        .free_lock_action(&state, merchant_id.to_string())
         .await
-        .change_context(
-            errors::ApiErrorResponse::DuplicateMerchantConnectorAccount {
-                profile_id,
-                connector_label: request_connector_label.unwrap_or_default(),
-            },
-        )
-        .attach_printable_lazy(|| {
-            format!("Failed while updating MerchantConnectorAccount: id: {merchant_connector_id}")
-        })?;
+        .map_err(|error| crate::logger::error!(?error, "Failed to free constraint graph lock"))
+        .ok();
 
-    let response = updated_mca.try_into()?;
+    let response = update_result?.try_into()?;
 
     Ok(service_api::ApplicationResponse::Json(response))
 }
@@ -1770,7 +1944,7 @@ pub async fn delete_payment_connector(
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
-    let _mca = db
+    let mca = db
         .find_by_merchant_connector_account_merchant_id_merchant_connector_id(
             key_manager_state,
             &merchant_id,
@@ -1782,15 +1956,36 @@ pub async fn delete_payment_connector(
             id: merchant_connector_id.clone(),
         })?;
 
-    let is_deleted = db
+    // The deletion invalidates the graph of the profile, so it must not happen while an update is
+    // patching the graph
+    let cgraph_lock = mca
+        .profile_id
+        .as_deref()
+        .map(payments::routing::get_cgraph_update_lock)
+        .unwrap_or(api_locking::LockAction::NotApplicable);
+    cgraph_lock
+        .clone()
+        .perform_locking_action(&state, merchant_id.clone())
+        .await?;
+
+    let delete_result = db
         .delete_merchant_connector_account_by_merchant_id_merchant_connector_id(
             &merchant_id,
             &merchant_connector_id,
         )
+        .await;
+
+    cgraph_lock
+        .free_lock_action(&state, merchant_id.clone())
         .await
-        .to_not_found_response(errors::ApiErrorResponse::MerchantConnectorAccountNotFound {
+        .map_err(|error| crate::logger::error!(?error, "Failed to free constraint graph lock"))
+        .ok();
+
+    let is_deleted = delete_result.to_not_found_response(
+        errors::ApiErrorResponse::MerchantConnectorAccountNotFound {
             id: merchant_connector_id.clone(),
-        })?;
+        },
+    )?;
 
     let response = api::MerchantConnectorDeleteResponse {
         merchant_id,
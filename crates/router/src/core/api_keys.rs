@@ -240,6 +240,7 @@ pub async fn add_api_key_expiry_task(
         [API_KEY_EXPIRY_TAG],
         api_key_expiry_tracker,
         schedule_time,
+        Some(api_key.merchant_id.clone()),
     )
     .change_context(errors::ApiErrorResponse::InternalServerError)
     .attach_printable("Failed to construct API key expiry process tracker task")?;
@@ -1,10 +1,21 @@
+use std::{borrow::Cow, time::Duration};
+
+use api_models::cache as api;
 use common_utils::errors::CustomResult;
 use error_stack::{report, ResultExt};
-use storage_impl::redis::cache::{publish_into_redact_channel, CacheKind};
+use storage_impl::redis::cache::{
+    self as in_memory_cache, publish_into_redact_channel, publish_into_redact_channel_with_ack,
+    CacheKey, CacheKind,
+};
 
-use super::errors;
+use super::errors::{self, RouterResponse, RouterResult};
 use crate::{routes::SessionState, services};
 
+const CACHE_KEYS_LIST_MAX_LIMIT: u16 = 100;
+
+/// How long to wait for the application instances to acknowledge the invalidation of a key
+const CACHE_INVALIDATION_ACK_TIMEOUT: Duration = Duration::from_secs(5);
+
 pub async fn invalidate(
     state: SessionState,
     key: &str,
@@ -26,3 +37,106 @@ pub async fn invalidate(
             .attach_printable("Failed to invalidate cache"))
     }
 }
+
+pub async fn statistics() -> RouterResponse<api::CacheStatisticsResponse> {
+    let mut caches = Vec::new();
+    for (kind, cache) in in_memory_cache::caches() {
+        let statistics = cache.statistics().await;
+        caches.push(api::CacheStatistics {
+            cache: kind.to_owned(),
+            name: statistics.name.to_owned(),
+            entry_count: statistics.entry_count,
+            weighted_size: statistics.weighted_size,
+            max_capacity: statistics.max_capacity,
+            time_to_live_in_seconds: statistics.time_to_live.map(|ttl| ttl.as_secs()),
+            time_to_idle_in_seconds: statistics.time_to_idle.map(|tti| tti.as_secs()),
+            hits: statistics.hits,
+            misses: statistics.misses,
+            evictions: statistics.evictions,
+            invalidations: statistics.invalidations,
+        });
+    }
+
+    Ok(services::api::ApplicationResponse::Json(
+        api::CacheStatisticsResponse { caches },
+    ))
+}
+
+pub async fn list_keys(
+    state: SessionState,
+    cache: String,
+    constraints: api::CacheKeysListConstraints,
+) -> RouterResponse<api::CacheKeysListResponse> {
+    let limit = constraints.limit.unwrap_or(CACHE_KEYS_LIST_MAX_LIMIT);
+    common_utils::fp_utils::when(limit > CACHE_KEYS_LIST_MAX_LIMIT, || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "`limit` must be a number less than or equal to {CACHE_KEYS_LIST_MAX_LIMIT}"
+            ),
+        }))
+    })?;
+
+    let keys = find_cache(&cache)?.keys(
+        CacheKey {
+            key: constraints.prefix.unwrap_or_default(),
+            prefix: state.tenant.redis_key_prefix.clone(),
+        },
+        usize::from(limit),
+    );
+
+    Ok(services::api::ApplicationResponse::Json(
+        api::CacheKeysListResponse {
+            cache,
+            count: keys.len(),
+            keys,
+        },
+    ))
+}
+
+/// Invalidates the key of the cache in all the application instances, and reports which of them
+/// acknowledged the invalidation.
+pub async fn invalidate_key(
+    state: SessionState,
+    cache: String,
+    key: String,
+) -> RouterResponse<api::CacheKeyInvalidateResponse> {
+    // Keys are invalidated in all the caches at once by the invalidate API instead
+    find_cache(&cache)?;
+    let cache_kind = CacheKind::new(&cache, Cow::Borrowed(key.as_str()))
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to build the cache kind of the key")?;
+
+    let report = publish_into_redact_channel_with_ack(
+        state.store.get_cache_store().as_ref(),
+        cache_kind,
+        CACHE_INVALIDATION_ACK_TIMEOUT,
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to invalidate cache key")?;
+
+    Ok(services::api::ApplicationResponse::Json(
+        api::CacheKeyInvalidateResponse {
+            propagated: report.acknowledgements.len() >= report.receivers,
+            receivers: report.receivers,
+            acknowledged_by: report
+                .acknowledgements
+                .into_iter()
+                .map(|acknowledgement| acknowledgement.subscriber_id)
+                .collect(),
+            cache,
+            key,
+        },
+    ))
+}
+
+fn find_cache(cache: &str) -> RouterResult<&'static in_memory_cache::Cache> {
+    in_memory_cache::caches()
+        .into_iter()
+        .find_map(|(kind, in_memory_cache)| (kind == cache).then_some(in_memory_cache))
+        .ok_or_else(|| {
+            report!(errors::ApiErrorResponse::GenericNotFoundError {
+                message: format!("Cache `{cache}` does not exist"),
+            })
+        })
+}
@@ -1,4 +1,9 @@
-use common_utils::fp_utils::when;
+use std::collections::HashSet;
+
+use actix_multipart::form::{bytes::Bytes, text::Text, MultipartForm};
+use api_models::cards_info as cards_info_api;
+use common_utils::{date_time, fp_utils::when};
+use csv::Reader;
 use error_stack::{report, ResultExt};
 use router_env::{instrument, tracing};
 
@@ -7,9 +12,10 @@ use crate::{
         errors::{self, RouterResponse},
         payments::helpers,
     },
+    db::cards_info::CardInfoRangeIndex,
     routes,
     services::ApplicationResponse,
-    types::{domain, transformers::ForeignFrom},
+    types::{domain, storage, transformers::ForeignFrom},
 };
 
 fn verify_iin_length(card_iin: &str) -> Result<(), errors::ApiErrorResponse> {
@@ -44,7 +50,163 @@ pub async fn retrieve_card_info(
         .attach_printable("Failed to retrieve card information")?
         .ok_or(report!(errors::ApiErrorResponse::InvalidCardIin))?;
 
+    // The card info can be that of a range of BINs, the BIN that was looked up is returned instead
+    let mut response = api_models::cards_info::CardInfoResponse::foreign_from(card_info);
+    response.card_iin = request.card_iin;
+
+    Ok(ApplicationResponse::Json(response))
+}
+
+#[derive(Debug, MultipartForm)]
+pub struct CardsInfoImportForm {
+    #[multipart(limit = "10MB")]
+    pub file: Bytes,
+    pub provider: Option<Text<String>>,
+}
+
+pub fn parse_card_info_csv(data: &[u8]) -> csv::Result<Vec<cards_info_api::CardInfoRecord>> {
+    Reader::from_reader(data).deserialize().collect()
+}
+
+pub fn get_card_info_import_request(
+    form: CardsInfoImportForm,
+) -> Result<cards_info_api::CardsInfoImportRequest, errors::ApiErrorResponse> {
+    let records = parse_card_info_csv(&form.file.data).map_err(|error| {
+        errors::ApiErrorResponse::PreconditionFailed {
+            message: error.to_string(),
+        }
+    })?;
+
+    Ok(cards_info_api::CardsInfoImportRequest {
+        provider: form.provider.map(Text::into_inner),
+        records,
+    })
+}
+
+fn validate_card_info_record(
+    record: &cards_info_api::CardInfoRecord,
+) -> Result<(), errors::ApiErrorResponse> {
+    let is_numeric = |bin: &str| bin.chars().all(|digit| digit.is_ascii_digit());
+
+    verify_iin_length(&record.card_iin)?;
+    when(!is_numeric(&record.card_iin), || {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!("card_iin {} is not numeric", record.card_iin),
+        })
+    })?;
+
+    if let Some(card_iin_range_end) = &record.card_iin_range_end {
+        let is_valid_range_end = card_iin_range_end.len() == record.card_iin.len()
+            && is_numeric(card_iin_range_end)
+            && card_iin_range_end.as_str() >= record.card_iin.as_str();
+        when(!is_valid_range_end, || {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "card_iin_range_end {card_iin_range_end} is not a BIN of the same length as, \
+                    and not lower than, card_iin {}",
+                    record.card_iin
+                ),
+            })
+        })?;
+    }
+
+    Ok(())
+}
+
+fn describe_range(card_info: &storage::CardInfo) -> String {
+    match &card_info.card_iin_range_end {
+        Some(card_iin_range_end) => format!("{}-{card_iin_range_end}", card_info.card_iin),
+        None => card_info.card_iin.clone(),
+    }
+}
+
+/// Imports card info in bulk, inserting the card info of new BINs and replacing the card info of
+/// existing ones. Every record is validated, and the BIN ranges checked not to overlap the ranges
+/// of the same length already in the table, before all of them are written in one transaction and
+/// the cached range index card info is looked up in is invalidated.
+#[instrument(skip_all)]
+pub async fn import_card_info(
+    state: routes::SessionState,
+    request: cards_info_api::CardsInfoImportRequest,
+) -> RouterResponse<cards_info_api::CardsInfoImportResponse> {
+    let db = state.store.as_ref();
+
+    when(request.records.is_empty(), || {
+        Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: "No records found".to_string(),
+        })
+    })?;
+
+    let mut imported_iins = HashSet::new();
+    for (index, record) in request.records.iter().enumerate() {
+        validate_card_info_record(record)
+            .attach_printable_lazy(|| format!("Invalid card info record at index {index}"))?;
+        when(!imported_iins.insert(record.card_iin.clone()), || {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!("card_iin {} is imported more than once", record.card_iin),
+            })
+        })?;
+    }
+
+    let existing_card_info = db
+        .list_card_info()
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to list card info")?;
+    let existing_iins = existing_card_info
+        .iter()
+        .map(|card_info| card_info.card_iin.clone())
+        .collect::<HashSet<_>>();
+
+    let now = date_time::now();
+    let imported_card_info = request
+        .records
+        .into_iter()
+        .map(|record| storage::CardInfo {
+            card_iin: record.card_iin,
+            card_issuer: record.card_issuer,
+            card_network: record.card_network,
+            card_type: record.card_type,
+            card_subtype: record.card_subtype,
+            card_issuing_country: record.card_issuing_country,
+            bank_code_id: record.bank_code_id,
+            bank_code: record.bank_code,
+            country_code: record.country_code,
+            date_created: now,
+            last_updated: Some(now),
+            last_updated_provider: request.provider.clone(),
+            card_iin_range_end: record.card_iin_range_end,
+        })
+        .collect::<Vec<_>>();
+
+    let range_index = CardInfoRangeIndex::new(
+        existing_card_info
+            .into_iter()
+            .filter(|card_info| !imported_iins.contains(&card_info.card_iin))
+            .chain(imported_card_info.iter().cloned()),
+    );
+    if let Some((first, second)) = range_index.find_overlapping_ranges() {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "BIN range {} overlaps BIN range {}",
+                describe_range(first),
+                describe_range(second)
+            ),
+        }))?
+    }
+
+    let updated = imported_card_info
+        .iter()
+        .filter(|card_info| existing_iins.contains(&card_info.card_iin))
+        .count();
+    let inserted = imported_card_info.len() - updated;
+
+    db.upsert_card_info(imported_card_info)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to upsert card info")?;
+
     Ok(ApplicationResponse::Json(
-        api_models::cards_info::CardInfoResponse::foreign_from(card_info),
+        cards_info_api::CardsInfoImportResponse { inserted, updated },
     ))
 }
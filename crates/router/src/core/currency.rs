@@ -31,6 +31,7 @@ pub async fn convert_forex(
     amount: i64,
     to_currency: String,
     from_currency: String,
+    as_of: Option<time::PrimitiveDateTime>,
 ) -> CustomResult<
     ApplicationResponse<api_models::currency::CurrencyConversionResponse>,
     ApiErrorResponse,
@@ -41,8 +42,16 @@ pub async fn convert_forex(
             amount,
             to_currency,
             from_currency,
+            as_of,
         ))
         .await
-        .change_context(ApiErrorResponse::InternalServerError)?,
+        .map_err(|error| match error.current_context() {
+            currency::ForexCacheError::HistoryEntryNotFound => {
+                error.change_context(ApiErrorResponse::GenericNotFoundError {
+                    message: "Exchange rates not found for the given time".to_string(),
+                })
+            }
+            _ => error.change_context(ApiErrorResponse::InternalServerError),
+        })?,
     ))
 }
@@ -308,6 +308,18 @@ pub enum RoutingError {
     VolumeSplitFailed,
     #[error("Unable to parse metadata")]
     MetadataParsingError,
+    #[error("Failed to update the success rate window of the connector")]
+    SuccessRateWindowUpdateFailed,
+}
+
+impl storage_impl::redis::cache::CacheLoadError for RoutingError {
+    fn copy_for_waiter(&self) -> Self {
+        self.clone()
+    }
+
+    fn type_mismatch() -> Self {
+        Self::CacheMiss
+    }
 }
 
 #[derive(Debug, Clone, thiserror::Error)]
@@ -62,6 +62,12 @@ counter_metric!(ROUTING_LINK_CONFIG, GLOBAL_METER);
 counter_metric!(ROUTING_LINK_CONFIG_SUCCESS_RESPONSE, GLOBAL_METER);
 counter_metric!(ROUTING_RETRIEVE_CONFIG, GLOBAL_METER);
 counter_metric!(ROUTING_RETRIEVE_CONFIG_SUCCESS_RESPONSE, GLOBAL_METER);
+counter_metric!(ROUTING_RETRIEVE_CONFIG_DSL, GLOBAL_METER);
+counter_metric!(ROUTING_RETRIEVE_CONFIG_DSL_SUCCESS_RESPONSE, GLOBAL_METER);
+counter_metric!(ROUTING_SIMULATE_CONFIG, GLOBAL_METER);
+counter_metric!(ROUTING_SIMULATE_CONFIG_SUCCESS_RESPONSE, GLOBAL_METER);
+counter_metric!(ROUTING_ANALYZE_ELIGIBILITY, GLOBAL_METER);
+counter_metric!(ROUTING_ANALYZE_ELIGIBILITY_SUCCESS_RESPONSE, GLOBAL_METER);
 counter_metric!(ROUTING_RETRIEVE_DEFAULT_CONFIG, GLOBAL_METER);
 counter_metric!(
     ROUTING_RETRIEVE_DEFAULT_CONFIG_SUCCESS_RESPONSE,
@@ -83,6 +89,7 @@ counter_metric!(
     ROUTING_RETRIEVE_CONFIG_FOR_PROFILE_SUCCESS_RESPONSE,
     GLOBAL_METER
 );
+counter_metric!(ROUTING_SUCCESS_RATE_WINDOW_CACHE_MISS, GLOBAL_METER); // No. of outcomes recorded after loading the algorithm of the profile, missing from the routing cache
 
 counter_metric!(API_KEY_REQUEST_INITIATED, GLOBAL_METER);
 counter_metric!(API_KEY_REQUEST_COMPLETED, GLOBAL_METER);
@@ -409,6 +409,7 @@ pub async fn add_payment_method_status_update_task(
         tag,
         tracking_data,
         schedule_time,
+        Some(merchant_id.to_string()),
     )
     .change_context(errors::ApiErrorResponse::InternalServerError)
     .attach_printable("Failed to construct PAYMENT_METHOD_STATUS_UPDATE process tracker task")?;
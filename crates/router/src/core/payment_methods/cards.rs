@@ -17,6 +17,7 @@ use api_models::{
     },
     payments::BankCodeResponse,
     pm_auth::PaymentMethodAuthConfig,
+    routing::ConnectorIneligibility,
     surcharge_decision_configs as api_surcharge_decision_configs,
 };
 use common_enums::enums::MerchantStorageScheme;
@@ -39,7 +40,10 @@ use euclid::{
     frontend::dir,
 };
 use hyperswitch_constraint_graph as cgraph;
-use kgraph_utils::transformers::IntoDirValue;
+use kgraph_utils::{
+    explanation::explain_graph_error, transformers::IntoDirValue,
+    types::MerchantConnectorNodeMetadata,
+};
 use masking::Secret;
 use router_env::{instrument, metrics::add_attributes, tracing};
 use strum::IntoEnumIterator;
@@ -777,10 +781,10 @@ pub async fn add_payment_method_data(
                         pm_resp.payment_method_id.clone_from(&pm_id);
                         pm_resp.client_secret = Some(client_secret.clone());
 
-                        let card_isin = card.card_number.get_card_isin();
+                        let card_bin = card.card_number.get_extended_card_bin();
 
                         let card_info = db
-                            .get_card_info(card_isin.as_str())
+                            .get_card_info(card_bin.as_str())
                             .await
                             .change_context(errors::ApiErrorResponse::InternalServerError)
                             .attach_printable("Failed to get card info")?;
@@ -2356,6 +2360,10 @@ pub async fn list_payment_methods(
     logger::debug!(mca_before_filtering=?filtered_mcas);
 
     let mut response: Vec<ResponsePaymentMethodIntermediate> = vec![];
+    let mut ineligible_connectors = req
+        .explain_ineligibility
+        .unwrap_or(false)
+        .then(Vec::<ConnectorIneligibility>::new);
     // Key creation for storing PM_FILTER_CGRAPH
     #[cfg(feature = "business_profile_routing")]
     let key = {
@@ -2392,6 +2400,7 @@ pub async fn list_payment_methods(
                 billing_address.as_ref(),
                 mca.connector_name.clone(),
                 &state.conf.saved_payment_methods,
+                ineligible_connectors.as_mut(),
             )
             .await?;
         }
@@ -2447,6 +2456,7 @@ pub async fn list_payment_methods(
                 billing_address.as_ref(),
                 mca.connector_name.clone(),
                 &state.conf.saved_payment_methods,
+                ineligible_connectors.as_mut(),
             )
             .await?;
         }
@@ -3232,6 +3242,7 @@ pub async fn list_payment_methods(
             request_external_three_ds_authentication,
             collect_shipping_details_from_wallets,
             collect_billing_details_from_wallets,
+            ineligible_connectors,
         },
     ))
 }
@@ -3380,6 +3391,7 @@ pub async fn filter_payment_methods(
     address: Option<&domain::Address>,
     connector: String,
     saved_payment_methods: &settings::EligiblePaymentMethods,
+    mut ineligible_connectors: Option<&mut Vec<ConnectorIneligibility>>,
 ) -> errors::CustomResult<(), errors::ApiErrorResponse> {
     for payment_method in payment_methods.iter() {
         let parse_result = serde_json::from_value::<PaymentMethodsEnabled>(payment_method.clone());
@@ -3520,10 +3532,11 @@ pub async fn filter_payment_methods(
                     logger::info!("Context created for List Payment method is {:?}", context);
 
                     let domain_ident: &[String] = &[mca_id.clone()];
+                    let mut memo = cgraph::Memoization::new();
                     let result = graph.key_value_analysis(
                         pm_dir_value.clone(),
                         &context,
-                        &mut cgraph::Memoization::new(),
+                        &mut memo,
                         &mut cgraph::CycleCheck::new(),
                         Some(domain_ident),
                     );
@@ -3533,6 +3546,21 @@ pub async fn filter_payment_methods(
                             for list payment methods {:?}",
                             e
                         );
+
+                        // The analysis trace is only alive as long as the memoization
+                        if let Some(ineligible_connectors) = ineligible_connectors.as_mut() {
+                            let reasons = explain_graph_error(
+                                e,
+                                MerchantConnectorNodeMetadata {
+                                    merchant_connector_id: Some(mca_id.clone()),
+                                    payment_method: Some(payment_method),
+                                    payment_method_type: Some(
+                                        payment_method_object.payment_method_type,
+                                    ),
+                                },
+                            );
+                            add_ineligibility_reasons(ineligible_connectors, &connector, reasons);
+                        }
                     } else if filter_pm_based_on_allowed_types
                         && filter_pm_card_network_based
                         && saved_payment_methods_filter
@@ -3554,6 +3582,23 @@ pub async fn filter_payment_methods(
     Ok(())
 }
 
+fn add_ineligibility_reasons(
+    ineligible_connectors: &mut Vec<ConnectorIneligibility>,
+    connector: &str,
+    reasons: Vec<api_models::routing::IneligibilityReason>,
+) {
+    match ineligible_connectors
+        .iter_mut()
+        .find(|ineligibility| ineligibility.connector == connector)
+    {
+        Some(ineligibility) => ineligibility.reasons.extend(reasons),
+        None => ineligible_connectors.push(ConnectorIneligibility {
+            connector: connector.to_string(),
+            reasons,
+        }),
+    }
+}
+
 fn filter_amount_based(
     payment_method: &RequestPaymentMethodTypes,
     amount: Option<MinorUnit>,
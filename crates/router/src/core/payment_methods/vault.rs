@@ -1141,6 +1141,7 @@ pub async fn add_delete_tokenized_data_task(
         tag,
         tracking_data,
         schedule_time,
+        None,
     )
     .change_context(errors::ApiErrorResponse::InternalServerError)
     .attach_printable("Failed to construct delete tokenized data process tracker task")?;
@@ -1248,3 +1249,57 @@ pub async fn retry_delete_tokenize(
 }
 
 // Fallback logic of old temp locker needs to be removed later
+
+#[cfg(all(test, feature = "masking_audit"))]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use std::str::FromStr;
+
+    use masking::{audit::RecordingSink, Secret, StrongSecret};
+
+    use super::*;
+    use crate::core::payment_methods::transformers;
+
+    type CardNumberSecret = StrongSecret<String, cards::CardNumberStrategy>;
+
+    #[test]
+    fn test_card_number_is_only_exposed_by_the_vault() {
+        let card = api::Card {
+            card_number: cards::CardNumber::from_str("4242424242424242").unwrap(),
+            card_exp_month: Secret::new("10".to_string()),
+            card_exp_year: Secret::new("2030".to_string()),
+            card_holder_name: Some(Secret::new("John Doe".to_string())),
+            card_cvc: Secret::new("123".to_string()),
+            card_issuer: None,
+            card_network: None,
+            card_type: None,
+            card_issuing_country: None,
+            bank_code: None,
+            nick_name: None,
+        };
+
+        let sink = RecordingSink::new();
+        let guard = sink.install();
+
+        let value1 = card.get_value1(None).unwrap();
+        let value2 = card.get_value2(None).unwrap();
+        let (vaulted_card, _) = api::Card::from_values(value1, value2).unwrap();
+        transformers::mk_card_value1(
+            vaulted_card.card_number.clone(),
+            "2030".to_string(),
+            "10".to_string(),
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        drop(guard);
+
+        assert_eq!(sink.exposures_of::<CardNumberSecret>().len(), 2);
+        sink.assert_not_exposed_outside::<CardNumberSecret>(&[
+            "crates/router/src/core/payment_methods/vault.rs",
+            "crates/router/src/core/payment_methods/transformers.rs",
+        ]);
+    }
+}
@@ -2,6 +2,7 @@ pub mod access_token;
 pub mod conditional_configs;
 pub mod connector_integration_v2_impls;
 pub mod customers;
+pub mod dcc;
 pub mod flows;
 pub mod helpers;
 pub mod operations;
@@ -239,15 +240,23 @@ where
 
         operation
             .to_domain()?
-            .call_external_three_ds_authentication_if_eligible(
-                state,
-                &mut payment_data,
-                &mut should_continue_transaction,
-                &connector_details,
-                &business_profile,
-                &key_store,
-            )
+            .call_dcc_if_eligible(state, &mut payment_data, &mut should_continue_transaction)
             .await?;
+
+        // The customer is authenticated for the amount in the currency they decided to pay in
+        if should_continue_transaction {
+            operation
+                .to_domain()?
+                .call_external_three_ds_authentication_if_eligible(
+                    state,
+                    &mut payment_data,
+                    &mut should_continue_transaction,
+                    &connector_details,
+                    &business_profile,
+                    &key_store,
+                )
+                .await?;
+        }
         if should_continue_transaction {
             #[cfg(feature = "frm")]
             match (
@@ -1055,6 +1064,7 @@ impl PaymentRedirectFlow for PaymentRedirectCompleteAuthorize {
                         api_models::payments::NextActionData::WaitScreenInformation{..} => None,
                         api_models::payments::NextActionData::ThreeDsInvoke{..} => None,
                         api_models::payments::NextActionData::InvokeSdkClient{..} => None,
+                        api_models::payments::NextActionData::DisplayDccOffer{..} => None,
                     })
                     .ok_or(errors::ApiErrorResponse::InternalServerError)
 
@@ -3148,6 +3158,7 @@ pub async fn add_process_sync_task(
         tag,
         tracking_data,
         schedule_time,
+        Some(payment_attempt.merchant_id.clone()),
     )
     .map_err(errors::StorageError::from)?;
 
@@ -0,0 +1,194 @@
+//! Dynamic currency conversion
+//!
+//! Customers paying with a card issued in a currency other than the currency of the payment are
+//! offered to pay in the currency of their card, at the current exchange rates with the markup of
+//! the merchant. The payment awaits their decision before it is authorized, and is authorized in
+//! the currency they decided on.
+
+use std::collections::HashMap;
+
+use api_models::payments::{Card, PaymentMethodData};
+use common_enums::Currency;
+use common_utils::{
+    ext_traits::StringExt,
+    types::{DccOffer, MinorUnit},
+};
+use currency_conversion::types::ConversionPolicy;
+use error_stack::ResultExt;
+use router_env::{instrument, logger, tracing};
+
+use crate::{
+    core::{
+        errors::{self, RouterResult},
+        payments::PaymentData,
+    },
+    routes::SessionState,
+    utils::currency,
+};
+
+/// Dynamic currency conversion configuration of a merchant, set in the configs under
+/// [`get_dcc_config_key`]. Dynamic currency conversion is disabled if it is not set.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DccConfig {
+    /// Currencies offered to the cards issued in each country, by the issuing country as
+    /// recorded in the card info
+    pub card_issuing_country_currencies: HashMap<String, Currency>,
+    /// Policy the amounts are converted under, the spread of which is the markup of the offer
+    #[serde(default)]
+    pub conversion_policy: ConversionPolicy,
+}
+
+pub fn get_dcc_config_key(merchant_id: &str) -> String {
+    format!("dcc_config_{merchant_id}")
+}
+
+async fn get_dcc_config(
+    state: &SessionState,
+    merchant_id: &str,
+) -> RouterResult<Option<DccConfig>> {
+    match state
+        .store
+        .find_config_by_key(&get_dcc_config_key(merchant_id))
+        .await
+    {
+        Ok(config) => config
+            .config
+            .parse_struct("DccConfig")
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to parse the dcc config")
+            .map(Some),
+        Err(error) if error.current_context().is_db_not_found() => Ok(None),
+        Err(error) => Err(error)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to fetch the dcc config"),
+    }
+}
+
+async fn get_card_issuing_country(state: &SessionState, card: &Card) -> Option<String> {
+    if let Some(card_issuing_country) = &card.card_issuing_country {
+        return Some(card_issuing_country.clone());
+    }
+
+    state
+        .store
+        .get_card_info(&card.card_number.get_extended_card_bin())
+        .await
+        .map_err(|error| logger::warn!(card_info_error=?error))
+        .ok()
+        .flatten()
+        .and_then(|card_info| card_info.card_issuing_country)
+}
+
+/// Offers to pay in the currency of the card if it differs from the currency of the payment, in
+/// which case the transaction does not continue until the customer decides on the offer.
+///
+/// Conversion is offered once per attempt. Failures to convert the amount are logged and the
+/// payment continues in its own currency, without an offer.
+#[instrument(skip_all)]
+pub async fn offer_dcc_if_eligible<F: Clone>(
+    state: &SessionState,
+    payment_data: &mut PaymentData<F>,
+    should_continue_confirm_transaction: &mut bool,
+) -> RouterResult<()> {
+    if !*should_continue_confirm_transaction || payment_data.payment_attempt.dcc_offer.is_some() {
+        return Ok(());
+    }
+
+    if let Some(dcc_offer) = make_dcc_offer(state, payment_data).await? {
+        payment_data.payment_attempt.dcc_offer = Some(dcc_offer);
+        *should_continue_confirm_transaction = false;
+    }
+
+    Ok(())
+}
+
+/// Makes an offer to pay in the currency of the card at the current exchange rates, if the
+/// currency of the card differs from the currency of the payment. The offer awaits the decision
+/// of the customer.
+#[instrument(skip_all)]
+pub async fn make_dcc_offer<F: Clone>(
+    state: &SessionState,
+    payment_data: &PaymentData<F>,
+) -> RouterResult<Option<DccOffer>> {
+    let card = match payment_data.payment_method_data.as_ref() {
+        Some(PaymentMethodData::Card(card)) => card,
+        _ => return Ok(None),
+    };
+
+    let dcc_config = match get_dcc_config(state, &payment_data.payment_attempt.merchant_id).await? {
+        Some(dcc_config) => dcc_config,
+        None => return Ok(None),
+    };
+
+    let card_currency = get_card_issuing_country(state, card)
+        .await
+        .and_then(|country| {
+            dcc_config
+                .card_issuing_country_currencies
+                .get(&country)
+                .copied()
+        })
+        .filter(|card_currency| *card_currency != payment_data.currency);
+    let card_currency = match card_currency {
+        Some(card_currency) => card_currency,
+        None => return Ok(None),
+    };
+
+    let amount: MinorUnit = payment_data
+        .surcharge_details
+        .as_ref()
+        .map(|surcharge_details| surcharge_details.final_amount)
+        .unwrap_or(payment_data.amount.into());
+
+    let conversion = match currency::convert_currency_with_policy(
+        state,
+        &dcc_config.conversion_policy,
+        payment_data.currency,
+        card_currency,
+        amount.get_amount_as_i64(),
+    )
+    .await
+    {
+        Ok(conversion) => conversion,
+        Err(error) => {
+            logger::error!(dcc_conversion_error=?error);
+            return Ok(None);
+        }
+    };
+
+    Ok(Some(DccOffer {
+        original_amount: amount,
+        original_currency: payment_data.currency,
+        converted_amount: MinorUnit::new(conversion.minor_amount),
+        converted_currency: card_currency,
+        rate: conversion.rate,
+        markup: conversion.spread,
+        applied_rate: conversion.applied_rate,
+        decision: None,
+    }))
+}
+
+/// Gets the amount and currency the connector is called with for an amount of the payment, such
+/// as an amount to capture or to refund. Once the customer accepted the offer, every call to the
+/// connector for the attempt is made in the currency of the card the attempt was authorized in.
+pub fn get_connector_amount_and_currency(
+    dcc_offer: Option<&DccOffer>,
+    amount: MinorUnit,
+    currency: Currency,
+) -> RouterResult<(MinorUnit, Currency)> {
+    match dcc_offer.filter(|dcc_offer| dcc_offer.is_accepted()) {
+        Some(dcc_offer) => {
+            let converted_amount = dcc_offer
+                .convert_amount(amount)
+                .ok_or(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable_lazy(|| {
+                    format!(
+                        "Failed to convert amount {amount:?} to the currency of the dcc offer \
+                         {dcc_offer:?}"
+                    )
+                })?;
+            Ok((converted_amount, dcc_offer.converted_currency))
+        }
+        None => Ok((amount, currency)),
+    }
+}
@@ -883,8 +883,21 @@ pub fn validate_card_data(
     payment_method_data: Option<api::PaymentMethodData>,
 ) -> CustomResult<(), errors::ApiErrorResponse> {
     if let Some(api::PaymentMethodData::Card(card)) = payment_method_data {
+        let card_network = card
+            .card_number
+            .validate_card_network(card.card_network.as_ref())
+            .map_err(|error| {
+                report!(errors::ApiErrorResponse::PreconditionFailed {
+                    message: format!("Invalid card_number: {error}"),
+                })
+            })?;
+
+        // The security code length of the network is only enforced if the network of the card is
+        // stated, as integrations commonly send security codes of either length otherwise
         let cvc = card.card_cvc.peek().to_string();
-        if cvc.len() < 3 || cvc.len() > 4 {
+        let claimed_card_network = card.card_network.as_ref().and(card_network.as_ref());
+        if !::cards::CardSecurityCode::is_valid_length_for_network(cvc.len(), claimed_card_network)
+        {
             Err(report!(errors::ApiErrorResponse::PreconditionFailed {
                 message: "Invalid card_cvc length".to_string()
             }))?
@@ -3674,6 +3687,8 @@ impl AttemptType {
             client_source: old_payment_attempt.client_source,
             client_version: old_payment_attempt.client_version,
             customer_acceptance: old_payment_attempt.customer_acceptance,
+            // Currency conversion is offered afresh for a retry
+            dcc_offer: None,
         }
     }
 
@@ -3876,15 +3891,12 @@ pub async fn get_additional_payment_data(
                     },
                 ))
             } else {
-                let card_info = card_isin
-                    .clone()
-                    .async_and_then(|card_isin| async move {
-                        db.get_card_info(&card_isin)
-                            .await
-                            .map_err(|error| services::logger::warn!(card_info_error=?error))
-                            .ok()
-                    })
+                // The extended BIN is looked up in the 8 digit BIN ranges before the 6 digit ones
+                let card_info = db
+                    .get_card_info(&card_data.card_number.get_extended_card_bin())
                     .await
+                    .map_err(|error| services::logger::warn!(card_info_error=?error))
+                    .ok()
                     .flatten()
                     .map(|card_info| {
                         api_models::payments::AdditionalPaymentData::Card(Box::new(
@@ -3997,7 +4009,6 @@ pub async fn populate_bin_details_for_payment_method_create(
     card_details: api_models::payment_methods::CardDetail,
     db: &dyn StorageInterface,
 ) -> api_models::payment_methods::CardDetail {
-    let card_isin: Option<_> = Some(card_details.card_number.get_card_isin());
     if card_details.card_issuer.is_some()
         && card_details.card_network.is_some()
         && card_details.card_type.is_some()
@@ -4015,15 +4026,11 @@ pub async fn populate_bin_details_for_payment_method_create(
             nick_name: card_details.nick_name.clone(),
         }
     } else {
-        let card_info = card_isin
-            .clone()
-            .async_and_then(|card_isin| async move {
-                db.get_card_info(&card_isin)
-                    .await
-                    .map_err(|error| services::logger::error!(card_info_error=?error))
-                    .ok()
-            })
+        let card_info = db
+            .get_card_info(&card_details.card_number.get_extended_card_bin())
             .await
+            .map_err(|error| services::logger::error!(card_info_error=?error))
+            .ok()
             .flatten()
             .map(|card_info| api::CardDetail {
                 card_issuer: card_info.card_issuer,
@@ -166,6 +166,15 @@ pub trait Domain<F: Clone, R>: Send + Sync {
         Ok(())
     }
 
+    async fn call_dcc_if_eligible<'a>(
+        &'a self,
+        _state: &SessionState,
+        _payment_data: &mut PaymentData<F>,
+        _should_continue_confirm_transaction: &mut bool,
+    ) -> CustomResult<(), errors::ApiErrorResponse> {
+        Ok(())
+    }
+
     async fn call_external_three_ds_authentication_if_eligible<'a>(
         &'a self,
         _state: &SessionState,
@@ -28,8 +28,8 @@ use crate::{
         mandate::helpers as m_helpers,
         payment_methods::cards::create_encrypted_data,
         payments::{
-            self, helpers, operations, populate_surcharge_details, CustomerDetails, PaymentAddress,
-            PaymentData,
+            self, dcc, helpers, operations, populate_surcharge_details, CustomerDetails,
+            PaymentAddress, PaymentData,
         },
         utils as core_utils,
     },
@@ -383,6 +383,23 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             .map(masking::Secret::new)
             .or(payment_attempt.customer_acceptance);
 
+        match (payment_attempt.dcc_offer.as_mut(), request.dcc_decision) {
+            (Some(dcc_offer), Some(dcc_decision)) if dcc_offer.is_pending() => {
+                dcc_offer.decision = Some(dcc_decision);
+            }
+            (Some(dcc_offer), None) if dcc_offer.is_pending() => {
+                Err(errors::ApiErrorResponse::MissingRequiredField {
+                    field_name: "dcc_decision",
+                })?
+            }
+            (_, Some(_)) => Err(errors::ApiErrorResponse::PreconditionFailed {
+                message: "dcc_decision can only be passed when the payment awaits a decision on \
+                          its dcc_offer"
+                    .to_string(),
+            })?,
+            (_, None) => (),
+        }
+
         currency = payment_attempt.currency.get_required_value("currency")?;
         amount = payment_attempt.get_total_amount().into();
 
@@ -800,6 +817,16 @@ impl<F: Clone + Send> Domain<F, api::PaymentsRequest> for PaymentConfirm {
         populate_surcharge_details(state, payment_data).await
     }
 
+    #[instrument(skip_all)]
+    async fn call_dcc_if_eligible<'a>(
+        &'a self,
+        state: &SessionState,
+        payment_data: &mut PaymentData<F>,
+        should_continue_confirm_transaction: &mut bool,
+    ) -> CustomResult<(), errors::ApiErrorResponse> {
+        dcc::offer_dcc_if_eligible(state, payment_data, should_continue_confirm_transaction).await
+    }
+
     async fn call_external_three_ds_authentication_if_eligible<'a>(
         &'a self,
         state: &SessionState,
@@ -1041,9 +1068,21 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
                 }
             };
 
+        // The payment is confirmed again once the customer decided on the currency conversion offer
+        let is_dcc_offer_pending = payment_data
+            .payment_attempt
+            .dcc_offer
+            .as_ref()
+            .is_some_and(|dcc_offer| dcc_offer.is_pending());
+
         let (intent_status, attempt_status, (error_code, error_message)) =
             match (frm_suggestion, payment_data.authentication.as_ref()) {
                 (Some(frm_suggestion), _) => status_handler_for_frm_results(frm_suggestion),
+                (None, _) if is_dcc_offer_pending => (
+                    storage_enums::IntentStatus::RequiresConfirmation,
+                    storage_enums::AttemptStatus::ConfirmationAwaited,
+                    (None, None),
+                ),
                 (_, Some(authentication_details)) => {
                     status_handler_for_authentication_results(authentication_details)
                 }
@@ -1237,6 +1276,7 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
                         client_source,
                         client_version,
                         customer_acceptance: payment_data.payment_attempt.customer_acceptance,
+                        dcc_offer: payment_data.payment_attempt.dcc_offer,
                     },
                     storage_scheme,
                 )
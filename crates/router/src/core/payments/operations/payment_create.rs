@@ -989,6 +989,7 @@ impl PaymentCreate {
                     .change_context(errors::ApiErrorResponse::InternalServerError)
                     .attach_printable("Failed to serialize customer_acceptance")?
                     .map(Secret::new),
+                dcc_offer: None,
             },
             additional_pm_data,
         ))
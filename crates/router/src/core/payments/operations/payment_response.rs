@@ -25,7 +25,7 @@ use crate::{
                 self as payments_helpers,
                 update_additional_payment_data_with_connector_response_pm_data,
             },
-            tokenization,
+            routing, tokenization,
             types::MultipleCaptureData,
             PaymentData,
         },
@@ -770,6 +770,8 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
                 }),
         )?;
 
+    let previous_attempt_status = payment_data.payment_attempt.status;
+
     router_data.payment_method_status.and_then(|status| {
         payment_data
             .payment_method_info
@@ -1354,6 +1356,11 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
 
     payment_data.payment_intent = payment_intent;
     payment_data.payment_attempt = payment_attempt;
+
+    if payment_data.payment_attempt.status != previous_attempt_status {
+        routing::success_rate::spawn_success_rate_window_update(state, &payment_data);
+    }
+
     router_data.payment_method_status.and_then(|status| {
         payment_data
             .payment_method_info
@@ -1,6 +1,9 @@
 use std::{str::FromStr, vec::IntoIter};
 
-use common_utils::{ext_traits::Encode, types::MinorUnit};
+use common_utils::{
+    ext_traits::Encode,
+    types::{DccOffer, MinorUnit},
+};
 use diesel_models::enums as storage_enums;
 use error_stack::{report, ResultExt};
 use router_env::{
@@ -343,13 +346,30 @@ where
     FData: Send,
 {
     let new_attempt_count = payment_data.payment_intent.attempt_count + 1;
-    let new_payment_attempt = make_new_payment_attempt(
+    let mut new_payment_attempt = make_new_payment_attempt(
         connector,
         payment_data.payment_attempt.clone(),
         new_attempt_count,
         is_step_up,
     );
 
+    // Currency conversion is offered afresh for the new attempt, at the current exchange rates.
+    // The customer is not asked again during an auto retry, so the decision they made on the offer
+    // of the failed attempt applies to the new offer.
+    if let Some(decision) = payment_data
+        .payment_attempt
+        .dcc_offer
+        .as_ref()
+        .and_then(|dcc_offer| dcc_offer.decision)
+    {
+        new_payment_attempt.dcc_offer = payments::dcc::make_dcc_offer(state, payment_data)
+            .await?
+            .map(|dcc_offer| DccOffer {
+                decision: Some(decision),
+                ..dcc_offer
+            });
+    }
+
     let db = &*state.store;
     let additional_payment_method_data =
         payments::helpers::update_additional_payment_data_with_connector_response_pm_data(
@@ -527,6 +547,8 @@ pub fn make_new_payment_attempt(
         payment_token: old_payment_attempt.payment_token,
         client_source: old_payment_attempt.client_source,
         client_version: old_payment_attempt.client_version,
+        // Currency conversion is offered afresh for a retry
+        dcc_offer: None,
         created_at,
         modified_at,
         last_synced,
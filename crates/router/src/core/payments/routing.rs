@@ -1,3 +1,5 @@
+pub mod simulation;
+pub mod success_rate;
 mod transformers;
 
 use std::{
@@ -11,7 +13,7 @@ use api_models::{
     admin as admin_api,
     enums::{self as api_enums, CountryAlpha2},
     payments::Address,
-    routing::ConnectorSelection,
+    routing::{ConnectorIneligibility, ConnectorSelection, IneligibilityReason},
 };
 use diesel_models::enums as storage_enums;
 use error_stack::ResultExt;
@@ -22,9 +24,9 @@ use euclid::{
     frontend::{ast, dir as euclid_dir},
 };
 use kgraph_utils::{
-    mca as mca_graph,
+    explanation, mca as mca_graph,
     transformers::{IntoContext, IntoDirValue},
-    types::CountryCurrencyFilter,
+    types::{CountryCurrencyFilter, MerchantConnectorNodeMetadata},
 };
 use masking::PeekInterface;
 use rand::{
@@ -37,11 +39,13 @@ use storage_impl::redis::cache::{CacheKey, CGRAPH_CACHE, ROUTING_CACHE};
 #[cfg(feature = "payouts")]
 use crate::core::payouts;
 use crate::{
+    consts,
     core::{
-        errors, errors as oss_errors, payments as payments_oss,
+        api_locking, errors, errors as oss_errors, payments as payments_oss,
         routing::{self, helpers as routing_helpers},
     },
     logger,
+    routes::lock_utils,
     types::{
         api::{self, routing as routing_types},
         domain, storage as oss_storage,
@@ -56,6 +60,7 @@ pub enum CachedAlgorithm {
     Priority(Vec<routing_types::RoutableConnectorChoice>),
     VolumeSplit(Vec<routing_types::ConnectorVolumeSplit>),
     Advanced(backend::VirInterpreterBackend<ConnectorSelection>),
+    SuccessRateBased(routing_types::SuccessRateBasedRoutingConfig),
 }
 
 pub struct SessionFlowRoutingInput<'a> {
@@ -144,6 +149,7 @@ pub fn make_dsl_input_for_payouts(
         metadata,
         payment,
         payment_method,
+        time: Some(dsl_inputs::TimeInput::now(&dsl_inputs::SystemClock)),
     })
 }
 
@@ -153,87 +159,95 @@ pub fn make_dsl_input<F>(
 where
     F: Clone,
 {
+    let card = payment_data
+        .payment_method_data
+        .as_ref()
+        .and_then(|pm_data| match pm_data {
+            api::PaymentMethodData::Card(card) => Some(card),
+            _ => None,
+        });
+
+    make_payment_dsl_input(
+        &payment_data.payment_intent,
+        &payment_data.payment_attempt,
+        payment_data.currency,
+        payment_data.setup_mandate.as_ref(),
+        card.and_then(|card| card.card_network.clone()),
+        card.map(|card| card.card_number.peek().chars().take(6).collect()),
+        payment_data
+            .address
+            .get_payment_method_billing()
+            .and_then(|bic| bic.address.as_ref())
+            .and_then(|add| add.country),
+        dsl_inputs::TimeInput::now(&dsl_inputs::SystemClock),
+    )
+}
+
+/// Makes the DSL input of a payment out of its parts, which is shared by the payments being routed
+/// and the stored payments being replayed by the routing simulation.
+#[allow(clippy::too_many_arguments)]
+pub fn make_payment_dsl_input(
+    payment_intent: &oss_storage::PaymentIntent,
+    payment_attempt: &oss_storage::PaymentAttempt,
+    currency: api_enums::Currency,
+    setup_mandate: Option<&hyperswitch_domain_models::mandates::MandateData>,
+    card_network: Option<api_enums::CardNetwork>,
+    card_bin: Option<String>,
+    billing_country: Option<CountryAlpha2>,
+    time: dsl_inputs::TimeInput,
+) -> RoutingResult<dsl_inputs::BackendInput> {
     let mandate_data = dsl_inputs::MandateData {
-        mandate_acceptance_type: payment_data
-            .setup_mandate
-            .as_ref()
-            .and_then(|mandate_data| {
-                mandate_data
-                    .customer_acceptance
-                    .clone()
-                    .map(|cat| match cat.acceptance_type {
-                        hyperswitch_domain_models::mandates::AcceptanceType::Online => {
-                            euclid_enums::MandateAcceptanceType::Online
-                        }
-                        hyperswitch_domain_models::mandates::AcceptanceType::Offline => {
-                            euclid_enums::MandateAcceptanceType::Offline
-                        }
-                    })
-            }),
-        mandate_type: payment_data
-            .setup_mandate
-            .as_ref()
-            .and_then(|mandate_data| {
-                mandate_data.mandate_type.clone().map(|mt| match mt {
-                    hyperswitch_domain_models::mandates::MandateDataType::SingleUse(_) => {
-                        euclid_enums::MandateType::SingleUse
+        mandate_acceptance_type: setup_mandate.and_then(|mandate_data| {
+            mandate_data
+                .customer_acceptance
+                .clone()
+                .map(|cat| match cat.acceptance_type {
+                    hyperswitch_domain_models::mandates::AcceptanceType::Online => {
+                        euclid_enums::MandateAcceptanceType::Online
                     }
-                    hyperswitch_domain_models::mandates::MandateDataType::MultiUse(_) => {
-                        euclid_enums::MandateType::MultiUse
+                    hyperswitch_domain_models::mandates::AcceptanceType::Offline => {
+                        euclid_enums::MandateAcceptanceType::Offline
                     }
                 })
-            }),
-        payment_type: Some(payment_data.setup_mandate.clone().map_or_else(
+        }),
+        mandate_type: setup_mandate.and_then(|mandate_data| {
+            mandate_data.mandate_type.clone().map(|mt| match mt {
+                hyperswitch_domain_models::mandates::MandateDataType::SingleUse(_) => {
+                    euclid_enums::MandateType::SingleUse
+                }
+                hyperswitch_domain_models::mandates::MandateDataType::MultiUse(_) => {
+                    euclid_enums::MandateType::MultiUse
+                }
+            })
+        }),
+        payment_type: Some(setup_mandate.map_or_else(
             || euclid_enums::PaymentType::NonMandate,
             |_| euclid_enums::PaymentType::SetupMandate,
         )),
     };
     let payment_method_input = dsl_inputs::PaymentMethodInput {
-        payment_method: payment_data.payment_attempt.payment_method,
-        payment_method_type: payment_data.payment_attempt.payment_method_type,
-        card_network: payment_data
-            .payment_method_data
-            .as_ref()
-            .and_then(|pm_data| match pm_data {
-                api::PaymentMethodData::Card(card) => card.card_network.clone(),
-
-                _ => None,
-            }),
+        payment_method: payment_attempt.payment_method,
+        payment_method_type: payment_attempt.payment_method_type,
+        card_network,
     };
 
     let payment_input = dsl_inputs::PaymentInput {
-        amount: payment_data.payment_intent.amount,
-        card_bin: payment_data
-            .payment_method_data
-            .as_ref()
-            .and_then(|pm_data| match pm_data {
-                api::PaymentMethodData::Card(card) => {
-                    Some(card.card_number.peek().chars().take(6).collect())
-                }
-                _ => None,
-            }),
-        currency: payment_data.currency,
-        authentication_type: payment_data.payment_attempt.authentication_type,
-        capture_method: payment_data
-            .payment_attempt
+        amount: payment_intent.amount,
+        card_bin,
+        currency,
+        authentication_type: payment_attempt.authentication_type,
+        capture_method: payment_attempt
             .capture_method
             .and_then(|cm| cm.foreign_into()),
-        business_country: payment_data
-            .payment_intent
+        business_country: payment_intent
             .business_country
             .map(api_enums::Country::from_alpha2),
-        billing_country: payment_data
-            .address
-            .get_payment_method_billing()
-            .and_then(|bic| bic.address.as_ref())
-            .and_then(|add| add.country)
-            .map(api_enums::Country::from_alpha2),
-        business_label: payment_data.payment_intent.business_label.clone(),
-        setup_future_usage: payment_data.payment_intent.setup_future_usage,
+        billing_country: billing_country.map(api_enums::Country::from_alpha2),
+        business_label: payment_intent.business_label.clone(),
+        setup_future_usage: payment_intent.setup_future_usage,
     };
 
-    let metadata = payment_data
-        .payment_intent
+    let metadata = payment_intent
         .metadata
         .clone()
         .map(|val| val.parse_value("routing_parameters"))
@@ -247,6 +261,7 @@ where
         payment: payment_input,
         payment_method: payment_method_input,
         mandate: mandate_data,
+        time: Some(time),
     })
 }
 
@@ -307,9 +322,39 @@ pub async fn perform_static_routing_v1<F: Clone>(
 
             execute_dsl_and_get_connector_v1(backend_input, interpreter)?
         }
+
+        CachedAlgorithm::SuccessRateBased(config) => match transaction_data {
+            routing::TransactionData::Payment(payment_data) => {
+                let backend_input = make_dsl_input(payment_data)?;
+                success_rate::perform_success_rate_based_routing(
+                    state,
+                    profile_id,
+                    config,
+                    &backend_input,
+                )
+                .await
+            }
+            #[cfg(feature = "payouts")]
+            routing::TransactionData::Payout(_) => config.connectors.clone(),
+        },
     })
 }
 
+/// Key of the active routing algorithm of the profile in the routing cache
+fn get_routing_cache_key(
+    merchant_id: &str,
+    profile_id: &str,
+    transaction_type: &api_enums::TransactionType,
+) -> String {
+    match transaction_type {
+        api_enums::TransactionType::Payment => format!("routing_config_{merchant_id}_{profile_id}"),
+        #[cfg(feature = "payouts")]
+        api_enums::TransactionType::Payout => {
+            format!("routing_config_po_{merchant_id}_{profile_id}")
+        }
+    }
+}
+
 async fn ensure_algorithm_cached_v1(
     state: &SessionState,
     merchant_id: &str,
@@ -317,37 +362,28 @@ async fn ensure_algorithm_cached_v1(
     profile_id: Option<String>,
     transaction_type: &api_enums::TransactionType,
 ) -> RoutingResult<Arc<CachedAlgorithm>> {
-    let key = {
-        let profile_id = profile_id
-            .clone()
+    let key = get_routing_cache_key(
+        merchant_id,
+        profile_id
+            .as_deref()
             .get_required_value("profile_id")
-            .change_context(errors::RoutingError::ProfileIdMissing)?;
-
-        match transaction_type {
-            api_enums::TransactionType::Payment => {
-                format!("routing_config_{merchant_id}_{profile_id}")
-            }
-            #[cfg(feature = "payouts")]
-            api_enums::TransactionType::Payout => {
-                format!("routing_config_po_{merchant_id}_{profile_id}")
-            }
-        }
-    };
-
-    let cached_algorithm = ROUTING_CACHE
-        .get_val::<Arc<CachedAlgorithm>>(CacheKey {
-            key: key.clone(),
-            prefix: state.tenant.redis_key_prefix.clone(),
-        })
-        .await;
-
-    let algorithm = if let Some(algo) = cached_algorithm {
-        algo
-    } else {
-        refresh_routing_cache_v1(state, key.clone(), algorithm_id, profile_id).await?
-    };
+            .change_context(errors::RoutingError::ProfileIdMissing)?,
+        transaction_type,
+    );
 
-    Ok(algorithm)
+    let loader_state = state.clone();
+    let algorithm_id = algorithm_id.to_owned();
+    ROUTING_CACHE
+        .get_or_populate(
+            CacheKey {
+                key,
+                prefix: state.tenant.redis_key_prefix.clone(),
+            },
+            move || async move {
+                load_routing_algorithm_v1(&loader_state, &algorithm_id, profile_id).await
+            },
+        )
+        .await
 }
 
 pub fn perform_straight_through_routing(
@@ -392,9 +428,9 @@ fn execute_dsl_and_get_connector_v1(
     })
 }
 
-pub async fn refresh_routing_cache_v1(
+/// Loads the routing algorithm of the profile from the database, to be cached in the routing cache
+async fn load_routing_algorithm_v1(
     state: &SessionState,
-    key: String,
     algorithm_id: &str,
     profile_id: Option<String>,
 ) -> RoutingResult<Arc<CachedAlgorithm>> {
@@ -427,21 +463,12 @@ pub async fn refresh_routing_cache_v1(
 
             CachedAlgorithm::Advanced(interpreter)
         }
+        routing_types::RoutingAlgorithm::SuccessRateBased(config) => {
+            CachedAlgorithm::SuccessRateBased(config)
+        }
     };
 
-    let arc_cached_algorithm = Arc::new(cached_algorithm);
-
-    ROUTING_CACHE
-        .push(
-            CacheKey {
-                key,
-                prefix: state.tenant.redis_key_prefix.clone(),
-            },
-            arc_cached_algorithm.clone(),
-        )
-        .await;
-
-    Ok(arc_cached_algorithm)
+    Ok(Arc::new(cached_algorithm))
 }
 
 pub fn perform_volume_split(
@@ -488,43 +515,128 @@ pub async fn get_merchant_cgraph<'a>(
 
     let key = {
         let profile_id = profile_id
-            .clone()
+            .as_deref()
             .get_required_value("profile_id")
             .change_context(errors::RoutingError::ProfileIdMissing)?;
-        match transaction_type {
-            api_enums::TransactionType::Payment => format!("cgraph_{}_{}", merchant_id, profile_id),
-            #[cfg(feature = "payouts")]
-            api_enums::TransactionType::Payout => {
-                format!("cgraph_po_{}_{}", merchant_id, profile_id)
-            }
-        }
+        get_cgraph_cache_key(merchant_id, profile_id, transaction_type)
     };
 
-    let cached_cgraph = CGRAPH_CACHE
-        .get_val::<Arc<hyperswitch_constraint_graph::ConstraintGraph<euclid_dir::DirValue>>>(
+    let loader_state = state.clone();
+    let key_store = key_store.clone();
+    let transaction_type = *transaction_type;
+    let snapshot_key = key.clone();
+    CGRAPH_CACHE
+        .get_or_populate(
             CacheKey {
-                key: key.clone(),
+                key,
                 prefix: state.tenant.redis_key_prefix.clone(),
             },
+            move || async move {
+                load_cgraph(
+                    &loader_state,
+                    &key_store,
+                    snapshot_key,
+                    profile_id,
+                    &transaction_type,
+                )
+                .await
+            },
         )
-        .await;
-
-    let cgraph = if let Some(graph) = cached_cgraph {
-        graph
-    } else {
-        refresh_cgraph_cache(state, key_store, key.clone(), profile_id, transaction_type).await?
-    };
+        .await
+}
 
-    Ok(cgraph)
+fn get_cgraph_cache_key(
+    merchant_id: &str,
+    profile_id: &str,
+    transaction_type: &api_enums::TransactionType,
+) -> String {
+    match transaction_type {
+        api_enums::TransactionType::Payment => format!("cgraph_{}_{}", merchant_id, profile_id),
+        #[cfg(feature = "payouts")]
+        api_enums::TransactionType::Payout => {
+            format!("cgraph_po_{}_{}", merchant_id, profile_id)
+        }
+    }
 }
 
-pub async fn refresh_cgraph_cache<'a>(
+/// Loads the graph from its snapshot in redis, building it if there is no snapshot of it, to be
+/// cached in the cgraph cache.
+async fn load_cgraph(
     state: &SessionState,
     key_store: &domain::MerchantKeyStore,
     key: String,
     profile_id: Option<String>,
     transaction_type: &api_enums::TransactionType,
 ) -> RoutingResult<Arc<hyperswitch_constraint_graph::ConstraintGraph<euclid_dir::DirValue>>> {
+    if let Some(graph) = load_cgraph_snapshot(state, &key).await {
+        return Ok(graph);
+    }
+
+    let cgraph =
+        Arc::new(build_merchant_cgraph(state, key_store, profile_id, transaction_type).await?);
+
+    store_cgraph_snapshot(state, &key, &cgraph)
+        .await
+        .map_err(|error| logger::error!(?error, "Failed to store constraint graph snapshot"))
+        .ok();
+
+    Ok(cgraph)
+}
+
+/// Loads a graph built by any instance from its snapshot in redis. The snapshot is stored under the
+/// same key as the in-memory cache entry, so it is removed along with the entry on invalidation.
+async fn load_cgraph_snapshot(
+    state: &SessionState,
+    key: &str,
+) -> Option<Arc<hyperswitch_constraint_graph::ConstraintGraph<euclid_dir::DirValue>>> {
+    let redis_conn = state
+        .store
+        .get_redis_conn()
+        .map_err(|error| logger::error!(?error, "Failed to get redis connection"))
+        .ok()?;
+
+    let snapshot = redis_conn
+        .get_and_deserialize_key::<hyperswitch_constraint_graph::ConstraintGraphSnapshot<
+            euclid_dir::DirValue,
+        >>(key, "ConstraintGraphSnapshot")
+        .await
+        .map_err(|error| logger::debug!(?error, "Constraint graph snapshot not loaded"))
+        .ok()?;
+
+    hyperswitch_constraint_graph::ConstraintGraph::from_snapshot(snapshot)
+        .map(Arc::new)
+        .map_err(|error| logger::warn!(?error, "Discarding invalid constraint graph snapshot"))
+        .ok()
+}
+
+async fn store_cgraph_snapshot(
+    state: &SessionState,
+    key: &str,
+    cgraph: &hyperswitch_constraint_graph::ConstraintGraph<euclid_dir::DirValue>,
+) -> RoutingResult<()> {
+    let snapshot = cgraph
+        .to_snapshot()
+        .change_context(errors::RoutingError::KgraphCacheFailure)?;
+
+    state
+        .store
+        .get_redis_conn()
+        .change_context(errors::RoutingError::KgraphCacheFailure)
+        .attach_printable("Failed to get redis connection")?
+        .serialize_and_set_key_with_expiry(key, snapshot, consts::CGRAPH_SNAPSHOT_TTL_IN_SECONDS)
+        .await
+        .change_context(errors::RoutingError::KgraphCacheFailure)
+        .attach_printable("Failed to store constraint graph snapshot")
+}
+
+/// Builds the knowledge graph of the merchant connector accounts of the merchant (and profile, if
+/// any) enabled for the transaction type, without going through the cache.
+pub async fn build_merchant_cgraph(
+    state: &SessionState,
+    key_store: &domain::MerchantKeyStore,
+    profile_id: Option<String>,
+    transaction_type: &api_enums::TransactionType,
+) -> RoutingResult<hyperswitch_constraint_graph::ConstraintGraph<euclid_dir::DirValue>> {
     let mut merchant_connector_accounts = state
         .store
         .find_merchant_connector_account_by_merchant_id_and_disabled_list(
@@ -536,21 +648,8 @@ pub async fn refresh_cgraph_cache<'a>(
         .await
         .change_context(errors::RoutingError::KgraphCacheRefreshFailed)?;
 
-    match transaction_type {
-        api_enums::TransactionType::Payment => {
-            merchant_connector_accounts.retain(|mca| {
-                mca.connector_type != storage_enums::ConnectorType::PaymentVas
-                    && mca.connector_type != storage_enums::ConnectorType::PaymentMethodAuth
-                    && mca.connector_type != storage_enums::ConnectorType::PayoutProcessor
-                    && mca.connector_type != storage_enums::ConnectorType::AuthenticationProcessor
-            });
-        }
-        #[cfg(feature = "payouts")]
-        api_enums::TransactionType::Payout => {
-            merchant_connector_accounts
-                .retain(|mca| mca.connector_type == storage_enums::ConnectorType::PayoutProcessor);
-        }
-    };
+    merchant_connector_accounts
+        .retain(|mca| is_connector_type_routable(mca.connector_type, transaction_type));
 
     let merchant_connector_accounts = payments_oss::helpers::filter_mca_based_on_business_profile(
         merchant_connector_accounts,
@@ -562,6 +661,32 @@ pub async fn refresh_cgraph_cache<'a>(
         .map(admin_api::MerchantConnectorResponse::try_from)
         .collect::<Result<Vec<_>, _>>()
         .change_context(errors::RoutingError::KgraphCacheRefreshFailed)?;
+    let config_pm_filters = get_config_pm_filters(state)?;
+    mca_graph::make_mca_graph(api_mcas, &config_pm_filters)
+        .change_context(errors::RoutingError::KgraphCacheRefreshFailed)
+        .attach_printable("when construction cgraph")
+}
+
+/// Whether accounts of the connector type are routed to for the transaction type
+fn is_connector_type_routable(
+    connector_type: storage_enums::ConnectorType,
+    transaction_type: &api_enums::TransactionType,
+) -> bool {
+    match transaction_type {
+        api_enums::TransactionType::Payment => {
+            connector_type != storage_enums::ConnectorType::PaymentVas
+                && connector_type != storage_enums::ConnectorType::PaymentMethodAuth
+                && connector_type != storage_enums::ConnectorType::PayoutProcessor
+                && connector_type != storage_enums::ConnectorType::AuthenticationProcessor
+        }
+        #[cfg(feature = "payouts")]
+        api_enums::TransactionType::Payout => {
+            connector_type == storage_enums::ConnectorType::PayoutProcessor
+        }
+    }
+}
+
+fn get_config_pm_filters(state: &SessionState) -> RoutingResult<CountryCurrencyFilter> {
     let connector_configs = state
         .conf
         .pm_filters
@@ -583,15 +708,86 @@ pub async fn refresh_cgraph_cache<'a>(
         .get("default")
         .cloned()
         .map(ForeignFrom::foreign_from);
-    let config_pm_filters = CountryCurrencyFilter {
+
+    Ok(CountryCurrencyFilter {
         connector_configs,
         default_configs,
-    };
-    let cgraph = Arc::new(
-        mca_graph::make_mca_graph(api_mcas, &config_pm_filters)
-            .change_context(errors::RoutingError::KgraphCacheRefreshFailed)
-            .attach_printable("when construction cgraph")?,
+    })
+}
+
+/// Lock serializing the changes to the merchant connector accounts of a profile, so that every
+/// update of the graph of the profile patches the graph stored by the previous one.
+pub fn get_cgraph_update_lock(profile_id: &str) -> api_locking::LockAction {
+    api_locking::LockAction::Hold {
+        input: api_locking::LockingInput {
+            unique_locking_key: format!("cgraph_{profile_id}"),
+            api_identifier: lock_utils::ApiIdentifier::MerchantConnector,
+            override_lock_retries: None,
+        },
+    }
+}
+
+/// Loads the graph of the profile for the transaction type from its snapshot in redis, to be
+/// updated with [`update_cgraph_for_merchant_connector_account`] once one of its accounts has
+/// changed. The in-memory cache is skipped as it may not have seen the updates of the other
+/// instances yet, so the graph must be loaded while holding [`get_cgraph_update_lock`].
+pub async fn load_merchant_cgraph_snapshot(
+    state: &SessionState,
+    merchant_id: &str,
+    profile_id: &str,
+    transaction_type: &api_enums::TransactionType,
+) -> Option<Arc<hyperswitch_constraint_graph::ConstraintGraph<euclid_dir::DirValue>>> {
+    load_cgraph_snapshot(
+        state,
+        &get_cgraph_cache_key(merchant_id, profile_id, transaction_type),
+    )
+    .await
+}
+
+/// Updates a cached graph for a change to one of the merchant connector accounts of the profile,
+/// recompiling only the part of the graph built from the account, and caches the updated graph in
+/// place of the whole graph being rebuilt on the next payment.
+pub async fn update_cgraph_for_merchant_connector_account(
+    state: &SessionState,
+    cgraph: &hyperswitch_constraint_graph::ConstraintGraph<euclid_dir::DirValue>,
+    merchant_connector_account: domain::MerchantConnectorAccount,
+    transaction_type: &api_enums::TransactionType,
+) -> RoutingResult<()> {
+    let profile_id = merchant_connector_account
+        .profile_id
+        .clone()
+        .get_required_value("profile_id")
+        .change_context(errors::RoutingError::ProfileIdMissing)?;
+    let key = get_cgraph_cache_key(
+        &merchant_connector_account.merchant_id,
+        &profile_id,
+        transaction_type,
     );
+    let merchant_connector_id = merchant_connector_account.merchant_connector_id.clone();
+
+    // Only accounts explicitly enabled are part of freshly built graphs
+    let is_routable = merchant_connector_account.disabled == Some(false)
+        && is_connector_type_routable(merchant_connector_account.connector_type, transaction_type);
+    let api_mca = is_routable
+        .then(|| admin_api::MerchantConnectorResponse::try_from(merchant_connector_account))
+        .transpose()
+        .change_context(errors::RoutingError::KgraphCacheRefreshFailed)?;
+
+    let updated_cgraph = Arc::new(
+        mca_graph::update_mca_graph(
+            cgraph,
+            &merchant_connector_id,
+            api_mca,
+            &get_config_pm_filters(state)?,
+        )
+        .change_context(errors::RoutingError::KgraphCacheRefreshFailed)
+        .attach_printable("when updating cgraph")?,
+    );
+
+    store_cgraph_snapshot(state, &key, &updated_cgraph)
+        .await
+        .map_err(|error| logger::error!(?error, "Failed to store constraint graph snapshot"))
+        .ok();
 
     CGRAPH_CACHE
         .push(
@@ -599,15 +795,18 @@ pub async fn refresh_cgraph_cache<'a>(
                 key,
                 prefix: state.tenant.redis_key_prefix.clone(),
             },
-            Arc::clone(&cgraph),
+            updated_cgraph,
         )
         .await;
 
-    Ok(cgraph)
+    Ok(())
 }
 
+/// Filters the chosen connectors down to the ones the constraint graph of the merchant allows for
+/// the input. Why the other connectors were excluded is only worked out if `explain` is set, the
+/// explanation being too costly to build while routing a payment.
 #[allow(clippy::too_many_arguments)]
-async fn perform_cgraph_filtering(
+pub async fn perform_cgraph_filtering(
     state: &SessionState,
     key_store: &domain::MerchantKeyStore,
     chosen: Vec<routing_types::RoutableConnectorChoice>,
@@ -615,7 +814,8 @@ async fn perform_cgraph_filtering(
     eligible_connectors: Option<&Vec<api_enums::RoutableConnectors>>,
     profile_id: Option<String>,
     transaction_type: &api_enums::TransactionType,
-) -> RoutingResult<Vec<routing_types::RoutableConnectorChoice>> {
+    explain: bool,
+) -> RoutingResult<routing_types::EligibilityAnalysisResult> {
     let context = euclid_graph::AnalysisContext::from_dir_values(
         backend_input
             .into_context()
@@ -623,32 +823,68 @@ async fn perform_cgraph_filtering(
     );
     let cached_cgraph = get_merchant_cgraph(state, key_store, profile_id, transaction_type).await?;
 
-    let mut final_selection = Vec::<routing_types::RoutableConnectorChoice>::new();
+    let mut result = routing_types::EligibilityAnalysisResult::default();
     for choice in chosen {
         let routable_connector = choice.connector;
         let euclid_choice: ast::ConnectorChoice = choice.clone().foreign_into();
         let dir_val = euclid_choice
             .into_dir_value()
             .change_context(errors::RoutingError::KgraphAnalysisError)?;
-        let cgraph_eligible = cached_cgraph
-            .check_value_validity(
-                dir_val,
-                &context,
-                &mut hyperswitch_constraint_graph::Memoization::new(),
-                &mut hyperswitch_constraint_graph::CycleCheck::new(),
-                None,
-            )
-            .change_context(errors::RoutingError::KgraphAnalysisError)?;
 
-        let filter_eligible =
+        // The trace of a failed analysis only lives as long as the memoization, so the failure
+        // is explained right away
+        let mut memo = hyperswitch_constraint_graph::Memoization::new();
+        let mut reasons = Vec::new();
+        let graph_eligible = match cached_cgraph.value_validity_analysis(
+            dir_val,
+            &context,
+            &mut memo,
+            &mut hyperswitch_constraint_graph::CycleCheck::new(),
+            None,
+        ) {
+            Ok(()) => true,
+            Err(
+                error @ (hyperswitch_constraint_graph::GraphError::AnalysisError(_)
+                | hyperswitch_constraint_graph::GraphError::ValueNodeNotFound(_)),
+            ) => {
+                if explain {
+                    reasons.extend(explanation::explain_graph_error(
+                        &error,
+                        MerchantConnectorNodeMetadata::default(),
+                    ));
+                }
+                false
+            }
+            Err(error) => Err(error).change_context(errors::RoutingError::KgraphAnalysisError)?,
+        };
+
+        let request_eligible =
             eligible_connectors.map_or(true, |list| list.contains(&routable_connector));
+        if explain && !request_eligible {
+            reasons.push(IneligibilityReason {
+                message: "Connector is not one of the eligible connectors of the request"
+                    .to_string(),
+                key: None,
+                found: None,
+                expected: Vec::new(),
+                excluded: true,
+                merchant_connector_id: choice.merchant_connector_id.clone(),
+                payment_method: None,
+                payment_method_type: None,
+            });
+        }
 
-        if cgraph_eligible && filter_eligible {
-            final_selection.push(choice);
+        if graph_eligible && request_eligible {
+            result.eligible_connectors.push(choice);
+        } else {
+            result.ineligible_connectors.push(ConnectorIneligibility {
+                connector: routable_connector.to_string(),
+                reasons,
+            });
         }
     }
 
-    Ok(final_selection)
+    Ok(result)
 }
 
 pub async fn perform_eligibility_analysis<F: Clone>(
@@ -673,8 +909,10 @@ pub async fn perform_eligibility_analysis<F: Clone>(
         eligible_connectors,
         profile_id,
         &api_enums::TransactionType::from(transaction_data),
+        false,
     )
     .await
+    .map(|result| result.eligible_connectors)
 }
 
 pub async fn perform_fallback_routing<F: Clone>(
@@ -715,8 +953,10 @@ pub async fn perform_fallback_routing<F: Clone>(
         eligible_connectors,
         profile_id,
         &api_enums::TransactionType::from(transaction_data),
+        false,
     )
     .await
+    .map(|result| result.eligible_connectors)
 }
 
 pub async fn perform_eligibility_analysis_with_fallback<F: Clone>(
@@ -849,6 +1089,7 @@ pub async fn perform_session_flow_routing(
             mandate_type: None,
             payment_type: None,
         },
+        time: Some(dsl_inputs::TimeInput::now(&dsl_inputs::SystemClock)),
     };
 
     for connector_data in session_input.chosen.iter() {
@@ -944,6 +1185,19 @@ async fn perform_session_routing_for_pm_type(
                         session_pm_input.backend_input.clone(),
                         interpreter,
                     )?,
+                    CachedAlgorithm::SuccessRateBased(config) => {
+                        success_rate::perform_success_rate_based_routing(
+                            session_pm_input.state,
+                            session_pm_input
+                                .profile_id
+                                .as_deref()
+                                .get_required_value("profile_id")
+                                .change_context(errors::RoutingError::ProfileIdMissing)?,
+                            config,
+                            &session_pm_input.backend_input,
+                        )
+                        .await
+                    }
                 }
             } else {
                 routing_helpers::get_merchant_default_config(
@@ -969,8 +1223,10 @@ async fn perform_session_routing_for_pm_type(
         None,
         session_pm_input.profile_id.clone(),
         transaction_type,
+        false,
     )
-    .await?;
+    .await?
+    .eligible_connectors;
 
     if final_selection.is_empty() {
         let fallback = routing_helpers::get_merchant_default_config(
@@ -993,8 +1249,10 @@ async fn perform_session_routing_for_pm_type(
             None,
             session_pm_input.profile_id.clone(),
             transaction_type,
+            false,
         )
-        .await?;
+        .await?
+        .eligible_connectors;
     }
 
     if final_selection.is_empty() {
@@ -1054,6 +1312,7 @@ pub fn make_dsl_input_for_surcharge(
         payment: payment_input,
         payment_method: payment_method_input,
         mandate: mandate_data,
+        time: Some(dsl_inputs::TimeInput::now(&dsl_inputs::SystemClock)),
     };
     Ok(backend_input)
 }
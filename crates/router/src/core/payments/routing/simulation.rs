@@ -0,0 +1,401 @@
+//! Routing simulation
+//!
+//! Replays historical payments through a routing algorithm, reconstructing the DSL input of every
+//! payment from its stored intent and active attempt, and compares the connectors the algorithm
+//! would have chosen with the ones that actually processed the payments. The chosen connectors go
+//! through the same eligibility analysis and fallback as in live routing.
+
+use std::{collections::HashMap, str::FromStr};
+
+use api_models::{enums as api_enums, payments as payment_api};
+use error_stack::ResultExt;
+use euclid::backend::{self, inputs as dsl_inputs, EuclidBackend};
+use hyperswitch_domain_models::mandates::MandateData;
+use masking::PeekInterface;
+
+use super::{
+    execute_dsl_and_get_connector_v1, make_payment_dsl_input, perform_cgraph_filtering,
+    perform_volume_split, success_rate, RoutingResult,
+};
+use crate::{
+    core::errors,
+    logger,
+    types::{api::routing as routing_types, domain, storage as oss_storage},
+    utils::{OptionExt, ValueExt},
+    SessionState,
+};
+
+/// Outcome of a single replayed payment
+struct SimulatedPayment {
+    /// Connector the payment was actually processed through
+    actual: Option<api_enums::RoutableConnectors>,
+    /// Whether the payment was successful, `None` if it did not reach a terminal state
+    outcome: Option<bool>,
+    /// Connector the simulated routing algorithm would have routed the payment to
+    simulated: Option<api_enums::RoutableConnectors>,
+}
+
+#[derive(Default)]
+struct ConnectorTally {
+    actual_volume: u64,
+    simulated_volume: u64,
+    successful: u64,
+    terminal: u64,
+}
+
+impl ConnectorTally {
+    fn success_rate(&self, min_sample_size: u64) -> Option<f64> {
+        if self.terminal == 0 || self.terminal < min_sample_size {
+            return None;
+        }
+
+        #[allow(clippy::as_conversions)]
+        Some(self.successful as f64 / self.terminal as f64)
+    }
+}
+
+/// Reconstructs the DSL input of a stored payment, the way it was made when the payment was
+/// routed, except for the time based keys which are evaluated at the time the payment was created.
+pub fn make_dsl_input_for_stored_payment(
+    payment_intent: &oss_storage::PaymentIntent,
+    payment_attempt: &oss_storage::PaymentAttempt,
+) -> RoutingResult<dsl_inputs::BackendInput> {
+    let card_info = payment_attempt
+        .payment_method_data
+        .clone()
+        .and_then(|data| {
+            data.parse_value::<payment_api::AdditionalPaymentData>("AdditionalPaymentData")
+                .ok()
+        })
+        .and_then(|data| match data {
+            payment_api::AdditionalPaymentData::Card(card_info) => Some(card_info),
+            _ => None,
+        });
+
+    let setup_mandate = payment_attempt
+        .mandate_details
+        .clone()
+        .map(|mandate_type| MandateData {
+            update_mandate_id: None,
+            customer_acceptance: None,
+            mandate_type: Some(mandate_type),
+        });
+
+    let billing_country = payment_intent
+        .billing_details
+        .clone()
+        .and_then(|billing_details| {
+            billing_details
+                .into_inner()
+                .peek()
+                .clone()
+                .parse_value::<payment_api::Address>("Address")
+                .ok()
+        })
+        .and_then(|billing| billing.address)
+        .and_then(|address| address.country);
+
+    let currency = payment_attempt
+        .currency
+        .or(payment_intent.currency)
+        .get_required_value("currency")
+        .change_context(errors::RoutingError::DslMissingRequiredField {
+            field_name: "currency".to_string(),
+        })?;
+
+    make_payment_dsl_input(
+        payment_intent,
+        payment_attempt,
+        currency,
+        setup_mandate.as_ref(),
+        card_info
+            .as_ref()
+            .and_then(|card_info| card_info.card_network.clone()),
+        card_info.and_then(|card_info| card_info.card_isin),
+        billing_country,
+        dsl_inputs::TimeInput::from_datetime(payment_intent.created_at),
+    )
+}
+
+fn simulate_payment(
+    algorithm: &routing_types::RoutingAlgorithm,
+    interpreter: Option<&backend::VirInterpreterBackend<routing_types::ConnectorSelection>>,
+    history: &HashMap<api_enums::RoutableConnectors, ConnectorTally>,
+    payment_intent: &oss_storage::PaymentIntent,
+    backend_input: dsl_inputs::BackendInput,
+) -> RoutingResult<Vec<routing_types::RoutableConnectorChoice>> {
+    Ok(match algorithm {
+        routing_types::RoutingAlgorithm::Single(connector) => vec![(**connector).clone()],
+
+        routing_types::RoutingAlgorithm::Priority(connectors) => connectors.clone(),
+
+        // The payment id seeds the volume split so that a payment is always simulated the same way
+        routing_types::RoutingAlgorithm::VolumeSplit(splits) => {
+            perform_volume_split(splits.clone(), Some(&payment_intent.payment_id))?
+        }
+
+        routing_types::RoutingAlgorithm::Advanced(_) => {
+            let interpreter = interpreter
+                .get_required_value("interpreter")
+                .change_context(errors::RoutingError::DslBackendInitError)?;
+
+            execute_dsl_and_get_connector_v1(backend_input, interpreter)?
+        }
+
+        // There are no success rate windows offline, so the connectors are ranked by their
+        // success rate over the replayed payments instead
+        routing_types::RoutingAlgorithm::SuccessRateBased(config) => {
+            let success_rates = config
+                .connectors
+                .iter()
+                .map(|choice| {
+                    history
+                        .get(&choice.connector)
+                        .and_then(|tally| tally.success_rate(u64::from(config.min_sample_size)))
+                })
+                .collect();
+
+            success_rate::order_connectors_by_success_rate(
+                config.connectors.clone(),
+                success_rates,
+                None,
+            )
+        }
+    })
+}
+
+/// Filters the connectors chosen for a payment through the constraint graph of the profile, and
+/// appends the eligible fallback connectors of the profile, the way live routing does
+async fn filter_eligible_connectors(
+    state: &SessionState,
+    key_store: &domain::MerchantKeyStore,
+    profile_id: &str,
+    fallback_config: &[routing_types::RoutableConnectorChoice],
+    chosen: Vec<routing_types::RoutableConnectorChoice>,
+    backend_input: dsl_inputs::BackendInput,
+) -> RoutingResult<Vec<routing_types::RoutableConnectorChoice>> {
+    let mut selection = perform_cgraph_filtering(
+        state,
+        key_store,
+        chosen,
+        backend_input.clone(),
+        None,
+        Some(profile_id.to_owned()),
+        &api_enums::TransactionType::Payment,
+        false,
+    )
+    .await?
+    .eligible_connectors;
+
+    let fallback_selection = perform_cgraph_filtering(
+        state,
+        key_store,
+        fallback_config.to_vec(),
+        backend_input,
+        None,
+        Some(profile_id.to_owned()),
+        &api_enums::TransactionType::Payment,
+        false,
+    )
+    .await
+    .map(|result| result.eligible_connectors)
+    .unwrap_or_default()
+    .into_iter()
+    .filter(|choice| !selection.contains(choice))
+    .collect::<Vec<_>>();
+    selection.extend(fallback_selection);
+
+    Ok(selection)
+}
+
+fn to_i64(count: u64) -> i64 {
+    i64::try_from(count).unwrap_or(i64::MAX)
+}
+
+fn summarize(payments: Vec<SimulatedPayment>) -> routing_types::RoutingSimulationResponse {
+    let mut tallies: HashMap<api_enums::RoutableConnectors, ConnectorTally> = HashMap::new();
+    let mut response = routing_types::RoutingSimulationResponse {
+        total_payments: 0,
+        rerouted_payments: 0,
+        unroutable_payments: 0,
+        connectors: Vec::new(),
+    };
+
+    for payment in payments {
+        response.total_payments += 1;
+
+        if let Some(actual) = payment.actual {
+            let tally = tallies.entry(actual).or_default();
+            tally.actual_volume += 1;
+            if let Some(is_successful) = payment.outcome {
+                tally.terminal += 1;
+                tally.successful += u64::from(is_successful);
+            }
+        }
+
+        match payment.simulated {
+            Some(simulated) => {
+                tallies.entry(simulated).or_default().simulated_volume += 1;
+                // Payments that were never routed to a connector cannot have been rerouted
+                if payment.actual.is_some_and(|actual| actual != simulated) {
+                    response.rerouted_payments += 1;
+                }
+            }
+            None => response.unroutable_payments += 1,
+        }
+    }
+
+    response.connectors = tallies
+        .into_iter()
+        .map(
+            |(connector, tally)| routing_types::ConnectorSimulationResult {
+                connector,
+                actual_volume: tally.actual_volume,
+                simulated_volume: tally.simulated_volume,
+                volume_diff: to_i64(tally.simulated_volume)
+                    .saturating_sub(to_i64(tally.actual_volume)),
+                historical_success_rate: tally.success_rate(1),
+            },
+        )
+        .collect();
+    response
+        .connectors
+        .sort_by_key(|result| result.connector.to_string());
+
+    response
+}
+
+/// Replays the payments of the profile through the routing algorithm, returning the difference in
+/// connector distribution between the simulated and the actual routing. Payments for which the
+/// algorithm could not be executed, or no eligible connector was left, are reported as
+/// unroutable.
+pub async fn simulate_routing_algorithm(
+    state: &SessionState,
+    key_store: &domain::MerchantKeyStore,
+    profile_id: &str,
+    fallback_config: &[routing_types::RoutableConnectorChoice],
+    algorithm: &routing_types::RoutingAlgorithm,
+    payments: &[(oss_storage::PaymentIntent, oss_storage::PaymentAttempt)],
+) -> RoutingResult<routing_types::RoutingSimulationResponse> {
+    let interpreter = match algorithm {
+        routing_types::RoutingAlgorithm::Advanced(program) => Some(
+            backend::VirInterpreterBackend::with_program(program.clone())
+                .change_context(errors::RoutingError::DslBackendInitError)
+                .attach_printable("Error initializing DSL interpreter backend")?,
+        ),
+        _ => None,
+    };
+
+    let mut simulated_payments = payments
+        .iter()
+        .map(|(_, payment_attempt)| SimulatedPayment {
+            actual: payment_attempt
+                .connector
+                .as_deref()
+                .and_then(|connector| api_enums::RoutableConnectors::from_str(connector).ok()),
+            outcome: success_rate::get_attempt_outcome(payment_attempt.status),
+            simulated: None,
+        })
+        .collect::<Vec<_>>();
+
+    let mut history: HashMap<api_enums::RoutableConnectors, ConnectorTally> = HashMap::new();
+    for payment in &simulated_payments {
+        if let (Some(actual), Some(is_successful)) = (payment.actual, payment.outcome) {
+            let tally = history.entry(actual).or_default();
+            tally.terminal += 1;
+            tally.successful += u64::from(is_successful);
+        }
+    }
+
+    for ((payment_intent, payment_attempt), payment) in
+        payments.iter().zip(simulated_payments.iter_mut())
+    {
+        let simulated = async {
+            let backend_input = make_dsl_input_for_stored_payment(payment_intent, payment_attempt)?;
+            let chosen = simulate_payment(
+                algorithm,
+                interpreter.as_ref(),
+                &history,
+                payment_intent,
+                backend_input.clone(),
+            )?;
+
+            filter_eligible_connectors(
+                state,
+                key_store,
+                profile_id,
+                fallback_config,
+                chosen,
+                backend_input,
+            )
+            .await
+        };
+
+        payment.simulated = simulated
+            .await
+            .map_err(|error| {
+                logger::warn!(
+                    ?error,
+                    payment_id = %payment_intent.payment_id,
+                    "Failed to simulate routing for payment"
+                );
+            })
+            .ok()
+            .and_then(|connectors| connectors.first().map(|choice| choice.connector));
+    }
+
+    Ok(summarize(simulated_payments))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payment(
+        actual: Option<api_enums::RoutableConnectors>,
+        outcome: Option<bool>,
+        simulated: Option<api_enums::RoutableConnectors>,
+    ) -> SimulatedPayment {
+        SimulatedPayment {
+            actual,
+            outcome,
+            simulated,
+        }
+    }
+
+    #[test]
+    fn test_simulation_summary() {
+        use api_enums::RoutableConnectors::{Adyen, Stripe};
+
+        let summary = summarize(vec![
+            payment(Some(Stripe), Some(true), Some(Stripe)),
+            payment(Some(Stripe), Some(false), Some(Adyen)),
+            payment(Some(Stripe), None, Some(Adyen)),
+            payment(Some(Adyen), Some(true), None),
+            payment(None, None, Some(Adyen)),
+        ]);
+
+        assert_eq!(summary.total_payments, 5);
+        assert_eq!(summary.rerouted_payments, 2);
+        assert_eq!(summary.unroutable_payments, 1);
+        assert_eq!(
+            summary.connectors,
+            vec![
+                routing_types::ConnectorSimulationResult {
+                    connector: Adyen,
+                    actual_volume: 1,
+                    simulated_volume: 3,
+                    volume_diff: 2,
+                    historical_success_rate: Some(1.0),
+                },
+                routing_types::ConnectorSimulationResult {
+                    connector: Stripe,
+                    actual_volume: 3,
+                    simulated_volume: 1,
+                    volume_diff: -2,
+                    historical_success_rate: Some(0.5),
+                },
+            ]
+        );
+    }
+}
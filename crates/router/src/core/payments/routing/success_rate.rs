@@ -0,0 +1,406 @@
+//! Success rate based routing
+//!
+//! The authorization outcomes of the most recent attempts are kept in a Redis list per
+//! (connector, payment method type, currency, card network) bucket, and the connectors of a
+//! [`routing_types::SuccessRateBasedRoutingConfig`] are ordered by the success rate of the bucket
+//! the current payment falls into.
+
+use std::{str::FromStr, sync::Arc};
+
+use api_models::enums as api_enums;
+use error_stack::ResultExt;
+use euclid::backend::inputs as dsl_inputs;
+use rand::Rng;
+use storage_impl::redis::cache::{CacheKey, ROUTING_CACHE};
+use tracing_futures::Instrument;
+
+use super::{
+    ensure_algorithm_cached_v1, get_routing_cache_key, make_dsl_input, CachedAlgorithm,
+    RoutingResult,
+};
+use crate::{
+    core::{errors, metrics, payments as payments_oss},
+    logger,
+    types::api::routing as routing_types,
+    utils::ValueExt,
+    SessionState,
+};
+
+/// Time after which the outcome window of a bucket that did not receive any new outcomes expires
+const SUCCESS_RATE_WINDOW_TTL_IN_SECS: i64 = 7 * 24 * 60 * 60;
+
+const SUCCESSFUL_OUTCOME: &str = "1";
+const FAILED_OUTCOME: &str = "0";
+
+fn get_success_rate_window_key(
+    profile_id: &str,
+    choice: &routing_types::RoutableConnectorChoice,
+    backend_input: &dsl_inputs::BackendInput,
+) -> String {
+    fn or_any<T: ToString>(value: Option<T>) -> String {
+        value.map_or_else(|| "any".to_string(), |value| value.to_string())
+    }
+
+    format!(
+        "success_rate_{profile_id}_{}_{}_{}_{}_{}",
+        choice.connector,
+        or_any(choice.merchant_connector_id.as_ref()),
+        or_any(backend_input.payment_method.payment_method_type),
+        backend_input.payment.currency,
+        or_any(backend_input.payment_method.card_network.as_ref()),
+    )
+}
+
+/// Computes the success rate of a window of outcomes, returning `None` if the window has fewer
+/// outcomes than `min_sample_size`.
+fn calculate_success_rate(window: &[String], min_sample_size: u16) -> Option<f64> {
+    if window.is_empty() || window.len() < usize::from(min_sample_size) {
+        return None;
+    }
+
+    let successful = window
+        .iter()
+        .filter(|outcome| outcome.as_str() == SUCCESSFUL_OUTCOME)
+        .count();
+
+    #[allow(clippy::as_conversions)]
+    Some(successful as f64 / window.len() as f64)
+}
+
+/// Orders the connectors by descending success rate, keeping connectors without enough outcomes
+/// after the ones with a known success rate in their configured order. If an exploration index is
+/// provided, the connector at that index of the configured list is moved to the front.
+pub(super) fn order_connectors_by_success_rate(
+    connectors: Vec<routing_types::RoutableConnectorChoice>,
+    success_rates: Vec<Option<f64>>,
+    exploration_index: Option<usize>,
+) -> Vec<routing_types::RoutableConnectorChoice> {
+    let mut ranked = connectors
+        .into_iter()
+        .zip(success_rates)
+        .enumerate()
+        .collect::<Vec<_>>();
+
+    ranked.sort_by(
+        |(_, (_, rate_a)), (_, (_, rate_b))| match (rate_a, rate_b) {
+            (Some(a), Some(b)) => b.total_cmp(a),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        },
+    );
+
+    if let Some(position) = exploration_index
+        .and_then(|index| ranked.iter().position(|(original, _)| *original == index))
+    {
+        let explored = ranked.remove(position);
+        ranked.insert(0, explored);
+    }
+
+    ranked
+        .into_iter()
+        .map(|(_, (connector, _))| connector)
+        .collect()
+}
+
+pub async fn perform_success_rate_based_routing(
+    state: &SessionState,
+    profile_id: &str,
+    config: &routing_types::SuccessRateBasedRoutingConfig,
+    backend_input: &dsl_inputs::BackendInput,
+) -> Vec<routing_types::RoutableConnectorChoice> {
+    let redis_conn = match state.store.get_redis_conn() {
+        Ok(conn) => conn,
+        Err(error) => {
+            logger::error!(
+                ?error,
+                "Failed to get redis connection for success rate routing"
+            );
+            return config.connectors.clone();
+        }
+    };
+
+    let mut success_rates = Vec::with_capacity(config.connectors.len());
+    for choice in &config.connectors {
+        let key = get_success_rate_window_key(profile_id, choice, backend_input);
+        let success_rate = redis_conn
+            .get_list_elements(&key, 0, i64::from(config.window_size) - 1)
+            .await
+            .map_err(|error| {
+                logger::warn!(?error, %key, "Failed to fetch success rate window");
+            })
+            .ok()
+            .and_then(|window| calculate_success_rate(&window, config.min_sample_size));
+        success_rates.push(success_rate);
+    }
+
+    let mut rng = rand::thread_rng();
+    let exploration_index = (!config.connectors.is_empty()
+        && rng.gen_range(0..100) < config.exploration_percent)
+        .then(|| rng.gen_range(0..config.connectors.len()));
+
+    order_connectors_by_success_rate(config.connectors.clone(), success_rates, exploration_index)
+}
+
+/// Maps the status of an attempt to its routing outcome, `None` if the attempt is not in a
+/// terminal authorization state.
+pub(super) fn get_attempt_outcome(status: api_enums::AttemptStatus) -> Option<bool> {
+    match status {
+        api_enums::AttemptStatus::Charged
+        | api_enums::AttemptStatus::Authorized
+        | api_enums::AttemptStatus::PartialCharged
+        | api_enums::AttemptStatus::PartialChargedAndChargeable => Some(true),
+        api_enums::AttemptStatus::Failure | api_enums::AttemptStatus::AuthorizationFailed => {
+            Some(false)
+        }
+        _ => None,
+    }
+}
+
+/// Records the outcome of the payment attempt in the background, in the success rate window of
+/// the connector it was processed with, if the attempt reached a terminal authorization state.
+pub fn spawn_success_rate_window_update<F: Clone>(
+    state: &SessionState,
+    payment_data: &payments_oss::PaymentData<F>,
+) {
+    let Some(is_successful) = get_attempt_outcome(payment_data.payment_attempt.status) else {
+        return;
+    };
+
+    let (Some(profile_id), Some(connector)) = (
+        payment_data.payment_intent.profile_id.clone(),
+        payment_data
+            .payment_attempt
+            .connector
+            .as_ref()
+            .and_then(|connector| api_enums::RoutableConnectors::from_str(connector).ok()),
+    ) else {
+        return;
+    };
+
+    let backend_input = match make_dsl_input(payment_data) {
+        Ok(backend_input) => backend_input,
+        Err(error) => {
+            logger::error!(?error, "Failed to construct success rate bucket of payment");
+            return;
+        }
+    };
+
+    let state = state.clone();
+    let merchant_id = payment_data.payment_attempt.merchant_id.clone();
+    let merchant_connector_id = payment_data.payment_attempt.merchant_connector_id.clone();
+
+    tokio::spawn(
+        async move {
+            update_success_rate_window(
+                &state,
+                &merchant_id,
+                &profile_id,
+                connector,
+                merchant_connector_id,
+                &backend_input,
+                is_successful,
+            )
+            .await
+            .map_err(|error| logger::error!(?error, "Failed to update success rate window"))
+        }
+        .in_current_span(),
+    );
+}
+
+/// Loads the active payment routing algorithm of the profile into the routing cache, `None` if the
+/// profile has no active algorithm
+async fn load_active_algorithm(
+    state: &SessionState,
+    merchant_id: &str,
+    profile_id: &str,
+) -> RoutingResult<Option<Arc<CachedAlgorithm>>> {
+    let business_profile = state
+        .store
+        .find_business_profile_by_profile_id(profile_id)
+        .await
+        .change_context(errors::RoutingError::ProfileNotFound)?;
+
+    let algorithm_ref = business_profile
+        .routing_algorithm
+        .map(|algorithm| {
+            algorithm.parse_value::<routing_types::RoutingAlgorithmRef>("RoutingAlgorithmRef")
+        })
+        .transpose()
+        .change_context(errors::RoutingError::InvalidRoutingAlgorithmStructure)?
+        .unwrap_or_default();
+
+    let Some(algorithm_id) = algorithm_ref.algorithm_id else {
+        return Ok(None);
+    };
+
+    ensure_algorithm_cached_v1(
+        state,
+        merchant_id,
+        &algorithm_id,
+        Some(profile_id.to_owned()),
+        &api_enums::TransactionType::Payment,
+    )
+    .await
+    .map(Some)
+}
+
+async fn update_success_rate_window(
+    state: &SessionState,
+    merchant_id: &str,
+    profile_id: &str,
+    connector: api_enums::RoutableConnectors,
+    merchant_connector_id: Option<String>,
+    backend_input: &dsl_inputs::BackendInput,
+    is_successful: bool,
+) -> RoutingResult<()> {
+    // The algorithm is looked up in the routing cache first, where the instances routing the
+    // payments of the profile hold it, for the database not to be queried for every payment
+    let cached_algorithm = match ROUTING_CACHE
+        .get_val::<Arc<CachedAlgorithm>>(CacheKey {
+            key: get_routing_cache_key(
+                merchant_id,
+                profile_id,
+                &api_enums::TransactionType::Payment,
+            ),
+            prefix: state.tenant.redis_key_prefix.clone(),
+        })
+        .await
+    {
+        Some(cached_algorithm) => cached_algorithm,
+        None => {
+            metrics::ROUTING_SUCCESS_RATE_WINDOW_CACHE_MISS.add(&metrics::CONTEXT, 1, &[]);
+            let Some(cached_algorithm) =
+                load_active_algorithm(state, merchant_id, profile_id).await?
+            else {
+                return Ok(());
+            };
+            cached_algorithm
+        }
+    };
+
+    let CachedAlgorithm::SuccessRateBased(config) = cached_algorithm.as_ref() else {
+        return Ok(());
+    };
+
+    let Some(choice) = config.connectors.iter().find(|choice| {
+        choice.connector == connector
+            && choice
+                .merchant_connector_id
+                .as_ref()
+                .map_or(true, |mca_id| {
+                    merchant_connector_id.as_ref() == Some(mca_id)
+                })
+    }) else {
+        return Ok(());
+    };
+
+    let key = get_success_rate_window_key(profile_id, choice, backend_input);
+    let outcome = if is_successful {
+        SUCCESSFUL_OUTCOME
+    } else {
+        FAILED_OUTCOME
+    };
+
+    let redis_conn = state
+        .store
+        .get_redis_conn()
+        .change_context(errors::RoutingError::SuccessRateWindowUpdateFailed)
+        .attach_printable("Failed to get redis connection")?;
+
+    redis_conn
+        .prepend_to_capped_list(
+            &key,
+            outcome,
+            i64::from(config.window_size),
+            SUCCESS_RATE_WINDOW_TTL_IN_SECS,
+        )
+        .await
+        .change_context(errors::RoutingError::SuccessRateWindowUpdateFailed)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use api_models::enums::RoutableConnectors;
+
+    use super::*;
+
+    fn choice(connector: RoutableConnectors) -> routing_types::RoutableConnectorChoice {
+        routing_types::RoutableConnectorChoice {
+            choice_kind: routing_types::RoutableChoiceKind::FullStruct,
+            connector,
+            merchant_connector_id: None,
+        }
+    }
+
+    fn window(successful: usize, failed: usize) -> Vec<String> {
+        std::iter::repeat(SUCCESSFUL_OUTCOME.to_string())
+            .take(successful)
+            .chain(std::iter::repeat(FAILED_OUTCOME.to_string()).take(failed))
+            .collect()
+    }
+
+    #[test]
+    fn test_success_rate_requires_min_sample_size() {
+        assert_eq!(calculate_success_rate(&window(3, 1), 5), None);
+        assert_eq!(calculate_success_rate(&[], 0), None);
+        assert_eq!(calculate_success_rate(&window(3, 1), 4), Some(0.75));
+    }
+
+    #[test]
+    fn test_connectors_ordered_by_success_rate() {
+        let connectors = vec![
+            choice(RoutableConnectors::Stripe),
+            choice(RoutableConnectors::Adyen),
+            choice(RoutableConnectors::Checkout),
+            choice(RoutableConnectors::Paypal),
+        ];
+
+        let ordered = order_connectors_by_success_rate(
+            connectors,
+            vec![Some(0.5), None, Some(0.9), None],
+            None,
+        );
+
+        assert_eq!(
+            ordered
+                .into_iter()
+                .map(|choice| choice.connector)
+                .collect::<Vec<_>>(),
+            vec![
+                RoutableConnectors::Checkout,
+                RoutableConnectors::Stripe,
+                RoutableConnectors::Adyen,
+                RoutableConnectors::Paypal,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_exploration_moves_chosen_connector_to_front() {
+        let connectors = vec![
+            choice(RoutableConnectors::Stripe),
+            choice(RoutableConnectors::Adyen),
+            choice(RoutableConnectors::Checkout),
+        ];
+
+        let ordered = order_connectors_by_success_rate(
+            connectors,
+            vec![Some(0.2), Some(0.1), Some(0.9)],
+            Some(1),
+        );
+
+        assert_eq!(
+            ordered
+                .into_iter()
+                .map(|choice| choice.connector)
+                .collect::<Vec<_>>(),
+            vec![
+                RoutableConnectors::Adyen,
+                RoutableConnectors::Checkout,
+                RoutableConnectors::Stripe,
+            ]
+        );
+    }
+}
@@ -14,7 +14,7 @@ use hyperswitch_domain_models::payments::payment_intent::CustomerData;
 use masking::{ExposeInterface, Maskable, PeekInterface, Secret};
 use router_env::{instrument, metrics::add_attributes, tracing};
 
-use super::{flows::Feature, types::AuthenticationData, PaymentData};
+use super::{dcc, flows::Feature, types::AuthenticationData, PaymentData};
 use crate::{
     configs::settings::ConnectorRequestReferenceIdConfig,
     connector::{Helcim, Nexinets},
@@ -702,6 +702,16 @@ where
             )
         }
 
+        // next action for the offer to pay in the currency of the card, until the customer decides on it
+        if let Some(dcc_offer) = payment_attempt
+            .dcc_offer
+            .clone()
+            .filter(|dcc_offer| dcc_offer.is_pending())
+        {
+            next_action_response =
+                Some(api_models::payments::NextActionData::DisplayDccOffer { dcc_offer });
+        }
+
         let mut response: api::PaymentsResponse = Default::default();
         let routed_through = payment_attempt.connector.clone();
 
@@ -890,6 +900,7 @@ where
                 .set_charges(charges_response)
                 .set_frm_metadata(payment_intent.frm_metadata)
                 .set_merchant_order_reference_id(payment_intent.merchant_order_reference_id)
+                .set_dcc_offer(payment_attempt.dcc_offer)
                 .to_owned(),
             headers,
         ))
@@ -1325,6 +1336,13 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsAuthoriz
             .map(|surcharge_details| surcharge_details.final_amount)
             .unwrap_or(payment_data.amount.into());
 
+        // The payment is authorized in the currency of the card if the customer accepted to
+        let (amount, currency) = dcc::get_connector_amount_and_currency(
+            payment_data.payment_attempt.dcc_offer.as_ref(),
+            amount,
+            payment_data.currency,
+        )?;
+
         let customer_name = additional_data
             .customer_data
             .as_ref()
@@ -1369,7 +1387,7 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsAuthoriz
             capture_method: payment_data.payment_attempt.capture_method,
             amount: amount.get_amount_as_i64(),
             minor_amount: amount,
-            currency: payment_data.currency,
+            currency,
             browser_info,
             email: payment_data.email,
             customer_name,
@@ -1416,6 +1434,12 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsSyncData
             .as_ref()
             .map(|surcharge_details| surcharge_details.final_amount)
             .unwrap_or(payment_data.amount.into());
+        let (amount, currency) = dcc::get_connector_amount_and_currency(
+            payment_data.payment_attempt.dcc_offer.as_ref(),
+            amount,
+            payment_data.currency,
+        )
+        .map_err(|error| error.current_context().clone())?;
         Ok(Self {
             amount,
             integrity_object: None,
@@ -1436,7 +1460,7 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsSyncData
                 None => types::SyncRequestType::SinglePaymentSync,
             },
             payment_method_type: payment_data.payment_attempt.payment_method_type,
-            currency: payment_data.currency,
+            currency,
             payment_experience: payment_data.payment_attempt.payment_experience,
         })
     }
@@ -1535,11 +1559,21 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsCaptureD
             .change_context(errors::ApiErrorResponse::InvalidDataValue {
                 field_name: "browser_info",
             })?;
-        let amount = MinorUnit::from(payment_data.amount);
+        let dcc_offer = payment_data.payment_attempt.dcc_offer.as_ref();
+        let (amount_to_capture, currency) = dcc::get_connector_amount_and_currency(
+            dcc_offer,
+            amount_to_capture,
+            payment_data.currency,
+        )?;
+        let (amount, _) = dcc::get_connector_amount_and_currency(
+            dcc_offer,
+            MinorUnit::from(payment_data.amount),
+            payment_data.currency,
+        )?;
         Ok(Self {
             amount_to_capture: amount_to_capture.get_amount_as_i64(), // This should be removed once we start moving to connector module
             minor_amount_to_capture: amount_to_capture,
-            currency: payment_data.currency,
+            currency,
             connector_transaction_id: connector
                 .connector
                 .connector_transaction_id(payment_data.payment_attempt.clone())?
@@ -1584,11 +1618,15 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsCancelDa
             .change_context(errors::ApiErrorResponse::InvalidDataValue {
                 field_name: "browser_info",
             })?;
-        let amount = MinorUnit::from(payment_data.amount);
+        let (amount, currency) = dcc::get_connector_amount_and_currency(
+            payment_data.payment_attempt.dcc_offer.as_ref(),
+            MinorUnit::from(payment_data.amount),
+            payment_data.currency,
+        )?;
         Ok(Self {
             amount: Some(amount.get_amount_as_i64()), // This should be removed once we start moving to connector module
             minor_amount: Some(amount),
-            currency: Some(payment_data.currency),
+            currency: Some(currency),
             connector_transaction_id: connector
                 .connector
                 .connector_transaction_id(payment_data.payment_attempt.clone())?
@@ -1812,6 +1850,11 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::CompleteAuthoriz
             .as_ref()
             .map(|surcharge_details| surcharge_details.final_amount)
             .unwrap_or(payment_data.amount.into());
+        let (amount, currency) = dcc::get_connector_amount_and_currency(
+            payment_data.payment_attempt.dcc_offer.as_ref(),
+            amount,
+            payment_data.currency,
+        )?;
         let complete_authorize_url = Some(helpers::create_complete_authorize_url(
             router_base_url,
             attempt,
@@ -1827,7 +1870,7 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::CompleteAuthoriz
             capture_method: payment_data.payment_attempt.capture_method,
             amount: amount.get_amount_as_i64(), // need to change once we move to connector module
             minor_amount: amount,
-            currency: payment_data.currency,
+            currency,
             browser_info,
             email: payment_data.email,
             payment_method_data: payment_data.payment_method_data.map(From::from),
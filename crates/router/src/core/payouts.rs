@@ -2461,6 +2461,7 @@ pub async fn add_external_account_addition_task(
         tag,
         tracking_data,
         schedule_time,
+        Some(payout_data.payouts.merchant_id.clone()),
     )
     .map_err(errors::StorageError::from)?;
 
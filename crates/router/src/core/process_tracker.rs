@@ -0,0 +1,352 @@
+use std::str::FromStr;
+
+use api_models::process_tracker as api;
+use common_utils::fp_utils::when;
+use diesel_models::process_tracker::{
+    ProcessTrackerEvent, ProcessTrackerEventKind, ProcessTrackerListConstraints,
+};
+use error_stack::{report, ResultExt};
+use router_env::{instrument, logger, tracing};
+use scheduler::{utils as pt_utils, SchedulerFlow};
+use time::PrimitiveDateTime;
+
+use crate::{
+    core::errors::{self, RouterResponse, RouterResult},
+    routes::SessionState,
+    services::ApplicationResponse,
+    types::storage::{self, enums as storage_enums},
+};
+
+const PROCESS_TRACKER_TASKS_LIST_MAX_LIMIT: i64 = 100;
+
+/// Tasks stuck in the `process_started` status for longer than this are considered to be in
+/// limbo, unless the operator specifies otherwise.
+const DEFAULT_LIMBO_TASK_AGE_IN_SECONDS: i64 = 60 * 60;
+
+#[instrument(skip(state))]
+pub async fn list_tasks(
+    state: SessionState,
+    constraints: api::ProcessTrackerTaskListConstraints,
+) -> RouterResponse<api::ProcessTrackerTaskListResponse> {
+    let limit = constraints
+        .limit
+        .map_or(PROCESS_TRACKER_TASKS_LIST_MAX_LIMIT, i64::from);
+    when(limit > PROCESS_TRACKER_TASKS_LIST_MAX_LIMIT, || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "`limit` must be a number less than or equal to {PROCESS_TRACKER_TASKS_LIST_MAX_LIMIT}"
+            ),
+        }))
+    })?;
+
+    let data = state
+        .store
+        .list_processes(ProcessTrackerListConstraints {
+            runner: constraints
+                .runner
+                .as_deref()
+                .map(parse_runner)
+                .transpose()?,
+            statuses: constraints
+                .status
+                .as_deref()
+                .map(parse_status)
+                .transpose()?
+                .map(|status| vec![status]),
+            merchant_id: constraints.merchant_id,
+            scheduled_after: constraints.scheduled_after,
+            scheduled_before: constraints.scheduled_before,
+            updated_before: None,
+            limit: Some(limit),
+            offset: constraints.offset.map(i64::from),
+        })
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to list process tracker tasks")?
+        .into_iter()
+        .map(|task| to_task_response(task, false))
+        .collect::<Vec<_>>();
+
+    Ok(ApplicationResponse::Json(
+        api::ProcessTrackerTaskListResponse {
+            count: data.len(),
+            data,
+        },
+    ))
+}
+
+#[instrument(skip(state))]
+pub async fn retrieve_task(
+    state: SessionState,
+    task_id: String,
+) -> RouterResponse<api::ProcessTrackerTaskResponse> {
+    let task = find_task(&state, &task_id).await?;
+
+    Ok(ApplicationResponse::Json(to_task_response(task, true)))
+}
+
+/// Schedules the task to be picked by the producer right away, regardless of when it was
+/// scheduled to run.
+#[instrument(skip(state))]
+pub async fn run_task(
+    state: SessionState,
+    task_id: String,
+) -> RouterResponse<api::ProcessTrackerTaskResponse> {
+    let task = find_task(&state, &task_id).await?;
+
+    schedule_task(
+        &state,
+        task,
+        common_utils::date_time::now(),
+        ProcessTrackerEventKind::ForcedRun,
+    )
+    .await
+    .map(ApplicationResponse::Json)
+}
+
+/// Schedules the task to run at the specified time. Finished tasks are scheduled to run again.
+#[instrument(skip(state))]
+pub async fn reschedule_task(
+    state: SessionState,
+    task_id: String,
+    request: api::ProcessTrackerTaskRescheduleRequest,
+) -> RouterResponse<api::ProcessTrackerTaskResponse> {
+    let task = find_task(&state, &task_id).await?;
+
+    schedule_task(
+        &state,
+        task,
+        request.schedule_time,
+        ProcessTrackerEventKind::Rescheduled,
+    )
+    .await
+    .map(ApplicationResponse::Json)
+}
+
+/// Cancels the task, which is finished without being run. Recurring tasks cannot be cancelled,
+/// they are to be paused instead.
+#[instrument(skip(state))]
+pub async fn cancel_task(
+    state: SessionState,
+    task_id: String,
+) -> RouterResponse<api::ProcessTrackerTaskResponse> {
+    let task = find_task(&state, &task_id).await?;
+    when(task.recurrence.is_some(), || {
+        Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: "Recurring tasks cannot be cancelled, pause the recurring job instead"
+                .to_owned(),
+        }))
+    })?;
+
+    let event = task.events_with(ProcessTrackerEvent::new(
+        ProcessTrackerEventKind::Cancelled,
+        &task,
+        task.retry_count,
+        task.schedule_time,
+    ));
+    let cancelled_task = state
+        .store
+        .update_process_if_status(
+            &task.id,
+            vec![
+                storage_enums::ProcessTrackerStatus::New,
+                storage_enums::ProcessTrackerStatus::Pending,
+                storage_enums::ProcessTrackerStatus::Paused,
+            ],
+            storage::ProcessTrackerUpdate::StatusEventUpdate {
+                status: storage_enums::ProcessTrackerStatus::Finish,
+                business_status: Some(storage::business_status::CANCELLED.to_owned()),
+                retry_count: None,
+                schedule_time: None,
+                event,
+            },
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to cancel process tracker task")?
+        .ok_or_else(|| {
+            report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: format!("The task cannot be cancelled while it is {}", task.status),
+            })
+        })?;
+
+    Ok(ApplicationResponse::Json(to_task_response(
+        cancelled_task,
+        true,
+    )))
+}
+
+/// Resets the tasks picked by a consumer which never completed them, for example because it was
+/// shut down while running them, and hands them over to the consumers again.
+#[instrument(skip(state))]
+pub async fn reset_limbo_tasks(
+    state: SessionState,
+    request: api::LimboTasksResetRequest,
+) -> RouterResponse<api::LimboTasksResetResponse> {
+    let scheduler_settings = state
+        .conf
+        .scheduler
+        .as_ref()
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Scheduler settings are not configured")?;
+    let limit = request
+        .limit
+        .map_or(PROCESS_TRACKER_TASKS_LIST_MAX_LIMIT, i64::from);
+    let started_before = request.started_before.unwrap_or_else(|| {
+        common_utils::date_time::now()
+            .saturating_sub(time::Duration::seconds(DEFAULT_LIMBO_TASK_AGE_IN_SECONDS))
+    });
+
+    let tasks = state
+        .store
+        .list_processes(ProcessTrackerListConstraints {
+            runner: request.runner.as_deref().map(parse_runner).transpose()?,
+            statuses: Some(vec![storage_enums::ProcessTrackerStatus::ProcessStarted]),
+            updated_before: Some(started_before),
+            limit: Some(limit),
+            ..Default::default()
+        })
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to list process tracker tasks in limbo")?;
+
+    let mut task_ids = Vec::with_capacity(tasks.len());
+    for batch in pt_utils::divide(tasks, scheduler_settings) {
+        let batch_task_ids = batch
+            .trackers
+            .iter()
+            .map(|task| task.id.clone())
+            .collect::<Vec<_>>();
+        match pt_utils::update_status_and_append(
+            state.store.as_scheduler(),
+            SchedulerFlow::Cleaner,
+            batch,
+        )
+        .await
+        {
+            Ok(()) => task_ids.extend(batch_task_ids),
+            Err(error) => {
+                logger::error!(?error, task_ids = ?batch_task_ids, "Failed to reset tasks in limbo")
+            }
+        }
+    }
+
+    Ok(ApplicationResponse::Json(api::LimboTasksResetResponse {
+        reset_count: task_ids.len(),
+        task_ids,
+    }))
+}
+
+async fn schedule_task(
+    state: &SessionState,
+    task: storage::ProcessTracker,
+    schedule_time: PrimitiveDateTime,
+    kind: ProcessTrackerEventKind,
+) -> RouterResult<api::ProcessTrackerTaskResponse> {
+    // Tasks which were never run are kept new, for workflows relying on the status of their
+    // first run
+    let status = if task.status == storage_enums::ProcessTrackerStatus::New {
+        storage_enums::ProcessTrackerStatus::New
+    } else {
+        storage_enums::ProcessTrackerStatus::Pending
+    };
+    let event = task.events_with(ProcessTrackerEvent::new(
+        kind,
+        &task,
+        task.retry_count,
+        Some(schedule_time),
+    ));
+
+    let scheduled_task = state
+        .store
+        .update_process_if_status(
+            &task.id,
+            vec![
+                storage_enums::ProcessTrackerStatus::New,
+                storage_enums::ProcessTrackerStatus::Pending,
+                storage_enums::ProcessTrackerStatus::Finish,
+            ],
+            storage::ProcessTrackerUpdate::StatusEventUpdate {
+                status,
+                business_status: Some(storage::business_status::PENDING.to_owned()),
+                retry_count: None,
+                schedule_time: Some(schedule_time),
+                event,
+            },
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to schedule process tracker task")?
+        .ok_or_else(|| {
+            report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: format!("The task cannot be scheduled while it is {}", task.status),
+            })
+        })?;
+
+    Ok(to_task_response(scheduled_task, true))
+}
+
+async fn find_task(state: &SessionState, task_id: &str) -> RouterResult<storage::ProcessTracker> {
+    state
+        .store
+        .find_process_by_id(task_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to find process tracker task")?
+        .ok_or_else(|| {
+            report!(errors::ApiErrorResponse::GenericNotFoundError {
+                message: format!("Task `{task_id}` does not exist"),
+            })
+        })
+}
+
+fn parse_runner(runner: &str) -> RouterResult<storage::ProcessTrackerRunner> {
+    storage::ProcessTrackerRunner::from_str(runner).map_err(|_| {
+        report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!("`{runner}` is not a valid runner"),
+        })
+    })
+}
+
+fn parse_status(status: &str) -> RouterResult<storage_enums::ProcessTrackerStatus> {
+    storage_enums::ProcessTrackerStatus::from_str(status).map_err(|_| {
+        report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!("`{status}` is not a valid task status"),
+        })
+    })
+}
+
+fn to_task_response(
+    task: storage::ProcessTracker,
+    with_history: bool,
+) -> api::ProcessTrackerTaskResponse {
+    let history = with_history.then(|| {
+        task.events()
+            .into_iter()
+            .map(|event| api::ProcessTrackerTaskEvent {
+                kind: event.kind.to_string(),
+                retry_count: event.retry_count,
+                schedule_time: event.schedule_time,
+                previous_business_status: event.previous_business_status,
+                created_at: event.created_at,
+            })
+            .collect()
+    });
+
+    api::ProcessTrackerTaskResponse {
+        task_id: task.id,
+        name: task.name,
+        runner: task.runner,
+        merchant_id: task.merchant_id,
+        status: task.status.to_string(),
+        business_status: task.business_status,
+        retry_count: task.retry_count,
+        schedule_time: task.schedule_time,
+        tag: task.tag,
+        tracking_data: task.tracking_data,
+        recurring: task.recurrence.is_some(),
+        history,
+        created_at: task.created_at,
+        updated_at: task.updated_at,
+    }
+}
@@ -0,0 +1,256 @@
+use api_models::process_tracker as api;
+use common_utils::fp_utils::when;
+use error_stack::{report, ResultExt};
+use router_env::{instrument, tracing};
+use scheduler::recurrence::{self, SystemClock};
+
+use crate::{
+    core::errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+    routes::SessionState,
+    services::ApplicationResponse,
+    types::storage::{self, enums as storage_enums},
+};
+
+const RECURRING_JOBS_LIST_MAX_LIMIT: i64 = 100;
+const RECURRING_JOB_TAG: &str = "RECURRING_JOB";
+const DEFAULT_RECURRING_JOB_TIMEZONE: &str = "UTC";
+
+/// Creates a recurring job for the merchant, scheduled for the first run of its schedule after
+/// the current time
+#[instrument(skip(state))]
+pub async fn create_recurring_job(
+    state: SessionState,
+    merchant_id: String,
+    request: api::RecurringJobCreateRequest,
+) -> RouterResponse<api::RecurringJobResponse> {
+    let runner = request
+        .runner
+        .parse::<storage::ProcessTrackerRunner>()
+        .map_err(|_| errors::ApiErrorResponse::InvalidRequestData {
+            message: format!("`{}` is not a workflow of the scheduler", request.runner),
+        })?;
+    let missed_run_policy = request
+        .missed_run_policy
+        .as_deref()
+        .map(str::parse::<recurrence::MissedRunPolicy>)
+        .transpose()
+        .map_err(|_| errors::ApiErrorResponse::InvalidRequestData {
+            message: "`missed_run_policy` must be either `skip` or `catch_up`".to_owned(),
+        })?
+        .unwrap_or(recurrence::MissedRunPolicy::Skip);
+
+    let run = recurrence::first_run(
+        &request.cron_expression,
+        request
+            .timezone
+            .as_deref()
+            .unwrap_or(DEFAULT_RECURRING_JOB_TIMEZONE),
+        missed_run_policy,
+        &SystemClock,
+    )
+    .map_err(|error| {
+        report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: error.current_context().to_string(),
+        })
+    })?;
+
+    state
+        .store
+        .get_merchant_key_store_by_merchant_id(
+            &(&state).into(),
+            &merchant_id,
+            &state.store.get_master_key().to_vec().into(),
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let process = storage::ProcessTrackerNew::new(
+        common_utils::generate_id_with_default_len("recurring_job"),
+        request.name,
+        runner,
+        [RECURRING_JOB_TAG],
+        request.tracking_data,
+        run.schedule_time,
+        Some(merchant_id),
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to construct recurring job")?
+    .recurring(run.recurrence);
+
+    let job = state
+        .store
+        .insert_process(process)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to insert recurring job")?;
+
+    to_recurring_job_response(job).map(ApplicationResponse::Json)
+}
+
+#[instrument(skip(state))]
+pub async fn list_recurring_jobs(
+    state: SessionState,
+    merchant_id: String,
+    constraints: api::RecurringJobListConstraints,
+) -> RouterResponse<api::RecurringJobListResponse> {
+    let limit = constraints
+        .limit
+        .map_or(RECURRING_JOBS_LIST_MAX_LIMIT, i64::from);
+    when(limit > RECURRING_JOBS_LIST_MAX_LIMIT, || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "`limit` must be a number less than or equal to {RECURRING_JOBS_LIST_MAX_LIMIT}"
+            ),
+        }))
+    })?;
+
+    let data = state
+        .store
+        .find_recurring_processes_by_merchant_id(
+            &merchant_id,
+            Some(limit),
+            constraints.offset.map(i64::from),
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to list recurring jobs")?
+        .into_iter()
+        .map(to_recurring_job_response)
+        .collect::<RouterResult<Vec<_>>>()?;
+
+    Ok(ApplicationResponse::Json(api::RecurringJobListResponse {
+        count: data.len(),
+        data,
+    }))
+}
+
+/// Pauses the recurring job, which is not scheduled anymore until it is resumed. A job cannot be
+/// paused while one of its runs is being executed.
+#[instrument(skip(state))]
+pub async fn pause_recurring_job(
+    state: SessionState,
+    merchant_id: String,
+    job_id: String,
+) -> RouterResponse<api::RecurringJobResponse> {
+    let job = find_recurring_job(&state, &merchant_id, &job_id).await?;
+
+    let paused_job = state
+        .store
+        .update_process_if_status(
+            &job.id,
+            vec![
+                storage_enums::ProcessTrackerStatus::New,
+                storage_enums::ProcessTrackerStatus::Pending,
+                storage_enums::ProcessTrackerStatus::Finish,
+            ],
+            storage::ProcessTrackerUpdate::StatusUpdate {
+                status: storage_enums::ProcessTrackerStatus::Paused,
+                business_status: None,
+            },
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to pause recurring job")?;
+
+    let paused_job = match paused_job {
+        Some(paused_job) => paused_job,
+        None if job.status == storage_enums::ProcessTrackerStatus::Paused => job,
+        None => Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: "The recurring job cannot be paused while it is running".to_owned(),
+        }))?,
+    };
+
+    to_recurring_job_response(paused_job).map(ApplicationResponse::Json)
+}
+
+/// Resumes the paused recurring job, from the first run of its schedule after the current time.
+/// The runs of the job missed while it was paused are not executed.
+#[instrument(skip(state))]
+pub async fn resume_recurring_job(
+    state: SessionState,
+    merchant_id: String,
+    job_id: String,
+) -> RouterResponse<api::RecurringJobResponse> {
+    let job = find_recurring_job(&state, &merchant_id, &job_id).await?;
+    when(
+        job.status != storage_enums::ProcessTrackerStatus::Paused,
+        || {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: "The recurring job is not paused".to_owned(),
+            }))
+        },
+    )?;
+
+    let recurrence = job
+        .recurrence
+        .as_ref()
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Recurring job without a recurring schedule")?;
+    let run = recurrence::resumed_run(recurrence, &SystemClock)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to schedule the next run of the recurring job")?;
+
+    let resumed_job = state
+        .store
+        .update_process_if_status(
+            &job.id,
+            vec![storage_enums::ProcessTrackerStatus::Paused],
+            storage::ProcessTrackerUpdate::RecurrenceUpdate {
+                schedule_time: run.schedule_time,
+                recurrence: run.recurrence,
+            },
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to resume recurring job")?
+        // The job was resumed concurrently
+        .ok_or(errors::ApiErrorResponse::PreconditionFailed {
+            message: "The recurring job is not paused".to_owned(),
+        })?;
+
+    to_recurring_job_response(resumed_job).map(ApplicationResponse::Json)
+}
+
+async fn find_recurring_job(
+    state: &SessionState,
+    merchant_id: &str,
+    job_id: &str,
+) -> RouterResult<storage::ProcessTracker> {
+    state
+        .store
+        .find_process_by_id(job_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to find recurring job")?
+        // Jobs of other merchants are reported as not found, for their existence not to be leaked
+        .filter(|job| job.recurrence.is_some() && job.merchant_id.as_deref() == Some(merchant_id))
+        .ok_or_else(|| {
+            report!(errors::ApiErrorResponse::GenericNotFoundError {
+                message: format!("Recurring job `{job_id}` does not exist"),
+            })
+        })
+}
+
+fn to_recurring_job_response(
+    job: storage::ProcessTracker,
+) -> RouterResult<api::RecurringJobResponse> {
+    let recurrence = job
+        .recurrence
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Recurring job without a recurring schedule")?;
+
+    Ok(api::RecurringJobResponse {
+        job_id: job.id,
+        merchant_id: job.merchant_id.unwrap_or_default(),
+        runner: job.runner,
+        name: job.name,
+        status: job.status.to_string(),
+        cron_expression: recurrence.cron_expression,
+        timezone: recurrence.timezone,
+        missed_run_policy: recurrence.missed_run_policy.to_string(),
+        scheduled_for: recurrence.scheduled_for,
+        schedule_time: job.schedule_time,
+        created_at: job.created_at,
+        updated_at: job.updated_at,
+    })
+}
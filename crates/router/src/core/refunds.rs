@@ -1395,6 +1395,7 @@ pub async fn add_refund_sync_task(
         tag,
         refund_workflow_tracking_data,
         schedule_time,
+        Some(refund.merchant_id.clone()),
     )
     .change_context(errors::ApiErrorResponse::InternalServerError)
     .attach_printable("Failed to construct refund sync process tracker task")?;
@@ -1432,6 +1433,7 @@ pub async fn add_refund_execute_task(
         tag,
         refund_workflow_tracking_data,
         schedule_time,
+        Some(refund.merchant_id.clone()),
     )
     .change_context(errors::ApiErrorResponse::InternalServerError)
     .attach_printable("Failed to construct refund execute process tracker task")?;
@@ -9,6 +9,9 @@ use api_models::{
 };
 use diesel_models::routing_algorithm::RoutingAlgorithm;
 use error_stack::ResultExt;
+use hyperswitch_domain_models::payments::payment_intent::{
+    PaymentIntentFetchConstraints, PaymentIntentListParams,
+};
 use rustc_hash::FxHashSet;
 
 use super::payments;
@@ -253,6 +256,276 @@ pub async fn retrieve_routing_config(
     metrics::ROUTING_RETRIEVE_CONFIG_SUCCESS_RESPONSE.add(&metrics::CONTEXT, 1, &[]);
     Ok(service_api::ApplicationResponse::Json(response))
 }
+
+/// Retrieves an advanced routing config as euclid DSL text
+pub async fn retrieve_routing_config_dsl(
+    state: SessionState,
+    merchant_account: domain::MerchantAccount,
+    algorithm_id: RoutingAlgorithmId,
+) -> RouterResponse<()> {
+    metrics::ROUTING_RETRIEVE_CONFIG_DSL.add(&metrics::CONTEXT, 1, &[]);
+    let db = state.store.as_ref();
+
+    let routing_algorithm = db
+        .find_routing_algorithm_by_algorithm_id_merchant_id(
+            &algorithm_id.0,
+            &merchant_account.merchant_id,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::ResourceIdNotFound)?;
+
+    core_utils::validate_and_get_business_profile(
+        db,
+        Some(&routing_algorithm.profile_id),
+        &merchant_account.merchant_id,
+    )
+    .await?
+    .get_required_value("BusinessProfile")
+    .change_context(errors::ApiErrorResponse::ResourceIdNotFound)?;
+
+    let algorithm = routing_algorithm
+        .algorithm_data
+        .parse_value::<routing_types::RoutingAlgorithm>("RoutingAlgorithm")
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("unable to parse routing algorithm")?;
+
+    let routing_types::RoutingAlgorithm::Advanced(program) = algorithm else {
+        return Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: "Only advanced routing configs can be represented in the routing DSL"
+                .to_string(),
+        }
+        .into());
+    };
+
+    metrics::ROUTING_RETRIEVE_CONFIG_DSL_SUCCESS_RESPONSE.add(&metrics::CONTEXT, 1, &[]);
+    Ok(service_api::ApplicationResponse::TextPlain(
+        euclid::frontend::ast::formatter::program(&program),
+    ))
+}
+
+pub async fn simulate_routing_config(
+    state: SessionState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    request: routing_types::RoutingSimulationRequest,
+) -> RouterResponse<routing_types::RoutingSimulationResponse> {
+    metrics::ROUTING_SIMULATE_CONFIG.add(&metrics::CONTEXT, 1, &[]);
+    let db = state.store.as_ref();
+
+    let (algorithm, profile_id) = match request.algorithm {
+        routing_types::RoutingSimulationAlgorithm::AlgorithmId(algorithm_id) => {
+            let routing_algorithm = db
+                .find_routing_algorithm_by_algorithm_id_merchant_id(
+                    &algorithm_id,
+                    &merchant_account.merchant_id,
+                )
+                .await
+                .to_not_found_response(errors::ApiErrorResponse::ResourceIdNotFound)?;
+
+            let algorithm = routing_algorithm
+                .algorithm_data
+                .parse_value::<routing_types::RoutingAlgorithm>("RoutingAlgorithm")
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("unable to parse routing algorithm")?;
+
+            (
+                algorithm,
+                request.profile_id.unwrap_or(routing_algorithm.profile_id),
+            )
+        }
+
+        routing_types::RoutingSimulationAlgorithm::Inline(algorithm) => {
+            let profile_id = request
+                .profile_id
+                .get_required_value("profile_id")
+                .change_context(errors::ApiErrorResponse::MissingRequiredField {
+                    field_name: "profile_id",
+                })
+                .attach_printable("Profile_id not provided for inline routing algorithm")?;
+
+            helpers::validate_connectors_in_routing_config(
+                &state,
+                &key_store,
+                &merchant_account.merchant_id,
+                &profile_id,
+                &algorithm,
+            )
+            .await?;
+
+            (algorithm, profile_id)
+        }
+    };
+
+    core_utils::validate_and_get_business_profile(
+        db,
+        Some(&profile_id),
+        &merchant_account.merchant_id,
+    )
+    .await?
+    .get_required_value("BusinessProfile")
+    .change_context(errors::ApiErrorResponse::BusinessProfileNotFound {
+        id: profile_id.clone(),
+    })?;
+
+    let limit = request
+        .limit
+        .unwrap_or(consts::ROUTING_SIMULATION_DEFAULT_LIMIT);
+    utils::when(
+        limit == 0 || limit > consts::ROUTING_SIMULATION_MAX_LIMIT,
+        || {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "limit should be between 1 and {}",
+                    consts::ROUTING_SIMULATION_MAX_LIMIT
+                ),
+            })
+        },
+    )?;
+
+    let starting_at = request.time_range.start_time;
+    let ending_at = request
+        .time_range
+        .end_time
+        .unwrap_or_else(common_utils::date_time::now);
+    utils::when(ending_at < starting_at, || {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "end_time should not be before start_time".to_string(),
+        })
+    })?;
+
+    let mut replayed_payments = Vec::new();
+    let mut offset = 0;
+    while offset < limit {
+        let page_size = consts::ROUTING_SIMULATION_PAGE_SIZE.min(limit - offset);
+        let constraints = PaymentIntentFetchConstraints::List(Box::new(PaymentIntentListParams {
+            offset,
+            starting_at: Some(starting_at),
+            ending_at: Some(ending_at),
+            amount_filter: None,
+            connector: None,
+            currency: None,
+            status: None,
+            payment_method: None,
+            payment_method_type: None,
+            authentication_type: None,
+            merchant_connector_id: None,
+            profile_id: Some(profile_id.clone()),
+            customer_id: None,
+            starting_after_id: None,
+            ending_before_id: None,
+            limit: Some(page_size),
+        }));
+
+        let page = db
+            .get_filtered_payment_intents_attempt(
+                &(&state).into(),
+                &merchant_account.merchant_id,
+                &constraints,
+                &key_store,
+                merchant_account.storage_scheme,
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to list the payments to replay for routing simulation")?;
+
+        let page_len = page.len();
+        replayed_payments.extend(page);
+        if page_len < usize::try_from(page_size).unwrap_or(usize::MAX) {
+            break;
+        }
+        offset += page_size;
+    }
+
+    let fallback_config =
+        helpers::get_merchant_default_config(db, &profile_id, &enums::TransactionType::Payment)
+            .await?;
+
+    let response = payments::routing::simulation::simulate_routing_algorithm(
+        &state,
+        &key_store,
+        &profile_id,
+        &fallback_config,
+        &algorithm,
+        &replayed_payments,
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("failed to simulate routing algorithm")?;
+
+    metrics::ROUTING_SIMULATE_CONFIG_SUCCESS_RESPONSE.add(&metrics::CONTEXT, 1, &[]);
+    Ok(service_api::ApplicationResponse::Json(response))
+}
+
+pub async fn analyze_routing_eligibility(
+    state: SessionState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    request: routing_types::RoutingEligibilityRequest,
+) -> RouterResponse<routing_types::EligibilityAnalysisResult> {
+    metrics::ROUTING_ANALYZE_ELIGIBILITY.add(&metrics::CONTEXT, 1, &[]);
+    let db = state.store.as_ref();
+
+    let payment_intent = db
+        .find_payment_intent_by_payment_id_merchant_id(
+            &(&state).into(),
+            &request.payment_id,
+            &merchant_account.merchant_id,
+            &key_store,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    let payment_attempt = db
+        .find_payment_attempt_by_payment_id_merchant_id_attempt_id(
+            &payment_intent.payment_id,
+            &merchant_account.merchant_id,
+            &payment_intent.active_attempt.get_id(),
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    let profile_id = payment_intent
+        .profile_id
+        .clone()
+        .get_required_value("profile_id")
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Profile id not found in the payment intent")?;
+
+    let connectors = match request.connectors {
+        Some(connectors) => connectors,
+        None => {
+            helpers::get_merchant_default_config(db, &profile_id, &enums::TransactionType::Payment)
+                .await?
+        }
+    };
+
+    let backend_input = payments::routing::simulation::make_dsl_input_for_stored_payment(
+        &payment_intent,
+        &payment_attempt,
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("failed to make the routing input of the payment")?;
+
+    let result = payments::routing::perform_cgraph_filtering(
+        &state,
+        &key_store,
+        connectors,
+        backend_input,
+        None,
+        Some(profile_id),
+        &enums::TransactionType::Payment,
+        true,
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("failed to analyze the eligibility of the connectors")?;
+
+    metrics::ROUTING_ANALYZE_ELIGIBILITY_SUCCESS_RESPONSE.add(&metrics::CONTEXT, 1, &[]);
+    Ok(service_api::ApplicationResponse::Json(result))
+}
+
 pub async fn unlink_routing_config(
     state: SessionState,
     merchant_account: domain::MerchantAccount,
@@ -403,6 +403,12 @@ pub async fn validate_connectors_in_routing_config(
                 check_connector_selection(&rule.connector_selection)?;
             }
         }
+
+        routing_types::RoutingAlgorithm::SuccessRateBased(config) => {
+            for choice in &config.connectors {
+                check_connector_choice(choice)?;
+            }
+        }
     }
 
     Ok(())
@@ -15,7 +15,7 @@ use maud::{html, PreEscaped};
 use router_env::{instrument, tracing};
 use uuid::Uuid;
 
-use super::payments::helpers;
+use super::payments::{dcc, helpers};
 #[cfg(feature = "payouts")]
 use super::payouts::PayoutData;
 #[cfg(feature = "payouts")]
@@ -259,7 +259,13 @@ pub async fn construct_refund_router_data<'a, F>(
 
     let status = payment_attempt.status;
 
-    let (payment_amount, currency) = money;
+    // A payment the customer accepted to pay in the currency of their card is refunded in it
+    let (payment_amount, payment_currency) = money;
+    let dcc_offer = payment_attempt.dcc_offer.as_ref();
+    let (payment_amount, currency) =
+        dcc::get_connector_amount_and_currency(dcc_offer, payment_amount, payment_currency)?;
+    let (refund_amount, _) =
+        dcc::get_connector_amount_and_currency(dcc_offer, refund.refund_amount, payment_currency)?;
 
     let payment_method_type = payment_attempt
         .payment_method
@@ -329,8 +335,8 @@ pub async fn construct_refund_router_data<'a, F>(
         request: types::RefundsData {
             refund_id: refund.refund_id.clone(),
             connector_transaction_id: refund.connector_transaction_id.clone(),
-            refund_amount: refund.refund_amount.get_amount_as_i64(),
-            minor_refund_amount: refund.refund_amount,
+            refund_amount: refund_amount.get_amount_as_i64(),
+            minor_refund_amount: refund_amount,
             currency,
             payment_amount: payment_amount.get_amount_as_i64(),
             minor_payment_amount: payment_amount,
@@ -510,6 +510,7 @@ pub(crate) async fn add_outgoing_webhook_retry_task_to_process_tracker(
         tag,
         tracking_data,
         schedule_time,
+        Some(business_profile.merchant_id.clone()),
     )
     .map_err(errors::StorageError::from)?;
 
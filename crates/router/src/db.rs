@@ -14,6 +14,7 @@ pub mod dashboard_metadata;
 pub mod dispute;
 pub mod ephemeral_key;
 pub mod events;
+pub mod exchange_rates_history;
 pub mod file;
 pub mod fraud_check;
 pub mod generic_link;
@@ -93,6 +94,7 @@ pub trait StorageInterface:
     + dispute::DisputeInterface
     + ephemeral_key::EphemeralKeyInterface
     + events::EventInterface
+    + exchange_rates_history::ExchangeRatesHistoryInterface
     + file::FileMetadataInterface
     + FraudCheckInterface
     + locker_mock_up::LockerMockUpInterface
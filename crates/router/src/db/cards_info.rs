@@ -1,5 +1,12 @@
-use error_stack::report;
+use std::{collections::BTreeMap, sync::Arc};
+
+use async_bb8_diesel::AsyncConnection;
+use error_stack::{report, ResultExt};
 use router_env::{instrument, tracing};
+use storage_impl::redis::{
+    cache::{self, CacheKey, CacheKind, CARD_INFO_CACHE},
+    kv_store::RedisConnInterface,
+};
 
 use crate::{
     connection,
@@ -9,12 +16,116 @@ use crate::{
     types::storage::cards_info::CardInfo,
 };
 
+/// Key of the card info range index in [`CARD_INFO_CACHE`]
+const CARD_INFO_RANGE_INDEX_KEY: &str = "card_info_range_index";
+
+/// Number of card info rows upserted per statement, which keeps the number of bind parameters of
+/// a statement under the limit of PostgreSQL
+const CARD_INFO_UPSERT_BATCH_SIZE: usize = 1000;
+
+/// Index of card info by the range of BINs it applies to, through which card info is looked up.
+///
+/// BINs of different lengths are indexed separately, and a BIN is looked up by its leading digits
+/// in the ranges of the lengths card info exists for, the longest ranges first, so 8 digit BINs
+/// are only compared when 8 digit ranges have been imported. Ranges of the same length are
+/// expected not to overlap, which is enforced when card info is imported.
+#[derive(Debug, Clone, Default)]
+pub struct CardInfoRangeIndex {
+    /// Card info by the length of its BINs, sorted by the first BIN of its range
+    ranges: BTreeMap<usize, Vec<CardInfo>>,
+}
+
+fn get_range_end(card_info: &CardInfo) -> &str {
+    card_info
+        .card_iin_range_end
+        .as_deref()
+        .unwrap_or(&card_info.card_iin)
+}
+
+impl CardInfoRangeIndex {
+    pub fn new(card_info: impl IntoIterator<Item = CardInfo>) -> Self {
+        let mut ranges = BTreeMap::<usize, Vec<CardInfo>>::new();
+        for card_info in card_info {
+            ranges
+                .entry(card_info.card_iin.len())
+                .or_default()
+                .push(card_info);
+        }
+        for card_info in ranges.values_mut() {
+            card_info.sort_by(|first, second| first.card_iin.cmp(&second.card_iin));
+        }
+
+        Self { ranges }
+    }
+
+    /// Finds the card info of the longest range the BIN is in
+    pub fn find(&self, card_bin: &str) -> Option<&CardInfo> {
+        self.ranges.iter().rev().find_map(|(length, card_info)| {
+            // Ranges and BINs of the same length compare the same as their numeric values
+            let card_bin = card_bin.get(..*length)?;
+            let index = card_info
+                .partition_point(|card_info| card_info.card_iin.as_str() <= card_bin)
+                .checked_sub(1)?;
+
+            card_info
+                .get(index)
+                .filter(|card_info| card_bin <= get_range_end(card_info))
+        })
+    }
+
+    /// Finds two ranges of the same length that overlap, if any
+    pub fn find_overlapping_ranges(&self) -> Option<(&CardInfo, &CardInfo)> {
+        self.ranges.values().find_map(|card_info| {
+            card_info.windows(2).find_map(|window| match window {
+                [first, second] if get_range_end(first) >= second.card_iin.as_str() => {
+                    Some((first, second))
+                }
+                _ => None,
+            })
+        })
+    }
+}
+
 #[async_trait::async_trait]
 pub trait CardsInfoInterface {
     async fn get_card_info(
         &self,
         _card_iin: &str,
     ) -> CustomResult<Option<CardInfo>, errors::StorageError>;
+
+    async fn list_card_info(&self) -> CustomResult<Vec<CardInfo>, errors::StorageError>;
+
+    /// Inserts the card info of new BINs and replaces the card info of existing ones, all in one
+    /// transaction
+    async fn upsert_card_info(
+        &self,
+        data: Vec<CardInfo>,
+    ) -> CustomResult<Vec<CardInfo>, errors::StorageError>;
+}
+
+/// Gets the range index of the card info table from the in-memory cache, building it from the
+/// table on a cache miss. The index is invalidated whenever card info is upserted.
+async fn get_card_info_range_index(
+    store: &Store,
+) -> CustomResult<Arc<CardInfoRangeIndex>, errors::StorageError> {
+    let redis = store
+        .get_redis_conn()
+        .map_err(Into::<errors::StorageError>::into)?;
+    let store = store.clone();
+
+    CARD_INFO_CACHE
+        .get_or_populate(
+            CacheKey {
+                key: CARD_INFO_RANGE_INDEX_KEY.to_string(),
+                prefix: redis.key_prefix.clone(),
+            },
+            || async move {
+                Ok(Arc::new(CardInfoRangeIndex::new(
+                    store.list_card_info().await?,
+                )))
+            },
+        )
+        .await
 }
 
 #[async_trait::async_trait]
@@ -24,11 +135,51 @@ impl CardsInfoInterface for Store {
         &self,
         card_iin: &str,
     ) -> CustomResult<Option<CardInfo>, errors::StorageError> {
+        let range_index = get_card_info_range_index(self)
+            .await
+            .attach_printable("Failed to get the card info range index")?;
+
+        Ok(range_index.find(card_iin).cloned())
+    }
+
+    #[instrument(skip_all)]
+    async fn list_card_info(&self) -> CustomResult<Vec<CardInfo>, errors::StorageError> {
         let conn = connection::pg_connection_read(self).await?;
-        CardInfo::find_by_iin(&conn, card_iin)
+        CardInfo::find_all(&conn)
             .await
             .map_err(|error| report!(errors::StorageError::from(error)))
     }
+
+    #[instrument(skip_all)]
+    async fn upsert_card_info(
+        &self,
+        data: Vec<CardInfo>,
+    ) -> CustomResult<Vec<CardInfo>, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        let upsert = || async {
+            conn.transaction_async(|conn| async move {
+                let mut card_info = Vec::with_capacity(data.len());
+                for batch in data.chunks(CARD_INFO_UPSERT_BATCH_SIZE) {
+                    card_info.extend(
+                        CardInfo::upsert_batch(&conn, batch.to_vec())
+                            .await
+                            .map_err(|error| *error.current_context())?,
+                    );
+                }
+
+                Ok::<_, errors::DatabaseError>(card_info)
+            })
+            .await
+            .map_err(|error| report!(errors::StorageError::from(report!(error))))
+        };
+
+        cache::publish_and_redact(
+            self,
+            CacheKind::CardInfo(CARD_INFO_RANGE_INDEX_KEY.into()),
+            upsert,
+        )
+        .await
+    }
 }
 
 #[async_trait::async_trait]
@@ -38,12 +189,108 @@ impl CardsInfoInterface for MockDb {
         &self,
         card_iin: &str,
     ) -> CustomResult<Option<CardInfo>, errors::StorageError> {
-        Ok(self
-            .cards_info
-            .lock()
-            .await
-            .iter()
-            .find(|ci| ci.card_iin == card_iin)
+        let cards_info = self.cards_info.lock().await;
+        Ok(CardInfoRangeIndex::new(cards_info.iter().cloned())
+            .find(card_iin)
             .cloned())
     }
+
+    async fn list_card_info(&self) -> CustomResult<Vec<CardInfo>, errors::StorageError> {
+        Ok(self.cards_info.lock().await.clone())
+    }
+
+    async fn upsert_card_info(
+        &self,
+        data: Vec<CardInfo>,
+    ) -> CustomResult<Vec<CardInfo>, errors::StorageError> {
+        let mut cards_info = self.cards_info.lock().await;
+        let mut upserted_card_info = Vec::with_capacity(data.len());
+        for card_info in data {
+            let upserted = match cards_info
+                .iter_mut()
+                .find(|existing| existing.card_iin == card_info.card_iin)
+            {
+                Some(existing) => {
+                    *existing = CardInfo {
+                        date_created: existing.date_created,
+                        ..card_info
+                    };
+                    existing.clone()
+                }
+                None => {
+                    cards_info.push(card_info.clone());
+                    card_info
+                }
+            };
+            upserted_card_info.push(upserted);
+        }
+
+        Ok(upserted_card_info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_utils::date_time;
+
+    use super::*;
+
+    fn card_info(card_iin: &str, card_iin_range_end: Option<&str>, issuer: &str) -> CardInfo {
+        CardInfo {
+            card_iin: card_iin.to_string(),
+            card_issuer: Some(issuer.to_string()),
+            card_network: None,
+            card_type: None,
+            card_subtype: None,
+            card_issuing_country: None,
+            bank_code_id: None,
+            bank_code: None,
+            country_code: None,
+            date_created: date_time::now(),
+            last_updated: None,
+            last_updated_provider: None,
+            card_iin_range_end: card_iin_range_end.map(ToString::to_string),
+        }
+    }
+
+    fn find_issuer(index: &CardInfoRangeIndex, card_bin: &str) -> Option<String> {
+        index
+            .find(card_bin)
+            .and_then(|card_info| card_info.card_issuer.clone())
+    }
+
+    #[test]
+    fn test_card_info_range_lookup() {
+        let index = CardInfoRangeIndex::new([
+            card_info("400000", Some("400099"), "range"),
+            card_info("400100", None, "single"),
+            card_info("40000025", Some("40000026"), "extended"),
+        ]);
+
+        assert_eq!(find_issuer(&index, "400000").as_deref(), Some("range"));
+        assert_eq!(find_issuer(&index, "400099").as_deref(), Some("range"));
+        assert_eq!(find_issuer(&index, "400100").as_deref(), Some("single"));
+        assert_eq!(find_issuer(&index, "400101"), None);
+        assert_eq!(find_issuer(&index, "399999"), None);
+
+        // 8 digit BINs fall back to the 6 digit ranges
+        assert_eq!(find_issuer(&index, "40000026").as_deref(), Some("extended"));
+        assert_eq!(find_issuer(&index, "40000027").as_deref(), Some("range"));
+
+        assert!(index.find_overlapping_ranges().is_none());
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_card_info_overlapping_ranges() {
+        let index = CardInfoRangeIndex::new([
+            card_info("400000", Some("400099"), "range"),
+            card_info("400050", None, "single"),
+            card_info("40005000", None, "extended"),
+        ]);
+
+        let (first, second) = index.find_overlapping_ranges().unwrap();
+        assert_eq!(first.card_iin, "400000");
+        assert_eq!(second.card_iin, "400050");
+    }
 }
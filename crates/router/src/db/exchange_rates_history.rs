@@ -0,0 +1,203 @@
+use error_stack::{report, ResultExt};
+use router_env::{instrument, logger, tracing};
+use storage_impl::{redis::kv_store::RedisConnInterface, MockDb};
+use time::PrimitiveDateTime;
+
+use super::Store;
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    db::kafka_store::KafkaStore,
+    types::storage,
+};
+
+/// Expiry of the snapshots cached in redis, in seconds
+const EXCHANGE_RATES_SNAPSHOT_CACHE_EXPIRY: i64 = 24 * 60 * 60;
+
+#[async_trait::async_trait]
+pub trait ExchangeRatesHistoryInterface {
+    async fn insert_exchange_rates_snapshot(
+        &self,
+        snapshot: storage::ExchangeRatesSnapshotNew,
+    ) -> CustomResult<storage::ExchangeRatesSnapshot, errors::StorageError>;
+
+    /// Finds the snapshot of the exchange rates that applied at `as_of`
+    async fn find_exchange_rates_snapshot_as_of(
+        &self,
+        as_of: PrimitiveDateTime,
+    ) -> CustomResult<Option<storage::ExchangeRatesSnapshot>, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl ExchangeRatesHistoryInterface for Store {
+    #[instrument(skip_all)]
+    async fn insert_exchange_rates_snapshot(
+        &self,
+        snapshot: storage::ExchangeRatesSnapshotNew,
+    ) -> CustomResult<storage::ExchangeRatesSnapshot, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        snapshot
+            .insert(&conn)
+            .await
+            .map_err(|error| report!(errors::StorageError::from(error)))
+    }
+
+    #[instrument(skip_all)]
+    async fn find_exchange_rates_snapshot_as_of(
+        &self,
+        as_of: PrimitiveDateTime,
+    ) -> CustomResult<Option<storage::ExchangeRatesSnapshot>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        let Some(id) = storage::ExchangeRatesSnapshot::find_latest_id_by_created_at(&conn, as_of)
+            .await
+            .map_err(|error| report!(errors::StorageError::from(error)))?
+        else {
+            return Ok(None);
+        };
+
+        // Snapshots are never updated, so a snapshot can be cached by its id for as long as it is
+        // kept in the history
+        let redis = self
+            .get_redis_conn()
+            .map_err(Into::<errors::StorageError>::into)?;
+        let key = format!("exchange_rates_snapshot_{id}");
+
+        match redis
+            .get_and_deserialize_key::<storage::ExchangeRatesSnapshot>(
+                &key,
+                "ExchangeRatesSnapshot",
+            )
+            .await
+        {
+            Ok(snapshot) => return Ok(Some(snapshot)),
+            Err(error) => logger::debug!(?error, "Exchange rates snapshot not found in redis"),
+        }
+
+        let snapshot = storage::ExchangeRatesSnapshot::find_by_id(&conn, id)
+            .await
+            .map_err(|error| report!(errors::StorageError::from(error)))?;
+        if let Some(snapshot) = &snapshot {
+            redis
+                .serialize_and_set_key_with_expiry(
+                    &key,
+                    snapshot,
+                    EXCHANGE_RATES_SNAPSHOT_CACHE_EXPIRY,
+                )
+                .await
+                .change_context(errors::StorageError::KVError)
+                .map_err(|error| logger::error!(?error, "Failed to cache exchange rates snapshot"))
+                .ok();
+        }
+
+        Ok(snapshot)
+    }
+}
+
+#[async_trait::async_trait]
+impl ExchangeRatesHistoryInterface for MockDb {
+    async fn insert_exchange_rates_snapshot(
+        &self,
+        snapshot: storage::ExchangeRatesSnapshotNew,
+    ) -> CustomResult<storage::ExchangeRatesSnapshot, errors::StorageError> {
+        let mut exchange_rates_history = self.exchange_rates_history.lock().await;
+        let snapshot = storage::ExchangeRatesSnapshot {
+            id: i32::try_from(exchange_rates_history.len())
+                .change_context(errors::StorageError::MockDbError)?,
+            base_currency: snapshot.base_currency,
+            conversion: snapshot.conversion,
+            created_at: snapshot.created_at,
+        };
+        exchange_rates_history.push(snapshot.clone());
+        Ok(snapshot)
+    }
+
+    async fn find_exchange_rates_snapshot_as_of(
+        &self,
+        as_of: PrimitiveDateTime,
+    ) -> CustomResult<Option<storage::ExchangeRatesSnapshot>, errors::StorageError> {
+        Ok(self
+            .exchange_rates_history
+            .lock()
+            .await
+            .iter()
+            .filter(|snapshot| snapshot.created_at <= as_of)
+            .max_by_key(|snapshot| snapshot.created_at)
+            .cloned())
+    }
+}
+
+#[async_trait::async_trait]
+impl ExchangeRatesHistoryInterface for KafkaStore {
+    #[instrument(skip_all)]
+    async fn insert_exchange_rates_snapshot(
+        &self,
+        snapshot: storage::ExchangeRatesSnapshotNew,
+    ) -> CustomResult<storage::ExchangeRatesSnapshot, errors::StorageError> {
+        self.diesel_store
+            .insert_exchange_rates_snapshot(snapshot)
+            .await
+    }
+
+    #[instrument(skip_all)]
+    async fn find_exchange_rates_snapshot_as_of(
+        &self,
+        as_of: PrimitiveDateTime,
+    ) -> CustomResult<Option<storage::ExchangeRatesSnapshot>, errors::StorageError> {
+        self.diesel_store
+            .find_exchange_rates_snapshot_as_of(as_of)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use common_enums::Currency;
+    use redis_interface::RedisSettings;
+    use time::macros::datetime;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_mockdb_finds_snapshot_as_of() {
+        let mockdb = MockDb::new(&RedisSettings::default()).await.unwrap();
+        for created_at in [
+            datetime!(2023-11-14 23:13:20),
+            datetime!(2023-11-14 22:13:20),
+        ] {
+            mockdb
+                .insert_exchange_rates_snapshot(storage::ExchangeRatesSnapshotNew {
+                    base_currency: Currency::USD,
+                    conversion: serde_json::json!({}),
+                    created_at,
+                })
+                .await
+                .unwrap();
+        }
+
+        let find_created_at = |as_of| {
+            let mockdb = &mockdb;
+            async move {
+                mockdb
+                    .find_exchange_rates_snapshot_as_of(as_of)
+                    .await
+                    .unwrap()
+                    .map(|snapshot| snapshot.created_at)
+            }
+        };
+
+        assert_eq!(find_created_at(datetime!(2023-11-14 22:13:19)).await, None);
+        assert_eq!(
+            find_created_at(datetime!(2023-11-14 22:13:20)).await,
+            Some(datetime!(2023-11-14 22:13:20))
+        );
+        assert_eq!(
+            find_created_at(datetime!(2023-11-14 23:13:19)).await,
+            Some(datetime!(2023-11-14 22:13:20))
+        );
+        assert_eq!(
+            find_created_at(datetime!(2023-11-15 00:00:00)).await,
+            Some(datetime!(2023-11-14 23:13:20))
+        );
+    }
+}
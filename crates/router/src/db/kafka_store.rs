@@ -276,6 +276,17 @@ impl CardsInfoInterface for KafkaStore {
     ) -> CustomResult<Option<storage::CardInfo>, errors::StorageError> {
         self.diesel_store.get_card_info(card_iin).await
     }
+
+    async fn list_card_info(&self) -> CustomResult<Vec<storage::CardInfo>, errors::StorageError> {
+        self.diesel_store.list_card_info().await
+    }
+
+    async fn upsert_card_info(
+        &self,
+        data: Vec<storage::CardInfo>,
+    ) -> CustomResult<Vec<storage::CardInfo>, errors::StorageError> {
+        self.diesel_store.upsert_card_info(data).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -1948,6 +1959,46 @@ impl ProcessTrackerInterface for KafkaStore {
             .find_processes_by_time_status(time_lower_limit, time_upper_limit, status, limit)
             .await
     }
+
+    async fn update_process_if_status(
+        &self,
+        id: &str,
+        statuses: Vec<ProcessTrackerStatus>,
+        process: storage::ProcessTrackerUpdate,
+    ) -> CustomResult<Option<storage::ProcessTracker>, errors::StorageError> {
+        self.diesel_store
+            .update_process_if_status(id, statuses, process)
+            .await
+    }
+
+    async fn find_recurring_processes_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        self.diesel_store
+            .find_recurring_processes_by_merchant_id(merchant_id, limit, offset)
+            .await
+    }
+
+    async fn find_recurring_processes_by_status(
+        &self,
+        statuses: Vec<ProcessTrackerStatus>,
+        time_upper_limit: PrimitiveDateTime,
+        limit: Option<i64>,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        self.diesel_store
+            .find_recurring_processes_by_status(statuses, time_upper_limit, limit)
+            .await
+    }
+
+    async fn list_processes(
+        &self,
+        constraints: diesel_models::process_tracker::ProcessTrackerListConstraints,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        self.diesel_store.list_processes(constraints).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -153,7 +153,9 @@ pub fn mk_app(
             .service(routes::User::server(state.clone()))
             .service(routes::ConnectorOnboarding::server(state.clone()))
             .service(routes::Verify::server(state.clone()))
-            .service(routes::WebhookEvents::server(state.clone()));
+            .service(routes::WebhookEvents::server(state.clone()))
+            .service(routes::RecurringJobs::server(state.clone()))
+            .service(routes::ProcessTracker::server(state.clone()));
     }
 
     #[cfg(feature = "payouts")]
@@ -180,6 +182,162 @@ pub fn mk_app(
     server_app
 }
 
+/// Builds the state of the tenant for commands run from the command line, outside of the server
+#[cfg(feature = "olap")]
+async fn build_cli_session_state(
+    conf: settings::Settings<SecuredSecret>,
+    tenant: &str,
+) -> ApplicationResult<SessionState> {
+    let (tx, _rx) = oneshot::channel();
+    let api_client = Box::new(
+        services::ProxyClient::new(
+            conf.proxy.clone(),
+            services::proxy_bypass_urls(
+                conf.key_manager.get_inner(),
+                &conf.locker,
+                &conf.proxy.bypass_proxy_urls,
+            ),
+        )
+        .map_err(|error| {
+            errors::ApplicationError::ApiClientError(error.current_context().clone())
+        })?,
+    );
+    let state = std::sync::Arc::new(Box::pin(AppState::new(conf, tx, api_client)).await);
+    state.get_session_state(tenant, || {
+        errors::ApplicationError::InvalidConfigurationValueError(format!(
+            "Tenant {tenant} is not configured"
+        ))
+    })
+}
+
+/// Imports card info from a CSV file into the card info table of the tenant, the way the card
+/// info import API does
+#[cfg(feature = "olap")]
+pub async fn import_cards_info(
+    conf: settings::Settings<SecuredSecret>,
+    file: std::path::PathBuf,
+    provider: Option<String>,
+    tenant: String,
+) -> ApplicationResult<()> {
+    let session_state = build_cli_session_state(conf, &tenant).await?;
+
+    let records = core::cards_info::parse_card_info_csv(&std::fs::read(file)?)
+        .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))?;
+    let response = core::cards_info::import_card_info(
+        session_state,
+        api_models::cards_info::CardsInfoImportRequest { provider, records },
+    )
+    .await
+    .map_err(|error| std::io::Error::new(std::io::ErrorKind::Other, format!("{error:?}")))?;
+
+    if let services::ApplicationResponse::Json(response) = response {
+        logger::info!(
+            inserted = response.inserted,
+            updated = response.updated,
+            "Imported card info"
+        );
+    }
+
+    Ok(())
+}
+
+/// Runs a command managing the tasks of the scheduler, printing its result as JSON.
+#[cfg(feature = "olap")]
+pub async fn run_scheduler_command(
+    conf: settings::Settings<SecuredSecret>,
+    tenant: String,
+    command: settings::SchedulerCommand,
+) -> ApplicationResult<()> {
+    use api_models::process_tracker as api;
+
+    use crate::core::process_tracker;
+
+    let session_state = build_cli_session_state(conf, &tenant).await?;
+
+    let response = match command {
+        settings::SchedulerCommand::List {
+            runner,
+            status,
+            merchant_id,
+            scheduled_after,
+            scheduled_before,
+            limit,
+            offset,
+        } => process_tracker::list_tasks(
+            session_state,
+            api::ProcessTrackerTaskListConstraints {
+                runner,
+                status,
+                merchant_id,
+                scheduled_after,
+                scheduled_before,
+                limit,
+                offset,
+            },
+        )
+        .await
+        .map(to_json_response),
+        settings::SchedulerCommand::Show { task_id } => {
+            process_tracker::retrieve_task(session_state, task_id)
+                .await
+                .map(to_json_response)
+        }
+        settings::SchedulerCommand::Run { task_id } => {
+            process_tracker::run_task(session_state, task_id)
+                .await
+                .map(to_json_response)
+        }
+        settings::SchedulerCommand::Reschedule {
+            task_id,
+            schedule_time,
+        } => process_tracker::reschedule_task(
+            session_state,
+            task_id,
+            api::ProcessTrackerTaskRescheduleRequest { schedule_time },
+        )
+        .await
+        .map(to_json_response),
+        settings::SchedulerCommand::Cancel { task_id } => {
+            process_tracker::cancel_task(session_state, task_id)
+                .await
+                .map(to_json_response)
+        }
+        settings::SchedulerCommand::ResetLimbo {
+            runner,
+            started_before,
+            limit,
+        } => process_tracker::reset_limbo_tasks(
+            session_state,
+            api::LimboTasksResetRequest {
+                runner,
+                started_before,
+                limit,
+            },
+        )
+        .await
+        .map(to_json_response),
+    }
+    .map_err(|error| std::io::Error::new(std::io::ErrorKind::Other, format!("{error:?}")))?;
+
+    if let Some(response) = response {
+        let output = serde_json::to_string_pretty(&response)
+            .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))?;
+        println!("{output}");
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "olap")]
+fn to_json_response<T: serde::Serialize>(
+    response: services::ApplicationResponse<T>,
+) -> Option<serde_json::Value> {
+    match response {
+        services::ApplicationResponse::Json(response) => serde_json::to_value(response).ok(),
+        _ => None,
+    }
+}
+
 /// Starts the server
 ///
 /// # Panics
@@ -34,8 +34,12 @@ pub mod payouts;
 #[cfg(any(feature = "olap", feature = "oltp"))]
 pub mod pm_auth;
 pub mod poll;
+#[cfg(feature = "olap")]
+pub mod process_tracker;
 #[cfg(feature = "recon")]
 pub mod recon;
+#[cfg(feature = "olap")]
+pub mod recurring_jobs;
 pub mod refunds;
 #[cfg(feature = "olap")]
 pub mod routing;
@@ -64,7 +68,7 @@ pub use self::app::{
     Refunds, SessionState, User, Webhooks,
 };
 #[cfg(feature = "olap")]
-pub use self::app::{Blocklist, Routing, Verify, WebhookEvents};
+pub use self::app::{Blocklist, ProcessTracker, RecurringJobs, Routing, Verify, WebhookEvents};
 #[cfg(feature = "payouts")]
 pub use self::app::{PayoutLink, Payouts};
 #[cfg(feature = "stripe")]
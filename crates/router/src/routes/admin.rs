@@ -317,6 +317,56 @@ pub async fn payment_connector_list(
     )
     .await
 }
+/// Merchant Connector - Knowledge Graph
+///
+/// Render the knowledge graph built from the Merchant Connectors of the merchant, which determines the connectors eligible for a payment or a payout, in the Graphviz DOT and Mermaid formats
+#[utoipa::path(
+    get,
+    path = "/accounts/{account_id}/connectors/graph",
+    params(
+        ("account_id" = String, Path, description = "The unique identifier for the merchant account"),
+        ("profile_id" = Option<String>, Query, description = "Build the graph from the Merchant Connectors of this business profile only"),
+        ("transaction_type" = Option<TransactionType>, Query, description = "Build the graph used to route transactions of this type, payments by default"),
+        ("connector" = Option<RoutableConnectors>, Query, description = "Prune the graph to the part relevant to this connector"),
+        ("payment_method_type" = Option<PaymentMethodType>, Query, description = "Prune the graph to the part relevant to this payment method type"),
+    ),
+    responses(
+        (status = 200, description = "Merchant Connector knowledge graph rendered successfully", body = MerchantConnectorGraphResponse),
+        (status = 404, description = "Merchant Account does not exist in records"),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Merchant Connector Account",
+    operation_id = "Render the knowledge graph of the Merchant Connectors",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::MerchantConnectorsGraph))]
+pub async fn payment_connector_graph(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<admin::MerchantConnectorGraphQuery>,
+) -> HttpResponse {
+    let flow = Flow::MerchantConnectorsGraph;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        query.into_inner(),
+        |state, _, query, _| get_merchant_connector_graph(state, merchant_id.clone(), query),
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuthMerchantFromRoute {
+                merchant_id: merchant_id.clone(),
+                required_permission: Permission::MerchantConnectorAccountRead,
+            },
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
 /// Merchant Connector - Update
 ///
 /// To update an existing Merchant Connector. Helpful in enabling / disabling different payment methods and other settings for the connector etc.
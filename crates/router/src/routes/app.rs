@@ -12,6 +12,7 @@ use hyperswitch_interfaces::{
     encryption_interface::EncryptionManagementInterface,
     secrets_interface::secret_state::{RawSecret, SecuredSecret},
 };
+use masking::PeekInterface;
 use router_env::tracing_actix_web::RequestId;
 use scheduler::SchedulerInterface;
 use storage_impl::{config::TenantConfig, redis::RedisStore, MockDb};
@@ -33,7 +34,8 @@ use super::verification::{apple_pay_merchant_registration, retrieve_apple_pay_ve
 #[cfg(feature = "olap")]
 use super::{
     admin::*, api_keys::*, apple_pay_certificates_migration, connector_onboarding::*, disputes::*,
-    files::*, gsm::*, payment_link::*, user::*, user_role::*, webhook_events::*,
+    files::*, gsm::*, payment_link::*, process_tracker, recurring_jobs, user::*, user_role::*,
+    webhook_events::*,
 };
 use super::{cache::*, health::*};
 #[cfg(any(feature = "olap", feature = "oltp"))]
@@ -48,6 +50,8 @@ use super::{pm_auth, poll::retrieve_poll_status};
 pub use crate::analytics::opensearch::OpenSearchClient;
 #[cfg(feature = "olap")]
 use crate::analytics::AnalyticsProvider;
+#[cfg(feature = "olap")]
+use crate::routes::cards_info::{cards_info_import, cards_info_import_csv};
 #[cfg(all(feature = "frm", feature = "oltp"))]
 use crate::routes::fraud_check as frm_routes;
 #[cfg(all(feature = "recon", feature = "olap"))]
@@ -234,6 +238,18 @@ impl AppState {
         ))
         .await;
 
+        // Values masked by hashing are tokenized with a key derived from the master key, for the
+        // tokens to be the same across the instances of the application
+        masking::set_hashing_key(blake3::derive_key(
+            "hyperswitch masking hashing key",
+            conf.secrets.get_inner().master_enc_key.peek().as_bytes(),
+        ))
+        .ok();
+
+        #[cfg(feature = "masking_audit")]
+        masking::audit::set_exposure_sink(super::metrics::secret_exposure::SecretExposureMetrics)
+            .ok();
+
         #[allow(clippy::expect_used)]
         let encryption_client = conf
             .encryption_management
@@ -632,6 +648,14 @@ impl Routing {
                     )
                 })),
             )
+            .service(
+                web::resource("/simulate")
+                    .route(web::post().to(cloud_routing::routing_simulate_config)),
+            )
+            .service(
+                web::resource("/eligibility")
+                    .route(web::post().to(cloud_routing::routing_analyze_eligibility)),
+            )
             .service(
                 web::resource("/decision")
                     .route(web::put().to(cloud_routing::upsert_decision_manager_config))
@@ -772,6 +796,10 @@ impl Routing {
                 web::resource("/{algorithm_id}")
                     .route(web::get().to(cloud_routing::routing_retrieve_config)),
             )
+            .service(
+                web::resource("/{algorithm_id}/dsl")
+                    .route(web::get().to(cloud_routing::routing_retrieve_config_dsl)),
+            )
             .service(
                 web::resource("/{algorithm_id}/activate").route(web::post().to(
                     |state, req, path| {
@@ -1059,6 +1087,10 @@ impl MerchantConnectorAccount {
                         .route(web::post().to(payment_connector_create))
                         .route(web::get().to(payment_connector_list)),
                 )
+                .service(
+                    web::resource("/{merchant_id}/connectors/graph")
+                        .route(web::get().to(payment_connector_graph)),
+                )
                 .service(
                     web::resource("/{merchant_id}/connectors/{merchant_connector_id}")
                         .route(web::get().to(payment_connector_retrieve))
@@ -1229,9 +1261,14 @@ pub struct Cards;
 
 impl Cards {
     pub fn server(state: AppState) -> Scope {
-        web::scope("/cards")
-            .app_data(web::Data::new(state))
-            .service(web::resource("/{bin}").route(web::get().to(card_iin_info)))
+        let mut route = web::scope("/cards").app_data(web::Data::new(state));
+        #[cfg(feature = "olap")]
+        {
+            route = route
+                .service(web::resource("/import").route(web::post().to(cards_info_import)))
+                .service(web::resource("/import/csv").route(web::post().to(cards_info_import_csv)));
+        }
+        route.service(web::resource("/{bin}").route(web::get().to(card_iin_info)))
     }
 }
 
@@ -1258,6 +1295,11 @@ impl Cache {
         web::scope("/cache")
             .app_data(web::Data::new(state))
             .service(web::resource("/invalidate/{key}").route(web::post().to(invalidate)))
+            .service(web::resource("/stats").route(web::get().to(statistics)))
+            .service(web::resource("/{cache}/keys").route(web::get().to(list_keys)))
+            .service(
+                web::resource("/{cache}/invalidate/{key}").route(web::post().to(invalidate_key)),
+            )
     }
 }
 
@@ -1539,6 +1581,62 @@ impl ConnectorOnboarding {
     }
 }
 
+#[cfg(feature = "olap")]
+pub struct RecurringJobs;
+
+#[cfg(feature = "olap")]
+impl RecurringJobs {
+    pub fn server(config: AppState) -> Scope {
+        web::scope("/account/{account_id}/recurring_jobs")
+            .app_data(web::Data::new(config))
+            .service(
+                web::resource("")
+                    .route(web::get().to(recurring_jobs::list_recurring_jobs))
+                    .route(web::post().to(recurring_jobs::create_recurring_job)),
+            )
+            .service(
+                web::scope("/{job_id}")
+                    .service(
+                        web::resource("/pause")
+                            .route(web::post().to(recurring_jobs::pause_recurring_job)),
+                    )
+                    .service(
+                        web::resource("/resume")
+                            .route(web::post().to(recurring_jobs::resume_recurring_job)),
+                    ),
+            )
+    }
+}
+
+#[cfg(feature = "olap")]
+pub struct ProcessTracker;
+
+#[cfg(feature = "olap")]
+impl ProcessTracker {
+    pub fn server(config: AppState) -> Scope {
+        web::scope("/process_tracker/tasks")
+            .app_data(web::Data::new(config))
+            .service(web::resource("").route(web::get().to(process_tracker::list_tasks)))
+            .service(
+                web::resource("/reset_limbo")
+                    .route(web::post().to(process_tracker::reset_limbo_tasks)),
+            )
+            .service(
+                web::scope("/{task_id}")
+                    .service(web::resource("").route(web::get().to(process_tracker::retrieve_task)))
+                    .service(web::resource("/run").route(web::post().to(process_tracker::run_task)))
+                    .service(
+                        web::resource("/reschedule")
+                            .route(web::post().to(process_tracker::reschedule_task)),
+                    )
+                    .service(
+                        web::resource("/cancel")
+                            .route(web::post().to(process_tracker::cancel_task)),
+                    ),
+            )
+    }
+}
+
 #[cfg(feature = "olap")]
 pub struct WebhookEvents;
 
@@ -1,4 +1,7 @@
 use actix_web::{web, HttpRequest, Responder};
+use api_models::cache::{
+    CacheKeyInvalidateRequestInternal, CacheKeysListConstraints, CacheKeysListRequestInternal,
+};
 use router_env::{instrument, tracing, Flow};
 
 use super::AppState;
@@ -28,3 +31,72 @@ pub async fn invalidate(
     )
     .await
 }
+
+#[instrument(skip_all, fields(flow = ?Flow::CacheStatistics))]
+pub async fn statistics(state: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    let flow = Flow::CacheStatistics;
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        (),
+        |_, _, _, _| cache::statistics(),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::CacheKeysList))]
+pub async fn list_keys(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<CacheKeysListConstraints>,
+) -> impl Responder {
+    let flow = Flow::CacheKeysList;
+
+    let request_internal = CacheKeysListRequestInternal {
+        cache: path.into_inner(),
+        constraints: query.into_inner(),
+    };
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        request_internal,
+        |state, _, request_internal, _| {
+            cache::list_keys(state, request_internal.cache, request_internal.constraints)
+        },
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::CacheKeyInvalidate))]
+pub async fn invalidate_key(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let flow = Flow::CacheKeyInvalidate;
+
+    let (cache, key) = path.into_inner();
+    let request_internal = CacheKeyInvalidateRequestInternal { cache, key };
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        request_internal,
+        |state, _, request_internal, _| {
+            cache::invalidate_key(state, request_internal.cache, request_internal.key)
+        },
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
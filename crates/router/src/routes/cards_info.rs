@@ -1,4 +1,5 @@
-use actix_web::{web, HttpRequest, Responder};
+use actix_multipart::form::MultipartForm;
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use router_env::{instrument, tracing, Flow};
 
 use super::app::AppState;
@@ -54,3 +55,60 @@ pub async fn card_iin_info(
     ))
     .await
 }
+
+/// Cards Info - Import
+///
+/// Import card information of BINs and BIN ranges in bulk
+#[utoipa::path(
+    post,
+    path = "/cards/import",
+    request_body = CardsInfoImportRequest,
+    responses(
+        (status = 200, description = "Card information imported", body = CardsInfoImportResponse),
+        (status = 400, description = "Invalid card information")
+    ),
+    operation_id = "Import card information",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::CardsInfoImport))]
+pub async fn cards_info_import(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<api_models::cards_info::CardsInfoImportRequest>,
+) -> impl Responder {
+    let flow = Flow::CardsInfoImport;
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        |state, _, req, _| cards_info::import_card_info(state, req),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::CardsInfoImport))]
+pub async fn cards_info_import_csv(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    MultipartForm(form): MultipartForm<cards_info::CardsInfoImportForm>,
+) -> HttpResponse {
+    let flow = Flow::CardsInfoImport;
+    let payload = match cards_info::get_card_info_import_request(form) {
+        Ok(payload) => payload,
+        Err(error) => return api::log_and_return_error_response(error.into()),
+    };
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, _, req, _| cards_info::import_card_info(state, req),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
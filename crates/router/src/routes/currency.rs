@@ -34,6 +34,7 @@ pub async fn convert_forex(
     let amount = params.amount;
     let to_currency = &params.to_currency;
     let from_currency = &params.from_currency;
+    let as_of = params.as_of;
     Box::pin(api::server_wrap(
         flow,
         state.clone(),
@@ -45,6 +46,7 @@ pub async fn convert_forex(
                 amount.get_amount_as_i64(),
                 to_currency.to_string(),
                 from_currency.to_string(),
+                as_of,
             )
         },
         auth::auth_type(
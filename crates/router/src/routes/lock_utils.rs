@@ -36,6 +36,7 @@ pub enum ApiIdentifier {
     Recon,
     Poll,
     ApplePayCertificatesMigration,
+    ProcessTracker,
 }
 
 impl From<Flow> for ApiIdentifier {
@@ -52,6 +53,9 @@ impl From<Flow> for ApiIdentifier {
             | Flow::RoutingLinkConfig
             | Flow::RoutingUnlinkConfig
             | Flow::RoutingRetrieveConfig
+            | Flow::RoutingRetrieveConfigDsl
+            | Flow::RoutingSimulateConfig
+            | Flow::RoutingAnalyzeEligibility
             | Flow::RoutingRetrieveActiveConfig
             | Flow::RoutingRetrieveDefaultConfig
             | Flow::RoutingRetrieveDictionary
@@ -73,7 +77,8 @@ impl From<Flow> for ApiIdentifier {
             | Flow::MerchantConnectorsRetrieve
             | Flow::MerchantConnectorsUpdate
             | Flow::MerchantConnectorsDelete
-            | Flow::MerchantConnectorsList => Self::MerchantConnector,
+            | Flow::MerchantConnectorsList
+            | Flow::MerchantConnectorsGraph => Self::MerchantConnector,
 
             Flow::ConfigKeyCreate
             | Flow::ConfigKeyFetch
@@ -167,11 +172,14 @@ impl From<Flow> for ApiIdentifier {
             | Flow::RetrieveDisputeEvidence
             | Flow::DeleteDisputeEvidence => Self::Disputes,
 
-            Flow::CardsInfo => Self::CardsInfo,
+            Flow::CardsInfo | Flow::CardsInfoImport => Self::CardsInfo,
 
             Flow::CreateFile | Flow::DeleteFile | Flow::RetrieveFile => Self::Files,
 
-            Flow::CacheInvalidate => Self::Cache,
+            Flow::CacheInvalidate
+            | Flow::CacheStatistics
+            | Flow::CacheKeysList
+            | Flow::CacheKeyInvalidate => Self::Cache,
 
             Flow::BusinessProfileCreate
             | Flow::BusinessProfileUpdate
@@ -262,6 +270,17 @@ impl From<Flow> for ApiIdentifier {
             | Flow::ReconVerifyToken => Self::Recon,
 
             Flow::RetrievePollStatus => Self::Poll,
+
+            Flow::RecurringJobsList
+            | Flow::RecurringJobCreate
+            | Flow::RecurringJobPause
+            | Flow::RecurringJobResume
+            | Flow::ProcessTrackerTasksList
+            | Flow::ProcessTrackerTaskRetrieve
+            | Flow::ProcessTrackerTaskRun
+            | Flow::ProcessTrackerTaskReschedule
+            | Flow::ProcessTrackerTaskCancel
+            | Flow::ProcessTrackerLimboTasksReset => Self::ProcessTracker,
         }
     }
 }
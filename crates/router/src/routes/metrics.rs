@@ -1,5 +1,7 @@
 pub mod bg_metrics_collector;
 pub mod request;
+#[cfg(feature = "masking_audit")]
+pub mod secret_exposure;
 pub mod utils;
 
 use router_env::{counter_metric, global_meter, histogram_metric, metrics_context};
@@ -134,3 +136,8 @@ counter_metric!(ACCESS_TOKEN_CACHE_MISS, GLOBAL_METER);
 
 // A counter to indicate the integrity check failures
 counter_metric!(INTEGRITY_CHECK_FAILED, GLOBAL_METER);
+
+// A counter to indicate the exposures of secrets, by how they were exposed and the type of the
+// secret
+#[cfg(feature = "masking_audit")]
+counter_metric!(SECRET_EXPOSURE_COUNT, GLOBAL_METER);
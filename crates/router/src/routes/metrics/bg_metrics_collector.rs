@@ -14,6 +14,7 @@ pub fn spawn_metrics_collector(metrics_collection_interval_in_secs: &Option<u16>
         &cache::PM_FILTERS_CGRAPH_CACHE,
         &cache::DECISION_MANAGER_CACHE,
         &cache::SURCHARGE_CACHE,
+        &cache::CARD_INFO_CACHE,
     ];
 
     tokio::spawn(async move {
@@ -0,0 +1,20 @@
+use masking::audit::{ExposureEvent, ExposureSink};
+use router_env::metrics::add_attributes;
+
+/// Sink of the exposures of secrets, which counts them in [`super::SECRET_EXPOSURE_COUNT`]
+#[derive(Debug, Clone, Copy)]
+pub struct SecretExposureMetrics;
+
+impl ExposureSink for SecretExposureMetrics {
+    fn record(&self, event: &ExposureEvent) {
+        super::SECRET_EXPOSURE_COUNT.add(
+            &super::CONTEXT,
+            1,
+            &add_attributes([
+                ("kind", event.kind.to_string()),
+                ("secret_type", event.secret_type.to_string()),
+                ("strategy", event.strategy.to_string()),
+            ]),
+        );
+    }
+}
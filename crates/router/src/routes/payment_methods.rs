@@ -194,12 +194,17 @@ pub async fn list_payment_method_api(
     json_payload: web::Query<payment_methods::PaymentMethodListRequest>,
 ) -> HttpResponse {
     let flow = Flow::PaymentMethodsList;
-    let payload = json_payload.into_inner();
-    let (auth, _) = match auth::check_client_secret_and_get_auth(req.headers(), &payload) {
-        Ok((auth, _auth_flow)) => (auth, _auth_flow),
+    let mut payload = json_payload.into_inner();
+    let (auth, auth_flow) = match auth::check_client_secret_and_get_auth(req.headers(), &payload) {
+        Ok((auth, auth_flow)) => (auth, auth_flow),
         Err(e) => return api::log_and_return_error_response(e),
     };
 
+    // Ineligibility reasons expose the connector configuration, which is not for the client
+    if matches!(auth_flow, api::AuthFlow::Client) {
+        payload.explain_ineligibility = None;
+    }
+
     Box::pin(api::server_wrap(
         flow,
         state,
@@ -0,0 +1,168 @@
+use actix_web::{web, HttpRequest, Responder};
+use api_models::process_tracker::{
+    LimboTasksResetRequest, ProcessTrackerTaskListConstraints, ProcessTrackerTaskRescheduleRequest,
+    ProcessTrackerTaskRescheduleRequestInternal,
+};
+use router_env::{instrument, tracing, Flow};
+
+use crate::{
+    core::{api_locking, process_tracker},
+    routes::AppState,
+    services::{api, authentication as auth, authorization::permissions::Permission},
+};
+
+#[instrument(skip_all, fields(flow = ?Flow::ProcessTrackerTasksList))]
+pub async fn list_tasks(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<ProcessTrackerTaskListConstraints>,
+) -> impl Responder {
+    let flow = Flow::ProcessTrackerTasksList;
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        query.into_inner(),
+        |state, _, constraints, _| process_tracker::list_tasks(state, constraints),
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuth(Permission::SchedulerManage),
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::ProcessTrackerTaskRetrieve))]
+pub async fn retrieve_task(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::ProcessTrackerTaskRetrieve;
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        path.into_inner(),
+        |state, _, task_id, _| process_tracker::retrieve_task(state, task_id),
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuth(Permission::SchedulerManage),
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::ProcessTrackerTaskRun))]
+pub async fn run_task(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::ProcessTrackerTaskRun;
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        path.into_inner(),
+        |state, _, task_id, _| process_tracker::run_task(state, task_id),
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuth(Permission::SchedulerManage),
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::ProcessTrackerTaskReschedule))]
+pub async fn reschedule_task(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    json_payload: web::Json<ProcessTrackerTaskRescheduleRequest>,
+) -> impl Responder {
+    let flow = Flow::ProcessTrackerTaskReschedule;
+
+    let request_internal = ProcessTrackerTaskRescheduleRequestInternal {
+        task_id: path.into_inner(),
+        request: json_payload.into_inner(),
+    };
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        request_internal,
+        |state, _, request_internal, _| {
+            process_tracker::reschedule_task(
+                state,
+                request_internal.task_id,
+                request_internal.request,
+            )
+        },
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuth(Permission::SchedulerManage),
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::ProcessTrackerTaskCancel))]
+pub async fn cancel_task(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::ProcessTrackerTaskCancel;
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        path.into_inner(),
+        |state, _, task_id, _| process_tracker::cancel_task(state, task_id),
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuth(Permission::SchedulerManage),
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::ProcessTrackerLimboTasksReset))]
+pub async fn reset_limbo_tasks(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<LimboTasksResetRequest>,
+) -> impl Responder {
+    let flow = Flow::ProcessTrackerLimboTasksReset;
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        |state, _, request, _| process_tracker::reset_limbo_tasks(state, request),
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuth(Permission::SchedulerManage),
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
@@ -0,0 +1,164 @@
+use actix_web::{web, HttpRequest, Responder};
+use api_models::process_tracker::{
+    RecurringJobCreateRequest, RecurringJobCreateRequestInternal, RecurringJobListConstraints,
+    RecurringJobListRequestInternal, RecurringJobRequestInternal,
+};
+use router_env::{instrument, tracing, Flow};
+
+use crate::{
+    core::{api_locking, recurring_jobs},
+    routes::AppState,
+    services::{api, authentication as auth, authorization::permissions::Permission},
+};
+
+#[instrument(skip_all, fields(flow = ?Flow::RecurringJobsList))]
+pub async fn list_recurring_jobs(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<RecurringJobListConstraints>,
+) -> impl Responder {
+    let flow = Flow::RecurringJobsList;
+    let merchant_id = path.into_inner();
+
+    let request_internal = RecurringJobListRequestInternal {
+        merchant_id: merchant_id.clone(),
+        constraints: query.into_inner(),
+    };
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        request_internal,
+        |state, _, request_internal, _| {
+            recurring_jobs::list_recurring_jobs(
+                state,
+                request_internal.merchant_id,
+                request_internal.constraints,
+            )
+        },
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuthMerchantFromRoute {
+                merchant_id,
+                required_permission: Permission::MerchantAccountRead,
+            },
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::RecurringJobCreate))]
+pub async fn create_recurring_job(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    json_payload: web::Json<RecurringJobCreateRequest>,
+) -> impl Responder {
+    let flow = Flow::RecurringJobCreate;
+
+    let request_internal = RecurringJobCreateRequestInternal {
+        merchant_id: path.into_inner(),
+        request: json_payload.into_inner(),
+    };
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        request_internal,
+        |state, _, request_internal, _| {
+            recurring_jobs::create_recurring_job(
+                state,
+                request_internal.merchant_id,
+                request_internal.request,
+            )
+        },
+        // The job runs a workflow with the tracking data of the request, so only admins can
+        // create it
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::RecurringJobPause))]
+pub async fn pause_recurring_job(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let flow = Flow::RecurringJobPause;
+    let (merchant_id, job_id) = path.into_inner();
+
+    let request_internal = RecurringJobRequestInternal {
+        merchant_id: merchant_id.clone(),
+        job_id,
+    };
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        request_internal,
+        |state, _, request_internal, _| {
+            recurring_jobs::pause_recurring_job(
+                state,
+                request_internal.merchant_id,
+                request_internal.job_id,
+            )
+        },
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuthMerchantFromRoute {
+                merchant_id,
+                required_permission: Permission::MerchantAccountWrite,
+            },
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::RecurringJobResume))]
+pub async fn resume_recurring_job(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let flow = Flow::RecurringJobResume;
+    let (merchant_id, job_id) = path.into_inner();
+
+    let request_internal = RecurringJobRequestInternal {
+        merchant_id: merchant_id.clone(),
+        job_id,
+    };
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        request_internal,
+        |state, _, request_internal, _| {
+            recurring_jobs::resume_recurring_job(
+                state,
+                request_internal.merchant_id,
+                request_internal.job_id,
+            )
+        },
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuthMerchantFromRoute {
+                merchant_id,
+                required_permission: Permission::MerchantAccountWrite,
+            },
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
@@ -66,6 +66,7 @@ pub static PREDEFINED_PERMISSIONS: Lazy<HashMap<&'static str, RoleInfo>> = Lazy:
                 Permission::MerchantAccountCreate,
                 Permission::PayoutRead,
                 Permission::PayoutWrite,
+                Permission::SchedulerManage,
             ],
             name: None,
             is_invitable: false,
@@ -1,9 +1,12 @@
 pub use api_models::{
     enums as api_enums,
     routing::{
-        ConnectorVolumeSplit, RoutableChoiceKind, RoutableConnectorChoice, RoutingAlgorithm,
+        ConnectorSelection, ConnectorSimulationResult, ConnectorVolumeSplit,
+        EligibilityAnalysisResult, RoutableChoiceKind, RoutableConnectorChoice, RoutingAlgorithm,
         RoutingAlgorithmKind, RoutingAlgorithmRef, RoutingConfigRequest, RoutingDictionary,
-        RoutingDictionaryRecord, StraightThroughAlgorithm,
+        RoutingDictionaryRecord, RoutingEligibilityRequest, RoutingSimulationAlgorithm,
+        RoutingSimulationRequest, RoutingSimulationResponse, StraightThroughAlgorithm,
+        SuccessRateBasedRoutingConfig,
     },
 };
 
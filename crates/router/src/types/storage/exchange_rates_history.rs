@@ -0,0 +1 @@
+pub use diesel_models::exchange_rates_history::{ExchangeRatesSnapshot, ExchangeRatesSnapshotNew};
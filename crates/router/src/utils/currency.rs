@@ -2,19 +2,25 @@ use std::{collections::HashMap, ops::Deref, str::FromStr, sync::Arc, time::Durat
 
 use api_models::enums;
 use common_utils::{date_time, errors::CustomResult, events::ApiEventMetric, ext_traits::AsyncExt};
-use currency_conversion::types::{CurrencyFactors, ExchangeRates};
+use currency_conversion::types::{
+    ConversionPolicy, ConversionResult, CurrencyFactors, ExchangeRates, ExchangeRatesHistory,
+};
 use error_stack::ResultExt;
 use masking::PeekInterface;
 use once_cell::sync::Lazy;
 use redis_interface::DelReply;
 use rust_decimal::Decimal;
 use strum::IntoEnumIterator;
+use time::PrimitiveDateTime;
 use tokio::{sync::RwLock, time::sleep};
 
 use crate::{
+    db::StorageInterface,
     logger,
     routes::app::settings::{Conversion, DefaultExchangeRates},
-    services, SessionState,
+    services,
+    types::storage,
+    SessionState,
 };
 const REDIX_FOREX_CACHE_KEY: &str = "{forex_cache}_lock";
 const REDIX_FOREX_CACHE_DATA: &str = "{forex_cache}_data";
@@ -69,6 +75,12 @@ pub enum ForexCacheError {
     RedisLockReleaseFailed,
     #[error("Error writing to redis")]
     RedisWriteError,
+    #[error("Error reading exchange rates history")]
+    HistoryReadError,
+    #[error("Error writing to exchange rates history")]
+    HistoryWriteError,
+    #[error("Exchange rates not found in history")]
+    HistoryEntryNotFound,
     #[error("Not able to acquire write lock")]
     WriteLockNotAcquired,
 }
@@ -252,6 +264,11 @@ async fn successive_save_data_to_redis_local(
     state: &SessionState,
     forex: FxExchangeRatesCacheEntry,
 ) -> CustomResult<FxExchangeRatesCacheEntry, ForexCacheError> {
+    save_forex_to_history(state, &forex)
+        .await
+        .map_err(|error| logger::error!(?error, "Failed to save forex rates to history"))
+        .ok();
+
     Ok(save_forex_to_redis(state, &forex)
         .await
         .async_and_then(|_rates| async { release_redis_lock(state).await })
@@ -484,6 +501,48 @@ async fn save_forex_to_redis(
         .change_context(ForexCacheError::RedisWriteError)
 }
 
+async fn save_forex_to_history(
+    state: &SessionState,
+    forex_exchange_cache_entry: &FxExchangeRatesCacheEntry,
+) -> CustomResult<(), ForexCacheError> {
+    let created_at =
+        time::OffsetDateTime::from_unix_timestamp(forex_exchange_cache_entry.timestamp)
+            .map(date_time::convert_to_pdt)
+            .change_context(ForexCacheError::ConversionError)?;
+    let conversion = serde_json::to_value(&forex_exchange_cache_entry.data.conversion)
+        .change_context(ForexCacheError::ParsingError)?;
+
+    state
+        .store
+        .insert_exchange_rates_snapshot(storage::ExchangeRatesSnapshotNew {
+            base_currency: forex_exchange_cache_entry.data.base_currency,
+            conversion,
+            created_at,
+        })
+        .await
+        .change_context(ForexCacheError::HistoryWriteError)?;
+    Ok(())
+}
+
+/// Gets the history of exchange rates around `as_of`, holding the snapshot that applied at it
+async fn retrieve_forex_history(
+    store: &dyn StorageInterface,
+    as_of: PrimitiveDateTime,
+) -> CustomResult<ExchangeRatesHistory, ForexCacheError> {
+    let snapshot = store
+        .find_exchange_rates_snapshot_as_of(as_of)
+        .await
+        .change_context(ForexCacheError::HistoryReadError)?
+        .ok_or(ForexCacheError::HistoryEntryNotFound)?;
+    let conversion = serde_json::from_value(snapshot.conversion)
+        .change_context(ForexCacheError::ParsingError)?;
+
+    Ok(ExchangeRatesHistory::from_iter([(
+        snapshot.created_at.assume_utc().unix_timestamp(),
+        ExchangeRates::new(snapshot.base_currency, conversion),
+    )]))
+}
+
 async fn retrieve_forex_from_redis(
     app_state: &SessionState,
 ) -> CustomResult<Option<FxExchangeRatesCacheEntry>, ForexCacheError> {
@@ -514,10 +573,62 @@ pub async fn convert_currency(
     amount: i64,
     to_currency: String,
     from_currency: String,
+    as_of: Option<PrimitiveDateTime>,
 ) -> CustomResult<api_models::currency::CurrencyConversionResponse, ForexCacheError> {
+    let to_currency = enums::Currency::from_str(to_currency.as_str())
+        .change_context(ForexCacheError::CurrencyNotAcceptable)?;
+
+    let from_currency = enums::Currency::from_str(from_currency.as_str())
+        .change_context(ForexCacheError::CurrencyNotAcceptable)?;
+
+    let converted_amount = match as_of {
+        Some(as_of) => {
+            let history = retrieve_forex_history(&*state.store, as_of).await?;
+            currency_conversion::conversion::convert_at(
+                &history,
+                as_of.assume_utc().unix_timestamp(),
+                from_currency,
+                to_currency,
+                amount,
+            )
+        }
+        None => {
+            let forex_api = state.conf.forex_api.get_inner();
+            let rates = get_forex_rates(
+                &state,
+                forex_api.call_delay,
+                forex_api.local_fetch_retry_delay,
+                forex_api.local_fetch_retry_count,
+            )
+            .await
+            .change_context(ForexCacheError::ApiError)?;
+            currency_conversion::conversion::convert(
+                &rates.data,
+                from_currency,
+                to_currency,
+                amount,
+            )
+        }
+    }
+    .change_context(ForexCacheError::ConversionError)?;
+
+    Ok(api_models::currency::CurrencyConversionResponse {
+        converted_amount: converted_amount.to_string(),
+        currency: to_currency.to_string(),
+    })
+}
+
+/// Converts the amount at the current exchange rates, under the conversion policy
+pub async fn convert_currency_with_policy(
+    state: &SessionState,
+    policy: &ConversionPolicy,
+    from_currency: enums::Currency,
+    to_currency: enums::Currency,
+    amount: i64,
+) -> CustomResult<ConversionResult, ForexCacheError> {
     let forex_api = state.conf.forex_api.get_inner();
     let rates = get_forex_rates(
-        &state,
+        state,
         forex_api.call_delay,
         forex_api.local_fetch_retry_delay,
         forex_api.local_fetch_retry_count,
@@ -525,18 +636,78 @@ pub async fn convert_currency(
     .await
     .change_context(ForexCacheError::ApiError)?;
 
-    let to_currency = enums::Currency::from_str(to_currency.as_str())
-        .change_context(ForexCacheError::CurrencyNotAcceptable)?;
+    currency_conversion::conversion::convert_with_policy(
+        &rates.data,
+        policy,
+        from_currency,
+        to_currency,
+        amount,
+    )
+    .change_context(ForexCacheError::ConversionError)
+}
 
-    let from_currency = enums::Currency::from_str(from_currency.as_str())
-        .change_context(ForexCacheError::CurrencyNotAcceptable)?;
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use redis_interface::RedisSettings;
+    use time::macros::datetime;
+
+    use super::*;
+    use crate::db::{exchange_rates_history::ExchangeRatesHistoryInterface, MockDb};
+
+    async fn insert_snapshot(db: &MockDb, created_at: PrimitiveDateTime, inr_to_factor: Decimal) {
+        let conversion = HashMap::from([(
+            enums::Currency::INR,
+            CurrencyFactors::new(inr_to_factor, Decimal::new(1214, 5)),
+        )]);
+        db.insert_exchange_rates_snapshot(storage::ExchangeRatesSnapshotNew {
+            base_currency: enums::Currency::USD,
+            conversion: serde_json::to_value(conversion).unwrap(),
+            created_at,
+        })
+        .await
+        .unwrap();
+    }
 
-    let converted_amount =
-        currency_conversion::conversion::convert(&rates.data, from_currency, to_currency, amount)
-            .change_context(ForexCacheError::ConversionError)?;
+    async fn convert_usd_to_inr_at(db: &MockDb, as_of: PrimitiveDateTime, amount: i64) -> Decimal {
+        let history = retrieve_forex_history(db, as_of).await.unwrap();
+        currency_conversion::conversion::convert_at(
+            &history,
+            as_of.assume_utc().unix_timestamp(),
+            enums::Currency::USD,
+            enums::Currency::INR,
+            amount,
+        )
+        .unwrap()
+    }
 
-    Ok(api_models::currency::CurrencyConversionResponse {
-        converted_amount: converted_amount.to_string(),
-        currency: to_currency.to_string(),
-    })
+    #[tokio::test]
+    async fn test_conversion_at_snapshot_boundary() {
+        let db = MockDb::new(&RedisSettings::default()).await.unwrap();
+        insert_snapshot(&db, datetime!(2023-11-14 22:13:20), Decimal::new(823173, 4)).await;
+        insert_snapshot(&db, datetime!(2023-11-14 23:13:20), Decimal::new(83, 0)).await;
+
+        assert_eq!(
+            convert_usd_to_inr_at(&db, datetime!(2023-11-14 23:13:19), 2000).await,
+            Decimal::new(1646346, 3)
+        );
+        assert_eq!(
+            convert_usd_to_inr_at(&db, datetime!(2023-11-14 23:13:20), 2000).await,
+            Decimal::new(1660, 0)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_history_before_first_snapshot_is_not_found() {
+        let db = MockDb::new(&RedisSettings::default()).await.unwrap();
+        insert_snapshot(&db, datetime!(2023-11-14 22:13:20), Decimal::new(823173, 4)).await;
+
+        let error = retrieve_forex_history(&db, datetime!(2023-11-14 22:13:19))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            ForexCacheError::HistoryEntryNotFound
+        ));
+    }
 }
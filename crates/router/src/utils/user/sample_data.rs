@@ -307,6 +307,7 @@ pub async fn generate_sample_data(
             client_source: None,
             client_version: None,
             customer_acceptance: None,
+            dcc_offer: None,
         };
 
         let refund = if refunds_count < number_of_refunds && !is_failed_payment {
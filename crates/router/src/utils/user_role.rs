@@ -48,6 +48,7 @@ impl From<Permission> for user_role_api::Permission {
             Permission::PayoutRead => Self::PayoutRead,
             Permission::PayoutWrite => Self::PayoutWrite,
             Permission::GenerateReport => Self::GenerateReport,
+            Permission::SchedulerManage => Self::SchedulerManage,
         }
     }
 }
@@ -65,6 +66,11 @@ pub fn validate_role_groups(groups: &[PermissionGroup]) -> UserResult<()> {
             .attach_printable("Organization manage group cannot be added to role");
     }
 
+    if unique_groups.contains(&PermissionGroup::InternalManage) {
+        return Err(report!(UserErrors::InvalidRoleOperation))
+            .attach_printable("Internal manage group cannot be added to role");
+    }
+
     if unique_groups.len() != groups.len() {
         return Err(report!(UserErrors::InvalidRoleOperation))
             .attach_printable("Duplicate permission group found");
@@ -161,6 +161,123 @@ async fn payments_create_adyen() {
     assert_eq!(refund_response.status(), awc::http::StatusCode::OK);
 }
 
+#[actix_web::test]
+#[ignore]
+// verify the API-KEY/merchant id has the dummy connector stripe_test as first choice, and that the
+// forex api is configured
+async fn payments_dcc_offer_dummy_connector() {
+    Box::pin(utils::setup()).await;
+
+    let payment_id = format!("test_{}", Uuid::new_v4());
+    let api_key = ("API-KEY", "MySecretApiKey");
+    let admin_api_key = ("api-key", "test_admin");
+
+    let client = awc::Client::default();
+
+    let dcc_config = serde_json::json!({
+        "card_issuing_country_currencies" : {"GERMANY" : "EUR"},
+        "conversion_policy" : {"spread" : {"type" : "percentage", "value" : "3"}},
+    });
+    let config_request = serde_json::json!({
+        "key" : "dcc_config_jarnura",
+        "value" : dcc_config.to_string(),
+    });
+    let config_response = client
+        .post("http://127.0.0.1:8080/configs/")
+        .insert_header(admin_api_key)
+        .send_json(&config_request)
+        .await
+        .unwrap();
+    assert_eq!(config_response.status(), awc::http::StatusCode::OK);
+
+    let card_info_request = serde_json::json!({
+        "provider" : "test",
+        "records" : [{"card_iin" : "424242", "card_issuing_country" : "GERMANY"}],
+    });
+    let card_info_response = client
+        .post("http://127.0.0.1:8080/cards/import")
+        .insert_header(admin_api_key)
+        .send_json(&card_info_request)
+        .await
+        .unwrap();
+    assert_eq!(card_info_response.status(), awc::http::StatusCode::OK);
+
+    let request = serde_json::json!({
+    "payment_id" : payment_id,
+    "amount" : 1000,
+    "currency" : "USD",
+    "confirm" : true,
+    "customer" : "test_customer",
+    "description" : "dcc",
+    "return_url" : "https://juspay.in/",
+    "payment_method_data" : {"card" : {"card_number":"4242424242424242","card_exp_month":"12","card_exp_year":"29","card_holder_name":"JohnDoe","card_cvc":"123"}},
+    "payment_method" : "card",
+    "routing" : {"type" : "single", "data" : "stripe_test"},
+    });
+
+    let mut create_response = client
+        .post("http://127.0.0.1:8080/payments")
+        .insert_header(api_key)
+        .send_json(&request)
+        .await
+        .unwrap();
+    let create_response_body: serde_json::Value = create_response.json().await.unwrap();
+    println!("{create_response:?} : {create_response_body:?}");
+    assert_eq!(create_response.status(), awc::http::StatusCode::OK);
+    assert_eq!(create_response_body["status"], "requires_confirmation");
+    assert_eq!(
+        create_response_body["next_action"]["type"],
+        "display_dcc_offer"
+    );
+    assert_eq!(create_response_body["dcc_offer"]["original_amount"], 1000);
+    assert_eq!(
+        create_response_body["dcc_offer"]["converted_currency"],
+        "EUR"
+    );
+    assert_eq!(
+        create_response_body["dcc_offer"]["decision"],
+        serde_json::Value::Null
+    );
+    let converted_amount = create_response_body["dcc_offer"]["converted_amount"].clone();
+
+    let confirm_request = serde_json::json!({
+        "dcc_decision" : "accepted",
+    });
+    let mut confirm_response = client
+        .post(format!(
+            "http://127.0.0.1:8080/payments/{payment_id}/confirm"
+        ))
+        .insert_header(api_key)
+        .send_json(&confirm_request)
+        .await
+        .unwrap();
+    let confirm_response_body: serde_json::Value = confirm_response.json().await.unwrap();
+    println!("{confirm_response:?} : {confirm_response_body:?}");
+    assert_eq!(confirm_response.status(), awc::http::StatusCode::OK);
+    assert_eq!(confirm_response_body["status"], "succeeded");
+    assert_eq!(confirm_response_body["dcc_offer"]["decision"], "accepted");
+    assert_eq!(
+        confirm_response_body["dcc_offer"]["converted_amount"],
+        converted_amount
+    );
+
+    // The decision cannot be changed once the payment went through
+    let mut reconfirm_response = client
+        .post(format!(
+            "http://127.0.0.1:8080/payments/{payment_id}/confirm"
+        ))
+        .insert_header(api_key)
+        .send_json(&serde_json::json!({"dcc_decision" : "declined"}))
+        .await
+        .unwrap();
+    let reconfirm_response_body = reconfirm_response.body().await;
+    println!("{reconfirm_response:?} : {reconfirm_response_body:?}");
+    assert_eq!(
+        reconfirm_response.status(),
+        awc::http::StatusCode::BAD_REQUEST
+    );
+}
+
 #[actix_web::test]
 // verify the API-KEY/merchant id has stripe as first choice
 #[ignore]
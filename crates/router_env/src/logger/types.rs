@@ -81,6 +81,8 @@ pub enum Flow {
     MerchantConnectorsDelete,
     /// Merchant Connectors list flow.
     MerchantConnectorsList,
+    /// Merchant Connectors knowledge graph flow.
+    MerchantConnectorsGraph,
     /// Merchant Transfer Keys
     MerchantTransferKey,
     /// ConfigKey create flow.
@@ -222,6 +224,12 @@ pub enum Flow {
     RoutingUnlinkConfig,
     /// Routing retrieve config
     RoutingRetrieveConfig,
+    /// Routing retrieve config as DSL
+    RoutingRetrieveConfigDsl,
+    /// Routing simulate config over historical payments
+    RoutingSimulateConfig,
+    /// Routing analyze connector eligibility for a payment
+    RoutingAnalyzeEligibility,
     /// Routing retrieve active config
     RoutingRetrieveActiveConfig,
     /// Routing retrieve default config
@@ -262,6 +270,8 @@ pub enum Flow {
     DisputesList,
     /// Cards Info flow
     CardsInfo,
+    /// Cards Info import flow
+    CardsInfoImport,
     /// Create File flow
     CreateFile,
     /// Delete File flow
@@ -280,6 +290,12 @@ pub enum Flow {
     RetrieveDisputeEvidence,
     /// Invalidate cache flow
     CacheInvalidate,
+    /// Cache statistics flow
+    CacheStatistics,
+    /// Cache keys list flow
+    CacheKeysList,
+    /// Invalidate cache key flow
+    CacheKeyInvalidate,
     /// Payment Link Retrieve flow
     PaymentLinkRetrieve,
     /// payment Link Initiate flow
@@ -450,6 +466,26 @@ pub enum Flow {
     WebhookEventDeliveryAttemptList,
     /// Manually retry the delivery for a webhook event
     WebhookEventDeliveryRetry,
+    /// List recurring jobs of a merchant
+    RecurringJobsList,
+    /// Create a recurring job for a merchant
+    RecurringJobCreate,
+    /// Pause a recurring job
+    RecurringJobPause,
+    /// Resume a paused recurring job
+    RecurringJobResume,
+    /// List tasks of the scheduler
+    ProcessTrackerTasksList,
+    /// Retrieve a task of the scheduler
+    ProcessTrackerTaskRetrieve,
+    /// Run a task of the scheduler right away
+    ProcessTrackerTaskRun,
+    /// Reschedule a task of the scheduler
+    ProcessTrackerTaskReschedule,
+    /// Cancel a task of the scheduler
+    ProcessTrackerTaskCancel,
+    /// Reset the tasks of the scheduler stuck in limbo
+    ProcessTrackerLimboTasksReset,
     /// Retrieve status of the Poll
     RetrievePollStatus,
     /// Toggles the extended card info feature in profile level
@@ -7,6 +7,7 @@ impl Default for super::settings::SchedulerSettings {
             graceful_shutdown_interval: 60000,
             loop_interval: 5000,
             server: super::settings::Server::default(),
+            lanes: Vec::new(),
         }
     }
 }
@@ -32,6 +33,12 @@ impl Default for super::settings::ConsumerSettings {
     }
 }
 
+impl super::settings::LaneSettings {
+    pub(crate) fn default_batches_per_iteration() -> u16 {
+        1
+    }
+}
+
 impl Default for super::settings::Server {
     fn default() -> Self {
         Self {
@@ -1,3 +1,4 @@
+use diesel_models::process_tracker::ProcessTrackerRunner;
 pub use router_env::config::{Log, LogConsole, LogFile, LogTelemetry};
 use serde::Deserialize;
 
@@ -10,6 +11,9 @@ pub struct SchedulerSettings {
     pub loop_interval: u64,
     pub graceful_shutdown_interval: u64,
     pub server: Server,
+    /// Lanes of the tasks of specific runners, the tasks of the other runners being run from the
+    /// scheduler stream
+    pub lanes: Vec<LaneSettings>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -37,3 +41,21 @@ pub struct ConsumerSettings {
     pub disabled: bool,
     pub consumer_group: String,
 }
+
+/// Lane of the tasks of a runner, which are appended by the producer to a stream of their own, for
+/// a backlog of the tasks of other runners not to delay them.
+#[derive(Debug, Clone, Deserialize)]
+pub struct LaneSettings {
+    pub runner: ProcessTrackerRunner,
+    /// Priority of the lane, the default lane having a priority of 0. A lane is not drained while
+    /// a lane of a higher priority has tasks waiting in its stream.
+    #[serde(default)]
+    pub priority: u16,
+    /// Number of batches a consumer reads from the lane in each of its iterations
+    #[serde(default = "LaneSettings::default_batches_per_iteration")]
+    pub batches_per_iteration: u16,
+    /// Maximum number of tasks of the lane a consumer runs at once
+    pub max_in_flight: Option<usize>,
+    /// Maximum number of tasks of the lane a consumer starts per second
+    pub max_tasks_per_second: Option<u32>,
+}
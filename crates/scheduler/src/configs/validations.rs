@@ -21,6 +21,17 @@ impl super::settings::SchedulerSettings {
 
         self.server.validate()?;
 
+        let mut runners = std::collections::HashSet::new();
+        for lane in &self.lanes {
+            when(!runners.insert(lane.runner), || {
+                Err(ApplicationError::InvalidConfigurationValueError(format!(
+                    "scheduler lane of runner {} must not be configured more than once",
+                    lane.runner
+                )))
+            })?;
+            lane.validate()?;
+        }
+
         Ok(())
     }
 }
@@ -35,6 +46,33 @@ impl super::settings::ProducerSettings {
     }
 }
 
+impl super::settings::LaneSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(self.batches_per_iteration == 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(format!(
+                "scheduler lane batches per iteration of runner {} must be greater than 0",
+                self.runner
+            )))
+        })?;
+
+        when(self.max_in_flight == Some(0), || {
+            Err(ApplicationError::InvalidConfigurationValueError(format!(
+                "scheduler lane max in flight tasks of runner {} must be greater than 0",
+                self.runner
+            )))
+        })?;
+
+        when(self.max_tasks_per_second == Some(0), || {
+            Err(ApplicationError::InvalidConfigurationValueError(format!(
+                "scheduler lane max tasks per second of runner {} must be greater than 0",
+                self.runner
+            )))
+        })
+    }
+}
+
 impl super::settings::Server {
     pub fn validate(&self) -> Result<(), ApplicationError> {
         common_utils::fp_utils::when(self.host.is_default_or_empty(), || {
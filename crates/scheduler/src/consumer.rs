@@ -24,7 +24,10 @@ use uuid::Uuid;
 use super::env::logger;
 pub use super::workflows::ProcessTrackerWorkflow;
 use crate::{
-    configs::settings::SchedulerSettings, db::process_tracker::ProcessTrackerInterface, errors,
+    configs::settings::SchedulerSettings,
+    db::process_tracker::ProcessTrackerInterface,
+    errors,
+    lanes::{lanes_to_drain, Lane},
     metrics, utils as pt_utils, SchedulerAppState, SchedulerInterface, SchedulerSessionState,
 };
 
@@ -64,6 +67,7 @@ where
     let mut shutdown_interval =
         tokio::time::interval(Duration::from_millis(settings.graceful_shutdown_interval));
 
+    let lanes = Lane::from_settings(&settings);
     let consumer_operation_counter = sync::Arc::new(atomic::AtomicU64::new(0));
     let signal = get_allowed_signals()
         .map_err(|error| {
@@ -84,29 +88,58 @@ where
                 if settings.consumer.disabled {
                     continue;
                 }
-                consumer_operation_counter.fetch_add(1, atomic::Ordering::SeqCst);
-                let start_time = std_time::Instant::now();
                 let tenants = state.get_tenants();
                 for tenant in tenants {
                     let session_state = app_state_to_session_state(state, tenant.as_str())?;
-                    pt_utils::consumer_operation_handler(
-                        session_state.clone(),
-                        settings.clone(),
-                        |error| {
-                            logger::error!(?error, "Failed to perform consumer operation");
-                        },
-                        workflow_selector,
-                    )
-                    .await;
+                    let stream_lengths = get_lane_stream_lengths(&session_state, &lanes).await;
+                    for lane in lanes_to_drain(&lanes, &stream_lengths) {
+                        // Lanes still being drained of the tasks read in a previous iteration are
+                        // skipped, without holding back the other lanes
+                        let Some(drain_guard) = lane.try_start_draining(&tenant) else {
+                            continue;
+                        };
+
+                        consumer_operation_counter.fetch_add(1, atomic::Ordering::SeqCst);
+                        let consumer_operation_counter = consumer_operation_counter.clone();
+                        let session_state = session_state.clone();
+                        let settings = settings.clone();
+                        let lane = lane.clone();
+                        tokio::spawn(
+                            async move {
+                                let start_time = std_time::Instant::now();
+                                pt_utils::consumer_operation_handler(
+                                    session_state,
+                                    settings,
+                                    &lane,
+                                    |error| {
+                                        logger::error!(
+                                            ?error,
+                                            lane = %lane.name,
+                                            "Failed to perform consumer operation"
+                                        );
+                                    },
+                                    workflow_selector,
+                                )
+                                .await;
+                                drop(drain_guard);
+
+                                let end_time = std_time::Instant::now();
+                                let duration =
+                                    end_time.saturating_duration_since(start_time).as_secs_f64();
+                                logger::debug!(
+                                    lane = %lane.name,
+                                    "Time taken to execute consumer_operation: {}s",
+                                    duration
+                                );
+
+                                let current_count = consumer_operation_counter
+                                    .fetch_sub(1, atomic::Ordering::SeqCst);
+                                logger::info!("Current tasks being executed: {}", current_count);
+                            }
+                            .in_current_span(),
+                        );
+                    }
                 }
-
-                let end_time = std_time::Instant::now();
-                let duration = end_time.saturating_duration_since(start_time).as_secs_f64();
-                logger::debug!("Time taken to execute consumer_operation: {}s", duration);
-
-                let current_count =
-                    consumer_operation_counter.fetch_sub(1, atomic::Ordering::SeqCst);
-                logger::info!("Current tasks being executed: {}", current_count);
             }
             Ok(()) | Err(mpsc::error::TryRecvError::Disconnected) => {
                 logger::debug!("Awaiting shutdown!");
@@ -134,13 +167,16 @@ where
     Ok(())
 }
 
-#[instrument(skip_all)]
+/// Reads as many batches from the stream of the lane as its batches per iteration, and runs their
+/// tasks within the limits of the lane
+#[instrument(skip_all, fields(lane = %lane.name))]
 pub async fn consumer_operations<T: SchedulerSessionState + 'static>(
     state: &T,
     settings: &SchedulerSettings,
+    lane: &Lane,
     workflow_selector: impl workflows::ProcessTrackerWorkflows<T> + 'static + Copy + std::fmt::Debug,
 ) -> CustomResult<(), errors::ProcessTrackerError> {
-    let stream_name = settings.stream.clone();
+    let stream_name = lane.stream_name.clone();
     let group_name = settings.consumer.consumer_group.clone();
     let consumer_name = format!("consumer_{}", Uuid::new_v4());
 
@@ -149,36 +185,69 @@ pub async fn consumer_operations<T: SchedulerSessionState + 'static>(
         .consumer_group_create(&stream_name, &group_name, &RedisEntryId::AfterLastID)
         .await;
 
-    let mut tasks = state
-        .get_db()
-        .as_scheduler()
-        .fetch_consumer_tasks(&stream_name, &group_name, &consumer_name)
-        .await?;
+    for _ in 0..lane.batches_per_iteration {
+        let mut tasks = state
+            .get_db()
+            .as_scheduler()
+            .fetch_consumer_tasks(&stream_name, &group_name, &consumer_name)
+            .await?;
 
-    if !tasks.is_empty() {
+        if tasks.is_empty() {
+            break;
+        }
         logger::info!("{} picked {} tasks", consumer_name, tasks.len());
-    }
-    let mut handler = vec![];
+        let mut handler = vec![];
 
-    for task in tasks.iter_mut() {
-        let pickup_time = common_utils::date_time::now();
+        for task in tasks.iter_mut() {
+            let slot = lane.acquire_slot().await;
+            let pickup_time = common_utils::date_time::now();
 
-        pt_utils::add_histogram_metrics(&pickup_time, task, &stream_name);
+            pt_utils::add_histogram_metrics(&pickup_time, task, &stream_name);
+            pt_utils::add_lane_metrics(&pickup_time, task, &lane.name);
 
-        metrics::TASK_CONSUMED.add(&metrics::CONTEXT, 1, &[]);
+            metrics::TASK_CONSUMED.add(&metrics::CONTEXT, 1, &[]);
 
-        handler.push(tokio::task::spawn(start_workflow(
-            state.clone(),
-            task.clone(),
-            pickup_time,
-            workflow_selector,
-        )))
+            let workflow =
+                start_workflow(state.clone(), task.clone(), pickup_time, workflow_selector);
+            handler.push(tokio::task::spawn(async move {
+                let result = workflow.await;
+                // The task is not in flight anymore
+                drop(slot);
+                result
+            }))
+        }
+        future::join_all(handler).await;
     }
-    future::join_all(handler).await;
 
     Ok(())
 }
 
+/// Number of batches waiting in the stream of each lane, a stream whose length cannot be obtained
+/// being considered empty
+async fn get_lane_stream_lengths<T: SchedulerSessionState>(
+    state: &T,
+    lanes: &[sync::Arc<Lane>],
+) -> Vec<usize> {
+    let db = state.get_db();
+    future::join_all(lanes.iter().map(|lane| async {
+        match db.stream_get_length(&lane.stream_name).await {
+            Ok(length) => {
+                pt_utils::add_lane_length_metrics(length, &lane.name);
+                length
+            }
+            Err(error) => {
+                logger::warn!(
+                    ?error,
+                    lane = %lane.name,
+                    "Failed to get the length of the stream of the lane"
+                );
+                0
+            }
+        }
+    }))
+    .await
+}
+
 #[instrument(skip(db, redis_conn))]
 pub async fn fetch_consumer_tasks(
     db: &dyn ProcessTrackerInterface,
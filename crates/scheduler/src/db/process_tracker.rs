@@ -62,6 +62,36 @@ pub trait ProcessTrackerInterface: Send + Sync + 'static {
         status: storage_enums::ProcessTrackerStatus,
         limit: Option<i64>,
     ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError>;
+
+    /// Updates the process only if its status is one of `statuses`, returning `None` otherwise
+    async fn update_process_if_status(
+        &self,
+        id: &str,
+        statuses: Vec<storage_enums::ProcessTrackerStatus>,
+        process: storage::ProcessTrackerUpdate,
+    ) -> CustomResult<Option<storage::ProcessTracker>, errors::StorageError>;
+
+    async fn find_recurring_processes_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError>;
+
+    /// Finds the recurring processes with one of `statuses` that are scheduled before
+    /// `time_upper_limit`, earliest first
+    async fn find_recurring_processes_by_status(
+        &self,
+        statuses: Vec<storage_enums::ProcessTrackerStatus>,
+        time_upper_limit: PrimitiveDateTime,
+        limit: Option<i64>,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError>;
+
+    /// Lists the processes matching the constraints, earliest scheduled first
+    async fn list_processes(
+        &self,
+        constraints: storage::process_tracker::ProcessTrackerListConstraints,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError>;
 }
 
 #[async_trait::async_trait]
@@ -151,12 +181,20 @@ impl ProcessTrackerInterface for Store {
     ) -> CustomResult<(), errors::StorageError> {
         metrics::TASK_RETRIED.add(&metrics::CONTEXT, 1, &[]);
         let retry_count = this.retry_count + 1;
+        let event = this.events_with(storage::process_tracker::ProcessTrackerEvent::new(
+            storage::process_tracker::ProcessTrackerEventKind::Retried,
+            &this,
+            retry_count,
+            Some(schedule_time),
+        ));
         self.update_process(
             this,
-            storage::ProcessTrackerUpdate::StatusRetryUpdate {
+            storage::ProcessTrackerUpdate::StatusEventUpdate {
                 status: storage_enums::ProcessTrackerStatus::Pending,
-                retry_count,
-                schedule_time,
+                business_status: None,
+                retry_count: Some(retry_count),
+                schedule_time: Some(schedule_time),
+                event,
             },
         )
         .await?;
@@ -191,6 +229,62 @@ impl ProcessTrackerInterface for Store {
             .await
             .map_err(|error| report!(errors::StorageError::from(error)))
     }
+
+    async fn update_process_if_status(
+        &self,
+        id: &str,
+        statuses: Vec<storage_enums::ProcessTrackerStatus>,
+        process: storage::ProcessTrackerUpdate,
+    ) -> CustomResult<Option<storage::ProcessTracker>, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::ProcessTracker::update_process_if_status(&conn, id, statuses, process)
+            .await
+            .map_err(|error| report!(errors::StorageError::from(error)))
+    }
+
+    async fn find_recurring_processes_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::ProcessTracker::find_recurring_processes_by_merchant_id(
+            &conn,
+            merchant_id,
+            limit,
+            offset,
+        )
+        .await
+        .map_err(|error| report!(errors::StorageError::from(error)))
+    }
+
+    async fn find_recurring_processes_by_status(
+        &self,
+        statuses: Vec<storage_enums::ProcessTrackerStatus>,
+        time_upper_limit: PrimitiveDateTime,
+        limit: Option<i64>,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::ProcessTracker::find_recurring_processes_by_status(
+            &conn,
+            statuses,
+            time_upper_limit,
+            limit,
+        )
+        .await
+        .map_err(|error| report!(errors::StorageError::from(error)))
+    }
+
+    async fn list_processes(
+        &self,
+        constraints: storage::process_tracker::ProcessTrackerListConstraints,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::ProcessTracker::list_processes_by_constraints(&conn, constraints)
+            .await
+            .map_err(|error| report!(errors::StorageError::from(error)))
+    }
 }
 
 #[async_trait::async_trait]
@@ -249,6 +343,8 @@ impl ProcessTrackerInterface for MockDb {
             event: new.event,
             created_at: new.created_at,
             updated_at: new.updated_at,
+            merchant_id: new.merchant_id,
+            recurrence: new.recurrence,
         };
         processes.push(process.clone());
         Ok(process)
@@ -298,4 +394,178 @@ impl ProcessTrackerInterface for MockDb {
         // [#172]: Implement function for `MockDb`
         Err(errors::StorageError::MockDbError)?
     }
+
+    async fn update_process_if_status(
+        &self,
+        id: &str,
+        statuses: Vec<storage_enums::ProcessTrackerStatus>,
+        process: storage::ProcessTrackerUpdate,
+    ) -> CustomResult<Option<storage::ProcessTracker>, errors::StorageError> {
+        Ok(self
+            .processes
+            .lock()
+            .await
+            .iter_mut()
+            .find(|this| this.id == id && statuses.contains(&this.status))
+            .map(|this| {
+                *this = process.apply_changeset(this.clone());
+                this.clone()
+            }))
+    }
+
+    async fn find_recurring_processes_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        let offset = offset
+            .and_then(|offset| usize::try_from(offset).ok())
+            .unwrap_or(0);
+        let limit = limit
+            .and_then(|limit| usize::try_from(limit).ok())
+            .unwrap_or(usize::MAX);
+
+        Ok(self
+            .processes
+            .lock()
+            .await
+            .iter()
+            .filter(|process| {
+                process.merchant_id.as_deref() == Some(merchant_id) && process.recurrence.is_some()
+            })
+            .skip(offset)
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    async fn find_recurring_processes_by_status(
+        &self,
+        statuses: Vec<storage_enums::ProcessTrackerStatus>,
+        time_upper_limit: PrimitiveDateTime,
+        limit: Option<i64>,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        let limit = limit
+            .and_then(|limit| usize::try_from(limit).ok())
+            .unwrap_or(usize::MAX);
+
+        let mut processes = self
+            .processes
+            .lock()
+            .await
+            .iter()
+            .filter(|process| {
+                process.recurrence.is_some()
+                    && statuses.contains(&process.status)
+                    && process
+                        .schedule_time
+                        .is_some_and(|schedule_time| schedule_time < time_upper_limit)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        processes.sort_by_key(|process| process.schedule_time);
+
+        Ok(processes.into_iter().take(limit).collect())
+    }
+
+    async fn list_processes(
+        &self,
+        constraints: storage::process_tracker::ProcessTrackerListConstraints,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        let offset = constraints
+            .offset
+            .and_then(|offset| usize::try_from(offset).ok())
+            .unwrap_or(0);
+        let limit = constraints
+            .limit
+            .and_then(|limit| usize::try_from(limit).ok())
+            .unwrap_or(usize::MAX);
+        let runner = constraints.runner.map(|runner| runner.to_string());
+
+        let mut processes = self
+            .processes
+            .lock()
+            .await
+            .iter()
+            .filter(|process| {
+                runner
+                    .as_ref()
+                    .map_or(true, |runner| process.runner.as_ref() == Some(runner))
+                    && constraints
+                        .statuses
+                        .as_ref()
+                        .map_or(true, |statuses| statuses.contains(&process.status))
+                    && constraints
+                        .merchant_id
+                        .as_ref()
+                        .map_or(true, |merchant_id| {
+                            process.merchant_id.as_ref() == Some(merchant_id)
+                        })
+                    && constraints.scheduled_after.map_or(true, |scheduled_after| {
+                        process.schedule_time >= Some(scheduled_after)
+                    })
+                    && constraints
+                        .scheduled_before
+                        .map_or(true, |scheduled_before| {
+                            process
+                                .schedule_time
+                                .is_some_and(|schedule_time| schedule_time <= scheduled_before)
+                        })
+                    && constraints
+                        .updated_before
+                        .map_or(true, |updated_before| process.updated_at < updated_before)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        processes.sort_by(|a, b| (a.schedule_time, &a.id).cmp(&(b.schedule_time, &b.id)));
+
+        Ok(processes.into_iter().skip(offset).take(limit).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    async fn insert_task(db: &MockDb, id: &str, merchant_id: Option<&str>) {
+        let process = storage::ProcessTrackerNew::new(
+            id,
+            "PAYMENTS_SYNC",
+            storage::ProcessTrackerRunner::PaymentsSyncWorkflow,
+            ["SYNC", "PAYMENT"],
+            (),
+            common_utils::date_time::now(),
+            merchant_id.map(ToOwned::to_owned),
+        )
+        .unwrap();
+
+        db.insert_process(process).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_list_non_recurring_processes_by_merchant_id() {
+        let db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        insert_task(&db, "merchant_1_task", Some("merchant_1")).await;
+        insert_task(&db, "merchant_2_task", Some("merchant_2")).await;
+        insert_task(&db, "task_without_merchant", None).await;
+
+        let processes = db
+            .list_processes(storage::process_tracker::ProcessTrackerListConstraints {
+                merchant_id: Some("merchant_1".to_owned()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(processes.len(), 1);
+        let process = processes.first().unwrap();
+        assert_eq!(process.id, "merchant_1_task");
+        assert_eq!(process.merchant_id.as_deref(), Some("merchant_1"));
+        assert!(process.recurrence.is_none());
+    }
 }
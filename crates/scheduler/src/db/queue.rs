@@ -40,6 +40,8 @@ pub trait QueueInterface {
     ) -> CustomResult<(), RedisError>;
 
     async fn get_key(&self, key: &str) -> CustomResult<Vec<u8>, RedisError>;
+
+    async fn stream_get_length(&self, stream: &str) -> CustomResult<usize, RedisError>;
 }
 
 #[async_trait::async_trait]
@@ -132,6 +134,10 @@ impl QueueInterface for Store {
     async fn get_key(&self, key: &str) -> CustomResult<Vec<u8>, RedisError> {
         self.get_redis_conn()?.get_key::<Vec<u8>>(key).await
     }
+
+    async fn stream_get_length(&self, stream: &str) -> CustomResult<usize, RedisError> {
+        self.get_redis_conn()?.stream_get_length(stream).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -187,4 +193,9 @@ impl QueueInterface for MockDb {
     async fn get_key(&self, _key: &str) -> CustomResult<Vec<u8>, RedisError> {
         Err(RedisError::RedisConnectionError.into())
     }
+
+    async fn stream_get_length(&self, _stream: &str) -> CustomResult<usize, RedisError> {
+        // [#172]: Implement function for `MockDb`
+        Err(RedisError::RedisConnectionError.into())
+    }
 }
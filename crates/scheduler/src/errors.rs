@@ -61,6 +61,8 @@ pub enum ProcessTrackerError {
     TypeConversionError,
     #[error("Tenant not found")]
     TenantNotFound,
+    #[error("Invalid recurring schedule: {0}")]
+    InvalidRecurringSchedule(String),
 }
 
 #[macro_export]
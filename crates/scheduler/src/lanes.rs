@@ -0,0 +1,359 @@
+//! Lanes of the tasks of the scheduler.
+//!
+//! The producer appends the tasks of the runners with a lane configured to a stream of their own,
+//! and the tasks of the other runners to the scheduler stream, which is drained as the default
+//! lane. The consumer drains each lane independently of the others, so that a backlog of the tasks
+//! of a runner does not delay the tasks of the other runners. Lanes of a higher priority are drained
+//! first: while a lane has tasks waiting in its stream, the lanes of a lower priority are held back.
+
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::configs::settings::{LaneSettings, SchedulerSettings};
+
+/// Name of the lane of the tasks of the runners without a lane configured
+pub const DEFAULT_LANE: &str = "DEFAULT";
+
+impl SchedulerSettings {
+    /// Name of the stream the tasks of the runner are appended to
+    pub fn lane_stream_name(&self, runner: Option<&str>) -> String {
+        runner
+            .and_then(|runner| {
+                self.lanes
+                    .iter()
+                    .find(|lane| lane.runner.to_string() == runner)
+            })
+            .map_or_else(
+                || self.stream.clone(),
+                |lane| lane.stream_name(&self.stream),
+            )
+    }
+}
+
+impl LaneSettings {
+    fn stream_name(&self, scheduler_stream: &str) -> String {
+        format!("{scheduler_stream}_{}", self.runner)
+    }
+}
+
+/// Lane drained by a consumer, which keeps track of the tasks of the lane run by the consumer
+#[derive(Debug)]
+pub struct Lane {
+    pub name: String,
+    pub stream_name: String,
+    pub priority: u16,
+    /// Number of batches read from the stream of the lane in each iteration of the consumer
+    pub batches_per_iteration: u16,
+    in_flight: Option<Arc<Semaphore>>,
+    rate_limiter: Option<Mutex<RateLimiter>>,
+    draining_tenants: Mutex<HashSet<String>>,
+}
+
+impl Lane {
+    /// Lanes configured in the settings along with the default lane, highest priority first
+    pub fn from_settings(settings: &SchedulerSettings) -> Vec<Arc<Self>> {
+        let default_lane = Self {
+            name: DEFAULT_LANE.to_owned(),
+            stream_name: settings.stream.clone(),
+            priority: 0,
+            batches_per_iteration: LaneSettings::default_batches_per_iteration(),
+            in_flight: None,
+            rate_limiter: None,
+            draining_tenants: Mutex::default(),
+        };
+
+        let mut lanes = settings
+            .lanes
+            .iter()
+            .map(|lane| Self {
+                name: lane.runner.to_string(),
+                stream_name: lane.stream_name(&settings.stream),
+                priority: lane.priority,
+                batches_per_iteration: lane.batches_per_iteration,
+                in_flight: lane
+                    .max_in_flight
+                    .map(|max_in_flight| Arc::new(Semaphore::new(max_in_flight))),
+                rate_limiter: lane.max_tasks_per_second.map(|max_tasks_per_second| {
+                    Mutex::new(RateLimiter::new(max_tasks_per_second, Instant::now()))
+                }),
+                draining_tenants: Mutex::default(),
+            })
+            .chain(std::iter::once(default_lane))
+            .map(Arc::new)
+            .collect::<Vec<_>>();
+        // The sort is stable, the default lane comes after the configured lanes of its priority
+        lanes.sort_by(|a, b| b.priority.cmp(&a.priority));
+
+        lanes
+    }
+
+    /// Marks the lane as being drained for the tenant, unless it already is. A lane is drained by
+    /// a single operation of the consumer at a time for each tenant, which ends when the guard is
+    /// dropped.
+    pub fn try_start_draining(self: &Arc<Self>, tenant: &str) -> Option<DrainGuard> {
+        self.draining_tenants
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(tenant.to_owned())
+            .then(|| DrainGuard {
+                lane: Arc::clone(self),
+                tenant: tenant.to_owned(),
+            })
+    }
+
+    /// Waits until a task of the lane can be started without exceeding the maximum number of
+    /// tasks in flight and the rate limit of the lane. The task is counted as in flight until the
+    /// returned permit is dropped.
+    pub async fn acquire_slot(&self) -> Option<OwnedSemaphorePermit> {
+        // The semaphore is never closed
+        let permit = match &self.in_flight {
+            Some(in_flight) => Arc::clone(in_flight).acquire_owned().await.ok(),
+            None => None,
+        };
+
+        if let Some(rate_limiter) = &self.rate_limiter {
+            loop {
+                let acquired = rate_limiter
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .try_acquire(Instant::now());
+                match acquired {
+                    Ok(()) => break,
+                    Err(wait) => tokio::time::sleep(wait).await,
+                }
+            }
+        }
+
+        permit
+    }
+}
+
+/// Lanes to drain in an iteration of the consumer, given the lanes ordered by priority and the
+/// number of batches waiting in the stream of each of them. The lanes of a lower priority than a
+/// lane with batches waiting are held back, for the consumer to catch up on that lane first.
+pub fn lanes_to_drain<'a>(lanes: &'a [Arc<Lane>], stream_lengths: &[usize]) -> &'a [Arc<Lane>] {
+    let Some(backlog_priority) = lanes
+        .iter()
+        .zip(stream_lengths)
+        .find(|(_, length)| **length > 0)
+        .map(|(lane, _)| lane.priority)
+    else {
+        return lanes;
+    };
+
+    let drained = lanes
+        .iter()
+        .take_while(|lane| lane.priority >= backlog_priority)
+        .count();
+    lanes.get(..drained).unwrap_or(lanes)
+}
+
+/// Guard of a lane being drained for a tenant
+#[derive(Debug)]
+pub struct DrainGuard {
+    lane: Arc<Lane>,
+    tenant: String,
+}
+
+impl Drop for DrainGuard {
+    fn drop(&mut self) {
+        self.lane
+            .draining_tenants
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&self.tenant);
+    }
+}
+
+/// Token bucket allowing bursts of up to a second worth of tasks
+#[derive(Debug)]
+struct RateLimiter {
+    tasks_per_second: f64,
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl RateLimiter {
+    fn new(tasks_per_second: u32, now: Instant) -> Self {
+        Self {
+            tasks_per_second: f64::from(tasks_per_second),
+            tokens: f64::from(tasks_per_second),
+            updated_at: now,
+        }
+    }
+
+    /// Takes a token if one is available, returns how long to wait for one otherwise
+    fn try_acquire(&mut self, now: Instant) -> Result<(), Duration> {
+        let elapsed = now.saturating_duration_since(self.updated_at);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * self.tasks_per_second)
+            .min(self.tasks_per_second);
+        self.updated_at = self.updated_at.max(now);
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - self.tokens) / self.tasks_per_second,
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use diesel_models::process_tracker::ProcessTrackerRunner;
+
+    use super::*;
+
+    fn lane_settings(
+        runner: ProcessTrackerRunner,
+        priority: u16,
+        batches_per_iteration: u16,
+    ) -> LaneSettings {
+        LaneSettings {
+            runner,
+            priority,
+            batches_per_iteration,
+            max_in_flight: None,
+            max_tasks_per_second: None,
+        }
+    }
+
+    #[test]
+    fn test_lane_stream_name() {
+        let settings = SchedulerSettings {
+            lanes: vec![lane_settings(
+                ProcessTrackerRunner::OutgoingWebhookRetryWorkflow,
+                0,
+                1,
+            )],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            settings.lane_stream_name(Some("OUTGOING_WEBHOOK_RETRY_WORKFLOW")),
+            "SCHEDULER_STREAM_OUTGOING_WEBHOOK_RETRY_WORKFLOW"
+        );
+        assert_eq!(
+            settings.lane_stream_name(Some("REFUND_WORKFLOW_ROUTER")),
+            "SCHEDULER_STREAM"
+        );
+        assert_eq!(settings.lane_stream_name(None), "SCHEDULER_STREAM");
+    }
+
+    fn lane_names(lanes: &[Arc<Lane>]) -> Vec<&str> {
+        lanes.iter().map(|lane| lane.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_lanes_are_ordered_by_priority() {
+        let settings = SchedulerSettings {
+            lanes: vec![
+                lane_settings(
+                    ProcessTrackerRunner::PaymentMethodStatusUpdateWorkflow,
+                    0,
+                    1,
+                ),
+                lane_settings(ProcessTrackerRunner::OutgoingWebhookRetryWorkflow, 2, 4),
+                lane_settings(ProcessTrackerRunner::RefundWorkflowRouter, 1, 2),
+            ],
+            ..Default::default()
+        };
+
+        let lanes = Lane::from_settings(&settings);
+
+        assert_eq!(
+            lane_names(&lanes),
+            [
+                "OUTGOING_WEBHOOK_RETRY_WORKFLOW",
+                "REFUND_WORKFLOW_ROUTER",
+                "PAYMENT_METHOD_STATUS_UPDATE_WORKFLOW",
+                DEFAULT_LANE,
+            ]
+        );
+        assert_eq!(
+            lanes
+                .iter()
+                .map(|lane| lane.batches_per_iteration)
+                .collect::<Vec<_>>(),
+            [4, 2, 1, 1]
+        );
+    }
+
+    #[test]
+    fn test_lanes_of_a_lower_priority_than_a_backlog_are_held_back() {
+        let settings = SchedulerSettings {
+            lanes: vec![
+                lane_settings(ProcessTrackerRunner::OutgoingWebhookRetryWorkflow, 2, 1),
+                lane_settings(ProcessTrackerRunner::RefundWorkflowRouter, 1, 1),
+                lane_settings(ProcessTrackerRunner::ApiKeyExpiryWorkflow, 1, 1),
+            ],
+            ..Default::default()
+        };
+        let lanes = Lane::from_settings(&settings);
+
+        // Without any backlog, every lane is drained
+        assert_eq!(lanes_to_drain(&lanes, &[0, 0, 0, 0]).len(), 4);
+        assert_eq!(lanes_to_drain(&lanes, &[0, 0, 0, 3]).len(), 4);
+
+        // The lanes of the same priority as the backlog are drained along with it
+        assert_eq!(
+            lane_names(lanes_to_drain(&lanes, &[0, 0, 5, 3])),
+            [
+                "OUTGOING_WEBHOOK_RETRY_WORKFLOW",
+                "REFUND_WORKFLOW_ROUTER",
+                "API_KEY_EXPIRY_WORKFLOW",
+            ]
+        );
+        assert_eq!(
+            lane_names(lanes_to_drain(&lanes, &[2, 0, 5, 3])),
+            ["OUTGOING_WEBHOOK_RETRY_WORKFLOW"]
+        );
+    }
+
+    #[test]
+    fn test_lane_is_drained_once_per_tenant() {
+        let lane = Lane::from_settings(&SchedulerSettings::default())
+            .pop()
+            .unwrap();
+
+        let guard = lane.try_start_draining("public");
+        assert!(guard.is_some());
+        assert!(lane.try_start_draining("public").is_none());
+        assert!(lane.try_start_draining("other").is_some());
+
+        drop(guard);
+        assert!(lane.try_start_draining("public").is_some());
+    }
+
+    #[test]
+    fn test_rate_limiter() {
+        let start = Instant::now();
+        let mut rate_limiter = RateLimiter::new(2, start);
+
+        assert_eq!(rate_limiter.try_acquire(start), Ok(()));
+        assert_eq!(rate_limiter.try_acquire(start), Ok(()));
+        assert_eq!(
+            rate_limiter.try_acquire(start),
+            Err(Duration::from_millis(500))
+        );
+
+        let later = start + Duration::from_millis(500);
+        assert_eq!(rate_limiter.try_acquire(later), Ok(()));
+        assert!(rate_limiter.try_acquire(later).is_err());
+
+        // Tokens do not accumulate beyond a second worth of tasks
+        let much_later = later + Duration::from_secs(10);
+        assert_eq!(rate_limiter.try_acquire(much_later), Ok(()));
+        assert_eq!(rate_limiter.try_acquire(much_later), Ok(()));
+        assert!(rate_limiter.try_acquire(much_later).is_err());
+    }
+}
@@ -4,8 +4,10 @@ pub mod db;
 pub mod env;
 pub mod errors;
 pub mod flow;
+pub mod lanes;
 pub mod metrics;
 pub mod producer;
+pub mod recurrence;
 pub mod scheduler;
 pub mod settings;
 pub mod utils;
@@ -1,15 +1,20 @@
-use router_env::{counter_metric, global_meter, histogram_metric, metrics_context};
+use router_env::{counter_metric, gauge_metric, global_meter, histogram_metric, metrics_context};
 
 metrics_context!(CONTEXT);
 global_meter!(PT_METER, "PROCESS_TRACKER");
 
 histogram_metric!(CONSUMER_STATS, PT_METER, "CONSUMER_OPS");
+histogram_metric!(LANE_LAG, PT_METER); // Time between the schedule time and the pickup of the tasks of a lane, in seconds
+
+gauge_metric!(LANE_STREAM_LENGTH, PT_METER); // Batches waiting in the stream of a lane
 
 counter_metric!(PAYMENT_COUNT, PT_METER); // No. of payments created
 counter_metric!(TASKS_PICKED_COUNT, PT_METER); // Tasks picked by
 counter_metric!(BATCHES_CREATED, PT_METER); // Batches added to stream
 counter_metric!(BATCHES_CONSUMED, PT_METER); // Batches consumed by consumer
 counter_metric!(TASK_CONSUMED, PT_METER); // Tasks consumed by consumer
+counter_metric!(LANE_TASKS_CONSUMED, PT_METER); // Tasks consumed by consumer, by lane
 counter_metric!(TASK_PROCESSED, PT_METER); // Tasks completed processing
 counter_metric!(TASK_FINISHED, PT_METER); // Tasks finished
 counter_metric!(TASK_RETRIED, PT_METER); // Tasks added for retries
+counter_metric!(RECURRING_TASKS_SCHEDULED, PT_METER); // Recurring tasks scheduled for their next run
@@ -4,7 +4,7 @@ use common_utils::errors::CustomResult;
 use diesel_models::enums::ProcessTrackerStatus;
 use error_stack::{report, ResultExt};
 use router_env::{
-    instrument,
+    instrument, opentelemetry,
     tracing::{self, Instrument},
 };
 use time::Duration;
@@ -15,8 +15,13 @@ use super::{
     metrics,
 };
 use crate::{
-    configs::settings::SchedulerSettings, errors, flow::SchedulerFlow,
-    scheduler::SchedulerInterface, utils::*, SchedulerAppState, SchedulerSessionState,
+    configs::settings::SchedulerSettings,
+    errors,
+    flow::SchedulerFlow,
+    recurrence::{self, Clock},
+    scheduler::SchedulerInterface,
+    utils::*,
+    SchedulerAppState, SchedulerSessionState,
 };
 
 #[instrument(skip_all)]
@@ -107,6 +112,18 @@ where
     T: SchedulerSessionState,
 {
     lock_acquire_release::<_, _, _>(state.get_db().as_scheduler(), settings, move || async {
+        if let Err(error) = schedule_recurring_tasks(
+            state.get_db().as_scheduler(),
+            settings,
+            &recurrence::SystemClock,
+        )
+        .await
+        {
+            // Recurring tasks that could not be scheduled are retried in the next iteration,
+            // they should not prevent the tasks already scheduled from being picked
+            error!(?error, "Failed to schedule recurring tasks");
+        }
+
         let tasks = fetch_producer_tasks(state.get_db().as_scheduler(), settings).await?;
         debug!("Producer count of tasks {}", tasks.len());
 
@@ -178,3 +195,225 @@ pub async fn fetch_producer_tasks(
     metrics::TASKS_PICKED_COUNT.add(&metrics::CONTEXT, new_tasks.len() as u64, &[]);
     Ok(new_tasks)
 }
+
+/// Schedules the recurring tasks that finished a run for their next run, and reschedules the runs
+/// of recurring tasks that are too old to be picked by the producer according to their missed run
+/// policy.
+#[instrument(skip_all)]
+pub async fn schedule_recurring_tasks(
+    db: &dyn SchedulerInterface,
+    conf: &SchedulerSettings,
+    clock: &dyn Clock,
+) -> CustomResult<(), errors::ProcessTrackerError> {
+    let now = clock.now();
+    let limit = i64::try_from(conf.producer.batch_size).ok();
+    let time_lower_limit = now
+        .checked_sub(Duration::seconds(conf.producer.lower_fetch_limit))
+        .ok_or_else(|| {
+            report!(errors::ProcessTrackerError::ConfigurationError)
+                .attach_printable("Error obtaining lower limit to fetch missed recurring tasks")
+        })?;
+
+    let finished_tasks = db
+        .find_recurring_processes_by_status(vec![ProcessTrackerStatus::Finish], now, limit)
+        .await
+        .change_context(errors::ProcessTrackerError::ProcessFetchingFailed)?;
+    let missed_tasks = db
+        .find_recurring_processes_by_status(
+            vec![ProcessTrackerStatus::New, ProcessTrackerStatus::Pending],
+            time_lower_limit,
+            limit,
+        )
+        .await
+        .change_context(errors::ProcessTrackerError::ProcessFetchingFailed)?;
+
+    let tasks = finished_tasks
+        .into_iter()
+        .map(|task| (task, vec![ProcessTrackerStatus::Finish]))
+        .chain(missed_tasks.into_iter().map(|task| {
+            (
+                task,
+                vec![ProcessTrackerStatus::New, ProcessTrackerStatus::Pending],
+            )
+        }));
+
+    for (task, statuses) in tasks {
+        if let Err(error) = schedule_recurring_task(db, &task, statuses, clock).await {
+            error!(?error, process_id = %task.id, "Failed to schedule recurring task");
+        }
+    }
+
+    Ok(())
+}
+
+async fn schedule_recurring_task(
+    db: &dyn SchedulerInterface,
+    task: &storage::ProcessTracker,
+    statuses: Vec<ProcessTrackerStatus>,
+    clock: &dyn Clock,
+) -> CustomResult<(), errors::ProcessTrackerError> {
+    let recurrence = task.recurrence.as_ref().ok_or_else(|| {
+        report!(errors::ProcessTrackerError::MissingRequiredField)
+            .attach_printable("Recurring task without a recurring schedule")
+    })?;
+    let run = if task.status == ProcessTrackerStatus::Finish {
+        recurrence::next_run(recurrence, clock)?
+    } else {
+        recurrence::missed_run(recurrence, clock)?
+    };
+
+    // The status is checked for the task not to be scheduled again if it was paused or picked
+    // since it was fetched
+    let scheduled = db
+        .update_process_if_status(
+            &task.id,
+            statuses,
+            storage::ProcessTrackerUpdate::RecurrenceUpdate {
+                schedule_time: run.schedule_time,
+                recurrence: run.recurrence,
+            },
+        )
+        .await
+        .change_context(errors::ProcessTrackerError::ProcessUpdateFailed)?;
+
+    if scheduled.is_some() {
+        debug!(process_id = %task.id, schedule_time = %run.schedule_time, "Scheduled recurring task");
+        metrics::RECURRING_TASKS_SCHEDULED.add(
+            &metrics::CONTEXT,
+            1,
+            &[opentelemetry::KeyValue::new(
+                "runner",
+                task.runner.clone().unwrap_or_default(),
+            )],
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use storage_impl::mock_db::MockDb;
+    use time::{format_description::well_known::Iso8601, PrimitiveDateTime};
+
+    use super::*;
+    use crate::{db::process_tracker::ProcessTrackerInterface, recurrence::MissedRunPolicy};
+
+    struct MockClock(PrimitiveDateTime);
+
+    impl Clock for MockClock {
+        fn now(&self) -> PrimitiveDateTime {
+            self.0
+        }
+    }
+
+    fn datetime(time: &str) -> PrimitiveDateTime {
+        PrimitiveDateTime::parse(time, &Iso8601::DEFAULT).unwrap()
+    }
+
+    async fn insert_recurring_task(
+        db: &MockDb,
+        id: &str,
+        missed_run_policy: MissedRunPolicy,
+    ) -> storage::ProcessTracker {
+        let clock = MockClock(datetime("2024-01-01T00:00:00"));
+        let run = recurrence::first_run("0 2 * * *", "UTC", missed_run_policy, &clock).unwrap();
+        let process = storage::ProcessTrackerNew::new(
+            id,
+            "RECURRING_TASK",
+            storage::ProcessTrackerRunner::ApiKeyExpiryWorkflow,
+            ["RECURRING"],
+            (),
+            run.schedule_time,
+            Some("merchant_1".to_owned()),
+        )
+        .unwrap()
+        .recurring(run.recurrence);
+
+        db.insert_process(process).await.unwrap()
+    }
+
+    async fn set_status(db: &MockDb, id: &str, status: ProcessTrackerStatus) {
+        db.update_process_if_status(
+            id,
+            vec![ProcessTrackerStatus::New],
+            storage::ProcessTrackerUpdate::StatusUpdate {
+                status,
+                business_status: None,
+            },
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    }
+
+    async fn find_task(db: &MockDb, id: &str) -> storage::ProcessTracker {
+        db.find_process_by_id(id).await.unwrap().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_schedule_recurring_tasks() {
+        let db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        let conf = SchedulerSettings::default();
+
+        // Every task is scheduled for its first run, at 2024-01-01T02:00:00
+        insert_recurring_task(&db, "finished", MissedRunPolicy::Skip).await;
+        insert_recurring_task(&db, "skipped", MissedRunPolicy::Skip).await;
+        insert_recurring_task(&db, "caught_up", MissedRunPolicy::CatchUp).await;
+        insert_recurring_task(&db, "paused", MissedRunPolicy::Skip).await;
+        set_status(&db, "finished", ProcessTrackerStatus::Finish).await;
+        set_status(&db, "paused", ProcessTrackerStatus::Paused).await;
+
+        // The finished task is scheduled for its next run, and the runs of the other tasks are
+        // not missed yet
+        schedule_recurring_tasks(&db, &conf, &MockClock(datetime("2024-01-01T02:01:00")))
+            .await
+            .unwrap();
+
+        let finished = find_task(&db, "finished").await;
+        assert_eq!(finished.status, ProcessTrackerStatus::New);
+        assert_eq!(
+            finished.schedule_time,
+            Some(datetime("2024-01-02T02:00:00"))
+        );
+        assert_eq!(
+            finished.recurrence.unwrap().scheduled_for,
+            datetime("2024-01-02T02:00:00")
+        );
+        assert_eq!(
+            find_task(&db, "skipped").await.schedule_time,
+            Some(datetime("2024-01-01T02:00:00"))
+        );
+
+        // The runs not picked within the lower fetch limit of the producer are missed
+        schedule_recurring_tasks(&db, &conf, &MockClock(datetime("2024-01-01T03:00:00")))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            find_task(&db, "skipped").await.schedule_time,
+            Some(datetime("2024-01-02T02:00:00"))
+        );
+        let caught_up = find_task(&db, "caught_up").await;
+        assert_eq!(
+            caught_up.schedule_time,
+            Some(datetime("2024-01-01T03:00:00"))
+        );
+        assert_eq!(
+            caught_up.recurrence.unwrap().scheduled_for,
+            datetime("2024-01-01T02:00:00")
+        );
+        assert_eq!(
+            find_task(&db, "finished").await.schedule_time,
+            Some(datetime("2024-01-02T02:00:00"))
+        );
+
+        let paused = find_task(&db, "paused").await;
+        assert_eq!(paused.status, ProcessTrackerStatus::Paused);
+        assert_eq!(paused.schedule_time, Some(datetime("2024-01-01T02:00:00")));
+    }
+}
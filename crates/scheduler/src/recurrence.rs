@@ -0,0 +1,539 @@
+//! Recurring tasks, run on the schedule of a cron expression evaluated in a timezone.
+//!
+//! A recurring task is a process tracker task with a [`RecurringSchedule`]. Once a run of the task
+//! finishes, the producer schedules the task for its next run, so workflows do not have to insert
+//! their next run themselves. A recurring task is created with the schedule of its first run:
+//!
+//! ```ignore
+//! let run = recurrence::first_run("0 2 * * *", "Asia/Kolkata", MissedRunPolicy::Skip, &SystemClock)?;
+//! let process =
+//!     ProcessTrackerNew::new(id, task, runner, tag, tracking_data, run.schedule_time, merchant_id)?
+//!         .recurring(run.recurrence);
+//! ```
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, TimeZone};
+use chrono_tz::Tz;
+use common_utils::errors::CustomResult;
+pub use diesel_models::process_tracker::{MissedRunPolicy, RecurringSchedule};
+use error_stack::report;
+use time::PrimitiveDateTime;
+
+use crate::errors;
+
+/// Number of days searched for the next run of a schedule, for schedules without runs such as
+/// `0 0 30 2 *` not to be searched forever
+const MAX_SEARCHED_DAYS: u32 = 5 * 366;
+
+const MONTH_NAMES: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const DAY_OF_WEEK_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// Source of the current time, in UTC
+pub trait Clock: Send + Sync {
+    fn now(&self) -> PrimitiveDateTime;
+}
+
+/// Clock of the system
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> PrimitiveDateTime {
+        common_utils::date_time::now()
+    }
+}
+
+/// Parsed cron expression, evaluated in a timezone.
+///
+/// The expression is made of the usual five fields: minute, hour, day of month, month and day of
+/// week, each being `*`, a value, a range `a-b` or a list of them, optionally with a step such as
+/// `*/15` or `10-40/10`. Months and days of week can also be given as names such as `JAN` or
+/// `MON`, and Sunday is both `0` and `7`. As with cron, a day matches if it matches either the day
+/// of month or the day of week when both are restricted. The `@yearly`, `@monthly`, `@weekly`,
+/// `@daily` and `@hourly` shorthands are supported as well.
+///
+/// Local times skipped by daylight saving time transitions are skipped, and local times repeated by
+/// them only match their first occurrence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    days_of_month_restricted: bool,
+    days_of_week_restricted: bool,
+    timezone: Tz,
+}
+
+impl CronSchedule {
+    pub fn new(
+        expression: &str,
+        timezone: &str,
+    ) -> CustomResult<Self, errors::ProcessTrackerError> {
+        let invalid = |message: String| {
+            report!(errors::ProcessTrackerError::InvalidRecurringSchedule(
+                message
+            ))
+        };
+
+        let timezone = timezone
+            .parse::<Tz>()
+            .map_err(|_| invalid(format!("unknown timezone `{timezone}`")))?;
+
+        let expression = match expression.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            expression => expression,
+        };
+        let fields = expression.split_whitespace().collect::<Vec<_>>();
+        let [minutes, hours, days_of_month, months, days_of_week] = fields[..] else {
+            return Err(invalid(format!(
+                "expected 5 fields in the cron expression `{expression}`, found {}",
+                fields.len()
+            )));
+        };
+
+        let parse = |field: &str, min, max, names: &[&str]| {
+            parse_field(field, min, max, names).map_err(|message| {
+                invalid(format!(
+                    "invalid field `{field}` in the cron expression `{expression}`: {message}"
+                ))
+            })
+        };
+        let days_of_week_with_seven = parse(days_of_week, 0, 7, &DAY_OF_WEEK_NAMES)?;
+
+        Ok(Self {
+            minutes: parse(minutes, 0, 59, &[])?,
+            hours: parse(hours, 0, 23, &[])?,
+            days_of_month: parse(days_of_month, 1, 31, &[])?,
+            months: parse(months, 1, 12, &MONTH_NAMES)?,
+            // Sunday is both 0 and 7
+            days_of_week: (days_of_week_with_seven | days_of_week_with_seven >> 7) & 0x7f,
+            days_of_month_restricted: !days_of_month.starts_with('*'),
+            days_of_week_restricted: !days_of_week.starts_with('*'),
+            timezone,
+        })
+    }
+
+    /// First run of the schedule strictly after `time`, in UTC. Returns `None` if the schedule
+    /// has no run in the next few years.
+    pub fn next_after(&self, time: PrimitiveDateTime) -> Option<PrimitiveDateTime> {
+        let after = time.assume_utc().unix_timestamp();
+        let local_time = chrono::DateTime::<chrono::Utc>::from_timestamp(after, 0)?
+            .with_timezone(&self.timezone)
+            .naive_local();
+
+        let mut date = local_time.date();
+        for _ in 0..MAX_SEARCHED_DAYS {
+            if self.matches_date(date) {
+                let run = (0..24)
+                    .filter(|hour| is_set(self.hours, *hour))
+                    .flat_map(|hour| {
+                        (0..60)
+                            .filter(|minute| is_set(self.minutes, *minute))
+                            .filter_map(move |minute| date.and_hms_opt(hour, minute, 0))
+                    })
+                    .filter(|run| *run > local_time)
+                    .filter_map(|run| self.to_unix_timestamp(run))
+                    // Local times repeated by daylight saving time transitions can be before `time`
+                    .find(|run| *run > after);
+
+                if let Some(run) = run {
+                    let run = time::OffsetDateTime::from_unix_timestamp(run).ok()?;
+                    return Some(PrimitiveDateTime::new(run.date(), run.time()));
+                }
+            }
+            date = date.succ_opt()?;
+        }
+
+        None
+    }
+
+    fn matches_date(&self, date: NaiveDate) -> bool {
+        let matches_day_of_month = is_set(self.days_of_month, date.day());
+        let matches_day_of_week = is_set(self.days_of_week, date.weekday().num_days_from_sunday());
+
+        is_set(self.months, date.month())
+            && match (self.days_of_month_restricted, self.days_of_week_restricted) {
+                (true, true) => matches_day_of_month || matches_day_of_week,
+                (true, false) => matches_day_of_month,
+                (false, true) => matches_day_of_week,
+                (false, false) => true,
+            }
+    }
+
+    fn to_unix_timestamp(&self, local_time: NaiveDateTime) -> Option<i64> {
+        self.timezone
+            .from_local_datetime(&local_time)
+            .earliest()
+            .map(|time| time.timestamp())
+    }
+}
+
+impl TryFrom<&RecurringSchedule> for CronSchedule {
+    type Error = error_stack::Report<errors::ProcessTrackerError>;
+
+    fn try_from(recurrence: &RecurringSchedule) -> Result<Self, Self::Error> {
+        Self::new(&recurrence.cron_expression, &recurrence.timezone)
+    }
+}
+
+#[inline]
+fn is_set(bits: u64, value: u32) -> bool {
+    bits & (1_u64 << value) != 0
+}
+
+/// Parses a field of a cron expression into the bit set of its values
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, String> {
+    let parse_value = |value: &str| {
+        names
+            .iter()
+            .zip(min..)
+            .find(|(name, _)| name.eq_ignore_ascii_case(value))
+            .map(|(_, value)| value)
+            .or_else(|| value.parse().ok())
+            .filter(|value| (min..=max).contains(value))
+            .ok_or_else(|| format!("`{value}` is not a value between {min} and {max}"))
+    };
+
+    field.split(',').try_fold(0, |bits, part| {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (
+                range,
+                Some(
+                    step.parse::<usize>()
+                        .ok()
+                        .filter(|step| *step > 0)
+                        .ok_or_else(|| format!("`{step}` is not a valid step"))?,
+                ),
+            ),
+            None => (part, None),
+        };
+
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((start, end)) => (parse_value(start)?, parse_value(end)?),
+            // `a/n` runs from `a` to the maximum value
+            None if step.is_some() => (parse_value(range)?, max),
+            None => {
+                let value = parse_value(range)?;
+                (value, value)
+            }
+        };
+        if start > end {
+            return Err(format!("`{range}` is not a valid range"));
+        }
+
+        Ok((start..=end)
+            .step_by(step.unwrap_or(1))
+            .fold(bits, |bits, value| bits | 1_u64 << value))
+    })
+}
+
+/// Run a recurring task is scheduled for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledRun {
+    /// Time the task is scheduled at
+    pub schedule_time: PrimitiveDateTime,
+    /// Recurring schedule of the task, with the time of the run it is scheduled for
+    pub recurrence: RecurringSchedule,
+}
+
+fn next_run_after(
+    schedule: &CronSchedule,
+    time: PrimitiveDateTime,
+) -> CustomResult<PrimitiveDateTime, errors::ProcessTrackerError> {
+    schedule.next_after(time).ok_or_else(|| {
+        report!(errors::ProcessTrackerError::InvalidRecurringSchedule(
+            "the schedule has no next run".to_owned()
+        ))
+    })
+}
+
+/// First run of a new recurring task, the first run of the schedule after the current time
+pub fn first_run(
+    cron_expression: &str,
+    timezone: &str,
+    missed_run_policy: MissedRunPolicy,
+    clock: &dyn Clock,
+) -> CustomResult<ScheduledRun, errors::ProcessTrackerError> {
+    let schedule = CronSchedule::new(cron_expression, timezone)?;
+    let run = next_run_after(&schedule, clock.now())?;
+
+    Ok(ScheduledRun {
+        schedule_time: run,
+        recurrence: RecurringSchedule {
+            cron_expression: cron_expression.to_owned(),
+            timezone: timezone.to_owned(),
+            missed_run_policy,
+            scheduled_for: run,
+        },
+    })
+}
+
+/// Run that follows the run the recurring task was scheduled for, once that run finished. If the
+/// following run was missed, because the run finished after it, the run is scheduled according
+/// to the missed run policy of the task.
+pub fn next_run(
+    recurrence: &RecurringSchedule,
+    clock: &dyn Clock,
+) -> CustomResult<ScheduledRun, errors::ProcessTrackerError> {
+    let schedule = CronSchedule::try_from(recurrence)?;
+    let run = next_run_after(&schedule, recurrence.scheduled_for)?;
+    let recurrence = RecurringSchedule {
+        scheduled_for: run,
+        ..recurrence.clone()
+    };
+
+    if run > clock.now() {
+        Ok(ScheduledRun {
+            schedule_time: run,
+            recurrence,
+        })
+    } else {
+        reschedule_missed_run(&schedule, recurrence, clock)
+    }
+}
+
+/// Reschedules the run of a recurring task that was missed, because the scheduler did not pick
+/// the task in time, according to the missed run policy of the task
+pub fn missed_run(
+    recurrence: &RecurringSchedule,
+    clock: &dyn Clock,
+) -> CustomResult<ScheduledRun, errors::ProcessTrackerError> {
+    let schedule = CronSchedule::try_from(recurrence)?;
+    reschedule_missed_run(&schedule, recurrence.clone(), clock)
+}
+
+/// Run of a recurring task that is resumed. The runs missed while the task was paused are
+/// skipped, whatever the missed run policy of the task.
+pub fn resumed_run(
+    recurrence: &RecurringSchedule,
+    clock: &dyn Clock,
+) -> CustomResult<ScheduledRun, errors::ProcessTrackerError> {
+    let schedule = CronSchedule::try_from(recurrence)?;
+    let run = next_run_after(&schedule, clock.now())?;
+
+    Ok(ScheduledRun {
+        schedule_time: run,
+        recurrence: RecurringSchedule {
+            scheduled_for: run,
+            ..recurrence.clone()
+        },
+    })
+}
+
+fn reschedule_missed_run(
+    schedule: &CronSchedule,
+    recurrence: RecurringSchedule,
+    clock: &dyn Clock,
+) -> CustomResult<ScheduledRun, errors::ProcessTrackerError> {
+    let now = clock.now();
+
+    match recurrence.missed_run_policy {
+        MissedRunPolicy::Skip => {
+            let run = next_run_after(schedule, now)?;
+            Ok(ScheduledRun {
+                schedule_time: run,
+                recurrence: RecurringSchedule {
+                    scheduled_for: run,
+                    ..recurrence
+                },
+            })
+        }
+        // The missed run is executed right away, and the following runs once it finishes
+        MissedRunPolicy::CatchUp => Ok(ScheduledRun {
+            schedule_time: now,
+            recurrence,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::sync::Mutex;
+
+    use time::format_description::well_known::Iso8601;
+
+    use super::*;
+
+    struct MockClock(Mutex<PrimitiveDateTime>);
+
+    impl MockClock {
+        fn at(time: &str) -> Self {
+            Self(Mutex::new(datetime(time)))
+        }
+
+        fn set(&self, time: &str) {
+            *self.0.lock().unwrap() = datetime(time);
+        }
+    }
+
+    impl Clock for MockClock {
+        fn now(&self) -> PrimitiveDateTime {
+            *self.0.lock().unwrap()
+        }
+    }
+
+    fn datetime(time: &str) -> PrimitiveDateTime {
+        PrimitiveDateTime::parse(time, &Iso8601::DEFAULT).unwrap()
+    }
+
+    fn next_after(expression: &str, timezone: &str, time: &str) -> Option<PrimitiveDateTime> {
+        CronSchedule::new(expression, timezone)
+            .unwrap()
+            .next_after(datetime(time))
+    }
+
+    #[test]
+    fn test_invalid_cron_expressions() {
+        for (expression, timezone) in [
+            ("* * * *", "UTC"),
+            ("* * * * * *", "UTC"),
+            ("60 * * * *", "UTC"),
+            ("* 24 * * *", "UTC"),
+            ("* * 0 * *", "UTC"),
+            ("*/0 * * * *", "UTC"),
+            ("10-5 * * * *", "UTC"),
+            ("* * * FOO *", "UTC"),
+            ("* * * * *", "Mars/Olympus_Mons"),
+        ] {
+            assert!(
+                CronSchedule::new(expression, timezone).is_err(),
+                "`{expression}` in `{timezone}` should be invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn test_next_run_of_cron_expressions() {
+        let cases = [
+            // Weekdays at 09:30, from a Friday after 09:30
+            (
+                "30 9 * * MON-FRI",
+                "2024-08-02T10:00:00",
+                "2024-08-05T09:30:00",
+            ),
+            ("*/15 * * * *", "2024-08-02T10:00:00", "2024-08-02T10:15:00"),
+            (
+                "5,35 10-12/2 * * *",
+                "2024-08-02T10:35:00",
+                "2024-08-02T12:05:00",
+            ),
+            (
+                "0 0 1 JAN,jul *",
+                "2024-08-02T10:00:00",
+                "2025-01-01T00:00:00",
+            ),
+            // The 13th or a Friday, from a Sunday
+            ("0 0 13 * 5", "2024-09-01T00:00:00", "2024-09-06T00:00:00"),
+            // Sunday as 7
+            ("0 0 * * 7", "2024-08-02T10:00:00", "2024-08-04T00:00:00"),
+            ("@monthly", "2024-08-02T10:00:00", "2024-09-01T00:00:00"),
+            ("0 0 29 2 *", "2024-03-01T00:00:00", "2028-02-29T00:00:00"),
+        ];
+
+        for (expression, time, expected) in cases {
+            assert_eq!(
+                next_after(expression, "UTC", time),
+                Some(datetime(expected)),
+                "Next run of `{expression}` after {time}"
+            );
+        }
+
+        assert_eq!(next_after("0 0 30 2 *", "UTC", "2024-08-02T10:00:00"), None);
+    }
+
+    #[test]
+    fn test_next_run_in_timezone() {
+        // 09:00 in India is 03:30 in UTC
+        assert_eq!(
+            next_after("0 9 * * *", "Asia/Kolkata", "2024-08-05T00:00:00"),
+            Some(datetime("2024-08-05T03:30:00"))
+        );
+        // 02:30 does not exist in New York on the 10th of March 2024
+        assert_eq!(
+            next_after("30 2 * * *", "America/New_York", "2024-03-10T00:00:00"),
+            Some(datetime("2024-03-11T06:30:00"))
+        );
+        // 01:30 happens twice in New York on the 3rd of November 2024, only the first one matches
+        assert_eq!(
+            next_after("30 1 * * *", "America/New_York", "2024-11-03T05:30:00"),
+            Some(datetime("2024-11-04T06:30:00"))
+        );
+    }
+
+    #[test]
+    fn test_recurring_task_runs() {
+        let clock = MockClock::at("2024-08-01T10:00:00");
+        let run = first_run("0 0 * * *", "UTC", MissedRunPolicy::Skip, &clock).unwrap();
+        assert_eq!(run.schedule_time, datetime("2024-08-02T00:00:00"));
+        assert_eq!(run.recurrence.scheduled_for, run.schedule_time);
+
+        // The run finishes on time
+        clock.set("2024-08-02T00:05:00");
+        let run = next_run(&run.recurrence, &clock).unwrap();
+        assert_eq!(run.schedule_time, datetime("2024-08-03T00:00:00"));
+        assert_eq!(run.recurrence.scheduled_for, run.schedule_time);
+
+        // The scheduler was down for a few days
+        clock.set("2024-08-05T12:00:00");
+        let skipped = next_run(&run.recurrence, &clock).unwrap();
+        assert_eq!(skipped.schedule_time, datetime("2024-08-06T00:00:00"));
+        assert_eq!(skipped.recurrence.scheduled_for, skipped.schedule_time);
+
+        let recurrence = RecurringSchedule {
+            missed_run_policy: MissedRunPolicy::CatchUp,
+            ..run.recurrence
+        };
+        let mut scheduled_for = Vec::new();
+        let mut run = next_run(&recurrence, &clock).unwrap();
+        while run.schedule_time == clock.now() {
+            scheduled_for.push(run.recurrence.scheduled_for);
+            run = next_run(&run.recurrence, &clock).unwrap();
+        }
+        assert_eq!(
+            scheduled_for,
+            [
+                datetime("2024-08-04T00:00:00"),
+                datetime("2024-08-05T00:00:00")
+            ]
+        );
+        assert_eq!(run.schedule_time, datetime("2024-08-06T00:00:00"));
+        assert_eq!(run.recurrence.scheduled_for, run.schedule_time);
+    }
+
+    #[test]
+    fn test_missed_and_resumed_runs() {
+        let clock = MockClock::at("2024-08-01T10:00:00");
+        let recurrence = RecurringSchedule {
+            cron_expression: "0 * * * *".to_owned(),
+            timezone: "UTC".to_owned(),
+            missed_run_policy: MissedRunPolicy::CatchUp,
+            scheduled_for: datetime("2024-08-01T07:00:00"),
+        };
+
+        let run = missed_run(&recurrence, &clock).unwrap();
+        assert_eq!(run.schedule_time, clock.now());
+        assert_eq!(run.recurrence, recurrence);
+
+        let run = resumed_run(&recurrence, &clock).unwrap();
+        assert_eq!(run.schedule_time, datetime("2024-08-01T11:00:00"));
+        assert_eq!(run.recurrence.scheduled_for, run.schedule_time);
+
+        let recurrence = RecurringSchedule {
+            missed_run_policy: MissedRunPolicy::Skip,
+            ..recurrence
+        };
+        let run = missed_run(&recurrence, &clock).unwrap();
+        assert_eq!(run.schedule_time, datetime("2024-08-01T11:00:00"));
+        assert_eq!(run.recurrence.scheduled_for, run.schedule_time);
+    }
+}
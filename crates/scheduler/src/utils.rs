@@ -5,7 +5,7 @@ use diesel_models::enums::{self, ProcessTrackerStatus};
 pub use diesel_models::process_tracker as storage;
 use error_stack::{report, ResultExt};
 use redis_interface::{RedisConnectionPool, RedisEntryId};
-use router_env::{instrument, opentelemetry, tracing};
+use router_env::{instrument, metrics::add_attributes, opentelemetry, tracing};
 use uuid::Uuid;
 
 use super::{
@@ -14,7 +14,7 @@ use super::{
 };
 use crate::{
     configs::settings::SchedulerSettings, consumer::types::ProcessTrackerBatch, errors,
-    flow::SchedulerFlow, metrics, SchedulerInterface, SchedulerSessionState,
+    flow::SchedulerFlow, lanes::Lane, metrics, SchedulerInterface, SchedulerSessionState,
 };
 
 pub async fn divide_and_append_tasks<T>(
@@ -144,19 +144,39 @@ where
     }
 }
 
+/// Divides the tasks into batches, each batch only having tasks of the same lane
 pub fn divide(
     tasks: Vec<storage::ProcessTracker>,
     conf: &SchedulerSettings,
 ) -> Vec<ProcessTrackerBatch> {
     let now = common_utils::date_time::now();
     let batch_size = conf.producer.batch_size;
-    divide_into_batches(batch_size, tasks, now, conf)
+
+    let mut lane_tasks: Vec<(String, Vec<storage::ProcessTracker>)> = Vec::new();
+    for task in tasks {
+        let stream_name = conf.lane_stream_name(task.runner.as_deref());
+        match lane_tasks
+            .iter_mut()
+            .find(|(lane_stream_name, _)| *lane_stream_name == stream_name)
+        {
+            Some((_, tasks)) => tasks.push(task),
+            None => lane_tasks.push((stream_name, vec![task])),
+        }
+    }
+
+    lane_tasks
+        .into_iter()
+        .flat_map(|(stream_name, tasks)| {
+            divide_into_batches(batch_size, tasks, now, &stream_name, conf)
+        })
+        .collect()
 }
 
 pub fn divide_into_batches(
     batch_size: usize,
     tasks: Vec<storage::ProcessTracker>,
     batch_creation_time: time::PrimitiveDateTime,
+    stream_name: &str,
     conf: &SchedulerSettings,
 ) -> Vec<ProcessTrackerBatch> {
     let batch_id = Uuid::new_v4().to_string();
@@ -167,7 +187,7 @@ pub fn divide_into_batches(
             let batch = ProcessTrackerBatch {
                 id: batch_id.clone(),
                 group_name: conf.consumer.consumer_group.clone(),
-                stream_name: conf.stream.clone(),
+                stream_name: stream_name.to_owned(),
                 connection_name: String::new(),
                 created_time: batch_creation_time,
                 rule: String::new(), // is it required?
@@ -264,6 +284,7 @@ pub fn get_time_from_delta(delta: Option<i32>) -> Option<time::PrimitiveDateTime
 pub async fn consumer_operation_handler<E, T>(
     state: T,
     settings: sync::Arc<SchedulerSettings>,
+    lane: &Lane,
     error_handler_fun: E,
     workflow_selector: impl workflows::ProcessTrackerWorkflows<T> + 'static + Copy + std::fmt::Debug,
 ) where
@@ -271,7 +292,7 @@ pub async fn consumer_operation_handler<E, T>(
     E: FnOnce(error_stack::Report<errors::ProcessTrackerError>),
     T: SchedulerSessionState + Send + Sync + 'static,
 {
-    match consumer::consumer_operations(&state, &settings, workflow_selector).await {
+    match consumer::consumer_operations(&state, &settings, lane, workflow_selector).await {
         Ok(_) => (),
         Err(err) => error_handler_fun(err),
     }
@@ -298,6 +319,30 @@ pub fn add_histogram_metrics(
     };
 }
 
+pub fn add_lane_metrics(
+    pickup_time: &time::PrimitiveDateTime,
+    task: &storage::ProcessTracker,
+    lane_name: &str,
+) {
+    let attributes = add_attributes([("lane", lane_name.to_owned())]);
+    metrics::LANE_TASKS_CONSUMED.add(&metrics::CONTEXT, 1, &attributes);
+    if let Some(schedule_time) = task.schedule_time {
+        metrics::LANE_LAG.record(
+            &metrics::CONTEXT,
+            (*pickup_time - schedule_time).as_seconds_f64(),
+            &attributes,
+        );
+    }
+}
+
+pub fn add_lane_length_metrics(length: usize, lane_name: &str) {
+    metrics::LANE_STREAM_LENGTH.observe(
+        &metrics::CONTEXT,
+        u64::try_from(length).unwrap_or(u64::MAX),
+        &add_attributes([("lane", lane_name.to_owned())]),
+    );
+}
+
 pub fn get_schedule_time(
     mapping: process_data::ConnectorPTMapping,
     merchant_name: &str,
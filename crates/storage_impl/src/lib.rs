@@ -114,6 +114,12 @@ impl<T: DatabaseStore> RouterStore<T> {
             .await
             .change_context(StorageError::InitializationError)
             .attach_printable("Failed to subscribe to inmemory cache stream")?;
+        cache_store
+            .redis_conn
+            .subscribe(redis::cache::IMC_INVALIDATION_ACK_CHANNEL)
+            .await
+            .change_context(StorageError::InitializationError)
+            .attach_printable("Failed to subscribe to inmemory cache acknowledgement stream")?;
 
         Ok(Self {
             db_store,
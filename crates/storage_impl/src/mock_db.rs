@@ -38,6 +38,8 @@ pub struct MockDb {
     pub ephemeral_keys: Arc<Mutex<Vec<store::EphemeralKey>>>,
     pub cards_info: Arc<Mutex<Vec<store::CardInfo>>>,
     pub events: Arc<Mutex<Vec<store::Event>>>,
+    pub exchange_rates_history:
+        Arc<Mutex<Vec<store::exchange_rates_history::ExchangeRatesSnapshot>>>,
     pub disputes: Arc<Mutex<Vec<store::Dispute>>>,
     pub lockers: Arc<Mutex<Vec<store::LockerMockUp>>>,
     pub mandates: Arc<Mutex<Vec<store::Mandate>>>,
@@ -84,6 +86,7 @@ impl MockDb {
             ephemeral_keys: Default::default(),
             cards_info: Default::default(),
             events: Default::default(),
+            exchange_rates_history: Default::default(),
             disputes: Default::default(),
             lockers: Default::default(),
             mandates: Default::default(),
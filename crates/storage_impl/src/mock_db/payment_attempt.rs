@@ -158,6 +158,7 @@ impl PaymentAttemptInterface for MockDb {
             client_source: payment_attempt.client_source,
             client_version: payment_attempt.client_version,
             customer_acceptance: payment_attempt.customer_acceptance,
+            dcc_offer: payment_attempt.dcc_offer,
         };
         payment_attempts.push(payment_attempt.clone());
         Ok(payment_attempt)
@@ -419,6 +419,7 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
                     client_source: payment_attempt.client_source.clone(),
                     client_version: payment_attempt.client_version.clone(),
                     customer_acceptance: payment_attempt.customer_acceptance.clone(),
+                    dcc_offer: payment_attempt.dcc_offer.clone(),
                 };
 
                 let field = format!("pa_{}", created_attempt.attempt_id);
@@ -1205,6 +1206,7 @@ impl DataModelExt for PaymentAttempt {
             client_source: self.client_source,
             client_version: self.client_version,
             customer_acceptance: self.customer_acceptance,
+            dcc_offer: self.dcc_offer,
         }
     }
 
@@ -1272,6 +1274,7 @@ impl DataModelExt for PaymentAttempt {
             client_source: storage_model.client_source,
             client_version: storage_model.client_version,
             customer_acceptance: storage_model.customer_acceptance,
+            dcc_offer: storage_model.dcc_offer,
         }
     }
 }
@@ -1347,6 +1350,7 @@ impl DataModelExt for PaymentAttempt {
             client_source: self.client_source,
             client_version: self.client_version,
             customer_acceptance: self.customer_acceptance,
+            dcc_offer: self.dcc_offer,
         }
     }
 
@@ -1414,6 +1418,7 @@ impl DataModelExt for PaymentAttempt {
             client_source: storage_model.client_source,
             client_version: storage_model.client_version,
             customer_acceptance: storage_model.customer_acceptance,
+            dcc_offer: storage_model.dcc_offer,
         }
     }
 }
@@ -1488,6 +1493,7 @@ impl DataModelExt for PaymentAttemptNew {
             client_source: self.client_source,
             client_version: self.client_version,
             customer_acceptance: self.customer_acceptance,
+            dcc_offer: self.dcc_offer,
         }
     }
 
@@ -1554,6 +1560,7 @@ impl DataModelExt for PaymentAttemptNew {
             client_source: storage_model.client_source,
             client_version: storage_model.client_version,
             customer_acceptance: storage_model.customer_acceptance,
+            dcc_offer: storage_model.dcc_offer,
         }
     }
 }
@@ -1679,6 +1686,7 @@ impl DataModelExt for PaymentAttemptUpdate {
                 client_source,
                 client_version,
                 customer_acceptance,
+                dcc_offer,
             } => DieselPaymentAttemptUpdate::ConfirmUpdate {
                 amount: amount.get_amount_as_i64(),
                 currency,
@@ -1712,6 +1720,7 @@ impl DataModelExt for PaymentAttemptUpdate {
                 client_source,
                 client_version,
                 customer_acceptance,
+                dcc_offer,
             },
             Self::VoidUpdate {
                 status,
@@ -2016,6 +2025,7 @@ impl DataModelExt for PaymentAttemptUpdate {
                 client_source,
                 client_version,
                 customer_acceptance,
+                dcc_offer,
             } => Self::ConfirmUpdate {
                 amount: MinorUnit::new(amount),
                 currency,
@@ -2047,6 +2057,7 @@ impl DataModelExt for PaymentAttemptUpdate {
                 client_source,
                 client_version,
                 customer_acceptance,
+                dcc_offer,
             },
             DieselPaymentAttemptUpdate::VoidUpdate {
                 status,
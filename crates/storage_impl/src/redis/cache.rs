@@ -1,4 +1,11 @@
-use std::{any::Any, borrow::Cow, fmt::Debug, sync::Arc};
+use std::{
+    any::Any,
+    borrow::Cow,
+    collections::HashMap,
+    fmt::Debug,
+    sync::{atomic, Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
 
 use common_utils::{
     errors::{self, CustomResult},
@@ -6,23 +13,31 @@ use common_utils::{
 };
 use dyn_clone::DynClone;
 use error_stack::{Report, ResultExt};
-use moka::future::Cache as MokaCache;
+use moka::{future::Cache as MokaCache, notification::RemovalCause};
 use once_cell::sync::Lazy;
 use redis_interface::{errors::RedisError, RedisConnectionPool, RedisValue};
 use router_env::{
+    logger,
     metrics::add_attributes,
-    tracing::{self, instrument},
+    tracing::{self, instrument, Instrument},
 };
 
 use crate::{
     errors::StorageError,
     metrics,
-    redis::{PubSubInterface, RedisConnInterface},
+    redis::{
+        pub_sub::{AckWaiter, CacheInvalidationAck},
+        PubSubInterface, RedisConnInterface,
+    },
 };
 
 /// Redis channel name used for publishing invalidation messages
 pub const IMC_INVALIDATION_CHANNEL: &str = "hyperswitch_invalidate";
 
+/// Redis channel name used by the subscribers of the invalidation channel for acknowledging the
+/// invalidation messages they handled, when their publisher requested it
+pub const IMC_INVALIDATION_ACK_CHANNEL: &str = "hyperswitch_invalidate_ack";
+
 /// Prefix for config cache key
 const CONFIG_CACHE_PREFIX: &str = "config";
 
@@ -41,6 +56,9 @@ const SURCHARGE_CACHE_PREFIX: &str = "surcharge";
 /// Prefix for cgraph cache key
 const CGRAPH_CACHE_PREFIX: &str = "cgraph";
 
+/// Prefix for card info cache key
+const CARD_INFO_CACHE_PREFIX: &str = "card_info";
+
 /// Prefix for all kinds of cache key
 const ALL_CACHE_PREFIX: &str = "all_cache_kind";
 
@@ -53,9 +71,15 @@ const CACHE_TTL: u64 = 30 * 60;
 /// Time to idle 10 mins
 const CACHE_TTI: u64 = 10 * 60;
 
+/// Time after which the entries of the caches with stale-while-revalidate are refreshed, 25 mins
+const CACHE_REFRESH_AFTER: Duration = Duration::from_secs(25 * 60);
+
 /// Max Capacity of Cache in MB
 const MAX_CAPACITY: u64 = 30;
 
+/// Loads of the redis keys in progress, shared by the callers of [`get_or_populate_redis`]
+static REDIS_LOADS: Lazy<Arc<InFlightLoads>> = Lazy::new(Arc::default);
+
 /// Config Cache with time_to_live as 30 mins and time_to_idle as 10 mins.
 pub static CONFIG_CACHE: Lazy<Cache> =
     Lazy::new(|| Cache::new("CONFIG_CACHE", CACHE_TTL, CACHE_TTI, None));
@@ -64,9 +88,11 @@ pub static CONFIG_CACHE: Lazy<Cache> =
 pub static ACCOUNTS_CACHE: Lazy<Cache> =
     Lazy::new(|| Cache::new("ACCOUNTS_CACHE", CACHE_TTL, CACHE_TTI, Some(MAX_CAPACITY)));
 
-/// Routing Cache
-pub static ROUTING_CACHE: Lazy<Cache> =
-    Lazy::new(|| Cache::new("ROUTING_CACHE", CACHE_TTL, CACHE_TTI, Some(MAX_CAPACITY)));
+/// Routing Cache, whose entries are refreshed while being served for 5 mins before they expire
+pub static ROUTING_CACHE: Lazy<Cache> = Lazy::new(|| {
+    Cache::new("ROUTING_CACHE", CACHE_TTL, CACHE_TTI, Some(MAX_CAPACITY))
+        .with_stale_while_revalidate(CACHE_REFRESH_AFTER)
+});
 
 /// 3DS Decision Manager Cache
 pub static DECISION_MANAGER_CACHE: Lazy<Cache> = Lazy::new(|| {
@@ -82,9 +108,11 @@ pub static DECISION_MANAGER_CACHE: Lazy<Cache> = Lazy::new(|| {
 pub static SURCHARGE_CACHE: Lazy<Cache> =
     Lazy::new(|| Cache::new("SURCHARGE_CACHE", CACHE_TTL, CACHE_TTI, Some(MAX_CAPACITY)));
 
-/// CGraph Cache
-pub static CGRAPH_CACHE: Lazy<Cache> =
-    Lazy::new(|| Cache::new("CGRAPH_CACHE", CACHE_TTL, CACHE_TTI, Some(MAX_CAPACITY)));
+/// CGraph Cache, whose entries are refreshed while being served for 5 mins before they expire
+pub static CGRAPH_CACHE: Lazy<Cache> = Lazy::new(|| {
+    Cache::new("CGRAPH_CACHE", CACHE_TTL, CACHE_TTI, Some(MAX_CAPACITY))
+        .with_stale_while_revalidate(CACHE_REFRESH_AFTER)
+});
 
 /// PM Filter CGraph Cache
 pub static PM_FILTERS_CGRAPH_CACHE: Lazy<Cache> = Lazy::new(|| {
@@ -96,11 +124,71 @@ pub static PM_FILTERS_CGRAPH_CACHE: Lazy<Cache> = Lazy::new(|| {
     )
 });
 
+/// Card Info Cache, holding the range index of the card info table
+pub static CARD_INFO_CACHE: Lazy<Cache> =
+    Lazy::new(|| Cache::new("CARD_INFO_CACHE", CACHE_TTL, CACHE_TTI, None));
+
+/// The in-memory caches, along with the kind of their keys in invalidation messages
+pub fn caches() -> [(&'static str, &'static Cache); 8] {
+    [
+        (CONFIG_CACHE_PREFIX, &*CONFIG_CACHE),
+        (ACCOUNTS_CACHE_PREFIX, &*ACCOUNTS_CACHE),
+        (ROUTING_CACHE_PREFIX, &*ROUTING_CACHE),
+        (DECISION_MANAGER_CACHE_PREFIX, &*DECISION_MANAGER_CACHE),
+        (SURCHARGE_CACHE_PREFIX, &*SURCHARGE_CACHE),
+        (CGRAPH_CACHE_PREFIX, &*CGRAPH_CACHE),
+        (PM_FILTERS_CGRAPH_CACHE_PREFIX, &*PM_FILTERS_CGRAPH_CACHE),
+        (CARD_INFO_CACHE_PREFIX, &*CARD_INFO_CACHE),
+    ]
+}
+
 /// Trait which defines the behaviour of types that's gonna be stored in Cache
 pub trait Cacheable: Any + Send + Sync + DynClone {
     fn as_any(&self) -> &dyn Any;
 }
 
+/// Errors of the loaders of [`Cache::get_or_populate`]. A failed load is shared by the callers
+/// which waited for it, each of them getting a copy of the error.
+pub trait CacheLoadError: error_stack::Context + Sized {
+    /// Copy of the error for a caller which waited for the failed load
+    fn copy_for_waiter(&self) -> Self;
+
+    /// Error of the cached value of a key not being of the requested type
+    fn type_mismatch() -> Self;
+}
+
+impl CacheLoadError for StorageError {
+    fn copy_for_waiter(&self) -> Self {
+        match self {
+            Self::DatabaseError(error) => {
+                Self::DatabaseError(Report::new(*error.current_context()))
+            }
+            Self::ValueNotFound(value) => Self::ValueNotFound(value.clone()),
+            Self::DuplicateValue { entity, key } => Self::DuplicateValue {
+                entity: *entity,
+                key: key.clone(),
+            },
+            Self::DatabaseConnectionError => Self::DatabaseConnectionError,
+            Self::KVError => Self::KVError,
+            Self::SerializationFailed => Self::SerializationFailed,
+            Self::MockDbError => Self::MockDbError,
+            Self::KafkaError => Self::KafkaError,
+            Self::CustomerRedacted => Self::CustomerRedacted,
+            Self::DeserializationFailed => Self::DeserializationFailed,
+            Self::EncryptionError => Self::EncryptionError,
+            Self::DecryptionError => Self::DecryptionError,
+            Self::RedisError(error) => {
+                Self::RedisError(Report::new(error.current_context().clone()))
+            }
+        }
+    }
+
+    fn type_mismatch() -> Self {
+        Self::DeserializationFailed
+    }
+}
+
+#[derive(Clone, Debug)]
 pub enum CacheKind<'a> {
     Config(Cow<'a, str>),
     Accounts(Cow<'a, str>),
@@ -109,6 +197,7 @@ pub enum CacheKind<'a> {
     Surcharge(Cow<'a, str>),
     CGraph(Cow<'a, str>),
     PmFiltersCGraph(Cow<'a, str>),
+    CardInfo(Cow<'a, str>),
     All(Cow<'a, str>),
 }
 
@@ -122,12 +211,32 @@ impl<'a> From<CacheKind<'a>> for RedisValue {
             CacheKind::Surcharge(s) => format!("{SURCHARGE_CACHE_PREFIX},{s}"),
             CacheKind::CGraph(s) => format!("{CGRAPH_CACHE_PREFIX},{s}"),
             CacheKind::PmFiltersCGraph(s) => format!("{PM_FILTERS_CGRAPH_CACHE_PREFIX},{s}"),
+            CacheKind::CardInfo(s) => format!("{CARD_INFO_CACHE_PREFIX},{s}"),
             CacheKind::All(s) => format!("{ALL_CACHE_PREFIX},{s}"),
         };
         Self::from_string(value)
     }
 }
 
+impl<'a> CacheKind<'a> {
+    /// Builds the cache kind of the key from the kind of the keys of a cache, as returned by
+    /// [`caches`], or the kind of the keys of all caches
+    pub fn new(kind: &str, key: Cow<'a, str>) -> Option<Self> {
+        match kind {
+            ACCOUNTS_CACHE_PREFIX => Some(Self::Accounts(key)),
+            CONFIG_CACHE_PREFIX => Some(Self::Config(key)),
+            ROUTING_CACHE_PREFIX => Some(Self::Routing(key)),
+            DECISION_MANAGER_CACHE_PREFIX => Some(Self::DecisionManager(key)),
+            SURCHARGE_CACHE_PREFIX => Some(Self::Surcharge(key)),
+            CGRAPH_CACHE_PREFIX => Some(Self::CGraph(key)),
+            PM_FILTERS_CGRAPH_CACHE_PREFIX => Some(Self::PmFiltersCGraph(key)),
+            CARD_INFO_CACHE_PREFIX => Some(Self::CardInfo(key)),
+            ALL_CACHE_PREFIX => Some(Self::All(key)),
+            _ => None,
+        }
+    }
+}
+
 impl<'a> TryFrom<RedisValue> for CacheKind<'a> {
     type Error = Report<errors::ValidationError>;
     fn try_from(kind: RedisValue) -> Result<Self, Self::Error> {
@@ -136,21 +245,7 @@ impl<'a> TryFrom<RedisValue> for CacheKind<'a> {
         };
         let kind = kind.as_string().ok_or(validation_err.clone())?;
         let split = kind.split_once(',').ok_or(validation_err.clone())?;
-        match split.0 {
-            ACCOUNTS_CACHE_PREFIX => Ok(Self::Accounts(Cow::Owned(split.1.to_string()))),
-            CONFIG_CACHE_PREFIX => Ok(Self::Config(Cow::Owned(split.1.to_string()))),
-            ROUTING_CACHE_PREFIX => Ok(Self::Routing(Cow::Owned(split.1.to_string()))),
-            DECISION_MANAGER_CACHE_PREFIX => {
-                Ok(Self::DecisionManager(Cow::Owned(split.1.to_string())))
-            }
-            SURCHARGE_CACHE_PREFIX => Ok(Self::Surcharge(Cow::Owned(split.1.to_string()))),
-            CGRAPH_CACHE_PREFIX => Ok(Self::CGraph(Cow::Owned(split.1.to_string()))),
-            PM_FILTERS_CGRAPH_CACHE_PREFIX => {
-                Ok(Self::PmFiltersCGraph(Cow::Owned(split.1.to_string())))
-            }
-            ALL_CACHE_PREFIX => Ok(Self::All(Cow::Owned(split.1.to_string()))),
-            _ => Err(validation_err.into()),
-        }
+        Self::new(split.0, Cow::Owned(split.1.to_string())).ok_or(validation_err.into())
     }
 }
 
@@ -167,7 +262,117 @@ dyn_clone::clone_trait_object!(Cacheable);
 
 pub struct Cache {
     name: &'static str,
-    inner: MokaCache<String, Arc<dyn Cacheable>>,
+    inner: MokaCache<String, CacheEntry>,
+    counters: Arc<CacheCounters>,
+    /// Age after which the entries are stale, being refreshed while still being served
+    refresh_after: Option<Duration>,
+    /// Refreshes of stale entries in progress
+    refreshes: Arc<InFlightLoads>,
+}
+
+#[derive(Clone)]
+struct CacheEntry {
+    value: Arc<dyn Cacheable>,
+    inserted_at: Instant,
+}
+
+impl CacheEntry {
+    fn new<T: Cacheable>(val: T) -> Self {
+        Self {
+            value: Arc::new(val),
+            inserted_at: Instant::now(),
+        }
+    }
+}
+
+/// Loads of keys in progress, for concurrent misses of a key to wait for a single load of it
+/// instead of each loading it
+#[derive(Debug, Default)]
+struct InFlightLoads {
+    loads: Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>,
+}
+
+impl InFlightLoads {
+    fn lock_of(&self, key: &str) -> Arc<tokio::sync::Mutex<()>> {
+        Arc::clone(
+            self.loads
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .entry(key.to_owned())
+                .or_default(),
+        )
+    }
+
+    /// Waits for the load of the key in progress if any, and marks the key as being loaded
+    async fn start(self: &Arc<Self>, key: String) -> InFlightLoad {
+        let guard = self.lock_of(&key).lock_owned().await;
+        InFlightLoad {
+            loads: Arc::clone(self),
+            key,
+            _guard: guard,
+        }
+    }
+
+    /// Marks the key as being loaded, unless it already is
+    fn try_start(self: &Arc<Self>, key: String) -> Option<InFlightLoad> {
+        let guard = self.lock_of(&key).try_lock_owned().ok()?;
+        Some(InFlightLoad {
+            loads: Arc::clone(self),
+            key,
+            _guard: guard,
+        })
+    }
+}
+
+/// Load of a key in progress, which ends when dropped
+struct InFlightLoad {
+    loads: Arc<InFlightLoads>,
+    key: String,
+    _guard: tokio::sync::OwnedMutexGuard<()>,
+}
+
+impl Drop for InFlightLoad {
+    fn drop(&mut self) {
+        let mut loads = self
+            .loads
+            .loads
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        // The lock is referenced by the map and by this load only when no caller is waiting for it
+        if loads
+            .get(&self.key)
+            .is_some_and(|lock| Arc::strong_count(lock) <= 2)
+        {
+            loads.remove(&self.key);
+        }
+    }
+}
+
+/// Counters of the operations on a cache since the application started
+#[derive(Debug, Default)]
+struct CacheCounters {
+    hits: atomic::AtomicU64,
+    misses: atomic::AtomicU64,
+    evictions: atomic::AtomicU64,
+    invalidations: atomic::AtomicU64,
+}
+
+/// Statistics of a cache, the counters being counted since the application started
+#[derive(Debug, Clone)]
+pub struct CacheStatistics {
+    pub name: &'static str,
+    /// Approximate number of entries in the cache
+    pub entry_count: u64,
+    pub weighted_size: u64,
+    pub max_capacity: Option<u64>,
+    pub time_to_live: Option<Duration>,
+    pub time_to_idle: Option<Duration>,
+    pub hits: u64,
+    pub misses: u64,
+    /// Entries removed because they expired or because the cache was full
+    pub evictions: u64,
+    /// Entries removed by the application
+    pub invalidations: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -200,8 +405,18 @@ impl Cache {
         time_to_idle: u64,
         max_capacity: Option<u64>,
     ) -> Self {
+        let counters = Arc::new(CacheCounters::default());
+        let listener_counters = Arc::clone(&counters);
         // Record the metrics of manual invalidation of cache entry by the application
-        let eviction_listener = move |_, _, cause| {
+        let eviction_listener = move |_, _, cause: RemovalCause| {
+            let counter = match cause {
+                RemovalCause::Expired | RemovalCause::Size => Some(&listener_counters.evictions),
+                RemovalCause::Explicit => Some(&listener_counters.invalidations),
+                RemovalCause::Replaced => None,
+            };
+            if let Some(counter) = counter {
+                counter.fetch_add(1, atomic::Ordering::Relaxed);
+            }
             metrics::IN_MEMORY_CACHE_EVICTION_COUNT.add(
                 &metrics::CONTEXT,
                 1,
@@ -223,24 +438,42 @@ impl Cache {
         Self {
             name,
             inner: cache_builder.build(),
+            counters,
+            refresh_after: None,
+            refreshes: Arc::default(),
         }
     }
 
+    /// Serves the entries older than `refresh_after` while [`Cache::get_or_populate`] refreshes
+    /// them in the background, instead of having the callers wait for their load once they
+    /// expire. `refresh_after` is expected to be less than the time to live.
+    pub fn with_stale_while_revalidate(mut self, refresh_after: Duration) -> Self {
+        self.refresh_after = Some(refresh_after);
+        self
+    }
+
     pub async fn push<T: Cacheable>(&self, key: CacheKey, val: T) {
-        self.inner.insert(key.into(), Arc::new(val)).await;
+        self.inner.insert(key.into(), CacheEntry::new(val)).await;
     }
 
     pub async fn get_val<T: Clone + Cacheable>(&self, key: CacheKey) -> Option<T> {
+        self.get_entry(key).await.map(|(val, _)| val)
+    }
+
+    /// Gets the value of the key along with whether it is stale
+    async fn get_entry<T: Clone + Cacheable>(&self, key: CacheKey) -> Option<(T, bool)> {
         let val = self.inner.get::<String>(&key.into()).await;
 
         // Add cache hit and cache miss metrics
         if val.is_some() {
+            self.counters.hits.fetch_add(1, atomic::Ordering::Relaxed);
             metrics::IN_MEMORY_CACHE_HIT.add(
                 &metrics::CONTEXT,
                 1,
                 &add_attributes([("cache_type", self.name)]),
             );
         } else {
+            self.counters.misses.fetch_add(1, atomic::Ordering::Relaxed);
             metrics::IN_MEMORY_CACHE_MISS.add(
                 &metrics::CONTEXT,
                 1,
@@ -248,9 +481,118 @@ impl Cache {
             );
         }
 
-        let val = (*val?).as_any().downcast_ref::<T>().cloned();
+        let entry = val?;
+        let is_stale = self
+            .refresh_after
+            .is_some_and(|refresh_after| entry.inserted_at.elapsed() >= refresh_after);
+
+        entry
+            .value
+            .as_any()
+            .downcast_ref::<T>()
+            .cloned()
+            .map(|val| (val, is_stale))
+    }
+
+    /// Gets the value of the key, loading it with `fun` if it is not cached. Concurrent misses of
+    /// a key share a single load of it, along with its result.
+    ///
+    /// With stale-while-revalidate, stale values are served while a single refresh of them runs in
+    /// a spawned task, the stale value being kept if the refresh fails.
+    pub async fn get_or_populate<T, E, F, Fut>(&self, key: CacheKey, fun: F) -> CustomResult<T, E>
+    where
+        T: Clone + Cacheable,
+        E: CacheLoadError,
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: futures::Future<Output = CustomResult<T, E>> + Send + 'static,
+    {
+        match self.get_entry::<T>(key.clone()).await {
+            Some((val, false)) => Ok(val),
+            Some((stale_val, true)) => {
+                self.spawn_refresh(key, fun);
+                Ok(stale_val)
+            }
+            None => self.load(key, fun).await,
+        }
+    }
 
-        val
+    /// Gets the value of the key like [`Cache::get_or_populate`], for loaders borrowing from their
+    /// caller. Stale values are served without being refreshed, until they expire.
+    async fn get_or_load<T, E, F, Fut>(&self, key: CacheKey, fun: F) -> CustomResult<T, E>
+    where
+        T: Clone + Cacheable,
+        E: CacheLoadError,
+        F: FnOnce() -> Fut + Send,
+        Fut: futures::Future<Output = CustomResult<T, E>> + Send,
+    {
+        match self.get_entry::<T>(key.clone()).await {
+            Some((val, _)) => Ok(val),
+            None => self.load(key, fun).await,
+        }
+    }
+
+    /// Loads the value of the key with `fun` and caches it. Concurrent loads of a key wait for the
+    /// first one, and get its value or a copy of its error.
+    async fn load<T, E, F, Fut>(&self, key: CacheKey, fun: F) -> CustomResult<T, E>
+    where
+        T: Clone + Cacheable,
+        E: CacheLoadError,
+        F: FnOnce() -> Fut + Send,
+        Fut: futures::Future<Output = CustomResult<T, E>> + Send,
+    {
+        let entry = self
+            .inner
+            .try_get_with(key.into(), async move { fun().await.map(CacheEntry::new) })
+            .await
+            .map_err(|error| {
+                Arc::try_unwrap(error).unwrap_or_else(|error| {
+                    Report::new(error.current_context().copy_for_waiter())
+                        .attach_printable(format!("Failed to load the cache entry: {error:?}"))
+                })
+            })?;
+
+        entry
+            .value
+            .as_any()
+            .downcast_ref::<T>()
+            .cloned()
+            .ok_or_else(|| Report::new(E::type_mismatch()))
+            .attach_printable_lazy(|| {
+                format!(
+                    "Cache entry of {} is not of type {}",
+                    self.name,
+                    std::any::type_name::<T>()
+                )
+            })
+    }
+
+    /// Refreshes the stale value of the key with `fun` in a spawned task, unless it is already
+    /// being refreshed. The stale value is kept if the refresh fails.
+    fn spawn_refresh<T, E, F, Fut>(&self, key: CacheKey, fun: F)
+    where
+        T: Cacheable,
+        E: CacheLoadError,
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: futures::Future<Output = CustomResult<T, E>> + Send + 'static,
+    {
+        let Some(refresh) = self.refreshes.try_start(String::from(key.clone())) else {
+            return;
+        };
+        let inner = self.inner.clone();
+        let name = self.name;
+
+        let _task_handle = tokio::spawn(
+            async move {
+                let _refresh = refresh;
+                match fun().await {
+                    Ok(val) => inner.insert(key.into(), CacheEntry::new(val)).await,
+                    Err(error) => {
+                        logger::error!(?error, cache = name, "Failed to refresh cache entry")
+                    }
+                }
+            }
+            .in_current_span(),
+        );
     }
 
     /// Check if a key exists in cache
@@ -276,6 +618,47 @@ impl Cache {
         self.name
     }
 
+    pub async fn statistics(&self) -> CacheStatistics {
+        self.run_pending_tasks().await;
+        let policy = self.inner.policy();
+
+        CacheStatistics {
+            name: self.name,
+            entry_count: self.get_entry_count(),
+            weighted_size: self.inner.weighted_size(),
+            max_capacity: policy.max_capacity(),
+            time_to_live: policy.time_to_live(),
+            time_to_idle: policy.time_to_idle(),
+            hits: self.counters.hits.load(atomic::Ordering::Relaxed),
+            misses: self.counters.misses.load(atomic::Ordering::Relaxed),
+            evictions: self.counters.evictions.load(atomic::Ordering::Relaxed),
+            invalidations: self.counters.invalidations.load(atomic::Ordering::Relaxed),
+        }
+    }
+
+    /// Lists the keys of the cache starting with the key of `prefix`, among the keys with the
+    /// prefix of `prefix`, in lexicographic order. The prefix of the keys is not included.
+    pub fn keys(&self, prefix: CacheKey, limit: usize) -> Vec<String> {
+        let key_prefix = if prefix.prefix.is_empty() {
+            String::new()
+        } else {
+            format!("{}:", prefix.prefix)
+        };
+        let mut keys = self
+            .inner
+            .iter()
+            .filter_map(|(key, _)| {
+                key.strip_prefix(&key_prefix)
+                    .filter(|key| key.starts_with(&prefix.key))
+                    .map(ToOwned::to_owned)
+            })
+            .collect::<Vec<_>>();
+        keys.sort_unstable();
+        keys.truncate(limit);
+
+        keys
+    }
+
     pub async fn record_entry_count_metric(&self) {
         self.run_pending_tasks().await;
 
@@ -298,30 +681,56 @@ where
     F: FnOnce() -> Fut + Send,
     Fut: futures::Future<Output = CustomResult<T, StorageError>> + Send,
 {
-    let type_name = std::any::type_name::<T>();
     let key = key.as_ref();
-    let redis_val = redis.get_and_deserialize_key::<T>(key, type_name).await;
-    let get_data_set_redis = || async {
-        let data = fun().await?;
-        redis
-            .serialize_and_set_key(key, &data)
-            .await
-            .change_context(StorageError::KVError)?;
-        Ok::<_, Report<StorageError>>(data)
-    };
-    match redis_val {
-        Err(err) => match err.current_context() {
-            RedisError::NotFound | RedisError::JsonDeserializationFailed => {
-                get_data_set_redis().await
+    if let Some(val) = get_from_redis(redis, key).await? {
+        return Ok(val);
+    }
+
+    // Concurrent misses of the key in this application instance wait for a single load of it
+    let _load = REDIS_LOADS
+        .start(
+            CacheKey {
+                key: key.to_owned(),
+                prefix: redis.key_prefix.clone(),
             }
+            .into(),
+        )
+        .await;
+    // The key may have been loaded by another caller while waiting
+    if let Some(val) = get_from_redis(redis, key).await? {
+        return Ok(val);
+    }
+
+    let data = fun().await?;
+    redis
+        .serialize_and_set_key(key, &data)
+        .await
+        .change_context(StorageError::KVError)?;
+    Ok(data)
+}
+
+async fn get_from_redis<T>(
+    redis: &Arc<RedisConnectionPool>,
+    key: &str,
+) -> CustomResult<Option<T>, StorageError>
+where
+    T: serde::de::DeserializeOwned + Debug,
+{
+    let type_name = std::any::type_name::<T>();
+    match redis.get_and_deserialize_key::<T>(key, type_name).await {
+        Err(err) => match err.current_context() {
+            RedisError::NotFound | RedisError::JsonDeserializationFailed => Ok(None),
             _ => Err(err
                 .change_context(StorageError::KVError)
                 .attach_printable(format!("Error while fetching cache for {type_name}"))),
         },
-        Ok(val) => Ok(val),
+        Ok(val) => Ok(Some(val)),
     }
 }
 
+/// Gets the value of the key from the in-memory cache, falling back to redis and then to `fun`.
+/// Stale values of caches with stale-while-revalidate are not refreshed, as `fun` borrows from its
+/// caller.
 #[instrument(skip_all)]
 pub async fn get_or_populate_in_memory<T, F, Fut>(
     store: &(dyn RedisConnInterface + Send + Sync),
@@ -340,27 +749,15 @@ where
             RedisError::RedisConnectionError.into(),
         ))
         .attach_printable("Failed to get redis connection")?;
-    let cache_val = cache
-        .get_val::<T>(CacheKey {
-            key: key.to_string(),
-            prefix: redis.key_prefix.clone(),
-        })
-        .await;
-    if let Some(val) = cache_val {
-        Ok(val)
-    } else {
-        let val = get_or_populate_redis(redis, key, fun).await?;
-        cache
-            .push(
-                CacheKey {
-                    key: key.to_string(),
-                    prefix: redis.key_prefix.clone(),
-                },
-                val.clone(),
-            )
-            .await;
-        Ok(val)
-    }
+    cache
+        .get_or_load(
+            CacheKey {
+                key: key.to_string(),
+                prefix: redis.key_prefix.clone(),
+            },
+            || get_or_populate_redis(redis, key, fun),
+        )
+        .await
 }
 
 #[instrument(skip_all)]
@@ -421,6 +818,55 @@ pub async fn publish_into_redact_channel<'a, K: IntoIterator<Item = CacheKind<'a
         .sum::<usize>())
 }
 
+/// Report of the propagation of the invalidation of a key to the subscribers of the invalidation
+/// channel
+#[derive(Debug, Clone)]
+pub struct InvalidationReport {
+    /// Number of subscribers the invalidation message was delivered to
+    pub receivers: usize,
+    /// Subscribers which acknowledged the invalidation, in the order of their acknowledgement
+    pub acknowledgements: Vec<CacheInvalidationAck>,
+}
+
+/// Publishes the invalidation of the key like [`publish_into_redact_channel`], along with a
+/// correlation id requesting its acknowledgement, and waits for the subscribers the invalidation
+/// message was delivered to for acknowledging it, for at most `timeout`.
+#[instrument(skip_all)]
+pub async fn publish_into_redact_channel_with_ack<'a>(
+    store: &(dyn RedisConnInterface + Send + Sync),
+    key: CacheKind<'a>,
+    timeout: Duration,
+) -> CustomResult<InvalidationReport, StorageError> {
+    let redis_conn = store
+        .get_redis_conn()
+        .change_context(StorageError::RedisError(
+            RedisError::RedisConnectionError.into(),
+        ))
+        .attach_printable("Failed to get redis connection")?;
+
+    // The waiter is registered before publishing, for acknowledgements not to be missed
+    let mut waiter = AckWaiter::register();
+
+    let receivers = redis_conn
+        .publish_with_ack_request(IMC_INVALIDATION_CHANNEL, key, waiter.correlation_id())
+        .await
+        .change_context(StorageError::KVError)?;
+
+    let deadline = tokio::time::Instant::now() + timeout;
+    let mut acknowledgements = Vec::with_capacity(receivers);
+    while acknowledgements.len() < receivers {
+        match tokio::time::timeout_at(deadline, waiter.recv()).await {
+            Ok(Some(acknowledgement)) => acknowledgements.push(acknowledgement),
+            Ok(None) | Err(_) => break,
+        }
+    }
+
+    Ok(InvalidationReport {
+        receivers,
+        acknowledgements,
+    })
+}
+
 #[instrument(skip_all)]
 pub async fn publish_and_redact<'a, T, F, Fut>(
     store: &(dyn RedisConnInterface + Send + Sync),
@@ -454,6 +900,8 @@ where
 
 #[cfg(test)]
 mod cache_tests {
+    #![allow(clippy::unwrap_used)]
+
     use super::*;
 
     #[tokio::test]
@@ -556,4 +1004,148 @@ mod cache_tests {
             None
         );
     }
+
+    #[tokio::test]
+    async fn cache_statistics() {
+        let cache = Cache::new("test", 1800, 1800, Some(1));
+        let key = CacheKey {
+            key: "key".to_string(),
+            prefix: "prefix".to_string(),
+        };
+        cache.push(key.clone(), "val".to_string()).await;
+
+        assert!(cache.get_val::<String>(key.clone()).await.is_some());
+        cache.remove(key.clone()).await;
+        assert!(cache.get_val::<String>(key).await.is_none());
+
+        let statistics = cache.statistics().await;
+        assert_eq!(statistics.entry_count, 0);
+        assert_eq!(statistics.max_capacity, Some(1024 * 1024));
+        assert_eq!(
+            statistics.time_to_live,
+            Some(std::time::Duration::from_secs(1800))
+        );
+        assert_eq!(statistics.hits, 1);
+        assert_eq!(statistics.misses, 1);
+        assert_eq!(statistics.evictions, 0);
+        assert_eq!(statistics.invalidations, 1);
+    }
+
+    #[tokio::test]
+    async fn list_cache_keys_by_prefix() {
+        let cache = Cache::new("test", 1800, 1800, None);
+        for (prefix, key) in [
+            ("tenant", "merchant_2"),
+            ("tenant", "merchant_1"),
+            ("tenant", "profile_1"),
+            ("other_tenant", "merchant_3"),
+        ] {
+            cache
+                .push(
+                    CacheKey {
+                        key: key.to_string(),
+                        prefix: prefix.to_string(),
+                    },
+                    "val".to_string(),
+                )
+                .await;
+        }
+        cache.run_pending_tasks().await;
+
+        let prefix = CacheKey {
+            key: "merchant".to_string(),
+            prefix: "tenant".to_string(),
+        };
+        assert_eq!(
+            cache.keys(prefix.clone(), 10),
+            vec!["merchant_1".to_string(), "merchant_2".to_string()]
+        );
+        assert_eq!(cache.keys(prefix, 1), vec!["merchant_1".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn concurrent_misses_load_once() {
+        let cache = Cache::new("test", 1800, 1800, None);
+        let loads = Arc::new(atomic::AtomicUsize::new(0));
+        let key = CacheKey {
+            key: "key".to_string(),
+            prefix: "prefix".to_string(),
+        };
+
+        let results = futures::future::join_all((0..10).map(|_| {
+            let loads = Arc::clone(&loads);
+            cache.get_or_populate(key.clone(), move || async move {
+                loads.fetch_add(1, atomic::Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                Ok::<_, Report<StorageError>>("val".to_string())
+            })
+        }))
+        .await;
+
+        assert_eq!(loads.load(atomic::Ordering::SeqCst), 1);
+        assert!(results
+            .into_iter()
+            .all(|result| result.ok() == Some("val".to_string())));
+    }
+
+    #[tokio::test]
+    async fn concurrent_misses_share_failed_load() {
+        let cache = Cache::new("test", 1800, 1800, None);
+        let loads = Arc::new(atomic::AtomicUsize::new(0));
+        let key = CacheKey {
+            key: "key".to_string(),
+            prefix: "prefix".to_string(),
+        };
+
+        let results = futures::future::join_all((0..10).map(|_| {
+            let loads = Arc::clone(&loads);
+            cache.get_or_populate(key.clone(), move || async move {
+                loads.fetch_add(1, atomic::Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                Err::<String, _>(Report::new(StorageError::ValueNotFound("key".to_string())))
+            })
+        }))
+        .await;
+
+        assert_eq!(loads.load(atomic::Ordering::SeqCst), 1);
+        assert!(results
+            .into_iter()
+            .all(|result| result.is_err_and(|error| error.current_context().is_db_not_found())));
+        assert!(!cache.exists(key).await);
+    }
+
+    #[tokio::test]
+    async fn stale_values_are_served_while_revalidating() {
+        let cache =
+            Cache::new("test", 1800, 1800, None).with_stale_while_revalidate(Duration::ZERO);
+        let loads = Arc::new(atomic::AtomicUsize::new(0));
+        let key = CacheKey {
+            key: "key".to_string(),
+            prefix: "prefix".to_string(),
+        };
+        cache.push(key.clone(), "stale".to_string()).await;
+
+        let results = futures::future::join_all((0..10).map(|_| {
+            let loads = Arc::clone(&loads);
+            cache.get_or_populate(key.clone(), move || async move {
+                loads.fetch_add(1, atomic::Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                Ok::<_, Report<StorageError>>("fresh".to_string())
+            })
+        }))
+        .await
+        .into_iter()
+        .map(|result| result.unwrap())
+        .collect::<Vec<_>>();
+
+        // The callers get the stale value without waiting for the refresh
+        assert!(results.iter().all(|val| val == "stale"));
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(loads.load(atomic::Ordering::SeqCst), 1);
+        assert_eq!(
+            cache.get_val::<String>(key).await,
+            Some("fresh".to_string())
+        );
+    }
 }
@@ -1,14 +1,132 @@
-use std::sync::atomic;
+use std::{
+    collections::HashMap,
+    sync::{atomic, Mutex, PoisonError},
+};
 
+use common_utils::errors;
 use error_stack::ResultExt;
+use once_cell::sync::Lazy;
 use redis_interface::{errors as redis_errors, PubsubInterface, RedisValue};
 use router_env::{logger, tracing::Instrument};
+use tokio::sync::mpsc;
 
 use crate::redis::cache::{
-    CacheKey, CacheKind, ACCOUNTS_CACHE, CGRAPH_CACHE, CONFIG_CACHE, DECISION_MANAGER_CACHE,
-    PM_FILTERS_CGRAPH_CACHE, ROUTING_CACHE, SURCHARGE_CACHE,
+    CacheKey, CacheKind, ACCOUNTS_CACHE, CARD_INFO_CACHE, CGRAPH_CACHE, CONFIG_CACHE,
+    DECISION_MANAGER_CACHE, PM_FILTERS_CGRAPH_CACHE, ROUTING_CACHE, SURCHARGE_CACHE,
 };
 
+/// Identifier of this application instance in the acknowledgements of the invalidation messages
+static SUBSCRIBER_ID: Lazy<String> = Lazy::new(|| {
+    let hostname = std::env::var("HOSTNAME").unwrap_or_else(|_| "subscriber".to_owned());
+    common_utils::generate_id(8, &hostname)
+});
+
+type AckSender = mpsc::UnboundedSender<CacheInvalidationAck>;
+
+/// Waiters for the acknowledgements of the invalidation messages published by this application
+/// instance, by the correlation id of the message they are waiting the acknowledgements of
+static ACK_WAITERS: Lazy<Mutex<HashMap<String, AckSender>>> = Lazy::new(Mutex::default);
+
+/// Prefix of the invalidation messages whose publisher waits for their acknowledgement, followed
+/// by the correlation id of the message and [`ACK_REQUEST_SEPARATOR`]
+const ACK_REQUEST_PREFIX: &str = "ack:";
+
+const ACK_REQUEST_SEPARATOR: char = ';';
+
+/// Acknowledgement of an invalidation message by a subscriber of the invalidation channel
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CacheInvalidationAck {
+    pub subscriber_id: String,
+    /// Correlation id of the acknowledged invalidation message
+    pub correlation_id: String,
+}
+
+/// Receiver of the acknowledgements of an invalidation message, which stops receiving them when
+/// dropped
+pub(crate) struct AckWaiter {
+    correlation_id: String,
+    receiver: mpsc::UnboundedReceiver<CacheInvalidationAck>,
+}
+
+impl AckWaiter {
+    /// Registers a waiter under a new correlation id, to be sent along with the invalidation
+    /// message
+    pub(crate) fn register() -> Self {
+        let correlation_id = common_utils::generate_id_with_default_len("invalidation");
+        let (sender, receiver) = mpsc::unbounded_channel();
+        ACK_WAITERS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(correlation_id.clone(), sender);
+
+        Self {
+            correlation_id,
+            receiver,
+        }
+    }
+
+    pub(crate) fn correlation_id(&self) -> &str {
+        &self.correlation_id
+    }
+
+    pub(crate) async fn recv(&mut self) -> Option<CacheInvalidationAck> {
+        self.receiver.recv().await
+    }
+}
+
+impl Drop for AckWaiter {
+    fn drop(&mut self) {
+        ACK_WAITERS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&self.correlation_id);
+    }
+}
+
+fn notify_ack_waiter(ack: CacheInvalidationAck) {
+    if let Some(sender) = ACK_WAITERS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&ack.correlation_id)
+    {
+        // The waiter may have stopped waiting already
+        let _ = sender.send(ack);
+    }
+}
+
+/// Builds the invalidation message of the key, requesting its acknowledgement under the
+/// correlation id if there is one
+fn invalidation_message(key: CacheKind<'_>, correlation_id: Option<&str>) -> RedisValue {
+    let message = RedisValue::from(key);
+    match (correlation_id, message.as_string()) {
+        (Some(correlation_id), Some(message)) => RedisValue::from_string(format!(
+            "{ACK_REQUEST_PREFIX}{correlation_id}{ACK_REQUEST_SEPARATOR}{message}"
+        )),
+        _ => message,
+    }
+}
+
+/// Parses an invalidation message into the invalidated key, along with the correlation id of the
+/// message if its publisher waits for its acknowledgement
+fn parse_invalidation_message(
+    message: RedisValue,
+) -> error_stack::Result<(CacheKind<'static>, Option<String>), errors::ValidationError> {
+    let ack_request = message.as_string().and_then(|message| {
+        message
+            .strip_prefix(ACK_REQUEST_PREFIX)
+            .and_then(|message| message.split_once(ACK_REQUEST_SEPARATOR))
+            .map(|(correlation_id, message)| (correlation_id.to_owned(), message.to_owned()))
+    });
+
+    match ack_request {
+        Some((correlation_id, message)) => Ok((
+            CacheKind::try_from(RedisValue::from_string(message))?,
+            Some(correlation_id),
+        )),
+        None => Ok((CacheKind::try_from(message)?, None)),
+    }
+}
+
 #[async_trait::async_trait]
 pub trait PubSubInterface {
     async fn subscribe(&self, channel: &str) -> error_stack::Result<(), redis_errors::RedisError>;
@@ -19,6 +137,15 @@ pub trait PubSubInterface {
         key: CacheKind<'a>,
     ) -> error_stack::Result<usize, redis_errors::RedisError>;
 
+    /// Publishes the invalidation of the key, requesting the subscribers to acknowledge it under
+    /// the correlation id
+    async fn publish_with_ack_request<'a>(
+        &self,
+        channel: &str,
+        key: CacheKind<'a>,
+        correlation_id: &str,
+    ) -> error_stack::Result<usize, redis_errors::RedisError>;
+
     async fn on_message(&self) -> error_stack::Result<(), redis_errors::RedisError>;
 }
 
@@ -67,7 +194,23 @@ impl PubSubInterface for std::sync::Arc<redis_interface::RedisConnectionPool> {
         key: CacheKind<'a>,
     ) -> error_stack::Result<usize, redis_errors::RedisError> {
         self.publisher
-            .publish(channel, RedisValue::from(key).into_inner())
+            .publish(channel, invalidation_message(key, None).into_inner())
+            .await
+            .change_context(redis_errors::RedisError::SubscribeError)
+    }
+
+    #[inline]
+    async fn publish_with_ack_request<'a>(
+        &self,
+        channel: &str,
+        key: CacheKind<'a>,
+        correlation_id: &str,
+    ) -> error_stack::Result<usize, redis_errors::RedisError> {
+        self.publisher
+            .publish(
+                channel,
+                invalidation_message(key, Some(correlation_id)).into_inner(),
+            )
             .await
             .change_context(redis_errors::RedisError::SubscribeError)
     }
@@ -82,15 +225,16 @@ impl PubSubInterface for std::sync::Arc<redis_interface::RedisConnectionPool> {
 
             match channel_name.as_str() {
                 super::cache::IMC_INVALIDATION_CHANNEL => {
-                    let key = match CacheKind::try_from(RedisValue::new(message.value))
-                        .change_context(redis_errors::RedisError::OnMessageError)
-                    {
-                        Ok(value) => value,
-                        Err(err) => {
-                            logger::error!(value_conversion_err=?err);
-                            continue;
-                        }
-                    };
+                    let (key, correlation_id) =
+                        match parse_invalidation_message(RedisValue::new(message.value))
+                            .change_context(redis_errors::RedisError::OnMessageError)
+                        {
+                            Ok(value) => value,
+                            Err(err) => {
+                                logger::error!(value_conversion_err=?err);
+                                continue;
+                            }
+                        };
 
                     let key = match key {
                         CacheKind::Config(key) => {
@@ -156,6 +300,15 @@ impl PubSubInterface for std::sync::Arc<redis_interface::RedisConnectionPool> {
                                 .await;
                             key
                         }
+                        CacheKind::CardInfo(key) => {
+                            CARD_INFO_CACHE
+                                .remove(CacheKey {
+                                    key: key.to_string(),
+                                    prefix: self.key_prefix.clone(),
+                                })
+                                .await;
+                            key
+                        }
                         CacheKind::All(key) => {
                             CONFIG_CACHE
                                 .remove(CacheKey {
@@ -199,6 +352,12 @@ impl PubSubInterface for std::sync::Arc<redis_interface::RedisConnectionPool> {
                                     prefix: self.key_prefix.clone(),
                                 })
                                 .await;
+                            CARD_INFO_CACHE
+                                .remove(CacheKey {
+                                    key: key.to_string(),
+                                    prefix: self.key_prefix.clone(),
+                                })
+                                .await;
 
                             key
                         }
@@ -212,6 +371,23 @@ impl PubSubInterface for std::sync::Arc<redis_interface::RedisConnectionPool> {
                     logger::debug!(
                         "Handled message on channel {channel_name} - Done invalidating {key}"
                     );
+
+                    if let Some(correlation_id) = correlation_id {
+                        acknowledge_invalidation(self, correlation_id).await;
+                    }
+                }
+                super::cache::IMC_INVALIDATION_ACK_CHANNEL => {
+                    let ack = RedisValue::new(message.value)
+                        .as_string()
+                        .ok_or(redis_errors::RedisError::OnMessageError)
+                        .and_then(|ack| {
+                            serde_json::from_str::<CacheInvalidationAck>(&ack)
+                                .map_err(|_| redis_errors::RedisError::OnMessageError)
+                        });
+                    match ack {
+                        Ok(ack) => notify_ack_waiter(ack),
+                        Err(err) => logger::error!(ack_deserialization_err=?err),
+                    }
                 }
                 _ => {
                     logger::debug!("Received message from unknown channel: {channel_name}");
@@ -221,3 +397,88 @@ impl PubSubInterface for std::sync::Arc<redis_interface::RedisConnectionPool> {
         Ok(())
     }
 }
+
+/// Acknowledges the handling of the invalidation message to its publisher
+async fn acknowledge_invalidation(
+    redis: &redis_interface::RedisConnectionPool,
+    correlation_id: String,
+) {
+    let ack = CacheInvalidationAck {
+        subscriber_id: SUBSCRIBER_ID.clone(),
+        correlation_id,
+    };
+    let ack = match serde_json::to_string(&ack) {
+        Ok(ack) => ack,
+        Err(err) => {
+            logger::error!(ack_serialization_err=?err);
+            return;
+        }
+    };
+
+    redis
+        .publisher
+        .publish::<usize, _, _>(super::cache::IMC_INVALIDATION_ACK_CHANNEL, ack)
+        .await
+        .map_err(|err| logger::error!(ack_publish_err=?err))
+        .ok();
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn ack(correlation_id: &str) -> CacheInvalidationAck {
+        CacheInvalidationAck {
+            subscriber_id: "subscriber".to_owned(),
+            correlation_id: correlation_id.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_invalidation_message_round_trip() {
+        let (key, correlation_id) = parse_invalidation_message(invalidation_message(
+            CacheKind::Accounts("merchant_1".into()),
+            None,
+        ))
+        .unwrap();
+        assert!(matches!(key, CacheKind::Accounts(key) if key == "merchant_1"));
+        assert_eq!(correlation_id, None);
+
+        let (key, correlation_id) = parse_invalidation_message(invalidation_message(
+            CacheKind::Routing("routing_profile_1".into()),
+            Some("invalidation_1"),
+        ))
+        .unwrap();
+        assert!(matches!(key, CacheKind::Routing(key) if key == "routing_profile_1"));
+        assert_eq!(correlation_id.as_deref(), Some("invalidation_1"));
+    }
+
+    #[tokio::test]
+    async fn test_acks_are_delivered_to_the_waiter_of_their_correlation_id() {
+        let mut waiter = AckWaiter::register();
+        let mut other_waiter = AckWaiter::register();
+        assert_ne!(waiter.correlation_id(), other_waiter.correlation_id());
+
+        notify_ack_waiter(ack(waiter.correlation_id()));
+
+        let received = waiter.recv().await.unwrap();
+        assert_eq!(received.correlation_id, waiter.correlation_id());
+        assert!(other_waiter.receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_dropped_waiters_are_unregistered() {
+        let waiter = AckWaiter::register();
+        let correlation_id = waiter.correlation_id().to_owned();
+        drop(waiter);
+
+        assert!(!ACK_WAITERS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains_key(&correlation_id));
+        // Acknowledgements arriving after the waiter stopped waiting are ignored
+        notify_ack_waiter(ack(&correlation_id));
+    }
+}
@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE cards_info ADD COLUMN IF NOT EXISTS card_iin_range_end VARCHAR(16);
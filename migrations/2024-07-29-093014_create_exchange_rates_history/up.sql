@@ -0,0 +1,9 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS exchange_rates_history (
+    id SERIAL PRIMARY KEY,
+    base_currency "Currency" NOT NULL,
+    conversion JSONB NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS exchange_rates_history_created_at_index ON exchange_rates_history (created_at);
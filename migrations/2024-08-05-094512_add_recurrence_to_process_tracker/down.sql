@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS process_tracker_merchant_id_schedule_time_index;
+
+ALTER TABLE process_tracker
+DROP COLUMN IF EXISTS merchant_id,
+DROP COLUMN IF EXISTS recurrence;
+
+-- The `paused` value of the `ProcessTrackerStatus` enum is not removed, as Postgres does not support
+-- removing enum values
@@ -0,0 +1,15 @@
+-- Your SQL goes here
+ALTER TYPE "ProcessTrackerStatus" ADD VALUE IF NOT EXISTS 'paused';
+
+ALTER TABLE process_tracker
+ADD COLUMN IF NOT EXISTS merchant_id VARCHAR(64),
+ADD COLUMN IF NOT EXISTS recurrence JSONB;
+
+-- The workflows of merchants store the merchant ID in their tracking data. Tasks whose tracking
+-- data has no merchant ID, such as the tasks deleting tokenized data, are left without one.
+UPDATE process_tracker
+SET merchant_id = tracking_data ->> 'merchant_id'
+WHERE merchant_id IS NULL
+    AND tracking_data ->> 'merchant_id' IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS process_tracker_merchant_id_schedule_time_index ON process_tracker (merchant_id, schedule_time);